@@ -0,0 +1,1592 @@
+//! Behaviours for the Adventures kingdom cards and Events. Reserve cards wait
+//! on their owner's Tavern mat and are offered every event through
+//! `Behaviour::on_tavern`, each deciding for itself whether it can be called.
+//! Events do everything from `Behaviour::on_buy`. Travellers offer to be
+//! exchanged for the next card in their line as they leave play.
+
+use super::{
+    before_clean_up, card, choose_one, choose_to_play, discard_all, discard_chosen, distinct,
+    from_hand, from_hand_of, gain_from_supply, next_turn, options, play_taken, protects_from_play,
+    take_extra_turn, Behaviour, Card, CardFilter, CardType, Cost,
+};
+use crate::{Answer, Event, Expiry, GainDestination, Game, GameError, Mat, Prompt, Token};
+
+pub(super) fn behaviours() -> Vec<(&'static str, Behaviour)> {
+    let play = |on_play| Behaviour {
+        on_play: Some(on_play),
+        ..Behaviour::default()
+    };
+    let reserve = |on_tavern| Behaviour {
+        on_tavern: Some(on_tavern),
+        ..Behaviour::default()
+    };
+    let event = |on_buy| Behaviour {
+        on_buy: Some(on_buy),
+        ..Behaviour::default()
+    };
+    vec![
+        ("Coin of the Realm", reserve(coin_of_the_realm)),
+        ("Page", play(traveller)),
+        ("Peasant", play(traveller)),
+        ("Ratcatcher", reserve(ratcatcher)),
+        ("Raze", play(raze)),
+        ("Amulet", play(amulet)),
+        (
+            "Caravan Guard",
+            Behaviour {
+                on_attack: Some(caravan_guard_reaction),
+                ..play(caravan_guard)
+            },
+        ),
+        ("Dungeon", play(dungeon)),
+        ("Gear", play(gear)),
+        ("Guide", reserve(guide)),
+        ("Duplicate", reserve(duplicate)),
+        ("Magpie", play(magpie)),
+        (
+            "Messenger",
+            Behaviour {
+                on_buy: Some(messenger_on_buy),
+                ..play(messenger)
+            },
+        ),
+        ("Miser", play(miser)),
+        (
+            "Port",
+            Behaviour {
+                on_buy: Some(port),
+                ..Behaviour::default()
+            },
+        ),
+        ("Ranger", play(ranger)),
+        ("Transmogrify", reserve(transmogrify)),
+        ("Artificer", play(artificer)),
+        (
+            "Bridge Troll",
+            Behaviour {
+                reduces_costs: Some(bridge_troll_cost),
+                ..play(bridge_troll)
+            },
+        ),
+        // Scored by `Player::get_victory_points`; playing it only puts it on
+        // the Tavern mat, like any Reserve card
+        ("Distant Lands", Behaviour::default()),
+        ("Giant", play(giant)),
+        ("Haunted Woods", play(haunted_woods)),
+        (
+            "Lost City",
+            Behaviour {
+                on_gain: Some(lost_city),
+                ..Behaviour::default()
+            },
+        ),
+        ("Relic", play(relic)),
+        ("Royal Carriage", reserve(royal_carriage)),
+        ("Storyteller", play(storyteller)),
+        ("Swamp Hag", play(swamp_hag)),
+        ("Treasure Trove", play(treasure_trove)),
+        ("Wine Merchant", reserve(wine_merchant)),
+        ("Hireling", play(hireling)),
+        // Travellers
+        ("Treasure Hunter", play(treasure_hunter)),
+        ("Warrior", play(warrior)),
+        ("Hero", play(hero)),
+        (
+            "Champion",
+            Behaviour {
+                protects: Some(protects_from_play),
+                ..play(champion)
+            },
+        ),
+        ("Soldier", play(soldier)),
+        ("Fugitive", play(fugitive)),
+        ("Disciple", play(disciple)),
+        ("Teacher", reserve(teacher)),
+        // Events
+        ("Alms", event(alms)),
+        ("Borrow", event(borrow)),
+        ("Quest", event(quest)),
+        ("Save", event(save)),
+        ("Scouting Party", event(scouting_party)),
+        ("Travelling Fair", event(travelling_fair)),
+        ("Bonfire", event(bonfire)),
+        ("Expedition", event(expedition)),
+        ("Ferry", event(ferry)),
+        ("Plan", event(plan)),
+        ("Mission", event(mission)),
+        ("Pilgrimage", event(pilgrimage)),
+        ("Ball", event(ball)),
+        ("Raid", event(raid)),
+        ("Seaway", event(seaway)),
+        ("Trade", event(trade)),
+        ("Lost Arts", event(lost_arts)),
+        ("Training", event(training)),
+        ("Inheritance", event(inheritance)),
+        ("Pathfinding", event(pathfinding)),
+    ]
+}
+
+/// Offers to call a Reserve card from the player's Tavern mat, resolving
+/// `effect` once it is in play. Further copies on the mat are offered in turn.
+fn offer_call(
+    game: &mut Game,
+    player_index: usize,
+    reserve: Card,
+    effect: impl FnOnce(&mut Game, usize) -> Result<(), GameError> + Clone + Send + 'static,
+) {
+    game.ask(
+        player_index,
+        reserve,
+        Prompt::Options {
+            options: vec![
+                format!("Call {}", reserve.name()),
+                "Leave it on your Tavern mat".to_owned(),
+            ],
+        },
+        move |game, player_index, answer| {
+            if answer.option() == 0 && game.players[player_index].call_from_tavern(reserve) {
+                effect.clone()(game, player_index)?;
+                if game.players[player_index].has_on_mat(Mat::Tavern, reserve) {
+                    offer_call(game, player_index, reserve, effect);
+                }
+            }
+            Ok(())
+        },
+    );
+}
+
+fn is_turn_start(event: Event, owner: usize) -> bool {
+    matches!(event, Event::TurnStart { player_index } if player_index == owner)
+}
+
+/// Each line of Travellers, in the order they are exchanged for each other.
+const TRAVELLER_LINES: [[&str; 5]; 2] = [
+    ["Page", "Treasure Hunter", "Warrior", "Hero", "Champion"],
+    ["Peasant", "Soldier", "Fugitive", "Disciple", "Teacher"],
+];
+
+fn next_in_line(traveller: Card) -> Option<Card> {
+    TRAVELLER_LINES.iter().find_map(|line| {
+        let index = line.iter().position(|&name| name == traveller.name())?;
+        line.get(index + 1).map(|&name| card(name))
+    })
+}
+
+/// Offers to exchange a Traveller that is still in play for the next card in
+/// its line when it is discarded, returning it to its pile. An exchange isn't
+/// a gain, so nothing that responds to gaining happens.
+fn exchange_when_discarded(game: &mut Game, traveller: Card) {
+    let Some(upgrade) = next_in_line(traveller) else {
+        return;
+    };
+    before_clean_up(game, move |game, player_index| {
+        let in_play = game.players[player_index].played.contains(&traveller);
+        if !in_play || game.supply.count(upgrade).unwrap_or(0) == 0 {
+            return;
+        }
+        game.ask(
+            player_index,
+            traveller,
+            Prompt::Options {
+                options: vec![
+                    format!("Exchange {} for a {}", traveller.name(), upgrade.name()),
+                    "Discard it as normal".to_owned(),
+                ],
+            },
+            move |game, player_index, answer| {
+                // Another Traveller may have taken the last one since
+                if answer.option() != 0 || game.supply.count(upgrade).unwrap_or(0) == 0 {
+                    return Ok(());
+                }
+                if let Some(exchanged) = game.players[player_index].take_from_play(traveller) {
+                    game.supply.return_card(exchanged)?;
+                    game.supply.take_card(upgrade)?;
+                    game.players[player_index].add_to_discard(upgrade);
+                }
+                Ok(())
+            },
+        );
+    });
+}
+
+/// Page and Peasant do nothing but their vanilla bonuses until they are
+/// exchanged.
+fn traveller(game: &mut Game, source: Card) -> Result<(), GameError> {
+    exchange_when_discarded(game, source);
+    Ok(())
+}
+
+/// Asks the player to move one of their tokens onto an Action supply pile,
+/// leaving out piles they already have a token on if `to_own_free_pile`.
+fn move_token(
+    game: &mut Game,
+    player_index: usize,
+    source: Card,
+    token: Token,
+    to_own_free_pile: bool,
+) {
+    let tokens = &game.players[player_index].tokens;
+    let piles: Vec<String> = game
+        .supply
+        .piles
+        .iter()
+        .filter(|pile| pile.kinds().iter().any(|card| card.is(CardType::Action)))
+        .filter(|pile| !to_own_free_pile || !tokens.values().any(|on| *on == pile.name))
+        .map(|pile| pile.name.clone())
+        .collect();
+    game.ask(
+        player_index,
+        source,
+        Prompt::Options {
+            options: piles
+                .iter()
+                .map(|pile| format!("Put your {token} token on {pile}"))
+                .collect(),
+        },
+        move |game, player_index, answer| {
+            let pile = piles[answer.option()].clone();
+            game.players[player_index].tokens.insert(token, pile);
+            Ok(())
+        },
+    );
+}
+
+fn coin_of_the_realm(game: &mut Game, owner: usize, reserve: Card, event: Event) {
+    let Event::Played { player_index, card } = event else {
+        return;
+    };
+    if player_index == owner && card.is(CardType::Action) {
+        offer_call(game, owner, reserve, |game, player_index| {
+            game.players[player_index].actions += 2;
+            Ok(())
+        });
+    }
+}
+
+fn ratcatcher(game: &mut Game, owner: usize, reserve: Card, event: Event) {
+    if !is_turn_start(event, owner) || game.players[owner].hand.is_empty() {
+        return;
+    }
+    offer_call(game, owner, reserve, move |game, player_index| {
+        game.ask(
+            player_index,
+            reserve,
+            from_hand(1, 1),
+            |game, player_index, answer| {
+                for trashed in answer.cards() {
+                    game.trash_card(player_index, trashed);
+                }
+                Ok(())
+            },
+        );
+        Ok(())
+    });
+}
+
+fn raze(game: &mut Game, source: Card) -> Result<(), GameError> {
+    game.ask(
+        game.curr_player_index,
+        source,
+        options(&["Trash Raze", "Trash a card from your hand"]),
+        move |game, player_index, answer| {
+            if answer.option() == 0 {
+                if let Some(raze) = game.players[player_index].take_from_play(source) {
+                    game.trash_card(player_index, raze);
+                    raze_look(game, player_index, source, raze);
+                }
+                return Ok(());
+            }
+            game.ask(
+                player_index,
+                source,
+                from_hand(1, 1),
+                move |game, player_index, answer| {
+                    for trashed in answer.cards() {
+                        game.trash_card(player_index, trashed);
+                        raze_look(game, player_index, source, trashed);
+                    }
+                    Ok(())
+                },
+            );
+            Ok(())
+        },
+    );
+    Ok(())
+}
+
+fn raze_look(game: &mut Game, player_index: usize, source: Card, trashed: Card) {
+    let count = game.cost_of(trashed).coins as usize;
+    let revealed = game.players[player_index].reveal_from_deck(count);
+    if revealed.is_empty() {
+        return;
+    }
+    game.ask(
+        player_index,
+        source,
+        Prompt::Options {
+            options: revealed
+                .iter()
+                .map(|card| format!("Put {} into your hand", card.name()))
+                .collect(),
+        },
+        move |game, player_index, answer| {
+            let kept = answer.option();
+            let player = &mut game.players[player_index];
+            for (index, card) in revealed.into_iter().enumerate() {
+                if index == kept {
+                    player.add_to_hand(card);
+                } else {
+                    player.add_to_discard(card);
+                }
+            }
+            Ok(())
+        },
+    );
+}
+
+fn amulet(game: &mut Game, source: Card) -> Result<(), GameError> {
+    amulet_choice(game, source);
+    next_turn(game, source, move |game, _| {
+        amulet_choice(game, source);
+        Ok(())
+    });
+    Ok(())
+}
+
+fn amulet_choice(game: &mut Game, source: Card) {
+    choose_one(
+        game,
+        source,
+        &["+$1", "Trash a card from your hand", "Gain a Silver"],
+        move |game, player_index, ability| {
+            match ability {
+                0 => game.players[player_index].coins += 1,
+                1 => game.ask(
+                    player_index,
+                    source,
+                    from_hand(1, 1),
+                    |game, player_index, answer| {
+                        for trashed in answer.cards() {
+                            game.trash_card(player_index, trashed);
+                        }
+                        Ok(())
+                    },
+                ),
+                _ => {
+                    game.gain_if_available(player_index, card("Silver"), GainDestination::Discard)?
+                }
+            }
+            Ok(())
+        },
+    );
+}
+
+fn caravan_guard(game: &mut Game, source: Card) -> Result<(), GameError> {
+    next_turn(game, source, |game, player_index| {
+        game.players[player_index].coins += 1;
+        Ok(())
+    });
+    Ok(())
+}
+
+/// Caravan Guard can be played on another player's turn, before their Attack
+/// resolves. It stays in play until the start of its owner's next turn.
+fn caravan_guard_reaction(
+    game: &mut Game,
+    player_index: usize,
+    caravan_guard: Card,
+    _attack: Card,
+) {
+    game.ask(
+        player_index,
+        caravan_guard,
+        options(&["Play Caravan Guard", "Keep it in your hand"]),
+        move |game, player_index, answer| {
+            let player = &mut game.players[player_index];
+            let in_hand = player.hand.iter().position(|&card| card == caravan_guard);
+            if let (0, Some(index)) = (answer.option(), in_hand) {
+                let played = player.hand.remove(index);
+                player.play_card(played);
+                // +1 Action does nothing when it isn't the player's turn
+                player.draw(1);
+                game.at_start_of_turn(player_index, caravan_guard, move |game| {
+                    game.players[player_index].coins += 1;
+                    Ok(())
+                });
+            }
+            Ok(())
+        },
+    );
+}
+
+fn dungeon(game: &mut Game, source: Card) -> Result<(), GameError> {
+    let draw_and_discard = move |game: &mut Game, player_index: usize| {
+        game.players[player_index].draw(2);
+        game.ask(
+            player_index,
+            source,
+            from_hand(2, 2),
+            |game, player_index, answer| {
+                for card in answer.cards() {
+                    game.players[player_index].add_to_discard(card);
+                }
+                Ok(())
+            },
+        );
+        Ok(())
+    };
+    draw_and_discard(game, game.curr_player_index)?;
+    next_turn(game, source, draw_and_discard);
+    Ok(())
+}
+
+fn gear(game: &mut Game, source: Card) -> Result<(), GameError> {
+    game.ask(
+        game.curr_player_index,
+        source,
+        from_hand(0, 2),
+        move |game, _, answer| {
+            let set_aside = answer.cards();
+            // With nothing set aside, Gear has nothing left to do and is discarded as normal
+            if !set_aside.is_empty() {
+                next_turn(game, source, move |game, player_index| {
+                    for card in set_aside {
+                        game.players[player_index].add_to_hand(card);
+                    }
+                    Ok(())
+                });
+            }
+            Ok(())
+        },
+    );
+    Ok(())
+}
+
+fn guide(game: &mut Game, owner: usize, reserve: Card, event: Event) {
+    if is_turn_start(event, owner) {
+        offer_call(game, owner, reserve, |game, player_index| {
+            let player = &mut game.players[player_index];
+            player.discard_hand();
+            player.draw(5);
+            Ok(())
+        });
+    }
+}
+
+fn duplicate(game: &mut Game, owner: usize, reserve: Card, event: Event) {
+    let Event::Gain { player_index, card } = event else {
+        return;
+    };
+    let affordable = game.cost_of(card).fits_within(&Cost::coins(6));
+    if player_index == owner && affordable && game.supply.count(card).unwrap_or(0) > 0 {
+        offer_call(game, owner, reserve, move |game, player_index| {
+            game.gain_if_available(player_index, card, GainDestination::Discard)
+        });
+    }
+}
+
+fn magpie(game: &mut Game, source: Card) -> Result<(), GameError> {
+    let player = game.current_player();
+    let Some(top) = player.take_from_deck() else {
+        return Ok(());
+    };
+    if top.is(CardType::Treasure) {
+        player.add_to_hand(top);
+    } else {
+        player.put_on_deck(top);
+    }
+    if top.is(CardType::Action) || top.is(CardType::Victory) {
+        game.gain_if_available(game.curr_player_index, source, GainDestination::Discard)?;
+    }
+    Ok(())
+}
+
+fn messenger(game: &mut Game, source: Card) -> Result<(), GameError> {
+    if game.current_player_read_only().deck.is_empty() {
+        return Ok(());
+    }
+    game.ask(
+        game.curr_player_index,
+        source,
+        options(&["Put your deck into your discard pile", "Keep your deck"]),
+        |game, player_index, answer| {
+            if answer.option() == 0 {
+                let player = &mut game.players[player_index];
+                let deck = std::mem::take(&mut player.deck);
+                player.discard.extend(deck);
+            }
+            Ok(())
+        },
+    );
+    Ok(())
+}
+
+fn messenger_on_buy(game: &mut Game, source: Card) -> Result<(), GameError> {
+    let owner = game.curr_player_index;
+    // Messenger itself has just been gained
+    if game.history.current_turn.gained_by(owner).count() != 1 {
+        return Ok(());
+    }
+    game.ask(
+        owner,
+        source,
+        gain_from_supply(Cost::coins(4), CardFilter::Any),
+        |game, player_index, answer| {
+            let gained = answer.supply_card();
+            game.gain_card(player_index, gained, GainDestination::Discard)?;
+            let num_players = game.players.len();
+            for offset in 1..num_players {
+                let other = (player_index + offset) % num_players;
+                game.gain_if_available(other, gained, GainDestination::Discard)?;
+            }
+            Ok(())
+        },
+    );
+    Ok(())
+}
+
+fn miser(game: &mut Game, source: Card) -> Result<(), GameError> {
+    choose_one(
+        game,
+        source,
+        &[
+            "Put a Copper from your hand onto your Tavern mat",
+            "+$1 per Copper on your Tavern mat",
+        ],
+        |game, player_index, ability| {
+            let player = &mut game.players[player_index];
+            let copper = card("Copper");
+            if ability == 0 {
+                if let Some(index) = player.hand.iter().position(|&card| card == copper) {
+                    let copper = player.hand.remove(index);
+                    player.mat_mut(Mat::Tavern).push(copper);
+                }
+            } else {
+                let coppers = player
+                    .mat_mut(Mat::Tavern)
+                    .iter()
+                    .filter(|&&card| card == copper)
+                    .count();
+                player.coins += coppers as u32;
+            }
+            Ok(())
+        },
+    );
+    Ok(())
+}
+
+fn port(game: &mut Game, source: Card) -> Result<(), GameError> {
+    game.gain_if_available(game.curr_player_index, source, GainDestination::Discard)
+}
+
+fn ranger(game: &mut Game, _source: Card) -> Result<(), GameError> {
+    let player = game.current_player();
+    if player.turn_journey_token() {
+        player.draw(5);
+    }
+    Ok(())
+}
+
+fn transmogrify(game: &mut Game, owner: usize, reserve: Card, event: Event) {
+    if !is_turn_start(event, owner) || game.players[owner].hand.is_empty() {
+        return;
+    }
+    offer_call(game, owner, reserve, move |game, player_index| {
+        game.ask(
+            player_index,
+            reserve,
+            from_hand(1, 1),
+            move |game, player_index, answer| {
+                for trashed in answer.cards() {
+                    let max_cost = game.cost_of(trashed).plus_coins(1);
+                    game.trash_card(player_index, trashed);
+                    game.ask(
+                        player_index,
+                        reserve,
+                        gain_from_supply(max_cost, CardFilter::Any),
+                        |game, player_index, answer| {
+                            game.gain_card(
+                                player_index,
+                                answer.supply_card(),
+                                GainDestination::Hand,
+                            )
+                        },
+                    );
+                }
+                Ok(())
+            },
+        );
+        Ok(())
+    });
+}
+
+fn artificer(game: &mut Game, source: Card) -> Result<(), GameError> {
+    let hand_size = game.current_player_read_only().hand.len();
+    game.ask(
+        game.curr_player_index,
+        source,
+        from_hand(0, hand_size),
+        move |game, player_index, answer| {
+            let discarded = answer.cards();
+            let cost = Cost::coins(discarded.len() as u32);
+            for card in discarded {
+                game.players[player_index].add_to_discard(card);
+            }
+            if game
+                .gainable_cards(&cost, true, &CardFilter::Any)
+                .is_empty()
+            {
+                return Ok(());
+            }
+            game.ask(
+                player_index,
+                source,
+                Prompt::Options {
+                    options: vec![
+                        format!("Gain a card costing exactly {cost} onto your deck"),
+                        "Don't gain a card".to_owned(),
+                    ],
+                },
+                move |game, player_index, answer| {
+                    if answer.option() == 0 {
+                        game.ask(
+                            player_index,
+                            source,
+                            Prompt::CardFromSupply {
+                                max_cost: cost,
+                                exact: true,
+                                filter: CardFilter::Any,
+                            },
+                            |game, player_index, answer| {
+                                game.gain_card(
+                                    player_index,
+                                    answer.supply_card(),
+                                    GainDestination::Deck,
+                                )
+                            },
+                        );
+                    }
+                    Ok(())
+                },
+            );
+            Ok(())
+        },
+    );
+    Ok(())
+}
+
+fn bridge_troll(game: &mut Game, source: Card) -> Result<(), GameError> {
+    for player_index in game.attacked_player_indices() {
+        game.players[player_index].take_minus_coin_token();
+    }
+    next_turn(game, source, |game, player_index| {
+        game.players[player_index].buys += 1;
+        Ok(())
+    });
+    Ok(())
+}
+
+/// While Bridge Troll is in play, cards cost $1 less on its owner's turns.
+fn bridge_troll_cost(_game: &Game, cost: Cost) -> Cost {
+    cost.minus_coins(1)
+}
+
+fn giant(game: &mut Game, _source: Card) -> Result<(), GameError> {
+    let player = game.current_player();
+    if !player.turn_journey_token() {
+        player.coins += 1;
+        return Ok(());
+    }
+    player.coins += 5;
+    for player_index in game.attacked_player_indices() {
+        let Some(top) = game.players[player_index].take_from_deck() else {
+            continue;
+        };
+        let cost = game.cost_of(top);
+        if cost.fits_within(&Cost::coins(6)) && cost.coins >= 3 {
+            game.trash_card(player_index, top);
+        } else {
+            game.players[player_index].add_to_discard(top);
+            game.gain_if_available(player_index, card("Curse"), GainDestination::Discard)?;
+        }
+    }
+    Ok(())
+}
+
+fn haunted_woods(game: &mut Game, source: Card) -> Result<(), GameError> {
+    let owner = game.curr_player_index;
+    let attacked = game.attacked_player_indices();
+    game.add_trigger(Expiry::StartOfTurn(owner), move |game, event| {
+        if let Event::Buy { player_index, .. } = event {
+            if attacked.contains(&player_index) {
+                // In the order they were held, the last card ending up on top
+                let player = &mut game.players[player_index];
+                let hand = std::mem::take(&mut player.hand);
+                player.deck.extend(hand);
+            }
+        }
+        Ok(true)
+    });
+    next_turn(game, source, |game, player_index| {
+        game.players[player_index].draw(3);
+        Ok(())
+    });
+    Ok(())
+}
+
+fn lost_city(game: &mut Game, player_index: usize, _card: Card) {
+    let num_players = game.players.len();
+    for offset in 1..num_players {
+        game.players[(player_index + offset) % num_players].draw(1);
+    }
+}
+
+fn relic(game: &mut Game, _source: Card) -> Result<(), GameError> {
+    for player_index in game.attacked_player_indices() {
+        game.players[player_index].minus_card_token = true;
+    }
+    Ok(())
+}
+
+/// Royal Carriage is offered once the Action has finished, and replaying it
+/// finishes it again, so any other Royal Carriage on the mat is offered then.
+fn royal_carriage(game: &mut Game, owner: usize, reserve: Card, event: Event) {
+    let Event::Played { player_index, card } = event else {
+        return;
+    };
+    if player_index != owner
+        || !card.is(CardType::Action)
+        || !game.players[owner].played.contains(&card)
+    {
+        return;
+    }
+    game.ask(
+        owner,
+        reserve,
+        Prompt::Options {
+            options: vec![
+                format!("Call Royal Carriage to replay {}", card.name()),
+                "Leave it on your Tavern mat".to_owned(),
+            ],
+        },
+        move |game, player_index, answer| {
+            let player = &mut game.players[player_index];
+            if answer.option() == 0
+                && player.played.contains(&card)
+                && player.call_from_tavern(reserve)
+            {
+                game.resolve_play(card)?;
+            }
+            Ok(())
+        },
+    );
+}
+
+fn storyteller(game: &mut Game, source: Card) -> Result<(), GameError> {
+    game.ask(
+        game.curr_player_index,
+        source,
+        Prompt::CardsFromHand {
+            min: 0,
+            max: 3,
+            filter: CardFilter::OfType(CardType::Treasure),
+        },
+        |game, _, answer| {
+            for treasure in answer.cards() {
+                game.play_treasure(treasure)?;
+            }
+            Ok(())
+        },
+    );
+    game.then(|game| {
+        let player = game.current_player();
+        let paid = std::mem::take(&mut player.coins);
+        player.draw(paid as usize);
+        Ok(())
+    });
+    Ok(())
+}
+
+fn swamp_hag(game: &mut Game, source: Card) -> Result<(), GameError> {
+    let owner = game.curr_player_index;
+    let attacked = game.attacked_player_indices();
+    game.add_trigger(Expiry::StartOfTurn(owner), move |game, event| {
+        if let Event::Buy { player_index, .. } = event {
+            if attacked.contains(&player_index) {
+                game.gain_if_available(player_index, card("Curse"), GainDestination::Discard)?;
+            }
+        }
+        Ok(true)
+    });
+    next_turn(game, source, |game, player_index| {
+        game.players[player_index].coins += 3;
+        Ok(())
+    });
+    Ok(())
+}
+
+fn treasure_trove(game: &mut Game, _source: Card) -> Result<(), GameError> {
+    let player_index = game.curr_player_index;
+    game.gain_if_available(player_index, card("Gold"), GainDestination::Discard)?;
+    game.gain_if_available(player_index, card("Copper"), GainDestination::Discard)
+}
+
+fn wine_merchant(game: &mut Game, owner: usize, reserve: Card, event: Event) {
+    let Event::BuyPhaseEnd { player_index } = event else {
+        return;
+    };
+    if player_index != owner || game.players[owner].coins < 2 {
+        return;
+    }
+    game.ask(
+        owner,
+        reserve,
+        options(&[
+            "Discard Wine Merchant from your Tavern mat",
+            "Leave it on your Tavern mat",
+        ]),
+        move |game, player_index, answer| {
+            let player = &mut game.players[player_index];
+            let tavern = player.mat_mut(Mat::Tavern);
+            if let (0, Some(index)) = (
+                answer.option(),
+                tavern.iter().position(|&card| card == reserve),
+            ) {
+                let wine_merchant = tavern.remove(index);
+                player.add_to_discard(wine_merchant);
+            }
+            Ok(())
+        },
+    );
+}
+
+/// Hireling stays in play for the rest of the game, so its effect schedules
+/// itself again every turn.
+fn hireling(game: &mut Game, source: Card) -> Result<(), GameError> {
+    hireling_next_turn(game, game.curr_player_index, source);
+    Ok(())
+}
+
+fn hireling_next_turn(game: &mut Game, player_index: usize, source: Card) {
+    game.at_start_of_turn(player_index, source, move |game| {
+        game.players[player_index].draw(1);
+        hireling_next_turn(game, player_index, source);
+        Ok(())
+    });
+}
+
+fn treasure_hunter(game: &mut Game, source: Card) -> Result<(), GameError> {
+    let owner = game.curr_player_index;
+    let num_players = game.players.len();
+    let right = (owner + num_players - 1) % num_players;
+    let gained = game
+        .history
+        .last_turn(right)
+        .map_or(0, |turn| turn.gained_by(right).count());
+    for _ in 0..gained {
+        game.gain_if_available(owner, card("Silver"), GainDestination::Discard)?;
+    }
+    exchange_when_discarded(game, source);
+    Ok(())
+}
+
+fn warrior(game: &mut Game, source: Card) -> Result<(), GameError> {
+    let travellers = game
+        .current_player_read_only()
+        .played
+        .iter()
+        .filter(|card| card.is(CardType::Traveller))
+        .count();
+    for player_index in game.attacked_player_indices() {
+        for _ in 0..travellers {
+            let Some(top) = game.players[player_index].take_from_deck() else {
+                break;
+            };
+            let cost = game.cost_of(top);
+            if (3..=4).contains(&cost.coins) && cost.potions == 0 && cost.debt == 0 {
+                game.trash_card(player_index, top);
+            } else {
+                game.players[player_index].add_to_discard(top);
+            }
+        }
+    }
+    exchange_when_discarded(game, source);
+    Ok(())
+}
+
+fn hero(game: &mut Game, source: Card) -> Result<(), GameError> {
+    let any_cost = Cost {
+        coins: u32::MAX,
+        potions: u32::MAX,
+        debt: u32::MAX,
+    };
+    game.ask(
+        game.curr_player_index,
+        source,
+        gain_from_supply(any_cost, CardFilter::OfType(CardType::Treasure)),
+        |game, player_index, answer| {
+            game.gain_card(player_index, answer.supply_card(), GainDestination::Discard)
+        },
+    );
+    exchange_when_discarded(game, source);
+    Ok(())
+}
+
+/// Champion stays in play for the rest of the game, protecting its owner from
+/// Attacks, and every turn it gives them +1 Action per Action played.
+fn champion(game: &mut Game, source: Card) -> Result<(), GameError> {
+    let owner = game.curr_player_index;
+    champion_actions(game, owner);
+    champion_next_turn(game, owner, source);
+    Ok(())
+}
+
+fn champion_next_turn(game: &mut Game, player_index: usize, source: Card) {
+    game.at_start_of_turn(player_index, source, move |game| {
+        champion_actions(game, player_index);
+        champion_next_turn(game, player_index, source);
+        Ok(())
+    });
+}
+
+fn champion_actions(game: &mut Game, owner: usize) {
+    game.add_trigger(Expiry::EndOfTurn, move |game, event| {
+        if let Event::Play { player_index, card } = event {
+            if player_index == owner && card.is(CardType::Action) {
+                game.players[owner].actions += 1;
+            }
+        }
+        Ok(true)
+    });
+}
+
+fn soldier(game: &mut Game, source: Card) -> Result<(), GameError> {
+    let player = game.current_player();
+    let attacks = player
+        .played
+        .iter()
+        .filter(|card| card.is(CardType::Attack))
+        .count();
+    // Not counting this one
+    player.coins += attacks.saturating_sub(1) as u32;
+    for player_index in game.attacked_player_indices() {
+        if game.players[player_index].hand.len() >= 4 {
+            discard_chosen(game, player_index, source, 1);
+        }
+    }
+    exchange_when_discarded(game, source);
+    Ok(())
+}
+
+fn fugitive(game: &mut Game, source: Card) -> Result<(), GameError> {
+    discard_chosen(game, game.curr_player_index, source, 1);
+    exchange_when_discarded(game, source);
+    Ok(())
+}
+
+fn disciple(game: &mut Game, source: Card) -> Result<(), GameError> {
+    let actions = game
+        .current_player_read_only()
+        .hand
+        .iter()
+        .copied()
+        .filter(|card| card.is(CardType::Action))
+        .collect();
+    choose_to_play(game, source, actions, |game, action| {
+        from_hand_of(game.current_player(), action);
+        play_taken(game, action)?;
+        game.then(move |game| game.resolve_play(action));
+        game.then(move |game| {
+            game.gain_if_available(game.curr_player_index, action, GainDestination::Discard)
+        });
+        Ok(())
+    });
+    exchange_when_discarded(game, source);
+    Ok(())
+}
+
+fn teacher(game: &mut Game, owner: usize, reserve: Card, event: Event) {
+    if !is_turn_start(event, owner) {
+        return;
+    }
+    offer_call(game, owner, reserve, move |game, player_index| {
+        let tokens = [
+            Token::PlusCard,
+            Token::PlusAction,
+            Token::PlusBuy,
+            Token::PlusCoin,
+        ];
+        game.ask(
+            player_index,
+            reserve,
+            Prompt::Options {
+                options: tokens
+                    .iter()
+                    .map(|token| format!("Move your {token} token"))
+                    .collect(),
+            },
+            move |game, player_index, answer| {
+                move_token(game, player_index, reserve, tokens[answer.option()], true);
+                Ok(())
+            },
+        );
+        Ok(())
+    });
+}
+
+fn alms(game: &mut Game, source: Card) -> Result<(), GameError> {
+    let player = game.current_player_read_only();
+    if player.played.iter().any(|card| card.is(CardType::Treasure)) {
+        return Ok(());
+    }
+    game.ask(
+        game.curr_player_index,
+        source,
+        gain_from_supply(Cost::coins(4), CardFilter::Any),
+        |game, player_index, answer| {
+            game.gain_card(player_index, answer.supply_card(), GainDestination::Discard)
+        },
+    );
+    Ok(())
+}
+
+fn borrow(game: &mut Game, _source: Card) -> Result<(), GameError> {
+    let player = game.current_player();
+    player.buys += 1;
+    if !player.minus_card_token {
+        player.minus_card_token = true;
+        player.coins += 1;
+    }
+    Ok(())
+}
+
+fn quest(game: &mut Game, source: Card) -> Result<(), GameError> {
+    let hand = &game.current_player_read_only().hand;
+    let curse = card("Curse");
+    // Only what the player can actually discard is offered
+    let choices: Vec<&str> = [
+        (
+            "Discard an Attack",
+            hand.iter().any(|card| card.is(CardType::Attack)),
+        ),
+        (
+            "Discard two Curses",
+            hand.iter().filter(|&&card| card == curse).count() >= 2,
+        ),
+        ("Discard six cards", hand.len() >= 6),
+    ]
+    .into_iter()
+    .filter(|&(_, possible)| possible)
+    .map(|(choice, _)| choice)
+    .chain(["Don't discard anything"])
+    .collect();
+    game.ask(
+        game.curr_player_index,
+        source,
+        options(&choices),
+        move |game, player_index, answer| {
+            match choices[answer.option()] {
+                "Discard an Attack" => game.ask(
+                    player_index,
+                    source,
+                    Prompt::CardsFromHand {
+                        min: 1,
+                        max: 1,
+                        filter: CardFilter::OfType(CardType::Attack),
+                    },
+                    quest_for_gold,
+                ),
+                "Discard two Curses" => {
+                    let player = &mut game.players[player_index];
+                    from_hand_of(player, curse);
+                    from_hand_of(player, curse);
+                    discard_all(game, player_index, vec![curse, curse]);
+                    game.gain_if_available(player_index, card("Gold"), GainDestination::Discard)?;
+                }
+                "Discard six cards" => {
+                    game.ask(player_index, source, from_hand(6, 6), quest_for_gold)
+                }
+                _ => {}
+            }
+            Ok(())
+        },
+    );
+    Ok(())
+}
+
+fn quest_for_gold(game: &mut Game, player_index: usize, answer: Answer) -> Result<(), GameError> {
+    discard_all(game, player_index, answer.cards());
+    game.gain_if_available(player_index, card("Gold"), GainDestination::Discard)
+}
+
+fn save(game: &mut Game, source: Card) -> Result<(), GameError> {
+    game.current_player().buys += 1;
+    game.ask(
+        game.curr_player_index,
+        source,
+        from_hand(1, 1),
+        |game, player_index, answer| {
+            game.players[player_index].saved.extend(answer.cards());
+            Ok(())
+        },
+    );
+    Ok(())
+}
+
+fn scouting_party(game: &mut Game, source: Card) -> Result<(), GameError> {
+    let player = game.current_player();
+    player.buys += 1;
+    let revealed = player.reveal_from_deck(5);
+    let to_discard = revealed.len().min(3);
+    discard_revealed(game, game.curr_player_index, source, revealed, to_discard);
+    Ok(())
+}
+
+/// Has the player discard `count` of the revealed cards one at a time, then
+/// puts the rest back on their deck.
+fn discard_revealed(
+    game: &mut Game,
+    player_index: usize,
+    source: Card,
+    revealed: Vec<Card>,
+    count: usize,
+) {
+    if count == 0 {
+        // Whatever is left goes back in the order it was revealed
+        for &card in revealed.iter().rev() {
+            game.players[player_index].put_on_deck(card);
+        }
+        return;
+    }
+    game.ask(
+        player_index,
+        source,
+        Prompt::Options {
+            options: revealed
+                .iter()
+                .map(|card| format!("Discard {}", card.name()))
+                .collect(),
+        },
+        move |game, player_index, answer| {
+            let mut revealed = revealed;
+            let discarded = revealed.remove(answer.option());
+            game.players[player_index].add_to_discard(discarded);
+            discard_revealed(game, player_index, source, revealed, count - 1);
+            Ok(())
+        },
+    );
+}
+
+fn travelling_fair(game: &mut Game, source: Card) -> Result<(), GameError> {
+    let owner = game.curr_player_index;
+    game.current_player().buys += 2;
+    game.add_trigger(Expiry::EndOfTurn, move |game, event| {
+        let Event::Gain { player_index, card } = event else {
+            return Ok(true);
+        };
+        if player_index != owner {
+            return Ok(true);
+        }
+        game.ask(
+            owner,
+            source,
+            Prompt::Options {
+                options: vec![
+                    format!("Put {} onto your deck", card.name()),
+                    "Leave it where it is".to_owned(),
+                ],
+            },
+            move |game, player_index, answer| {
+                let player = &mut game.players[player_index];
+                if answer.option() == 0 {
+                    if let Some(index) = player.discard.iter().rposition(|&c| c == card) {
+                        let gained = player.discard.remove(index);
+                        player.put_on_deck(gained);
+                    }
+                }
+                Ok(())
+            },
+        );
+        Ok(true)
+    });
+    Ok(())
+}
+
+fn bonfire(game: &mut Game, source: Card) -> Result<(), GameError> {
+    trash_from_play(game, source, 2);
+    Ok(())
+}
+
+/// Lets the current player trash up to `count` cards they have in play, one at a time.
+fn trash_from_play(game: &mut Game, source: Card, count: usize) {
+    let in_play = distinct(game.current_player_read_only().played.iter().copied());
+    if count == 0 || in_play.is_empty() {
+        return;
+    }
+    let mut choices: Vec<String> = in_play
+        .iter()
+        .map(|card| format!("Trash {}", card.name()))
+        .collect();
+    choices.push("Stop trashing".to_owned());
+    game.ask(
+        game.curr_player_index,
+        source,
+        Prompt::Options { options: choices },
+        move |game, player_index, answer| {
+            let Some(&chosen) = in_play.get(answer.option()) else {
+                return Ok(());
+            };
+            if let Some(trashed) = game.players[player_index].take_from_play(chosen) {
+                game.trash_card(player_index, trashed);
+            }
+            trash_from_play(game, source, count - 1);
+            Ok(())
+        },
+    );
+}
+
+fn expedition(game: &mut Game, _source: Card) -> Result<(), GameError> {
+    game.current_player().next_hand_bonus += 2;
+    Ok(())
+}
+
+fn ferry(game: &mut Game, source: Card) -> Result<(), GameError> {
+    move_token(
+        game,
+        game.curr_player_index,
+        source,
+        Token::MinusCost,
+        false,
+    );
+    Ok(())
+}
+
+fn plan(game: &mut Game, source: Card) -> Result<(), GameError> {
+    move_token(game, game.curr_player_index, source, Token::Trashing, false);
+    Ok(())
+}
+
+fn mission(game: &mut Game, source: Card) -> Result<(), GameError> {
+    // The extra turn is the player's next one, and cards can't be bought in it
+    if take_extra_turn(game) {
+        next_turn(game, source, |game, _| {
+            game.buy_restrictions.push(CardFilter::Any);
+            Ok(())
+        });
+    }
+    Ok(())
+}
+
+fn pilgrimage(game: &mut Game, source: Card) -> Result<(), GameError> {
+    if game.current_player().turn_journey_token() {
+        gain_copies_of_played(game, source, Vec::new());
+    }
+    Ok(())
+}
+
+/// Lets the current player pick up to 3 differently named cards they have in
+/// play, then gains a copy of each.
+fn gain_copies_of_played(game: &mut Game, source: Card, chosen: Vec<Card>) {
+    let candidates: Vec<Card> = distinct(game.current_player_read_only().played.iter().copied())
+        .into_iter()
+        .filter(|card| !chosen.contains(card) && game.supply.count(*card).unwrap_or(0) > 0)
+        .collect();
+    if chosen.len() == 3 || candidates.is_empty() {
+        game.then(move |game| {
+            for card in chosen {
+                game.gain_if_available(game.curr_player_index, card, GainDestination::Discard)?;
+            }
+            Ok(())
+        });
+        return;
+    }
+    let mut choices: Vec<String> = candidates
+        .iter()
+        .map(|card| format!("Gain a copy of {}", card.name()))
+        .collect();
+    choices.push("Stop choosing".to_owned());
+    game.ask(
+        game.curr_player_index,
+        source,
+        Prompt::Options { options: choices },
+        move |game, _, answer| {
+            let mut chosen = chosen;
+            let done = match candidates.get(answer.option()) {
+                Some(&card) => {
+                    chosen.push(card);
+                    false
+                }
+                None => true,
+            };
+            if done {
+                for card in chosen {
+                    game.gain_if_available(game.curr_player_index, card, GainDestination::Discard)?;
+                }
+            } else {
+                gain_copies_of_played(game, source, chosen);
+            }
+            Ok(())
+        },
+    );
+}
+
+fn ball(game: &mut Game, source: Card) -> Result<(), GameError> {
+    game.current_player().take_minus_coin_token();
+    for _ in 0..2 {
+        game.ask(
+            game.curr_player_index,
+            source,
+            gain_from_supply(Cost::coins(4), CardFilter::Any),
+            |game, player_index, answer| {
+                game.gain_card(player_index, answer.supply_card(), GainDestination::Discard)
+            },
+        );
+    }
+    Ok(())
+}
+
+/// Raid isn't an Attack, so every other player is affected.
+fn raid(game: &mut Game, _source: Card) -> Result<(), GameError> {
+    let owner = game.curr_player_index;
+    let silver = card("Silver");
+    let silvers = game.players[owner]
+        .played
+        .iter()
+        .filter(|&&card| card == silver)
+        .count();
+    for _ in 0..silvers {
+        game.gain_if_available(owner, silver, GainDestination::Discard)?;
+    }
+    let num_players = game.players.len();
+    for offset in 1..num_players {
+        game.players[(owner + offset) % num_players].minus_card_token = true;
+    }
+    Ok(())
+}
+
+fn seaway(game: &mut Game, source: Card) -> Result<(), GameError> {
+    game.ask(
+        game.curr_player_index,
+        source,
+        gain_from_supply(Cost::coins(4), CardFilter::OfType(CardType::Action)),
+        |game, player_index, answer| {
+            let gained = answer.supply_card();
+            game.gain_card(player_index, gained, GainDestination::Discard)?;
+            if let Some(pile) = game.supply.pile(gained) {
+                let pile = pile.name.clone();
+                game.players[player_index]
+                    .tokens
+                    .insert(Token::PlusBuy, pile);
+            }
+            Ok(())
+        },
+    );
+    Ok(())
+}
+
+fn trade(game: &mut Game, source: Card) -> Result<(), GameError> {
+    game.ask(
+        game.curr_player_index,
+        source,
+        from_hand(0, 2),
+        |game, player_index, answer| {
+            for trashed in answer.cards() {
+                game.trash_card(player_index, trashed);
+                game.gain_if_available(player_index, card("Silver"), GainDestination::Discard)?;
+            }
+            Ok(())
+        },
+    );
+    Ok(())
+}
+
+fn lost_arts(game: &mut Game, source: Card) -> Result<(), GameError> {
+    move_token(
+        game,
+        game.curr_player_index,
+        source,
+        Token::PlusAction,
+        false,
+    );
+    Ok(())
+}
+
+fn training(game: &mut Game, source: Card) -> Result<(), GameError> {
+    move_token(game, game.curr_player_index, source, Token::PlusCoin, false);
+    Ok(())
+}
+
+/// The inherited card is set aside for good; from then on the player's Estates
+/// play as it on their turns (see `Player::inherited_by`).
+fn inheritance(game: &mut Game, source: Card) -> Result<(), GameError> {
+    let candidates: Vec<Card> = game
+        .gainable_cards(
+            &Cost::coins(4),
+            false,
+            &CardFilter::OfType(CardType::Action),
+        )
+        .into_iter()
+        .filter(|card| !card.is(CardType::Victory) && card.is_implemented())
+        .collect();
+    game.ask(
+        game.curr_player_index,
+        source,
+        Prompt::Options {
+            options: candidates
+                .iter()
+                .map(|card| format!("Set aside {} for your Estates", card.name()))
+                .collect(),
+        },
+        move |game, player_index, answer| {
+            let inherited = candidates[answer.option()];
+            game.supply.take_card(inherited)?;
+            game.players[player_index].inherited = Some(inherited);
+            Ok(())
+        },
+    );
+    Ok(())
+}
+
+fn pathfinding(game: &mut Game, source: Card) -> Result<(), GameError> {
+    move_token(game, game.curr_player_index, source, Token::PlusCard, false);
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::cards::testing::{choose_option, play, scenario};
+    use crate::{GameMove, GamePhase};
+
+    const KINGDOM: [&str; 11] = [
+        "Page",
+        "Peasant",
+        "Ratcatcher",
+        "Raze",
+        "Guide",
+        "Duplicate",
+        "Magpie",
+        "Giant",
+        "Relic",
+        "Royal Carriage",
+        "Pathfinding",
+    ];
+
+    /// Chooses the option that mentions `text`.
+    fn choose_option_with(game: &mut Game, text: &str) {
+        let Some(Prompt::Options { options }) = game.pending_decision().map(|d| &d.prompt) else {
+            panic!("no options to choose from");
+        };
+        let option_index = options
+            .iter()
+            .position(|option| option.contains(text))
+            .unwrap();
+        choose_option(game, option_index);
+    }
+
+    #[test]
+    fn reserve_cards_wait_on_the_tavern_mat_until_called() {
+        let mut game = scenario(&KINGDOM, [&["Duplicate"], &[]]);
+        let player_index = game.curr_player_index;
+        let duplicate = card("Duplicate");
+        play(&mut game, "Duplicate");
+        let player = game.current_player_read_only();
+        assert!(player.played.is_empty());
+        assert!(player.has_on_mat(Mat::Tavern, duplicate));
+
+        game.gain_card(player_index, card("Silver"), GainDestination::Discard)
+            .unwrap();
+        game.run_pending().unwrap();
+        choose_option(&mut game, 0);
+
+        let player = game.current_player_read_only();
+        assert_eq!(player.discard, [card("Silver"), card("Silver")]);
+        assert_eq!(player.played, [duplicate]);
+        assert!(!player.has_on_mat(Mat::Tavern, duplicate));
+    }
+
+    #[test]
+    fn travellers_can_be_exchanged_as_they_are_discarded() {
+        let mut game = scenario(&KINGDOM, [&["Page"], &[]]);
+        let player_index = game.curr_player_index;
+        let (page, treasure_hunter) = (card("Page"), card("Treasure Hunter"));
+        let pages = game.supply.count(page).unwrap();
+        let treasure_hunters = game.supply.count(treasure_hunter).unwrap();
+        play(&mut game, "Page");
+        for game_move in [
+            GameMove::EndActions,
+            GameMove::EndTreasures,
+            GameMove::EndBuys,
+        ] {
+            game.accept_move(player_index, game_move).unwrap();
+        }
+
+        choose_option(&mut game, 0);
+
+        let player = game.current_player_read_only();
+        assert!(player.played.is_empty());
+        assert_eq!(player.discard, [treasure_hunter]);
+        // An exchange isn't a gain or a buy
+        assert!(game.history.current_turn.gained.is_empty());
+        assert_eq!(game.supply.count(page), Some(pages + 1));
+        assert_eq!(
+            game.supply.count(treasure_hunter),
+            Some(treasure_hunters - 1)
+        );
+    }
+
+    #[test]
+    fn a_plus_card_token_draws_for_each_card_played_from_its_pile() {
+        let mut game = scenario(&KINGDOM, [&["Ratcatcher"], &[]]);
+        let player_index = game.curr_player_index;
+        game.game_phase = GamePhase::BuyPhase;
+        game.current_player().coins = 8;
+        game.accept_move(
+            player_index,
+            GameMove::BuyCard {
+                card: card("Pathfinding"),
+            },
+        )
+        .unwrap();
+        choose_option_with(&mut game, "Ratcatcher");
+        assert_eq!(
+            game.tokens_on(player_index, card("Ratcatcher")),
+            [Token::PlusCard]
+        );
+
+        game.game_phase = GamePhase::ActionPhase;
+        play(&mut game, "Ratcatcher");
+
+        // One card from Ratcatcher and one from the token
+        assert_eq!(game.current_player_read_only().hand.len(), 2);
+    }
+
+    #[test]
+    fn giant_attacks_every_other_time_its_journey_token_turns() {
+        let mut game = scenario(&KINGDOM, [&["Giant", "Giant"], &[]]);
+        game.current_player().actions = 2;
+
+        play(&mut game, "Giant");
+        assert_eq!(game.current_player_read_only().coins, 1);
+        assert!(game.trash.cards.is_empty());
+
+        play(&mut game, "Giant");
+        assert_eq!(game.current_player_read_only().coins, 6);
+        assert_eq!(game.trash.cards, [card("Silver")]);
+    }
+
+    #[test]
+    fn relic_gives_each_other_player_their_minus_card_token() {
+        let mut game = scenario(&KINGDOM, [&["Relic"], &[]]);
+        let player_index = game.curr_player_index;
+        let other = (player_index + 1) % 2;
+        game.accept_move(player_index, GameMove::EndActions)
+            .unwrap();
+
+        play(&mut game, "Relic");
+
+        assert!(game.players[other].minus_card_token);
+        assert!(!game.current_player_read_only().minus_card_token);
+        assert_eq!(game.current_player_read_only().coins, 2);
+    }
+}
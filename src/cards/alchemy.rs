@@ -0,0 +1,399 @@
+//! Behaviours for the Alchemy kingdom cards. Herbalist and Alchemist act as
+//! they leave play, so they listen for the end of the Buy phase, just before
+//! clean-up.
+
+use super::{
+    before_clean_up, card, from_hand, options, reveal_until, Behaviour, Card, CardFilter, CardType,
+    Cost,
+};
+use crate::{GainDestination, Game, GameError, Player, Prompt};
+
+pub(super) fn behaviours() -> Vec<(&'static str, Behaviour)> {
+    let play = |on_play| Behaviour {
+        on_play: Some(on_play),
+        ..Behaviour::default()
+    };
+    vec![
+        ("Transmute", play(transmute)),
+        (
+            "Vineyard",
+            Behaviour {
+                victory_points: Some(vineyard),
+                ..Behaviour::default()
+            },
+        ),
+        ("Herbalist", play(herbalist)),
+        ("Apothecary", play(apothecary)),
+        ("Scrying Pool", play(scrying_pool)),
+        ("University", play(university)),
+        ("Alchemist", play(alchemist)),
+        ("Familiar", play(familiar)),
+        ("Philosopher's Stone", play(philosophers_stone)),
+        ("Golem", play(golem)),
+        ("Apprentice", play(apprentice)),
+        ("Possession", play(possession)),
+    ]
+}
+
+fn transmute(game: &mut Game, source: Card) -> Result<(), GameError> {
+    game.ask(
+        game.curr_player_index,
+        source,
+        from_hand(1, 1),
+        |game, player_index, answer| {
+            for trashed in answer.cards() {
+                game.trash_card(player_index, trashed);
+                let gains = [
+                    (CardType::Action, "Duchy"),
+                    (CardType::Treasure, "Transmute"),
+                    (CardType::Victory, "Gold"),
+                ];
+                for (card_type, gained) in gains {
+                    if trashed.is(card_type) {
+                        game.gain_if_available(
+                            player_index,
+                            card(gained),
+                            GainDestination::Discard,
+                        )?;
+                    }
+                }
+            }
+            Ok(())
+        },
+    );
+    Ok(())
+}
+
+fn vineyard(player: &Player) -> i32 {
+    let actions = player
+        .all_cards()
+        .filter(|card| card.is(CardType::Action))
+        .count();
+    (actions / 3) as i32
+}
+
+fn herbalist(game: &mut Game, source: Card) -> Result<(), GameError> {
+    before_clean_up(game, move |game, player_index| {
+        let mut treasures: Vec<Card> = game.players[player_index]
+            .played
+            .iter()
+            .copied()
+            .filter(|card| card.is(CardType::Treasure))
+            .collect();
+        treasures.sort_by_key(|card| card.name());
+        treasures.dedup();
+        if treasures.is_empty() {
+            return;
+        }
+        let mut choices: Vec<String> = treasures
+            .iter()
+            .map(|treasure| format!("Put {} onto your deck", treasure.name()))
+            .collect();
+        choices.push("Discard them as normal".to_owned());
+        game.ask(
+            player_index,
+            source,
+            Prompt::Options { options: choices },
+            move |game, player_index, answer| {
+                let player = &mut game.players[player_index];
+                if let Some(&treasure) = treasures.get(answer.option()) {
+                    if let Some(treasure) = player.take_from_play(treasure) {
+                        player.put_on_deck(treasure);
+                    }
+                }
+                Ok(())
+            },
+        );
+    });
+    Ok(())
+}
+
+fn apothecary(game: &mut Game, _source: Card) -> Result<(), GameError> {
+    let player = game.current_player();
+    let revealed = player.reveal_from_deck(4);
+    // Whatever is left goes back in the order it was revealed
+    for &card in revealed.iter().rev() {
+        if matches!(card.name(), "Copper" | "Potion") {
+            player.add_to_hand(card);
+        } else {
+            player.put_on_deck(card);
+        }
+    }
+    Ok(())
+}
+
+fn scrying_pool(game: &mut Game, source: Card) -> Result<(), GameError> {
+    let owner = game.curr_player_index;
+    let targets = std::iter::once(owner).chain(game.attacked_player_indices());
+    for target in targets.collect::<Vec<_>>() {
+        let Some(top) = game.players[target].take_from_deck() else {
+            continue;
+        };
+        game.players[target].put_on_deck(top);
+        game.ask(
+            owner,
+            source,
+            Prompt::Options {
+                options: vec![
+                    format!("Discard Player {target}'s {}", top.name()),
+                    format!("Put Player {target}'s {} back", top.name()),
+                ],
+            },
+            move |game, _, answer| {
+                if answer.option() == 0 {
+                    let player = &mut game.players[target];
+                    if let Some(top) = player.deck.pop() {
+                        player.add_to_discard(top);
+                    }
+                }
+                Ok(())
+            },
+        );
+    }
+    game.then(move |game| {
+        let player = &mut game.players[owner];
+        while let Some(card) = player.take_from_deck() {
+            player.add_to_hand(card);
+            if !card.is(CardType::Action) {
+                break;
+            }
+        }
+        Ok(())
+    });
+    Ok(())
+}
+
+fn university(game: &mut Game, source: Card) -> Result<(), GameError> {
+    game.ask(
+        game.curr_player_index,
+        source,
+        options(&["Gain an Action card costing up to $5", "Don't gain a card"]),
+        move |game, player_index, answer| {
+            if answer.option() == 0 {
+                game.ask(
+                    player_index,
+                    source,
+                    Prompt::CardFromSupply {
+                        max_cost: Cost::coins(5),
+                        exact: false,
+                        filter: CardFilter::OfType(CardType::Action),
+                    },
+                    |game, player_index, answer| {
+                        game.gain_card(player_index, answer.supply_card(), GainDestination::Discard)
+                    },
+                );
+            }
+            Ok(())
+        },
+    );
+    Ok(())
+}
+
+fn alchemist(game: &mut Game, source: Card) -> Result<(), GameError> {
+    before_clean_up(game, move |game, player_index| {
+        let player = &game.players[player_index];
+        if !player.played.contains(&card("Potion")) || !player.played.contains(&source) {
+            return;
+        }
+        game.ask(
+            player_index,
+            source,
+            options(&["Put Alchemist onto your deck", "Discard it as normal"]),
+            move |game, player_index, answer| {
+                let player = &mut game.players[player_index];
+                if answer.option() == 0 {
+                    if let Some(alchemist) = player.take_from_play(source) {
+                        player.put_on_deck(alchemist);
+                    }
+                }
+                Ok(())
+            },
+        );
+    });
+    Ok(())
+}
+
+fn familiar(game: &mut Game, _source: Card) -> Result<(), GameError> {
+    for player_index in game.attacked_player_indices() {
+        game.gain_if_available(player_index, card("Curse"), GainDestination::Discard)?;
+    }
+    Ok(())
+}
+
+fn philosophers_stone(game: &mut Game, _source: Card) -> Result<(), GameError> {
+    let player = game.current_player();
+    let counted = player.deck.len() + player.discard.len();
+    player.coins += (counted / 5) as u32;
+    Ok(())
+}
+
+fn golem(game: &mut Game, _source: Card) -> Result<(), GameError> {
+    let player_index = game.curr_player_index;
+    let mut actions = Vec::new();
+    let mut revealed = Vec::new();
+    while actions.len() < 2 {
+        let (found, others) = reveal_until(game, player_index, |_, card| {
+            card.is(CardType::Action) && card.name() != "Golem"
+        });
+        revealed.extend(others);
+        let Some(action) = found else {
+            break;
+        };
+        actions.push(action);
+    }
+    game.players[player_index].discard.extend(revealed);
+    // Each Action resolves completely, decisions included, before the next
+    for action in actions {
+        game.then(move |game| {
+            game.play_card(action)?;
+            game.resolve_play(action)
+        });
+    }
+    Ok(())
+}
+
+fn apprentice(game: &mut Game, source: Card) -> Result<(), GameError> {
+    game.ask(
+        game.curr_player_index,
+        source,
+        from_hand(1, 1),
+        |game, player_index, answer| {
+            for trashed in answer.cards() {
+                let cost = game.cost_of(trashed);
+                game.trash_card(player_index, trashed);
+                let potion_bonus = if cost.potions > 0 { 2 } else { 0 };
+                game.players[player_index].draw(cost.coins as usize + potion_bonus);
+            }
+            Ok(())
+        },
+    );
+    Ok(())
+}
+
+/// The possessed turn itself is run by the engine, which hands the player's
+/// decisions, gains and Debt to the possessor.
+fn possession(game: &mut Game, _source: Card) -> Result<(), GameError> {
+    if game.players.len() > 1 {
+        game.possessions.push(game.curr_player_index);
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::cards::testing::{choose_cards, choose_option, end_turn, play, scenario};
+    use crate::GameMove;
+
+    const KINGDOM: [&str; 10] = [
+        "Transmute",
+        "Vineyard",
+        "Herbalist",
+        "Apothecary",
+        "Scrying Pool",
+        "University",
+        "Alchemist",
+        "Familiar",
+        "Golem",
+        "Apprentice",
+    ];
+
+    /// Plays the named Treasures and moves on to the Buy phase.
+    fn pay_with(game: &mut Game, treasures: &[&str]) {
+        let player_index = game.curr_player_index;
+        game.accept_move(player_index, GameMove::EndActions)
+            .unwrap();
+        for &treasure in treasures {
+            play(game, treasure);
+        }
+        game.accept_move(player_index, GameMove::EndTreasures)
+            .unwrap();
+    }
+
+    #[test]
+    fn the_potion_pile_is_only_set_out_for_potion_costs() {
+        let game = scenario(&KINGDOM, [&[], &[]]);
+        assert!(game.supply.pile_named("Potion").is_some());
+
+        // A full kingdom, so that none of it is picked at random
+        let mut kingdom = crate::DEFAULT_KINGDOM.to_vec();
+        kingdom.extend(["Herbalist", "Apprentice"]);
+        let game = scenario(&kingdom, [&[], &[]]);
+        assert!(game.supply.pile_named("Potion").is_none());
+    }
+
+    #[test]
+    fn buying_a_potion_cost_takes_a_potion() {
+        let mut game = scenario(&KINGDOM, [&["Potion", "Copper", "Copper"], &[]]);
+        let player_index = game.curr_player_index;
+        pay_with(&mut game, &["Potion", "Copper", "Copper"]);
+
+        game.accept_move(
+            player_index,
+            GameMove::BuyCard {
+                card: card("University"),
+            },
+        )
+        .unwrap();
+
+        let player = game.current_player_read_only();
+        assert_eq!((player.coins, player.potions), (0, 0));
+        assert_eq!(player.discard, [card("University")]);
+    }
+
+    #[test]
+    fn coins_alone_cannot_buy_a_potion_cost() {
+        let treasures = ["Copper"; 4];
+        let mut game = scenario(&KINGDOM, [&treasures, &[]]);
+        let player_index = game.curr_player_index;
+        pay_with(&mut game, &treasures);
+
+        let university = card("University");
+        assert!(!game
+            .legal_moves()
+            .contains(&GameMove::BuyCard { card: university }));
+        let result = game.accept_move(player_index, GameMove::BuyCard { card: university });
+        assert!(result.is_err());
+    }
+
+    #[test]
+    fn gaining_up_to_a_coin_cost_leaves_out_potion_costs() {
+        let mut game = scenario(&KINGDOM, [&["University"], &[]]);
+        play(&mut game, "University");
+
+        choose_option(&mut game, 0);
+
+        let moves = game.legal_moves();
+        assert!(moves.contains(&GameMove::GainCard {
+            card: card("Herbalist")
+        }));
+        assert!(!moves.contains(&GameMove::GainCard {
+            card: card("Apothecary")
+        }));
+    }
+
+    #[test]
+    fn apprentice_draws_two_more_for_trashing_a_potion_cost() {
+        let mut game = scenario(&KINGDOM, [&["Apprentice", "University"], &[]]);
+        play(&mut game, "Apprentice");
+
+        choose_cards(&mut game, &["University"]);
+
+        // $2 and a Potion
+        assert_eq!(game.current_player_read_only().hand.len(), 4);
+    }
+
+    #[test]
+    fn alchemist_can_go_back_on_the_deck_with_a_potion_in_play() {
+        let mut game = scenario(&KINGDOM, [&["Alchemist", "Potion"], &[]]);
+        let owner = game.curr_player_index;
+        play(&mut game, "Alchemist");
+        pay_with(&mut game, &["Potion"]);
+        game.accept_move(owner, GameMove::EndBuys).unwrap();
+
+        choose_option(&mut game, 0);
+        end_turn(&mut game);
+
+        assert!(game.players[owner].hand.contains(&card("Alchemist")));
+    }
+}
@@ -0,0 +1,2128 @@
+//! Behaviours for the Allies kingdom cards and Allies. Favors are kept with the
+//! player's cards: Liaisons hand them out, and the game's one Ally watches for
+//! events through `Behaviour::on_event`, like a Landmark, to offer spending
+//! them. Cards in a rotating split pile rotate it with `Supply::rotate`.
+
+use std::mem::take;
+
+use super::{
+    card, choose_one, choose_to_play, discard_all, discard_down_to, distinct, from_discard_of,
+    from_hand, from_hand_of, gain_from_supply, gain_to_discard, next_turn, options, play_taken,
+    take_extra_turn, Behaviour, Card, CardFilter, CardType, Cost,
+};
+use crate::{Event, Expiry, ExtraTurn, GainDestination, Game, GameError, Player, Prompt};
+
+pub(super) fn behaviours() -> Vec<(&'static str, Behaviour)> {
+    let play = |on_play| Behaviour {
+        on_play: Some(on_play),
+        ..Behaviour::default()
+    };
+    let event = |on_event| Behaviour {
+        on_event: Some(on_event),
+        ..Behaviour::default()
+    };
+    vec![
+        ("Bauble", play(bauble)),
+        (
+            "Sycophant",
+            Behaviour {
+                on_gain: Some(sycophant_favors),
+                on_trash: Some(sycophant_favors),
+                ..play(sycophant)
+            },
+        ),
+        ("Importer", play(importer)),
+        ("Merchant Camp", play(merchant_camp)),
+        ("Sentinel", play(sentinel)),
+        ("Underling", play(underling)),
+        ("Broker", play(broker)),
+        ("Carpenter", play(carpenter)),
+        ("Courier", play(courier)),
+        ("Innkeeper", play(innkeeper)),
+        ("Royal Galley", play(royal_galley)),
+        ("Modify", play(modify)),
+        ("Skirmisher", play(skirmisher)),
+        ("Specialist", play(specialist)),
+        ("Swap", play(swap)),
+        ("Barbarian", play(barbarian)),
+        ("Capital City", play(capital_city)),
+        ("Contract", play(contract)),
+        ("Emissary", play(emissary)),
+        ("Galleria", play(galleria)),
+        ("Guildmaster", play(guildmaster)),
+        ("Highwayman", play(highwayman)),
+        ("Hunter", play(hunter)),
+        ("Marquis", play(marquis)),
+        // Rotating split piles
+        ("Town Crier", play(town_crier)),
+        ("Blacksmith", play(blacksmith)),
+        ("Miller", play(miller)),
+        ("Elder", play(elder)),
+        ("Herb Gatherer", play(herb_gatherer)),
+        ("Acolyte", play(acolyte)),
+        ("Sorceress", play(sorceress)),
+        ("Sibyl", play(sibyl)),
+        ("Battle Plan", play(battle_plan)),
+        ("Archer", play(archer)),
+        ("Warlord", play(warlord)),
+        (
+            "Territory",
+            Behaviour {
+                victory_points: Some(territory),
+                on_gain: Some(territory_on_gain),
+                ..Behaviour::default()
+            },
+        ),
+        ("Tent", play(tent)),
+        ("Garrison", play(garrison)),
+        ("Hill Fort", play(hill_fort)),
+        ("Stronghold", play(stronghold)),
+        ("Old Map", play(old_map)),
+        ("Voyage", play(voyage)),
+        ("Sunken Treasure", play(sunken_treasure)),
+        ("Distant Shore", play(distant_shore)),
+        ("Student", play(student)),
+        ("Conjurer", play(conjurer)),
+        ("Sorcerer", play(sorcerer)),
+        (
+            "Lich",
+            Behaviour {
+                on_trash: Some(lich_on_trash),
+                ..play(lich)
+            },
+        ),
+        // Allies
+        ("Architects' Guild", event(architects_guild)),
+        ("Band of Nomads", event(band_of_nomads)),
+        ("Cave Dwellers", event(cave_dwellers)),
+        ("Circle of Witches", event(circle_of_witches)),
+        ("City-state", event(city_state)),
+        ("Coastal Haven", event(coastal_haven)),
+        ("Crafters' Guild", event(crafters_guild)),
+        ("Desert Guides", event(desert_guides)),
+        ("Family of Inventors", event(family_of_inventors)),
+        ("Fellowship of Scribes", event(fellowship_of_scribes)),
+        ("Forest Dwellers", event(forest_dwellers)),
+        ("Gang of Pickpockets", event(gang_of_pickpockets)),
+        ("Island Folk", event(island_folk)),
+        ("League of Bankers", event(league_of_bankers)),
+        ("League of Shopkeepers", event(league_of_shopkeepers)),
+        ("Market Towns", event(market_towns)),
+        ("Mountain Folk", event(mountain_folk)),
+        // Both act while their holder shuffles, in Player::spend_favors_on_shuffle
+        ("Order of Astrologers", Behaviour::default()),
+        ("Order of Masons", Behaviour::default()),
+        ("Peaceful Cult", event(peaceful_cult)),
+        (
+            "Plateau Shepherds",
+            Behaviour {
+                score: Some(plateau_shepherds),
+                ..Behaviour::default()
+            },
+        ),
+        ("Trappers' Lodge", event(trappers_lodge)),
+        ("Woodworkers' Guild", event(woodworkers_guild)),
+    ]
+}
+
+/// Lets the current player play an Action or Treasure from their discard pile.
+fn offer_play_from_discard(game: &mut Game, source: Card, playable: fn(Card) -> bool) {
+    let candidates = game
+        .current_player_read_only()
+        .discard
+        .iter()
+        .copied()
+        .filter(|&card| playable(card))
+        .collect();
+    choose_to_play(game, source, candidates, |game, chosen| {
+        from_discard_of(game.current_player(), chosen);
+        play_taken(game, chosen)
+    });
+}
+
+fn is_action_or_treasure(card: Card) -> bool {
+    card.is(CardType::Action) || card.is(CardType::Treasure)
+}
+
+/// Offers to rotate the supply pile with the given name, if that would change
+/// which card is on top.
+fn offer_rotate(game: &mut Game, source: Card, pile: &'static str) {
+    let rotatable = game
+        .supply
+        .pile_named(pile)
+        .is_some_and(|pile| pile.cards.iter().any(|&card| Some(card) != pile.top()));
+    if !rotatable {
+        return;
+    }
+    game.ask(
+        game.curr_player_index,
+        source,
+        Prompt::Options {
+            options: vec![format!("Rotate the {pile}"), "Don't rotate them".to_owned()],
+        },
+        move |game, _, answer| {
+            if answer.option() == 0 {
+                game.supply.rotate(pile);
+            }
+            Ok(())
+        },
+    );
+}
+
+/// Offers to put a card the player has just gained onto their deck.
+fn offer_onto_deck(game: &mut Game, player_index: usize, source: Card, gained: Card) {
+    game.ask(
+        player_index,
+        source,
+        Prompt::Options {
+            options: vec![
+                format!("Put {} onto your deck", gained.name()),
+                "Leave it where it is".to_owned(),
+            ],
+        },
+        move |game, player_index, answer| {
+            let player = &mut game.players[player_index];
+            if answer.option() == 0 {
+                if let Some(gained) = player.take_gained(gained) {
+                    player.put_on_deck(gained);
+                }
+            }
+            Ok(())
+        },
+    );
+}
+
+/// Offers to put a card onto its owner's deck when it would be discarded from
+/// play at the end of the turn. It leaves play just before clean-up.
+fn onto_deck_from_play(game: &mut Game, source: Card) {
+    let owner = game.curr_player_index;
+    game.add_trigger(Expiry::EndOfTurn, move |game, event| {
+        let Event::BuyPhaseEnd { player_index } = event else {
+            return Ok(true);
+        };
+        if player_index != owner {
+            return Ok(true);
+        }
+        game.ask(
+            owner,
+            source,
+            Prompt::Options {
+                options: vec![
+                    format!("Put {} onto your deck", source.name()),
+                    "Discard it as normal".to_owned(),
+                ],
+            },
+            move |game, player_index, answer| {
+                let player = &mut game.players[player_index];
+                if answer.option() == 0 {
+                    if let Some(card) = player.take_from_play(source) {
+                        player.put_on_deck(card);
+                    }
+                }
+                Ok(())
+            },
+        );
+        Ok(false)
+    });
+}
+
+/// Asks a player to name a card, from among the cards they own.
+fn name_own_card(
+    game: &mut Game,
+    player_index: usize,
+    source: Card,
+    then: impl FnOnce(&mut Game, usize, Card) -> Result<(), GameError> + Send + 'static,
+) {
+    let candidates = distinct(game.players[player_index].all_cards());
+    game.ask(
+        player_index,
+        source,
+        Prompt::Options {
+            options: candidates
+                .iter()
+                .map(|card| format!("Name {}", card.name()))
+                .collect(),
+        },
+        move |game, player_index, answer| {
+            let named = candidates[answer.option()];
+            then(game, player_index, named)
+        },
+    );
+}
+
+const BAUBLE_OPTIONS: [&str; 4] = [
+    "+1 Buy",
+    "+$1",
+    "+1 Favor",
+    "you may put cards you gain this turn onto your deck",
+];
+
+fn bauble(game: &mut Game, source: Card) -> Result<(), GameError> {
+    let pairs: Vec<(usize, usize)> = (0..BAUBLE_OPTIONS.len())
+        .flat_map(|first| (first + 1..BAUBLE_OPTIONS.len()).map(move |second| (first, second)))
+        .collect();
+    let choices = pairs
+        .iter()
+        .map(|&(first, second)| format!("{}; {}", BAUBLE_OPTIONS[first], BAUBLE_OPTIONS[second]))
+        .collect();
+    game.ask(
+        game.curr_player_index,
+        source,
+        Prompt::Options { options: choices },
+        move |game, player_index, answer| {
+            let (first, second) = pairs[answer.option()];
+            for choice in [first, second] {
+                match choice {
+                    0 => game.players[player_index].buys += 1,
+                    1 => game.players[player_index].coins += 1,
+                    2 => game.players[player_index].favors += 1,
+                    _ => gains_onto_deck(game, player_index, source),
+                }
+            }
+            Ok(())
+        },
+    );
+    Ok(())
+}
+
+/// For the rest of the turn, offers to put each card the player gains onto their deck.
+fn gains_onto_deck(game: &mut Game, owner: usize, source: Card) {
+    game.add_trigger(Expiry::EndOfTurn, move |game, event| {
+        if let Event::Gain { player_index, card } = event {
+            if player_index == owner {
+                offer_onto_deck(game, owner, source, card);
+            }
+        }
+        Ok(true)
+    });
+}
+
+fn sycophant(game: &mut Game, source: Card) -> Result<(), GameError> {
+    game.ask(
+        game.curr_player_index,
+        source,
+        from_hand(3, 3),
+        |game, player_index, answer| {
+            let discarded = answer.cards();
+            if !discarded.is_empty() {
+                game.players[player_index].coins += 3;
+            }
+            discard_all(game, player_index, discarded);
+            Ok(())
+        },
+    );
+    Ok(())
+}
+
+fn sycophant_favors(game: &mut Game, player_index: usize, _sycophant: Card) {
+    game.players[player_index].favors += 2;
+}
+
+fn importer(game: &mut Game, source: Card) -> Result<(), GameError> {
+    next_turn(game, source, move |game, player_index| {
+        gain_to_discard(game, player_index, source, Cost::coins(5));
+        Ok(())
+    });
+    Ok(())
+}
+
+fn merchant_camp(game: &mut Game, source: Card) -> Result<(), GameError> {
+    onto_deck_from_play(game, source);
+    Ok(())
+}
+
+fn sentinel(game: &mut Game, source: Card) -> Result<(), GameError> {
+    let looked_at = game.current_player().reveal_from_deck(5);
+    sentinel_look(game, game.curr_player_index, source, looked_at, 2);
+    Ok(())
+}
+
+/// Decides what happens to each card Sentinel looked at in turn, while it can
+/// still trash some of them.
+fn sentinel_look(
+    game: &mut Game,
+    player_index: usize,
+    source: Card,
+    mut remaining: Vec<Card>,
+    trashes_left: usize,
+) {
+    let Some(card) = remaining.pop() else {
+        return;
+    };
+    if trashes_left == 0 {
+        let player = &mut game.players[player_index];
+        player.put_on_deck(card);
+        for card in remaining.into_iter().rev() {
+            player.put_on_deck(card);
+        }
+        return;
+    }
+    game.ask(
+        player_index,
+        source,
+        Prompt::Options {
+            options: vec![
+                format!("Trash {}", card.name()),
+                format!("Put {} back", card.name()),
+            ],
+        },
+        move |game, player_index, answer| {
+            let trashes_left = if answer.option() == 0 {
+                game.trash_card(player_index, card);
+                trashes_left - 1
+            } else {
+                game.players[player_index].put_on_deck(card);
+                trashes_left
+            };
+            sentinel_look(game, player_index, source, remaining, trashes_left);
+            Ok(())
+        },
+    );
+}
+
+fn underling(game: &mut Game, _source: Card) -> Result<(), GameError> {
+    game.current_player().favors += 1;
+    Ok(())
+}
+
+fn broker(game: &mut Game, source: Card) -> Result<(), GameError> {
+    game.ask(
+        game.curr_player_index,
+        source,
+        from_hand(1, 1),
+        move |game, player_index, answer| {
+            for trashed in answer.cards() {
+                let amount = game.cost_of(trashed).coins;
+                game.trash_card(player_index, trashed);
+                choose_one(
+                    game,
+                    source,
+                    &[
+                        &format!("+{amount} Cards"),
+                        &format!("+{amount} Actions"),
+                        &format!("+${amount}"),
+                        &format!("+{amount} Favors"),
+                    ],
+                    move |game, player_index, ability| {
+                        let player = &mut game.players[player_index];
+                        match ability {
+                            0 => {
+                                player.draw(amount as usize);
+                            }
+                            1 => player.actions += amount,
+                            2 => player.coins += amount,
+                            _ => player.favors += amount,
+                        }
+                        Ok(())
+                    },
+                );
+            }
+            Ok(())
+        },
+    );
+    Ok(())
+}
+
+fn carpenter(game: &mut Game, source: Card) -> Result<(), GameError> {
+    let player_index = game.curr_player_index;
+    if game.supply.num_empty_supply_piles() == 0 {
+        game.current_player().actions += 1;
+        gain_to_discard(game, player_index, source, Cost::coins(4));
+        return Ok(());
+    }
+    game.ask(
+        player_index,
+        source,
+        from_hand(1, 1),
+        move |game, player_index, answer| {
+            for trashed in answer.cards() {
+                let cost = game.cost_of(trashed);
+                game.trash_card(player_index, trashed);
+                gain_to_discard(game, player_index, source, cost.plus_coins(2));
+            }
+            Ok(())
+        },
+    );
+    Ok(())
+}
+
+fn courier(game: &mut Game, source: Card) -> Result<(), GameError> {
+    let player = game.current_player();
+    if let Some(top) = player.take_from_deck() {
+        player.add_to_discard(top);
+    }
+    offer_play_from_discard(game, source, is_action_or_treasure);
+    Ok(())
+}
+
+fn innkeeper(game: &mut Game, source: Card) -> Result<(), GameError> {
+    choose_one(
+        game,
+        source,
+        &[
+            "+1 Card",
+            "+3 Cards, then discard 3 cards",
+            "+5 Cards, then discard 6 cards",
+        ],
+        move |game, player_index, ability| {
+            let (draw, discard) = match ability {
+                0 => (1, 0),
+                1 => (3, 3),
+                _ => (5, 6),
+            };
+            game.players[player_index].draw(draw);
+            if discard > 0 {
+                game.ask(
+                    player_index,
+                    source,
+                    from_hand(discard, discard),
+                    |game, player_index, answer| {
+                        discard_all(game, player_index, answer.cards());
+                        Ok(())
+                    },
+                );
+            }
+            Ok(())
+        },
+    );
+    Ok(())
+}
+
+fn royal_galley(game: &mut Game, source: Card) -> Result<(), GameError> {
+    let candidates = game
+        .current_player_read_only()
+        .hand
+        .iter()
+        .copied()
+        .filter(|card| card.is(CardType::Action) && !card.is(CardType::Duration))
+        .collect();
+    choose_to_play(game, source, candidates, move |game, action| {
+        from_hand_of(game.current_player(), action);
+        play_taken(game, action)?;
+        // Once it has resolved, the card is set aside to be played again
+        game.then(move |game| {
+            if let Some(action) = game.current_player().take_from_play(action) {
+                next_turn(game, source, move |game, _| play_taken(game, action));
+            }
+            Ok(())
+        });
+        Ok(())
+    });
+    Ok(())
+}
+
+fn modify(game: &mut Game, source: Card) -> Result<(), GameError> {
+    game.ask(
+        game.curr_player_index,
+        source,
+        from_hand(1, 1),
+        move |game, player_index, answer| {
+            for trashed in answer.cards() {
+                let max_cost = game.cost_of(trashed).plus_coins(2);
+                game.trash_card(player_index, trashed);
+                choose_one(
+                    game,
+                    source,
+                    &[
+                        "+1 Card and +1 Action",
+                        &format!("Gain a card costing up to {max_cost}"),
+                    ],
+                    move |game, player_index, ability| {
+                        if ability == 0 {
+                            let player = &mut game.players[player_index];
+                            player.draw(1);
+                            player.actions += 1;
+                        } else {
+                            gain_to_discard(game, player_index, source, max_cost);
+                        }
+                        Ok(())
+                    },
+                );
+            }
+            Ok(())
+        },
+    );
+    Ok(())
+}
+
+fn skirmisher(game: &mut Game, source: Card) -> Result<(), GameError> {
+    let owner = game.curr_player_index;
+    let attacked = game.attacked_player_indices();
+    game.add_trigger(Expiry::EndOfTurn, move |game, event| {
+        if let Event::Gain { player_index, card } = event {
+            if player_index == owner && card.is(CardType::Attack) {
+                for &victim in &attacked {
+                    discard_down_to(game, victim, source, 3);
+                }
+            }
+        }
+        Ok(true)
+    });
+    Ok(())
+}
+
+fn specialist(game: &mut Game, source: Card) -> Result<(), GameError> {
+    let candidates = game
+        .current_player_read_only()
+        .hand
+        .iter()
+        .copied()
+        .filter(|&card| is_action_or_treasure(card))
+        .collect();
+    choose_to_play(game, source, candidates, move |game, chosen| {
+        from_hand_of(game.current_player(), chosen);
+        play_taken(game, chosen)?;
+        choose_one(
+            game,
+            source,
+            &[
+                &format!("Play {} again", chosen.name()),
+                &format!("Gain a copy of {}", chosen.name()),
+            ],
+            move |game, player_index, ability| {
+                if ability == 0 {
+                    game.resolve_play(chosen)
+                } else {
+                    game.gain_if_available(player_index, chosen, GainDestination::Discard)
+                }
+            },
+        );
+        Ok(())
+    });
+    Ok(())
+}
+
+fn swap(game: &mut Game, source: Card) -> Result<(), GameError> {
+    game.ask(
+        game.curr_player_index,
+        source,
+        Prompt::CardsFromHand {
+            min: 0,
+            max: 1,
+            filter: CardFilter::OfType(CardType::Action),
+        },
+        move |game, player_index, answer| {
+            for returned in answer.cards() {
+                if game.supply.pile(returned).is_none() {
+                    game.players[player_index].add_to_hand(returned);
+                    continue;
+                }
+                game.supply.return_card(returned)?;
+                let choices: Vec<Card> = game
+                    .gainable_cards(
+                        &Cost::coins(5),
+                        false,
+                        &CardFilter::OfType(CardType::Action),
+                    )
+                    .into_iter()
+                    .filter(|&card| card != returned)
+                    .collect();
+                game.ask(
+                    player_index,
+                    source,
+                    Prompt::Options {
+                        options: choices
+                            .iter()
+                            .map(|card| format!("Gain {} to your hand", card.name()))
+                            .collect(),
+                    },
+                    move |game, player_index, answer| {
+                        let gained = choices[answer.option()];
+                        game.gain_card(player_index, gained, GainDestination::Hand)
+                    },
+                );
+            }
+            Ok(())
+        },
+    );
+    Ok(())
+}
+
+fn barbarian(game: &mut Game, source: Card) -> Result<(), GameError> {
+    for player_index in game.attacked_player_indices() {
+        let Some(top) = game.players[player_index].take_from_deck() else {
+            continue;
+        };
+        let cost = game.cost_of(top);
+        game.trash_card(player_index, top);
+        if cost.coins < 3 {
+            game.gain_if_available(player_index, card("Curse"), GainDestination::Discard)?;
+            continue;
+        }
+        let shares_a_type = move |candidate: Card| {
+            candidate
+                .info()
+                .types
+                .iter()
+                .any(|&card_type| top.is(card_type))
+        };
+        let choices: Vec<Card> = game
+            .gainable_cards(&cost.minus_coins(1), false, &CardFilter::Any)
+            .into_iter()
+            .filter(|&candidate| shares_a_type(candidate))
+            .collect();
+        game.ask(
+            player_index,
+            source,
+            Prompt::Options {
+                options: choices
+                    .iter()
+                    .map(|card| format!("Gain {}", card.name()))
+                    .collect(),
+            },
+            move |game, player_index, answer| {
+                game.gain_card(
+                    player_index,
+                    choices[answer.option()],
+                    GainDestination::Discard,
+                )
+            },
+        );
+    }
+    Ok(())
+}
+
+fn capital_city(game: &mut Game, source: Card) -> Result<(), GameError> {
+    if game.current_player_read_only().hand.len() >= 2 {
+        game.ask(
+            game.curr_player_index,
+            source,
+            options(&["Discard 2 cards for +$2", "Don't discard anything"]),
+            move |game, player_index, answer| {
+                if answer.option() == 0 {
+                    game.ask(
+                        player_index,
+                        source,
+                        from_hand(2, 2),
+                        |game, player_index, answer| {
+                            discard_all(game, player_index, answer.cards());
+                            game.players[player_index].coins += 2;
+                            Ok(())
+                        },
+                    );
+                }
+                Ok(())
+            },
+        );
+    }
+    game.then(move |game| {
+        if game.current_player_read_only().coins >= 2 {
+            game.ask(
+                game.curr_player_index,
+                source,
+                options(&["Pay $2 for +2 Cards", "Don't pay"]),
+                |game, player_index, answer| {
+                    if answer.option() == 0 {
+                        let player = &mut game.players[player_index];
+                        player.coins -= 2;
+                        player.draw(2);
+                    }
+                    Ok(())
+                },
+            );
+        }
+        Ok(())
+    });
+    Ok(())
+}
+
+fn contract(game: &mut Game, source: Card) -> Result<(), GameError> {
+    game.current_player().favors += 1;
+    let candidates: Vec<Card> = distinct(game.current_player_read_only().hand.iter().copied())
+        .into_iter()
+        .filter(|card| card.is(CardType::Action) && card.is_implemented())
+        .collect();
+    if candidates.is_empty() {
+        return Ok(());
+    }
+    let mut choices: Vec<String> = candidates
+        .iter()
+        .map(|card| format!("Set aside {} to play next turn", card.name()))
+        .collect();
+    choices.push("Don't set anything aside".to_owned());
+    game.ask(
+        game.curr_player_index,
+        source,
+        Prompt::Options { options: choices },
+        move |game, player_index, answer| {
+            let Some(&action) = candidates.get(answer.option()) else {
+                return Ok(());
+            };
+            from_hand_of(&mut game.players[player_index], action);
+            next_turn(game, source, move |game, _| play_taken(game, action));
+            Ok(())
+        },
+    );
+    Ok(())
+}
+
+fn emissary(game: &mut Game, _source: Card) -> Result<(), GameError> {
+    let player = game.current_player();
+    let shuffles = player.shuffle_count();
+    player.draw(3);
+    if player.shuffle_count() > shuffles {
+        player.actions += 1;
+        player.favors += 2;
+    }
+    Ok(())
+}
+
+fn galleria(game: &mut Game, _source: Card) -> Result<(), GameError> {
+    let owner = game.curr_player_index;
+    game.add_trigger(Expiry::EndOfTurn, move |game, event| {
+        if let Event::Gain { player_index, card } = event {
+            let cost = game.cost_of(card);
+            if player_index == owner && (cost == Cost::coins(3) || cost == Cost::coins(4)) {
+                game.players[owner].buys += 1;
+            }
+        }
+        Ok(true)
+    });
+    Ok(())
+}
+
+fn guildmaster(game: &mut Game, _source: Card) -> Result<(), GameError> {
+    let owner = game.curr_player_index;
+    game.add_trigger(Expiry::EndOfTurn, move |game, event| {
+        if let Event::Gain { player_index, .. } = event {
+            if player_index == owner {
+                game.players[owner].favors += 1;
+            }
+        }
+        Ok(true)
+    });
+    Ok(())
+}
+
+fn highwayman(game: &mut Game, source: Card) -> Result<(), GameError> {
+    let victims = game.attacked_player_indices();
+    for &victim in &victims {
+        game.players[victim].highwayman_attacks += 1;
+    }
+    next_turn(game, source, move |game, player_index| {
+        for victim in victims {
+            let player = &mut game.players[victim];
+            player.highwayman_attacks = player.highwayman_attacks.saturating_sub(1);
+        }
+        let player = &mut game.players[player_index];
+        if let Some(highwayman) = player.take_from_play(source) {
+            player.add_to_discard(highwayman);
+        }
+        player.draw(3);
+        Ok(())
+    });
+    Ok(())
+}
+
+fn hunter(game: &mut Game, _source: Card) -> Result<(), GameError> {
+    let player = game.current_player();
+    let mut revealed = player.reveal_from_deck(3);
+    for card_type in [CardType::Action, CardType::Treasure, CardType::Victory] {
+        if let Some(index) = revealed.iter().position(|card| card.is(card_type)) {
+            player.add_to_hand(revealed.remove(index));
+        }
+    }
+    player.discard.extend(revealed);
+    Ok(())
+}
+
+fn marquis(game: &mut Game, source: Card) -> Result<(), GameError> {
+    let player = game.current_player();
+    let hand_size = player.hand.len();
+    player.draw(hand_size);
+    discard_down_to(game, game.curr_player_index, source, 10);
+    Ok(())
+}
+
+// ROTATING SPLIT PILES
+fn town_crier(game: &mut Game, source: Card) -> Result<(), GameError> {
+    choose_one(
+        game,
+        source,
+        &["+$2", "Gain a Silver", "+1 Card and +1 Action"],
+        |game, player_index, ability| {
+            let player = &mut game.players[player_index];
+            match ability {
+                0 => player.coins += 2,
+                1 => {
+                    game.gain_if_available(player_index, card("Silver"), GainDestination::Discard)?
+                }
+                _ => {
+                    player.draw(1);
+                    player.actions += 1;
+                }
+            }
+            Ok(())
+        },
+    );
+    offer_rotate(game, source, "Townsfolk");
+    Ok(())
+}
+
+fn blacksmith(game: &mut Game, source: Card) -> Result<(), GameError> {
+    choose_one(
+        game,
+        source,
+        &[
+            "Draw until you have 6 cards in hand",
+            "+2 Cards",
+            "+1 Card and +1 Action",
+        ],
+        |game, player_index, ability| {
+            let player = &mut game.players[player_index];
+            match ability {
+                0 => {
+                    let missing = 6usize.saturating_sub(player.hand.len());
+                    player.draw(missing);
+                }
+                1 => {
+                    player.draw(2);
+                }
+                _ => {
+                    player.draw(1);
+                    player.actions += 1;
+                }
+            }
+            Ok(())
+        },
+    );
+    Ok(())
+}
+
+fn miller(game: &mut Game, source: Card) -> Result<(), GameError> {
+    let looked_at = game.current_player().reveal_from_deck(4);
+    let candidates = distinct(looked_at.iter().copied());
+    game.ask(
+        game.curr_player_index,
+        source,
+        Prompt::Options {
+            options: candidates
+                .iter()
+                .map(|card| format!("Put {} into your hand", card.name()))
+                .collect(),
+        },
+        move |game, player_index, answer| {
+            let mut rest = looked_at;
+            let chosen = candidates[answer.option()];
+            if let Some(index) = rest.iter().position(|&card| card == chosen) {
+                rest.remove(index);
+            }
+            game.players[player_index].add_to_hand(chosen);
+            discard_all(game, player_index, rest);
+            Ok(())
+        },
+    );
+    Ok(())
+}
+
+/// The card Elder plays is remembered for the turn, so that `choose_one` and
+/// `extra_choice` offer it an extra ability.
+fn elder(game: &mut Game, source: Card) -> Result<(), GameError> {
+    let actions = game
+        .current_player_read_only()
+        .hand
+        .iter()
+        .copied()
+        .filter(|card| card.is(CardType::Action))
+        .collect();
+    choose_to_play(game, source, actions, |game, chosen| {
+        let player = game.current_player();
+        from_hand_of(player, chosen);
+        player.elder_played.push(chosen);
+        play_taken(game, chosen)
+    });
+    Ok(())
+}
+
+fn herb_gatherer(game: &mut Game, source: Card) -> Result<(), GameError> {
+    if !game.current_player_read_only().deck.is_empty() {
+        game.ask(
+            game.curr_player_index,
+            source,
+            options(&["Put your deck into your discard pile", "Leave your deck"]),
+            |game, player_index, answer| {
+                if answer.option() == 0 {
+                    let player = &mut game.players[player_index];
+                    let deck = take(&mut player.deck);
+                    player.discard.extend(deck);
+                }
+                Ok(())
+            },
+        );
+    }
+    game.then(move |game| {
+        offer_play_from_discard(game, source, |card| card.is(CardType::Treasure));
+        Ok(())
+    });
+    game.then(move |game| {
+        offer_rotate(game, source, "Augurs");
+        Ok(())
+    });
+    Ok(())
+}
+
+fn acolyte(game: &mut Game, source: Card) -> Result<(), GameError> {
+    let candidates: Vec<Card> = distinct(game.current_player_read_only().hand.iter().copied())
+        .into_iter()
+        .filter(|card| card.is(CardType::Action) || card.is(CardType::Victory))
+        .collect();
+    if !candidates.is_empty() {
+        let mut choices: Vec<String> = candidates
+            .iter()
+            .map(|card| format!("Trash {} to gain a Gold", card.name()))
+            .collect();
+        choices.push("Don't trash anything".to_owned());
+        game.ask(
+            game.curr_player_index,
+            source,
+            Prompt::Options { options: choices },
+            move |game, player_index, answer| {
+                let Some(&trashed) = candidates.get(answer.option()) else {
+                    return Ok(());
+                };
+                from_hand_of(&mut game.players[player_index], trashed);
+                game.trash_card(player_index, trashed);
+                game.gain_if_available(player_index, card("Gold"), GainDestination::Discard)
+            },
+        );
+    }
+    game.then(move |game| {
+        let Some(augur) = game.supply.top_of("Augurs") else {
+            return Ok(());
+        };
+        game.ask(
+            game.curr_player_index,
+            source,
+            Prompt::Options {
+                options: vec![
+                    format!("Trash Acolyte to gain {}", augur.name()),
+                    "Keep it".to_owned(),
+                ],
+            },
+            move |game, player_index, answer| {
+                if answer.option() == 1 {
+                    return Ok(());
+                }
+                let Some(acolyte) = game.players[player_index].take_from_play(source) else {
+                    return Ok(());
+                };
+                game.trash_card(player_index, acolyte);
+                game.gain_if_available(player_index, augur, GainDestination::Discard)
+            },
+        );
+        Ok(())
+    });
+    Ok(())
+}
+
+fn sorceress(game: &mut Game, source: Card) -> Result<(), GameError> {
+    name_own_card(
+        game,
+        game.curr_player_index,
+        source,
+        |game, player_index, named| {
+            let Some(top) = game.players[player_index].take_from_deck() else {
+                return Ok(());
+            };
+            game.players[player_index].add_to_hand(top);
+            if top == named {
+                for victim in game.attacked_player_indices() {
+                    game.gain_if_available(victim, card("Curse"), GainDestination::Discard)?;
+                }
+            }
+            Ok(())
+        },
+    );
+    Ok(())
+}
+
+fn sibyl(game: &mut Game, source: Card) -> Result<(), GameError> {
+    let player_index = game.curr_player_index;
+    game.ask(
+        player_index,
+        source,
+        from_hand(1, 1),
+        |game, player_index, answer| {
+            for card in answer.cards() {
+                game.players[player_index].put_on_deck(card);
+            }
+            Ok(())
+        },
+    );
+    game.ask(
+        player_index,
+        source,
+        from_hand(1, 1),
+        |game, player_index, answer| {
+            for card in answer.cards() {
+                game.players[player_index].deck.insert(0, card);
+            }
+            Ok(())
+        },
+    );
+    Ok(())
+}
+
+fn battle_plan(game: &mut Game, source: Card) -> Result<(), GameError> {
+    let attack = CardFilter::OfType(CardType::Attack);
+    if game.current_player_read_only().has_card_in_hand(&attack) {
+        game.ask(
+            game.curr_player_index,
+            source,
+            options(&["Reveal an Attack card for +1 Card", "Don't reveal anything"]),
+            |game, player_index, answer| {
+                if answer.option() == 0 {
+                    game.players[player_index].draw(1);
+                }
+                Ok(())
+            },
+        );
+    }
+    let rotatable: Vec<String> = game
+        .supply
+        .piles
+        .iter()
+        .filter(|pile| pile.cards.iter().any(|&card| Some(card) != pile.top()))
+        .map(|pile| pile.name.clone())
+        .collect();
+    if rotatable.is_empty() {
+        return Ok(());
+    }
+    let mut choices: Vec<String> = rotatable
+        .iter()
+        .map(|pile| format!("Rotate the {pile} pile"))
+        .collect();
+    choices.push("Don't rotate anything".to_owned());
+    game.ask(
+        game.curr_player_index,
+        source,
+        Prompt::Options { options: choices },
+        move |game, _, answer| {
+            if let Some(pile) = rotatable.get(answer.option()) {
+                game.supply.rotate(pile);
+            }
+            Ok(())
+        },
+    );
+    Ok(())
+}
+
+fn archer(game: &mut Game, source: Card) -> Result<(), GameError> {
+    let attacker = game.curr_player_index;
+    for victim in game.attacked_player_indices() {
+        let hand = game.players[victim].hand.clone();
+        if hand.len() < 5 {
+            continue;
+        }
+        let candidates = distinct(hand.iter().copied());
+        game.ask(
+            victim,
+            source,
+            Prompt::Options {
+                options: candidates
+                    .iter()
+                    .map(|card| format!("Keep {} hidden", card.name()))
+                    .collect(),
+            },
+            move |game, victim, answer| {
+                let mut revealed = hand;
+                let hidden = candidates[answer.option()];
+                if let Some(index) = revealed.iter().position(|&card| card == hidden) {
+                    revealed.remove(index);
+                }
+                let revealed = distinct(revealed);
+                game.ask(
+                    attacker,
+                    source,
+                    Prompt::Options {
+                        options: revealed
+                            .iter()
+                            .map(|card| format!("Player {victim} discards {}", card.name()))
+                            .collect(),
+                    },
+                    move |game, _, answer| {
+                        let discarded = revealed[answer.option()];
+                        let player = &mut game.players[victim];
+                        from_hand_of(player, discarded);
+                        player.add_to_discard(discarded);
+                        Ok(())
+                    },
+                );
+                Ok(())
+            },
+        );
+    }
+    Ok(())
+}
+
+fn warlord(game: &mut Game, source: Card) -> Result<(), GameError> {
+    let victims = game.attacked_player_indices();
+    for &victim in &victims {
+        game.players[victim].warlord_attacks += 1;
+    }
+    next_turn(game, source, move |game, player_index| {
+        for victim in victims {
+            let player = &mut game.players[victim];
+            player.warlord_attacks = player.warlord_attacks.saturating_sub(1);
+        }
+        game.players[player_index].draw(2);
+        Ok(())
+    });
+    Ok(())
+}
+
+fn territory(player: &Player) -> i32 {
+    distinct(player.all_cards().filter(|card| card.is(CardType::Victory))).len() as i32
+}
+
+fn territory_on_gain(game: &mut Game, player_index: usize, _territory: Card) {
+    let golds = game.supply.num_empty_supply_piles();
+    game.then(move |game| {
+        for _ in 0..golds {
+            game.gain_if_available(player_index, card("Gold"), GainDestination::Discard)?;
+        }
+        Ok(())
+    });
+}
+
+fn tent(game: &mut Game, source: Card) -> Result<(), GameError> {
+    offer_rotate(game, source, "Forts");
+    onto_deck_from_play(game, source);
+    Ok(())
+}
+
+fn garrison(game: &mut Game, source: Card) -> Result<(), GameError> {
+    let owner = game.curr_player_index;
+    // Each card gained for the rest of the turn adds a token
+    let gained_before = game.history.current_turn.gained_by(owner).count();
+    next_turn(game, source, move |game, player_index| {
+        let tokens = game.history.last_turn(player_index).map_or(0, |turn| {
+            turn.gained_by(player_index)
+                .count()
+                .saturating_sub(gained_before)
+        });
+        game.players[player_index].draw(tokens);
+        Ok(())
+    });
+    Ok(())
+}
+
+fn hill_fort(game: &mut Game, source: Card) -> Result<(), GameError> {
+    game.ask(
+        game.curr_player_index,
+        source,
+        gain_from_supply(Cost::coins(4), CardFilter::Any),
+        move |game, player_index, answer| {
+            let gained = answer.supply_card();
+            game.gain_card(player_index, gained, GainDestination::Discard)?;
+            choose_one(
+                game,
+                source,
+                &[
+                    &format!("Put {} into your hand", gained.name()),
+                    "+1 Card and +1 Action",
+                ],
+                move |game, player_index, ability| {
+                    let player = &mut game.players[player_index];
+                    if ability == 0 {
+                        if let Some(gained) = player.take_gained(gained) {
+                            player.add_to_hand(gained);
+                        }
+                    } else {
+                        player.draw(1);
+                        player.actions += 1;
+                    }
+                    Ok(())
+                },
+            );
+            Ok(())
+        },
+    );
+    Ok(())
+}
+
+fn stronghold(game: &mut Game, source: Card) -> Result<(), GameError> {
+    game.ask(
+        game.curr_player_index,
+        source,
+        options(&["+$3", "+3 Cards at the start of your next turn"]),
+        move |game, player_index, answer| {
+            if answer.option() == 0 {
+                game.players[player_index].coins += 3;
+            } else {
+                next_turn(game, source, |game, player_index| {
+                    game.players[player_index].draw(3);
+                    Ok(())
+                });
+            }
+            Ok(())
+        },
+    );
+    Ok(())
+}
+
+fn old_map(game: &mut Game, source: Card) -> Result<(), GameError> {
+    game.ask(
+        game.curr_player_index,
+        source,
+        from_hand(1, 1),
+        |game, player_index, answer| {
+            discard_all(game, player_index, answer.cards());
+            Ok(())
+        },
+    );
+    game.then(|game| {
+        game.current_player().draw(1);
+        Ok(())
+    });
+    offer_rotate(game, source, "Odysseys");
+    Ok(())
+}
+
+fn voyage(game: &mut Game, source: Card) -> Result<(), GameError> {
+    // The extra turn is the player's next one
+    if take_extra_turn(game) {
+        next_turn(game, source, |game, player_index| {
+            game.players[player_index].hand_plays_left = Some(3);
+            Ok(())
+        });
+    }
+    Ok(())
+}
+
+fn sunken_treasure(game: &mut Game, source: Card) -> Result<(), GameError> {
+    let in_play = game.current_player_read_only().played.clone();
+    let choices: Vec<Card> = game
+        .supply
+        .available_cards()
+        .into_iter()
+        .filter(|card| card.is(CardType::Action) && !in_play.contains(card))
+        .collect();
+    game.ask(
+        game.curr_player_index,
+        source,
+        Prompt::Options {
+            options: choices
+                .iter()
+                .map(|card| format!("Gain {}", card.name()))
+                .collect(),
+        },
+        move |game, player_index, answer| {
+            game.gain_card(
+                player_index,
+                choices[answer.option()],
+                GainDestination::Discard,
+            )
+        },
+    );
+    Ok(())
+}
+
+fn distant_shore(game: &mut Game, _source: Card) -> Result<(), GameError> {
+    game.gain_if_available(
+        game.curr_player_index,
+        card("Estate"),
+        GainDestination::Discard,
+    )
+}
+
+fn student(game: &mut Game, source: Card) -> Result<(), GameError> {
+    offer_rotate(game, source, "Wizards");
+    game.ask(
+        game.curr_player_index,
+        source,
+        from_hand(1, 1),
+        move |game, player_index, answer| {
+            for trashed in answer.cards() {
+                game.trash_card(player_index, trashed);
+                if trashed.is(CardType::Treasure) {
+                    let player = &mut game.players[player_index];
+                    player.favors += 1;
+                    if let Some(student) = player.take_from_play(source) {
+                        player.put_on_deck(student);
+                    }
+                }
+            }
+            Ok(())
+        },
+    );
+    Ok(())
+}
+
+fn conjurer(game: &mut Game, source: Card) -> Result<(), GameError> {
+    gain_to_discard(game, game.curr_player_index, source, Cost::coins(4));
+    next_turn(game, source, move |game, player_index| {
+        let player = &mut game.players[player_index];
+        if let Some(conjurer) = player.take_from_play(source) {
+            player.add_to_hand(conjurer);
+        }
+        Ok(())
+    });
+    Ok(())
+}
+
+fn sorcerer(game: &mut Game, source: Card) -> Result<(), GameError> {
+    for victim in game.attacked_player_indices() {
+        name_own_card(game, victim, source, |game, victim, named| {
+            let player = &mut game.players[victim];
+            let Some(top) = player.take_from_deck() else {
+                return Ok(());
+            };
+            player.put_on_deck(top);
+            if top != named {
+                game.gain_if_available(victim, card("Curse"), GainDestination::Discard)?;
+            }
+            Ok(())
+        });
+    }
+    Ok(())
+}
+
+fn lich(game: &mut Game, _source: Card) -> Result<(), GameError> {
+    game.current_player().skip_next_turn = true;
+    Ok(())
+}
+
+fn lich_on_trash(game: &mut Game, player_index: usize, lich: Card) {
+    let Some(index) = game.trash.cards.iter().rposition(|&card| card == lich) else {
+        return;
+    };
+    game.trash.take(index);
+    game.players[player_index].add_to_discard(lich);
+    let cost = game.cost_of(lich).coins;
+    let choices: Vec<Card> = distinct(
+        game.trash_cards_costing(0, cost.saturating_sub(1))
+            .into_iter()
+            .map(|(_, card)| card),
+    );
+    if cost == 0 {
+        return;
+    }
+    game.ask(
+        player_index,
+        lich,
+        Prompt::Options {
+            options: choices
+                .iter()
+                .map(|card| format!("Gain {} from the trash", card.name()))
+                .collect(),
+        },
+        move |game, player_index, answer| {
+            game.gain_from_trash(
+                player_index,
+                choices[answer.option()],
+                GainDestination::Discard,
+            )
+        },
+    );
+}
+
+// ALLIES
+/// Offers a player the chance to spend Favors on the Ally, for `what`.
+fn offer_favors(
+    game: &mut Game,
+    player_index: usize,
+    ally: Card,
+    favors: u32,
+    what: String,
+    then: impl FnOnce(&mut Game, usize) -> Result<(), GameError> + Send + 'static,
+) {
+    if game.players[player_index].favors < favors {
+        return;
+    }
+    let spend = match favors {
+        1 => "Spend a Favor".to_owned(),
+        _ => format!("Spend {favors} Favors"),
+    };
+    game.ask(
+        player_index,
+        ally,
+        Prompt::Options {
+            options: vec![format!("{spend} {what}"), "Don't spend any".to_owned()],
+        },
+        move |game, player_index, answer| {
+            let player = &mut game.players[player_index];
+            if answer.option() == 1 || player.favors < favors {
+                return Ok(());
+            }
+            player.favors -= favors;
+            then(game, player_index)
+        },
+    );
+}
+
+/// The player starting their turn.
+fn turn_start(event: Event) -> Option<usize> {
+    match event {
+        Event::TurnStart { player_index } => Some(player_index),
+        _ => None,
+    }
+}
+
+/// The player starting their Buy phase.
+fn buy_phase_start(event: Event) -> Option<usize> {
+    match event {
+        Event::BuyPhaseStart { player_index } => Some(player_index),
+        _ => None,
+    }
+}
+
+/// The current player, once they have finished playing a card of the given type.
+fn after_playing(game: &Game, event: Event, card_type: CardType) -> Option<usize> {
+    match event {
+        Event::Play { player_index, card }
+            if player_index == game.curr_player_index && card.is(card_type) =>
+        {
+            Some(player_index)
+        }
+        _ => None,
+    }
+}
+
+fn architects_guild(game: &mut Game, ally: Card, event: Event) {
+    let Event::Gain { player_index, card } = event else {
+        return;
+    };
+    let cost = game.cost_of(card);
+    if cost.coins == 0 {
+        return;
+    }
+    offer_favors(
+        game,
+        player_index,
+        ally,
+        2,
+        "to gain a cheaper non-Victory card".to_owned(),
+        move |game, player_index| {
+            game.ask(
+                player_index,
+                ally,
+                gain_from_supply(
+                    cost.minus_coins(1),
+                    CardFilter::NotOfType(CardType::Victory),
+                ),
+                |game, player_index, answer| {
+                    game.gain_card(player_index, answer.supply_card(), GainDestination::Discard)
+                },
+            );
+            Ok(())
+        },
+    );
+}
+
+fn band_of_nomads(game: &mut Game, ally: Card, event: Event) {
+    let Event::Gain { player_index, card } = event else {
+        return;
+    };
+    if game.cost_of(card).coins < 3 || game.players[player_index].favors == 0 {
+        return;
+    }
+    game.ask(
+        player_index,
+        ally,
+        options(&[
+            "Spend a Favor for +1 Card",
+            "Spend a Favor for +1 Action",
+            "Spend a Favor for +1 Buy",
+            "Don't spend any",
+        ]),
+        |game, player_index, answer| {
+            let player = &mut game.players[player_index];
+            let choice = answer.option();
+            if choice == 3 || player.favors == 0 {
+                return Ok(());
+            }
+            player.favors -= 1;
+            match choice {
+                0 => {
+                    player.draw(1);
+                }
+                1 => player.actions += 1,
+                _ => player.buys += 1,
+            }
+            Ok(())
+        },
+    );
+}
+
+fn cave_dwellers(game: &mut Game, ally: Card, event: Event) {
+    if let Some(player_index) = turn_start(event) {
+        cave_dwellers_offer(game, player_index, ally);
+    }
+}
+
+fn cave_dwellers_offer(game: &mut Game, player_index: usize, ally: Card) {
+    offer_favors(
+        game,
+        player_index,
+        ally,
+        1,
+        "to discard a card, then draw a card".to_owned(),
+        move |game, player_index| {
+            game.ask(
+                player_index,
+                ally,
+                from_hand(1, 1),
+                |game, player_index, answer| {
+                    discard_all(game, player_index, answer.cards());
+                    Ok(())
+                },
+            );
+            game.then(move |game| {
+                game.players[player_index].draw(1);
+                cave_dwellers_offer(game, player_index, ally);
+                Ok(())
+            });
+            Ok(())
+        },
+    );
+}
+
+fn circle_of_witches(game: &mut Game, ally: Card, event: Event) {
+    let Some(player_index) = after_playing(game, event, CardType::Liaison) else {
+        return;
+    };
+    game.then(move |game| {
+        offer_favors(
+            game,
+            player_index,
+            ally,
+            3,
+            "to have each other player gain a Curse".to_owned(),
+            |game, player_index| {
+                for offset in 1..game.players.len() {
+                    let other = (player_index + offset) % game.players.len();
+                    game.gain_if_available(other, card("Curse"), GainDestination::Discard)?;
+                }
+                Ok(())
+            },
+        );
+        Ok(())
+    });
+}
+
+fn city_state(game: &mut Game, ally: Card, event: Event) {
+    let Event::Gain { player_index, card } = event else {
+        return;
+    };
+    if player_index != game.curr_player_index
+        || !card.is(CardType::Action)
+        || !card.is_implemented()
+    {
+        return;
+    }
+    offer_favors(
+        game,
+        player_index,
+        ally,
+        2,
+        format!("to play {}", card.name()),
+        move |game, player_index| match game.players[player_index].take_gained(card) {
+            Some(gained) => play_taken(game, gained),
+            None => Ok(()),
+        },
+    );
+}
+
+fn coastal_haven(game: &mut Game, ally: Card, event: Event) {
+    if let Event::BuyPhaseEnd { player_index } = event {
+        coastal_haven_offer(game, player_index, ally);
+    }
+}
+
+/// Offers to keep one more card in hand for a Favor, until the player stops.
+fn coastal_haven_offer(game: &mut Game, player_index: usize, ally: Card) {
+    let player = &game.players[player_index];
+    if player.favors == 0 {
+        return;
+    }
+    let candidates = distinct(player.hand.iter().copied());
+    if candidates.is_empty() {
+        return;
+    }
+    let mut choices: Vec<String> = candidates
+        .iter()
+        .map(|card| format!("Spend a Favor to keep {} in hand", card.name()))
+        .collect();
+    choices.push("Don't keep anything".to_owned());
+    game.ask(
+        player_index,
+        ally,
+        Prompt::Options { options: choices },
+        move |game, player_index, answer| {
+            let Some(&kept) = candidates.get(answer.option()) else {
+                return Ok(());
+            };
+            let player = &mut game.players[player_index];
+            from_hand_of(player, kept);
+            // Saved cards join the next hand once it is drawn
+            player.saved.push(kept);
+            player.favors -= 1;
+            coastal_haven_offer(game, player_index, ally);
+            Ok(())
+        },
+    );
+}
+
+fn crafters_guild(game: &mut Game, ally: Card, event: Event) {
+    let Some(player_index) = turn_start(event) else {
+        return;
+    };
+    offer_favors(
+        game,
+        player_index,
+        ally,
+        2,
+        "to gain a card costing up to $4 onto your deck".to_owned(),
+        move |game, player_index| {
+            game.ask(
+                player_index,
+                ally,
+                gain_from_supply(Cost::coins(4), CardFilter::Any),
+                |game, player_index, answer| {
+                    game.gain_card(player_index, answer.supply_card(), GainDestination::Deck)
+                },
+            );
+            Ok(())
+        },
+    );
+}
+
+fn desert_guides(game: &mut Game, ally: Card, event: Event) {
+    if let Some(player_index) = turn_start(event) {
+        desert_guides_offer(game, player_index, ally);
+    }
+}
+
+fn desert_guides_offer(game: &mut Game, player_index: usize, ally: Card) {
+    offer_favors(
+        game,
+        player_index,
+        ally,
+        1,
+        "to discard your hand and draw 5 cards".to_owned(),
+        move |game, player_index| {
+            let player = &mut game.players[player_index];
+            player.discard_hand();
+            player.draw(5);
+            desert_guides_offer(game, player_index, ally);
+            Ok(())
+        },
+    );
+}
+
+fn family_of_inventors(game: &mut Game, ally: Card, event: Event) {
+    let Some(player_index) = buy_phase_start(event) else {
+        return;
+    };
+    let piles: Vec<String> = game
+        .supply
+        .piles
+        .iter()
+        .filter(|pile| pile.top().is_some_and(|card| !card.is(CardType::Victory)))
+        .map(|pile| pile.name.clone())
+        .collect();
+    if piles.is_empty() {
+        return;
+    }
+    offer_favors(
+        game,
+        player_index,
+        ally,
+        1,
+        "to put a Favor token on a pile".to_owned(),
+        move |game, player_index| {
+            game.ask(
+                player_index,
+                ally,
+                Prompt::Options {
+                    options: piles
+                        .iter()
+                        .map(|pile| format!("Put a Favor token on the {pile} pile"))
+                        .collect(),
+                },
+                move |game, _, answer| {
+                    let pile = piles[answer.option()].clone();
+                    *game.supply.favor_tokens.entry(pile).or_default() += 1;
+                    Ok(())
+                },
+            );
+            Ok(())
+        },
+    );
+}
+
+fn fellowship_of_scribes(game: &mut Game, ally: Card, event: Event) {
+    let Some(player_index) = after_playing(game, event, CardType::Action) else {
+        return;
+    };
+    game.then(move |game| {
+        if game.players[player_index].hand.len() <= 4 {
+            offer_favors(
+                game,
+                player_index,
+                ally,
+                1,
+                "for +1 Card".to_owned(),
+                |game, player_index| {
+                    game.players[player_index].draw(1);
+                    Ok(())
+                },
+            );
+        }
+        Ok(())
+    });
+}
+
+fn forest_dwellers(game: &mut Game, ally: Card, event: Event) {
+    let Some(player_index) = turn_start(event) else {
+        return;
+    };
+    offer_favors(
+        game,
+        player_index,
+        ally,
+        1,
+        "to look at the top 3 cards of your deck".to_owned(),
+        move |game, player_index| {
+            for card in game.players[player_index].reveal_from_deck(3) {
+                game.ask(
+                    player_index,
+                    ally,
+                    Prompt::Options {
+                        options: vec![
+                            format!("Discard {}", card.name()),
+                            format!("Put {} back", card.name()),
+                        ],
+                    },
+                    move |game, player_index, answer| {
+                        let player = &mut game.players[player_index];
+                        if answer.option() == 0 {
+                            player.add_to_discard(card);
+                        } else {
+                            player.put_on_deck(card);
+                        }
+                        Ok(())
+                    },
+                );
+            }
+            Ok(())
+        },
+    );
+}
+
+fn gang_of_pickpockets(game: &mut Game, ally: Card, event: Event) {
+    let Some(player_index) = turn_start(event) else {
+        return;
+    };
+    if game.players[player_index].hand.len() <= 4 {
+        return;
+    }
+    if game.players[player_index].favors == 0 {
+        discard_down_to(game, player_index, ally, 4);
+        return;
+    }
+    game.ask(
+        player_index,
+        ally,
+        options(&["Spend a Favor", "Discard down to 4 cards in hand"]),
+        move |game, player_index, answer| {
+            if answer.option() == 0 {
+                game.players[player_index].favors -= 1;
+            } else {
+                discard_down_to(game, player_index, ally, 4);
+            }
+            Ok(())
+        },
+    );
+}
+
+fn island_folk(game: &mut Game, ally: Card, event: Event) {
+    let Event::BuyPhaseEnd { player_index } = event else {
+        return;
+    };
+    // Not a third turn in a row
+    if game.history.current_turn.extra_turn {
+        return;
+    }
+    offer_favors(
+        game,
+        player_index,
+        ally,
+        5,
+        "to take an extra turn after this one".to_owned(),
+        |game, player_index| {
+            game.extra_turns.push_back(ExtraTurn {
+                player_index,
+                hand_size: 5,
+            });
+            Ok(())
+        },
+    );
+}
+
+fn league_of_bankers(game: &mut Game, _ally: Card, event: Event) {
+    if let Some(player_index) = buy_phase_start(event) {
+        let player = &mut game.players[player_index];
+        player.coins += player.favors / 4;
+    }
+}
+
+fn league_of_shopkeepers(game: &mut Game, _ally: Card, event: Event) {
+    let Some(player_index) = after_playing(game, event, CardType::Liaison) else {
+        return;
+    };
+    game.then(move |game| {
+        let player = &mut game.players[player_index];
+        if player.favors >= 5 {
+            player.coins += 1;
+        }
+        if player.favors >= 10 {
+            player.actions += 1;
+            player.buys += 1;
+        }
+        Ok(())
+    });
+}
+
+fn market_towns(game: &mut Game, ally: Card, event: Event) {
+    if let Some(player_index) = buy_phase_start(event) {
+        market_towns_offer(game, player_index, ally);
+    }
+}
+
+fn market_towns_offer(game: &mut Game, player_index: usize, ally: Card) {
+    if game.players[player_index].favors == 0 {
+        return;
+    }
+    let candidates: Vec<Card> = distinct(game.players[player_index].hand.iter().copied())
+        .into_iter()
+        .filter(|card| card.is(CardType::Action) && card.is_implemented())
+        .collect();
+    if candidates.is_empty() {
+        return;
+    }
+    let mut choices: Vec<String> = candidates
+        .iter()
+        .map(|card| format!("Spend a Favor to play {}", card.name()))
+        .collect();
+    choices.push("Don't spend any".to_owned());
+    game.ask(
+        player_index,
+        ally,
+        Prompt::Options { options: choices },
+        move |game, player_index, answer| {
+            let Some(&action) = candidates.get(answer.option()) else {
+                return Ok(());
+            };
+            let player = &mut game.players[player_index];
+            player.favors -= 1;
+            from_hand_of(player, action);
+            play_taken(game, action)?;
+            game.then(move |game| {
+                market_towns_offer(game, player_index, ally);
+                Ok(())
+            });
+            Ok(())
+        },
+    );
+}
+
+fn mountain_folk(game: &mut Game, ally: Card, event: Event) {
+    let Some(player_index) = turn_start(event) else {
+        return;
+    };
+    offer_favors(
+        game,
+        player_index,
+        ally,
+        5,
+        "for +3 Cards".to_owned(),
+        |game, player_index| {
+            game.players[player_index].draw(3);
+            Ok(())
+        },
+    );
+}
+
+fn peaceful_cult(game: &mut Game, ally: Card, event: Event) {
+    let Some(player_index) = buy_phase_start(event) else {
+        return;
+    };
+    let favors = game.players[player_index].favors as usize;
+    if favors == 0 {
+        return;
+    }
+    // Each card chosen costs a Favor
+    game.ask(
+        player_index,
+        ally,
+        from_hand(0, favors),
+        |game, player_index, answer| {
+            for trashed in answer.cards() {
+                game.players[player_index].favors -= 1;
+                game.trash_card(player_index, trashed);
+            }
+            Ok(())
+        },
+    );
+}
+
+fn plateau_shepherds(game: &Game, player_index: usize) -> i32 {
+    let player = &game.players[player_index];
+    let costing_two = player
+        .all_cards()
+        .filter(|card| card.cost() == Cost::coins(2))
+        .count();
+    2 * costing_two.min(player.favors as usize) as i32
+}
+
+fn trappers_lodge(game: &mut Game, ally: Card, event: Event) {
+    let Event::Gain { player_index, card } = event else {
+        return;
+    };
+    offer_favors(
+        game,
+        player_index,
+        ally,
+        1,
+        format!("to put {} onto your deck", card.name()),
+        move |game, player_index| {
+            let player = &mut game.players[player_index];
+            if let Some(gained) = player.take_gained(card) {
+                player.put_on_deck(gained);
+            }
+            Ok(())
+        },
+    );
+}
+
+fn woodworkers_guild(game: &mut Game, ally: Card, event: Event) {
+    let Some(player_index) = buy_phase_start(event) else {
+        return;
+    };
+    let action = CardFilter::OfType(CardType::Action);
+    if !game.players[player_index].has_card_in_hand(&action) {
+        return;
+    }
+    offer_favors(
+        game,
+        player_index,
+        ally,
+        1,
+        "to trash an Action card from your hand".to_owned(),
+        move |game, player_index| {
+            game.ask(
+                player_index,
+                ally,
+                Prompt::CardsFromHand {
+                    min: 1,
+                    max: 1,
+                    filter: action,
+                },
+                move |game, player_index, answer| {
+                    let any_cost = Cost {
+                        coins: u32::MAX,
+                        potions: u32::MAX,
+                        debt: u32::MAX,
+                    };
+                    for trashed in answer.cards() {
+                        game.trash_card(player_index, trashed);
+                        game.ask(
+                            player_index,
+                            ally,
+                            gain_from_supply(any_cost, CardFilter::OfType(CardType::Action)),
+                            |game, player_index, answer| {
+                                game.gain_card(
+                                    player_index,
+                                    answer.supply_card(),
+                                    GainDestination::Discard,
+                                )
+                            },
+                        );
+                    }
+                    Ok(())
+                },
+            );
+            Ok(())
+        },
+    );
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::cards::testing::{choose_option, play, scenario};
+
+    const KINGDOM: [&str; 11] = [
+        "Underling",
+        "Sycophant",
+        "Merchant Camp",
+        "Sentinel",
+        "Broker",
+        "Carpenter",
+        "Courier",
+        "Innkeeper",
+        "Skirmisher",
+        "Royal Galley",
+        "Band of Nomads",
+    ];
+
+    fn gain_to_discard(game: &mut Game, player_index: usize, name: &str) {
+        game.gain_card(player_index, card(name), GainDestination::Discard)
+            .unwrap();
+        game.run_pending().unwrap();
+    }
+
+    #[test]
+    fn every_player_starts_with_a_favor_for_the_ally() {
+        let game = scenario(&KINGDOM, [&[], &[]]);
+
+        assert_eq!(game.supply.ally, Some(card("Band of Nomads")));
+        assert!(game.players.iter().all(|player| player.favors == 1));
+    }
+
+    #[test]
+    fn a_liaison_without_an_ally_brings_one_in() {
+        let game = scenario(&KINGDOM[..10], [&[], &[]]);
+
+        assert!(game.supply.ally.is_some_and(|ally| ally.is(CardType::Ally)));
+        assert!(game.players.iter().all(|player| player.favors == 1));
+    }
+
+    #[test]
+    fn liaisons_give_favors_when_played_and_gained() {
+        let mut game = scenario(&KINGDOM, [&["Underling"], &[]]);
+        let player_index = game.curr_player_index;
+
+        play(&mut game, "Underling");
+        assert_eq!(game.players[player_index].favors, 2);
+
+        gain_to_discard(&mut game, player_index, "Sycophant");
+        assert_eq!(game.players[player_index].favors, 4);
+    }
+
+    #[test]
+    fn favors_are_spent_on_the_allys_ability() {
+        let mut game = scenario(&KINGDOM, [&[], &[]]);
+        let player_index = game.curr_player_index;
+
+        gain_to_discard(&mut game, player_index, "Silver");
+        // +1 Card, +1 Action or +1 Buy, for a Favor
+        choose_option(&mut game, 1);
+
+        let player = &game.players[player_index];
+        assert_eq!((player.favors, player.actions), (0, 2));
+    }
+
+    #[test]
+    fn the_ally_isnt_offered_without_favors() {
+        let mut game = scenario(&KINGDOM, [&[], &[]]);
+        let player_index = game.curr_player_index;
+        game.current_player().favors = 0;
+
+        gain_to_discard(&mut game, player_index, "Silver");
+
+        assert!(game.pending_decision().is_none());
+    }
+}
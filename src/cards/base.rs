@@ -0,0 +1,250 @@
+//! Behaviours for the special cards of the Base set.
+
+use super::{
+    card, from_hand, gain_from_supply, gain_to_discard, protects_from_hand, Behaviour, Card,
+    CardFilter, CardType,
+};
+use crate::{Event, Expiry, GainDestination, Game, GameError, Player, Prompt};
+
+pub(super) fn behaviours() -> Vec<(&'static str, Behaviour)> {
+    let play = |on_play| Behaviour {
+        on_play: Some(on_play),
+        ..Behaviour::default()
+    };
+    vec![
+        ("Chapel", play(chapel)),
+        ("Merchant", play(merchant)),
+        (
+            "Moat",
+            Behaviour {
+                protects: Some(protects_from_hand),
+                ..Behaviour::default()
+            },
+        ),
+        ("Moneylender", play(moneylender)),
+        ("Remodel", play(remodel)),
+        ("Bandit", play(bandit)),
+        ("Council Room", play(council_room)),
+        ("Mine", play(mine)),
+        ("Sentry", play(sentry)),
+        ("Witch", play(witch)),
+        (
+            "Gardens",
+            Behaviour {
+                victory_points: Some(gardens),
+                ..Behaviour::default()
+            },
+        ),
+    ]
+}
+
+fn chapel(game: &mut Game, source: Card) -> Result<(), GameError> {
+    game.ask(
+        game.curr_player_index,
+        source,
+        from_hand(0, 4),
+        |game, player_index, answer| {
+            for card in answer.cards() {
+                game.trash_card(player_index, card);
+            }
+            Ok(())
+        },
+    );
+    Ok(())
+}
+
+fn merchant(game: &mut Game, _source: Card) -> Result<(), GameError> {
+    // Too late if a Silver has already been played this turn
+    if game.history.current_turn.played_count("Silver") > 0 {
+        return Ok(());
+    }
+    let owner = game.curr_player_index;
+    game.add_trigger(Expiry::EndOfTurn, move |game, event| match event {
+        Event::Play { player_index, card } if player_index == owner && card.name() == "Silver" => {
+            game.players[owner].coins += 1;
+            Ok(false)
+        }
+        _ => Ok(true),
+    });
+    Ok(())
+}
+
+fn moneylender(game: &mut Game, source: Card) -> Result<(), GameError> {
+    let filter = CardFilter::Named("Copper");
+    if game.current_player_read_only().has_card_in_hand(&filter) {
+        game.ask(
+            game.curr_player_index,
+            source,
+            Prompt::CardsFromHand {
+                min: 0,
+                max: 1,
+                filter,
+            },
+            |game, player_index, answer| {
+                for card in answer.cards() {
+                    game.trash_card(player_index, card);
+                    game.players[player_index].coins += 3;
+                }
+                Ok(())
+            },
+        );
+    }
+    Ok(())
+}
+
+fn remodel(game: &mut Game, source: Card) -> Result<(), GameError> {
+    if game.current_player_read_only().hand.is_empty() {
+        return Ok(());
+    }
+    game.ask(
+        game.curr_player_index,
+        source,
+        from_hand(1, 1),
+        move |game, player_index, answer| {
+            for card in answer.cards() {
+                let max_cost = game.cost_of(card).plus_coins(2);
+                game.trash_card(player_index, card);
+                gain_to_discard(game, player_index, source, max_cost);
+            }
+            Ok(())
+        },
+    );
+    Ok(())
+}
+
+fn is_bandit_target(card: Card) -> bool {
+    card.is(CardType::Treasure) && card.name() != "Copper"
+}
+
+/// Trashes the first revealed copy of `target` and discards the rest.
+fn resolve_bandit(game: &mut Game, player_index: usize, revealed: Vec<Card>, target: Card) {
+    let mut trashed = false;
+    for card in revealed {
+        if !trashed && card == target {
+            trashed = true;
+            game.trash_card(player_index, card);
+        } else {
+            game.players[player_index].add_to_discard(card);
+        }
+    }
+}
+
+fn bandit(game: &mut Game, source: Card) -> Result<(), GameError> {
+    game.gain_if_available(
+        game.curr_player_index,
+        card("Gold"),
+        GainDestination::Discard,
+    )?;
+
+    // Each attacked player trashes a revealed Treasure other than Copper,
+    // choosing which if they revealed two different ones.
+    for player_index in game.attacked_player_indices() {
+        let revealed = game.players[player_index].reveal_from_deck(2);
+        let mut targets: Vec<Card> = revealed
+            .iter()
+            .copied()
+            .filter(|&card| is_bandit_target(card))
+            .collect();
+        targets.dedup();
+        match targets[..] {
+            [] => {
+                for card in revealed {
+                    game.players[player_index].add_to_discard(card);
+                }
+            }
+            [target] => resolve_bandit(game, player_index, revealed, target),
+            _ => {
+                let options = targets.iter().map(|card| card.name().to_owned()).collect();
+                game.ask(
+                    player_index,
+                    source,
+                    Prompt::Options { options },
+                    move |game, player_index, answer| {
+                        resolve_bandit(game, player_index, revealed, targets[answer.option()]);
+                        Ok(())
+                    },
+                );
+            }
+        }
+    }
+    Ok(())
+}
+
+fn council_room(game: &mut Game, _source: Card) -> Result<(), GameError> {
+    // Every other player draws one card
+    let current_player_index = game.curr_player_index;
+    for player in game.players.iter_mut() {
+        if player.index != current_player_index {
+            player.draw(1);
+        }
+    }
+    Ok(())
+}
+
+fn mine(game: &mut Game, source: Card) -> Result<(), GameError> {
+    let filter = CardFilter::OfType(CardType::Treasure);
+    if !game.current_player_read_only().has_card_in_hand(&filter) {
+        return Ok(());
+    }
+    game.ask(
+        game.curr_player_index,
+        source,
+        Prompt::CardsFromHand {
+            min: 0,
+            max: 1,
+            filter,
+        },
+        move |game, player_index, answer| {
+            for card in answer.cards() {
+                let max_cost = game.cost_of(card).plus_coins(3);
+                game.trash_card(player_index, card);
+                game.ask(
+                    player_index,
+                    source,
+                    gain_from_supply(max_cost, CardFilter::OfType(CardType::Treasure)),
+                    |game, player_index, answer| {
+                        game.gain_card(player_index, answer.supply_card(), GainDestination::Hand)
+                    },
+                );
+            }
+            Ok(())
+        },
+    );
+    Ok(())
+}
+
+fn sentry(game: &mut Game, source: Card) -> Result<(), GameError> {
+    for card in game.current_player().reveal_from_deck(2) {
+        let options = ["Trash", "Discard", "Put back"]
+            .iter()
+            .map(|option| format!("{} {}", option, card.name()))
+            .collect();
+        game.ask(
+            game.curr_player_index,
+            source,
+            Prompt::Options { options },
+            move |game, player_index, answer| {
+                match answer.option() {
+                    0 => game.trash_card(player_index, card),
+                    1 => game.players[player_index].add_to_discard(card),
+                    _ => game.players[player_index].put_on_deck(card),
+                }
+                Ok(())
+            },
+        );
+    }
+    Ok(())
+}
+
+fn witch(game: &mut Game, _source: Card) -> Result<(), GameError> {
+    // Starting from the left of the current player,
+    // each player will gain a curse if there's one left.
+    for player_index in game.attacked_player_indices() {
+        game.gain_if_available(player_index, card("Curse"), GainDestination::Discard)?;
+    }
+    Ok(())
+}
+
+fn gardens(player: &Player) -> i32 {
+    (player.all_cards().count() / 10) as i32
+}
@@ -0,0 +1,510 @@
+//! Behaviours for the Cornucopia kingdom cards and Prizes. Several of them
+//! reward variety, counting differently named cards with the queries on
+//! `Player`. Nobody ever gains from revealing a Province to Tournament's player
+//! or a Bane to Young Witch's, so those reveals happen without asking.
+
+use super::{
+    card, discard_all, discard_down_to, distinct, from_hand, options, reveal_until, Behaviour,
+    Card, CardFilter, CardType, Cost,
+};
+use crate::{CostModifier, GainDestination, Game, GameError, Player, Prompt};
+
+pub(super) fn behaviours() -> Vec<(&'static str, Behaviour)> {
+    let play = |on_play| Behaviour {
+        on_play: Some(on_play),
+        ..Behaviour::default()
+    };
+    vec![
+        ("Hamlet", play(hamlet)),
+        ("Fortune Teller", play(fortune_teller)),
+        ("Menagerie", play(menagerie)),
+        ("Farming Village", play(farming_village)),
+        (
+            "Horse Traders",
+            Behaviour {
+                on_attack: Some(horse_traders_reaction),
+                ..play(horse_traders)
+            },
+        ),
+        ("Remake", play(remake)),
+        ("Tournament", play(tournament)),
+        ("Young Witch", play(young_witch)),
+        ("Harvest", play(harvest)),
+        ("Horn of Plenty", play(horn_of_plenty)),
+        ("Hunting Party", play(hunting_party)),
+        ("Jester", play(jester)),
+        (
+            "Fairgrounds",
+            Behaviour {
+                victory_points: Some(fairgrounds),
+                ..Behaviour::default()
+            },
+        ),
+        // Prizes
+        ("Bag of Gold", play(bag_of_gold)),
+        ("Diadem", play(diadem)),
+        ("Followers", play(followers)),
+        ("Princess", play(princess)),
+        ("Trusty Steed", play(trusty_steed)),
+    ]
+}
+
+const PRIZES: [&str; 5] = [
+    "Bag of Gold",
+    "Diadem",
+    "Followers",
+    "Princess",
+    "Trusty Steed",
+];
+
+fn hamlet(game: &mut Game, source: Card) -> Result<(), GameError> {
+    hamlet_discard(game, source, |player| player.actions += 1);
+    hamlet_discard(game, source, |player| player.buys += 1);
+    Ok(())
+}
+
+/// Lets the player discard a card for one of Hamlet's bonuses.
+fn hamlet_discard(game: &mut Game, source: Card, bonus: fn(&mut Player)) {
+    game.ask(
+        game.curr_player_index,
+        source,
+        from_hand(0, 1),
+        move |game, player_index, answer| {
+            let discarded = answer.cards();
+            if !discarded.is_empty() {
+                bonus(&mut game.players[player_index]);
+            }
+            discard_all(game, player_index, discarded);
+            Ok(())
+        },
+    );
+}
+
+fn fortune_teller(game: &mut Game, _source: Card) -> Result<(), GameError> {
+    for player_index in game.attacked_player_indices() {
+        let (found, revealed) = reveal_until(game, player_index, |_, card| {
+            card.is(CardType::Victory) || card.is(CardType::Curse)
+        });
+        let player = &mut game.players[player_index];
+        for card in revealed {
+            player.add_to_discard(card);
+        }
+        if let Some(card) = found {
+            player.put_on_deck(card);
+        }
+    }
+    Ok(())
+}
+
+fn menagerie(game: &mut Game, _source: Card) -> Result<(), GameError> {
+    let player = game.current_player();
+    let cards = if player.has_duplicates_in_hand() {
+        1
+    } else {
+        3
+    };
+    player.draw(cards);
+    Ok(())
+}
+
+fn farming_village(game: &mut Game, _source: Card) -> Result<(), GameError> {
+    let player_index = game.curr_player_index;
+    let (found, revealed) = reveal_until(game, player_index, |_, card| {
+        card.is(CardType::Treasure) || card.is(CardType::Action)
+    });
+    let player = &mut game.players[player_index];
+    for card in revealed {
+        player.add_to_discard(card);
+    }
+    if let Some(card) = found {
+        player.add_to_hand(card);
+    }
+    Ok(())
+}
+
+fn horse_traders(game: &mut Game, source: Card) -> Result<(), GameError> {
+    game.ask(
+        game.curr_player_index,
+        source,
+        from_hand(2, 2),
+        |game, player_index, answer| {
+            discard_all(game, player_index, answer.cards());
+            Ok(())
+        },
+    );
+    Ok(())
+}
+
+fn horse_traders_reaction(
+    game: &mut Game,
+    player_index: usize,
+    horse_traders: Card,
+    _attack: Card,
+) {
+    game.ask(
+        player_index,
+        horse_traders,
+        options(&["Set Horse Traders aside until your next turn", "Keep it"]),
+        move |game, player_index, answer| {
+            let hand = &mut game.players[player_index].hand;
+            let Some(index) = hand.iter().position(|&card| card == horse_traders) else {
+                return Ok(());
+            };
+            if answer.option() == 0 {
+                let set_aside = hand.remove(index);
+                // Set aside rather than in play, so nothing is kept in play
+                game.at_start_of_turn(player_index, horse_traders, move |game| {
+                    let player = &mut game.players[player_index];
+                    player.draw(1);
+                    player.add_to_hand(set_aside);
+                    Ok(())
+                });
+            }
+            Ok(())
+        },
+    );
+}
+
+fn remake(game: &mut Game, source: Card) -> Result<(), GameError> {
+    for _ in 0..2 {
+        game.ask(
+            game.curr_player_index,
+            source,
+            from_hand(1, 1),
+            move |game, player_index, answer| {
+                for trashed in answer.cards() {
+                    let cost = game.cost_of(trashed).plus_coins(1);
+                    game.trash_card(player_index, trashed);
+                    game.ask(
+                        player_index,
+                        source,
+                        Prompt::CardFromSupply {
+                            max_cost: cost,
+                            exact: true,
+                            filter: CardFilter::Any,
+                        },
+                        |game, player_index, answer| {
+                            game.gain_card(
+                                player_index,
+                                answer.supply_card(),
+                                GainDestination::Discard,
+                            )
+                        },
+                    );
+                }
+                Ok(())
+            },
+        );
+    }
+    Ok(())
+}
+
+fn tournament(game: &mut Game, source: Card) -> Result<(), GameError> {
+    let province = card("Province");
+    let player_index = game.curr_player_index;
+    let other_revealed = (1..game.players.len())
+        .map(|offset| (player_index + offset) % game.players.len())
+        .any(|other| game.players[other].hand.contains(&province));
+    if !other_revealed {
+        let player = game.current_player();
+        player.draw(1);
+        player.coins += 1;
+    }
+    if !game.current_player_read_only().hand.contains(&province) {
+        return Ok(());
+    }
+    let prizes: Vec<Card> = PRIZES
+        .iter()
+        .map(|&name| card(name))
+        .chain([card("Duchy")])
+        .filter(|&prize| game.supply.count(prize).unwrap_or(0) > 0)
+        .collect();
+    let mut choices: Vec<String> = prizes
+        .iter()
+        .map(|prize| format!("Discard a Province to gain {} onto your deck", prize.name()))
+        .collect();
+    choices.push("Don't reveal a Province".to_owned());
+    game.ask(
+        player_index,
+        source,
+        Prompt::Options { options: choices },
+        move |game, player_index, answer| {
+            let Some(&prize) = prizes.get(answer.option()) else {
+                return Ok(());
+            };
+            let player = &mut game.players[player_index];
+            let Some(index) = player.hand.iter().position(|&card| card == province) else {
+                return Ok(());
+            };
+            player.hand.remove(index);
+            player.add_to_discard(province);
+            game.gain_card(player_index, prize, GainDestination::Deck)
+        },
+    );
+    Ok(())
+}
+
+fn young_witch(game: &mut Game, source: Card) -> Result<(), GameError> {
+    game.ask(
+        game.curr_player_index,
+        source,
+        from_hand(2, 2),
+        |game, player_index, answer| {
+            discard_all(game, player_index, answer.cards());
+            Ok(())
+        },
+    );
+    let bane = game.supply.bane;
+    for player_index in game.attacked_player_indices() {
+        let has_bane = bane.is_some_and(|bane| game.players[player_index].hand.contains(&bane));
+        if !has_bane {
+            game.gain_if_available(player_index, card("Curse"), GainDestination::Discard)?;
+        }
+    }
+    Ok(())
+}
+
+fn harvest(game: &mut Game, _source: Card) -> Result<(), GameError> {
+    let player = game.current_player();
+    let revealed = player.reveal_from_deck(4);
+    player.coins += distinct(revealed.iter().copied()).len() as u32;
+    for card in revealed {
+        player.add_to_discard(card);
+    }
+    Ok(())
+}
+
+fn horn_of_plenty(game: &mut Game, source: Card) -> Result<(), GameError> {
+    let differently_named = game.current_player_read_only().differently_named_in_play();
+    game.ask(
+        game.curr_player_index,
+        source,
+        Prompt::CardFromSupply {
+            max_cost: Cost::coins(differently_named as u32),
+            exact: false,
+            filter: CardFilter::Any,
+        },
+        move |game, player_index, answer| {
+            let gained = answer.supply_card();
+            game.gain_card(player_index, gained, GainDestination::Discard)?;
+            if gained.is(CardType::Victory) {
+                if let Some(horn) = game.players[player_index].take_from_play(source) {
+                    game.trash_card(player_index, horn);
+                }
+            }
+            Ok(())
+        },
+    );
+    Ok(())
+}
+
+fn hunting_party(game: &mut Game, _source: Card) -> Result<(), GameError> {
+    let player_index = game.curr_player_index;
+    let hand = game.players[player_index].hand.clone();
+    let (found, revealed) = reveal_until(game, player_index, |_, card| !hand.contains(&card));
+    let player = &mut game.players[player_index];
+    for card in revealed {
+        player.add_to_discard(card);
+    }
+    if let Some(card) = found {
+        player.add_to_hand(card);
+    }
+    Ok(())
+}
+
+fn jester(game: &mut Game, source: Card) -> Result<(), GameError> {
+    for player_index in game.attacked_player_indices() {
+        let Some(top) = game.players[player_index].take_from_deck() else {
+            continue;
+        };
+        game.players[player_index].add_to_discard(top);
+        if top.is(CardType::Victory) {
+            game.gain_if_available(player_index, card("Curse"), GainDestination::Discard)?;
+            continue;
+        }
+        if game.supply.count(top).unwrap_or(0) == 0 {
+            continue;
+        }
+        game.ask(
+            game.curr_player_index,
+            source,
+            Prompt::Options {
+                options: vec![
+                    format!("You gain a {}", top.name()),
+                    format!("Player {} gains a {}", player_index, top.name()),
+                ],
+            },
+            move |game, current, answer| {
+                let gainer = if answer.option() == 0 {
+                    current
+                } else {
+                    player_index
+                };
+                game.gain_if_available(gainer, top, GainDestination::Discard)
+            },
+        );
+    }
+    Ok(())
+}
+
+fn fairgrounds(player: &Player) -> i32 {
+    2 * (player.differently_named_cards() / 5) as i32
+}
+
+fn bag_of_gold(game: &mut Game, _source: Card) -> Result<(), GameError> {
+    game.gain_if_available(game.curr_player_index, card("Gold"), GainDestination::Deck)
+}
+
+fn diadem(game: &mut Game, _source: Card) -> Result<(), GameError> {
+    let player = game.current_player();
+    player.coins += player.actions;
+    Ok(())
+}
+
+fn followers(game: &mut Game, source: Card) -> Result<(), GameError> {
+    game.gain_if_available(
+        game.curr_player_index,
+        card("Estate"),
+        GainDestination::Discard,
+    )?;
+    for player_index in game.attacked_player_indices() {
+        game.gain_if_available(player_index, card("Curse"), GainDestination::Discard)?;
+        discard_down_to(game, player_index, source, 3);
+    }
+    Ok(())
+}
+
+fn princess(game: &mut Game, _source: Card) -> Result<(), GameError> {
+    game.cost_modifiers.push(CostModifier {
+        filter: CardFilter::Any,
+        coin_reduction: 2,
+    });
+    Ok(())
+}
+
+const TRUSTY_STEED_CHOICES: [&str; 6] = [
+    "+2 Cards and +2 Actions",
+    "+2 Cards and +$2",
+    "+2 Cards, then gain 4 Silvers and put your deck into your discard pile",
+    "+2 Actions and +$2",
+    "+2 Actions, then gain 4 Silvers and put your deck into your discard pile",
+    "+$2, then gain 4 Silvers and put your deck into your discard pile",
+];
+
+fn trusty_steed(game: &mut Game, source: Card) -> Result<(), GameError> {
+    game.ask(
+        game.curr_player_index,
+        source,
+        options(&TRUSTY_STEED_CHOICES),
+        |game, player_index, answer| {
+            let choice = TRUSTY_STEED_CHOICES[answer.option()];
+            let player = &mut game.players[player_index];
+            if choice.starts_with("+2 Cards") {
+                player.draw(2);
+            }
+            if choice.contains("+2 Actions") {
+                player.actions += 2;
+            }
+            if choice.contains("+$2") {
+                player.coins += 2;
+            }
+            if choice.contains("Silvers") {
+                for _ in 0..4 {
+                    game.gain_if_available(player_index, card("Silver"), GainDestination::Discard)?;
+                }
+                let player = &mut game.players[player_index];
+                let deck = std::mem::take(&mut player.deck);
+                for card in deck {
+                    player.add_to_discard(card);
+                }
+            }
+            Ok(())
+        },
+    );
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::cards::testing::{choose_cards, choose_option, play, scenario};
+
+    const KINGDOM: [&str; 10] = [
+        "Tournament",
+        "Young Witch",
+        "Hamlet",
+        "Fortune Teller",
+        "Menagerie",
+        "Farming Village",
+        "Horse Traders",
+        "Remake",
+        "Harvest",
+        "Hunting Party",
+    ];
+
+    #[test]
+    fn prizes_are_set_aside_out_of_the_supply() {
+        let game = scenario(&KINGDOM, [&[], &[]]);
+
+        for prize in PRIZES {
+            assert_eq!(game.supply.count(card(prize)), Some(1));
+            assert!(!game.supply.in_supply(card(prize)));
+        }
+    }
+
+    #[test]
+    fn tournament_discards_a_province_to_gain_a_prize_onto_the_deck() {
+        let mut game = scenario(&KINGDOM, [&["Tournament", "Province"], &[]]);
+        let player_index = game.curr_player_index;
+
+        play(&mut game, "Tournament");
+        // Options follow the order of the Prizes, with the Duchy after them
+        choose_option(&mut game, 0);
+
+        let player = &game.players[player_index];
+        assert_eq!(player.deck.last(), Some(&card("Bag of Gold")));
+        assert_eq!(player.discard, [card("Province")]);
+        // Nobody else revealed a Province, so it drew a Silver as well
+        assert_eq!(player.hand, [card("Silver")]);
+        assert_eq!(player.coins, 1);
+        assert_eq!(game.supply.count(card("Bag of Gold")), Some(0));
+    }
+
+    #[test]
+    fn tournament_gives_nothing_extra_when_another_player_reveals_a_province() {
+        let mut game = scenario(&KINGDOM, [&["Tournament"], &["Province"]]);
+        let player_index = game.curr_player_index;
+
+        play(&mut game, "Tournament");
+
+        assert!(game.pending_decision().is_none());
+        assert!(game.players[player_index].hand.is_empty());
+        assert_eq!(game.players[player_index].coins, 0);
+    }
+
+    #[test]
+    fn young_witch_adds_a_bane_pile_costing_two_or_three() {
+        let game = scenario(&KINGDOM, [&[], &[]]);
+
+        let bane = game.supply.bane.unwrap();
+        assert!((2..=3).contains(&bane.cost().coins));
+        assert!(!KINGDOM.contains(&bane.name()));
+        assert!(game.supply.in_supply(bane));
+    }
+
+    #[test]
+    fn the_bane_in_hand_blocks_young_witchs_curse() {
+        let mut game = scenario(&KINGDOM, [&["Young Witch"], &[], &[]]);
+        let player_index = game.curr_player_index;
+        let holder = (player_index + 1) % 3;
+        let cursed = (player_index + 2) % 3;
+        game.players[holder].hand = vec![game.supply.bane.unwrap()];
+
+        play(&mut game, "Young Witch");
+        choose_cards(&mut game, &["Silver", "Silver"]);
+
+        assert!(game.players[holder].discard.is_empty());
+        assert_eq!(game.players[cursed].discard, [card("Curse")]);
+        assert_eq!(game.players[player_index].discard.len(), 2);
+    }
+}
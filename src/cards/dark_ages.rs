@@ -0,0 +1,1489 @@
+//! Behaviours for the Dark Ages cards: the kingdom, the Shelters, the Ruins,
+//! the Knights and the cards from non-supply piles. Many of them care about
+//! being trashed, which the `on_trash` hook handles.
+
+use super::{
+    card, choose_one, choose_to_play, discard_all, discard_down_to, distinct, from_hand,
+    from_hand_of, gain_from_supply, options, play_from_supply, play_taken, reveal_until, Behaviour,
+    Card, CardFilter, CardType, Cost,
+};
+use crate::{Event, Expiry, GainDestination, Game, GameError, Player, Prompt};
+
+pub(super) fn behaviours() -> Vec<(&'static str, Behaviour)> {
+    let play = |on_play| Behaviour {
+        on_play: Some(on_play),
+        ..Behaviour::default()
+    };
+    let on_trash = |on_play, on_trash| Behaviour {
+        on_trash: Some(on_trash),
+        ..play(on_play)
+    };
+    vec![
+        (
+            "Hovel",
+            Behaviour {
+                on_buy_in_hand: Some(hovel),
+                ..Behaviour::default()
+            },
+        ),
+        (
+            "Overgrown Estate",
+            Behaviour {
+                on_trash: Some(overgrown_estate),
+                ..Behaviour::default()
+            },
+        ),
+        ("Survivors", play(survivors)),
+        ("Spoils", play(spoils)),
+        ("Madman", play(madman)),
+        ("Mercenary", play(mercenary)),
+        ("Poor House", play(poor_house)),
+        (
+            "Beggar",
+            Behaviour {
+                on_attack: Some(beggar_reaction),
+                ..play(beggar)
+            },
+        ),
+        ("Squire", on_trash(squire, squire_trashed)),
+        ("Vagrant", play(vagrant)),
+        ("Forager", play(forager)),
+        ("Hermit", play(hermit)),
+        (
+            "Market Square",
+            Behaviour {
+                on_event: Some(market_square),
+                ..Behaviour::default()
+            },
+        ),
+        ("Sage", play(sage)),
+        ("Storeroom", play(storeroom)),
+        ("Urchin", play(urchin)),
+        ("Armory", play(armory)),
+        (
+            "Death Cart",
+            Behaviour {
+                on_gain: Some(death_cart_gained),
+                ..play(death_cart)
+            },
+        ),
+        (
+            "Feodum",
+            Behaviour {
+                victory_points: Some(feodum),
+                on_trash: Some(feodum_trashed),
+                ..Behaviour::default()
+            },
+        ),
+        (
+            "Fortress",
+            Behaviour {
+                on_trash: Some(fortress_trashed),
+                ..Behaviour::default()
+            },
+        ),
+        ("Ironmonger", play(ironmonger)),
+        ("Marauder", play(marauder)),
+        ("Procession", play(procession)),
+        ("Rats", on_trash(rats, rats_trashed)),
+        ("Scavenger", play(scavenger)),
+        ("Wandering Minstrel", play(wandering_minstrel)),
+        ("Band of Misfits", play(band_of_misfits)),
+        ("Bandit Camp", play(bandit_camp)),
+        ("Catacombs", on_trash(catacombs, catacombs_trashed)),
+        ("Count", play(count)),
+        ("Counterfeit", play(counterfeit)),
+        ("Cultist", on_trash(cultist, cultist_trashed)),
+        ("Graverobber", play(graverobber)),
+        ("Junk Dealer", play(junk_dealer)),
+        ("Mystic", play(mystic)),
+        ("Pillage", play(pillage)),
+        ("Rebuild", play(rebuild)),
+        ("Rogue", play(rogue)),
+        ("Altar", play(altar)),
+        (
+            "Hunting Grounds",
+            Behaviour {
+                on_trash: Some(hunting_grounds_trashed),
+                ..Behaviour::default()
+            },
+        ),
+        ("Dame Anna", play(dame_anna)),
+        ("Dame Josephine", play(knight)),
+        ("Dame Molly", play(knight)),
+        ("Dame Natalie", play(dame_natalie)),
+        ("Dame Sylvia", play(knight)),
+        ("Sir Bailey", play(knight)),
+        ("Sir Destry", play(knight)),
+        ("Sir Martin", play(knight)),
+        ("Sir Michael", play(sir_michael)),
+        ("Sir Vander", on_trash(knight, sir_vander_trashed)),
+    ]
+}
+
+fn gain_ruins(game: &mut Game, player_index: usize) -> Result<(), GameError> {
+    match game.supply.top_of("Ruins") {
+        Some(ruins) => game.gain_card(player_index, ruins, GainDestination::Discard),
+        None => Ok(()),
+    }
+}
+
+/// Trashes a card from the player's hand by name, if it is still there.
+fn trash_from_hand(game: &mut Game, player_index: usize, card: Card) {
+    let hand = &mut game.players[player_index].hand;
+    if let Some(index) = hand.iter().position(|&in_hand| in_hand == card) {
+        hand.remove(index);
+        game.trash_card(player_index, card);
+    }
+}
+
+/// Trashes a card the current player has in play, returning whether it was there.
+fn trash_from_play(game: &mut Game, source: Card) -> bool {
+    let player_index = game.curr_player_index;
+    match game.players[player_index].take_from_play(source) {
+        Some(card) => {
+            game.trash_card(player_index, card);
+            true
+        }
+        None => false,
+    }
+}
+
+fn hovel(game: &mut Game, player_index: usize, hovel: Card, bought: Card) {
+    if !bought.is(CardType::Victory) {
+        return;
+    }
+    game.ask(
+        player_index,
+        hovel,
+        options(&["Trash Hovel", "Keep it"]),
+        move |game, player_index, answer| {
+            if answer.option() == 0 {
+                trash_from_hand(game, player_index, hovel);
+            }
+            Ok(())
+        },
+    );
+}
+
+fn overgrown_estate(game: &mut Game, player_index: usize, _card: Card) {
+    game.players[player_index].draw(1);
+}
+
+fn survivors(game: &mut Game, source: Card) -> Result<(), GameError> {
+    let player = game.current_player();
+    let revealed = player.reveal_from_deck(2);
+    for &card in revealed.iter().rev() {
+        player.put_on_deck(card);
+    }
+    let names: Vec<&str> = revealed.iter().map(|card| card.name()).collect();
+    game.ask(
+        game.curr_player_index,
+        source,
+        Prompt::Options {
+            options: vec![
+                format!("Discard {}", names.join(" and ")),
+                "Put them back".to_owned(),
+            ],
+        },
+        move |game, player_index, answer| {
+            if answer.option() == 0 {
+                let player = &mut game.players[player_index];
+                for _ in 0..revealed.len() {
+                    if let Some(card) = player.deck.pop() {
+                        player.add_to_discard(card);
+                    }
+                }
+            }
+            Ok(())
+        },
+    );
+    Ok(())
+}
+
+fn spoils(game: &mut Game, source: Card) -> Result<(), GameError> {
+    match game.current_player().take_from_play(source) {
+        Some(spoils) => game.supply.return_card(spoils),
+        None => Ok(()),
+    }
+}
+
+fn madman(game: &mut Game, source: Card) -> Result<(), GameError> {
+    let Some(madman) = game.current_player().take_from_play(source) else {
+        return Ok(());
+    };
+    game.supply.return_card(madman)?;
+    let player = game.current_player();
+    let hand_size = player.hand.len();
+    player.draw(hand_size);
+    Ok(())
+}
+
+fn mercenary(game: &mut Game, source: Card) -> Result<(), GameError> {
+    game.ask(
+        game.curr_player_index,
+        source,
+        from_hand(0, 2),
+        move |game, player_index, answer| {
+            let trashed = answer.cards();
+            let trashed_two = trashed.len() == 2;
+            for card in trashed {
+                game.trash_card(player_index, card);
+            }
+            if trashed_two {
+                let player = &mut game.players[player_index];
+                player.draw(2);
+                player.coins += 2;
+                for target in game.attacked_player_indices() {
+                    discard_down_to(game, target, source, 3);
+                }
+            }
+            Ok(())
+        },
+    );
+    Ok(())
+}
+
+fn poor_house(game: &mut Game, _source: Card) -> Result<(), GameError> {
+    let player = game.current_player();
+    let treasures = player
+        .hand
+        .iter()
+        .filter(|card| card.is(CardType::Treasure))
+        .count();
+    player.coins = player.coins.saturating_sub(treasures as u32);
+    Ok(())
+}
+
+fn beggar(game: &mut Game, _source: Card) -> Result<(), GameError> {
+    for _ in 0..3 {
+        game.gain_if_available(
+            game.curr_player_index,
+            card("Copper"),
+            GainDestination::Hand,
+        )?;
+    }
+    Ok(())
+}
+
+fn beggar_reaction(game: &mut Game, player_index: usize, beggar: Card, _attack: Card) {
+    game.ask(
+        player_index,
+        beggar,
+        options(&["Discard Beggar to gain 2 Silvers", "Don't discard it"]),
+        move |game, player_index, answer| {
+            let player = &mut game.players[player_index];
+            let in_hand = player.hand.iter().position(|&card| card == beggar);
+            if let (0, Some(index)) = (answer.option(), in_hand) {
+                let beggar = player.hand.remove(index);
+                player.add_to_discard(beggar);
+                let silver = card("Silver");
+                game.gain_if_available(player_index, silver, GainDestination::Deck)?;
+                game.gain_if_available(player_index, silver, GainDestination::Discard)?;
+            }
+            Ok(())
+        },
+    );
+}
+
+fn squire(game: &mut Game, source: Card) -> Result<(), GameError> {
+    choose_one(
+        game,
+        source,
+        &["+2 Actions", "+2 Buys", "Gain a Silver"],
+        |game, player_index, ability| {
+            let player = &mut game.players[player_index];
+            match ability {
+                0 => player.actions += 2,
+                1 => player.buys += 2,
+                _ => {
+                    game.gain_if_available(player_index, card("Silver"), GainDestination::Discard)?
+                }
+            }
+            Ok(())
+        },
+    );
+    Ok(())
+}
+
+fn squire_trashed(game: &mut Game, player_index: usize, squire: Card) {
+    let attacks: Vec<Card> = game
+        .supply
+        .available_cards()
+        .into_iter()
+        .filter(|card| card.is(CardType::Attack))
+        .collect();
+    let options = attacks
+        .iter()
+        .map(|attack| format!("Gain {}", attack.name()))
+        .collect();
+    game.ask(
+        player_index,
+        squire,
+        Prompt::Options { options },
+        move |game, player_index, answer| {
+            game.gain_card(
+                player_index,
+                attacks[answer.option()],
+                GainDestination::Discard,
+            )
+        },
+    );
+}
+
+fn vagrant(game: &mut Game, _source: Card) -> Result<(), GameError> {
+    let player = game.current_player();
+    for card in player.reveal_from_deck(1) {
+        let wanted = [
+            CardType::Curse,
+            CardType::Ruins,
+            CardType::Shelter,
+            CardType::Victory,
+        ];
+        if wanted.iter().any(|&card_type| card.is(card_type)) {
+            player.add_to_hand(card);
+        } else {
+            player.put_on_deck(card);
+        }
+    }
+    Ok(())
+}
+
+fn forager(game: &mut Game, source: Card) -> Result<(), GameError> {
+    game.ask(
+        game.curr_player_index,
+        source,
+        from_hand(1, 1),
+        |game, player_index, answer| {
+            for card in answer.cards() {
+                game.trash_card(player_index, card);
+            }
+            Ok(())
+        },
+    );
+    game.then(|game| {
+        let treasures = distinct(
+            game.trash
+                .cards
+                .iter()
+                .copied()
+                .filter(|card| card.is(CardType::Treasure)),
+        );
+        game.current_player().coins += treasures.len() as u32;
+        Ok(())
+    });
+    Ok(())
+}
+
+fn hermit(game: &mut Game, source: Card) -> Result<(), GameError> {
+    let owner = game.curr_player_index;
+    let player = game.current_player_read_only();
+    let non_treasure = |card: &Card| !card.is(CardType::Treasure);
+    let in_discard = distinct(player.discard.iter().copied().filter(non_treasure));
+    let in_hand = distinct(player.hand.iter().copied().filter(non_treasure));
+    let mut choices: Vec<String> = in_discard
+        .iter()
+        .map(|card| format!("Trash {} from your discard pile", card.name()))
+        .chain(
+            in_hand
+                .iter()
+                .map(|card| format!("Trash {} from your hand", card.name())),
+        )
+        .collect();
+    choices.push("Don't trash anything".to_owned());
+    game.ask(
+        owner,
+        source,
+        Prompt::Options { options: choices },
+        move |game, player_index, answer| {
+            let option = answer.option();
+            let player = &mut game.players[player_index];
+            let taken = if let Some(&card) = in_discard.get(option) {
+                let index = player.discard.iter().rposition(|&c| c == card);
+                index.map(|index| player.discard.remove(index))
+            } else if let Some(&card) = in_hand.get(option - in_discard.len()) {
+                let index = player.hand.iter().position(|&c| c == card);
+                index.map(|index| player.hand.remove(index))
+            } else {
+                None
+            };
+            if let Some(card) = taken {
+                game.trash_card(player_index, card);
+            }
+            Ok(())
+        },
+    );
+    game.ask(
+        owner,
+        source,
+        gain_from_supply(Cost::coins(3), CardFilter::Any),
+        |game, player_index, answer| {
+            game.gain_card(player_index, answer.supply_card(), GainDestination::Discard)
+        },
+    );
+
+    game.add_trigger(Expiry::EndOfTurn, move |game, event| {
+        let Event::BuyPhaseEnd { player_index } = event else {
+            return Ok(true);
+        };
+        let bought_nothing = game.history.current_turn.bought.is_empty();
+        if player_index == owner && bought_nothing && trash_from_play(game, source) {
+            game.gain_if_available(owner, card("Madman"), GainDestination::Discard)?;
+        }
+        Ok(false)
+    });
+    Ok(())
+}
+
+/// Watches the trash for the whole game, as the Market Squares that react are
+/// in their owners' hands.
+fn market_square(game: &mut Game, market_square: Card, event: Event) {
+    if let Event::Trash { player_index } = event {
+        market_square_offer(game, player_index, market_square);
+    }
+}
+
+/// Offers to discard a Market Square for a Gold, once per copy in hand.
+fn market_square_offer(game: &mut Game, player_index: usize, market_square: Card) {
+    if !game.players[player_index].hand.contains(&market_square) {
+        return;
+    }
+    game.ask(
+        player_index,
+        market_square,
+        options(&["Discard Market Square to gain a Gold", "Keep it"]),
+        move |game, player_index, answer| {
+            if answer.option() == 0 {
+                from_hand_of(&mut game.players[player_index], market_square);
+                discard_all(game, player_index, vec![market_square]);
+                game.gain_if_available(player_index, card("Gold"), GainDestination::Discard)?;
+                market_square_offer(game, player_index, market_square);
+            }
+            Ok(())
+        },
+    );
+}
+
+fn sage(game: &mut Game, _source: Card) -> Result<(), GameError> {
+    let player_index = game.curr_player_index;
+    let (found, revealed) = reveal_until(game, player_index, |game, card| {
+        game.cost_of(card).coins >= 3
+    });
+    game.players[player_index].discard.extend(revealed);
+    if let Some(card) = found {
+        game.players[player_index].add_to_hand(card);
+    }
+    Ok(())
+}
+
+fn storeroom(game: &mut Game, source: Card) -> Result<(), GameError> {
+    let hand_size = game.current_player_read_only().hand.len();
+    game.ask(
+        game.curr_player_index,
+        source,
+        from_hand(0, hand_size),
+        move |game, player_index, answer| {
+            let discarded = answer.cards();
+            let player = &mut game.players[player_index];
+            let count = discarded.len();
+            for card in discarded {
+                player.add_to_discard(card);
+            }
+            player.draw(count);
+            let hand_size = player.hand.len();
+            game.ask(
+                player_index,
+                source,
+                from_hand(0, hand_size),
+                |game, player_index, answer| {
+                    let player = &mut game.players[player_index];
+                    for card in answer.cards() {
+                        player.add_to_discard(card);
+                        player.coins += 1;
+                    }
+                    Ok(())
+                },
+            );
+            Ok(())
+        },
+    );
+    Ok(())
+}
+
+fn urchin(game: &mut Game, source: Card) -> Result<(), GameError> {
+    for player_index in game.attacked_player_indices() {
+        discard_down_to(game, player_index, source, 4);
+    }
+    let owner = game.curr_player_index;
+    game.add_trigger(Expiry::EndOfTurn, move |game, event| {
+        let Event::Play {
+            player_index,
+            card: played,
+        } = event
+        else {
+            return Ok(true);
+        };
+        let in_play = game.players[owner].played.contains(&source);
+        if player_index != owner || !played.is(CardType::Attack) || !in_play {
+            return Ok(in_play);
+        }
+        game.ask(
+            owner,
+            source,
+            options(&["Trash Urchin to gain a Mercenary", "Keep it"]),
+            move |game, player_index, answer| {
+                if answer.option() == 0 && trash_from_play(game, source) {
+                    game.gain_if_available(
+                        player_index,
+                        card("Mercenary"),
+                        GainDestination::Discard,
+                    )?;
+                }
+                Ok(())
+            },
+        );
+        Ok(true)
+    });
+    Ok(())
+}
+
+fn armory(game: &mut Game, source: Card) -> Result<(), GameError> {
+    game.ask(
+        game.curr_player_index,
+        source,
+        gain_from_supply(Cost::coins(4), CardFilter::Any),
+        |game, player_index, answer| {
+            game.gain_card(player_index, answer.supply_card(), GainDestination::Deck)
+        },
+    );
+    Ok(())
+}
+
+fn death_cart(game: &mut Game, source: Card) -> Result<(), GameError> {
+    let actions = distinct(
+        game.current_player_read_only()
+            .hand
+            .iter()
+            .copied()
+            .filter(|card| card.is(CardType::Action)),
+    );
+    let mut choices: Vec<String> = actions
+        .iter()
+        .map(|action| format!("Trash {} for +$5", action.name()))
+        .collect();
+    choices.push("Trash Death Cart for +$5".to_owned());
+    choices.push("Trash nothing".to_owned());
+    game.ask(
+        game.curr_player_index,
+        source,
+        Prompt::Options { options: choices },
+        move |game, player_index, answer| {
+            let option = answer.option();
+            let trashed = if let Some(&action) = actions.get(option) {
+                let had_it = game.players[player_index].hand.contains(&action);
+                trash_from_hand(game, player_index, action);
+                had_it
+            } else if option == actions.len() {
+                trash_from_play(game, source)
+            } else {
+                false
+            };
+            if trashed {
+                game.players[player_index].coins += 5;
+            }
+            Ok(())
+        },
+    );
+    Ok(())
+}
+
+fn death_cart_gained(game: &mut Game, player_index: usize, _card: Card) {
+    game.then(move |game| {
+        gain_ruins(game, player_index)?;
+        gain_ruins(game, player_index)
+    });
+}
+
+fn feodum(player: &Player) -> i32 {
+    let silvers = player
+        .all_cards()
+        .filter(|card| card.name() == "Silver")
+        .count();
+    (silvers / 3) as i32
+}
+
+fn feodum_trashed(game: &mut Game, player_index: usize, _card: Card) {
+    game.then(move |game| {
+        for _ in 0..3 {
+            game.gain_if_available(player_index, card("Silver"), GainDestination::Discard)?;
+        }
+        Ok(())
+    });
+}
+
+fn fortress_trashed(game: &mut Game, player_index: usize, fortress: Card) {
+    if let Some(index) = game.trash.cards.iter().rposition(|&card| card == fortress) {
+        if let Some(fortress) = game.trash.take(index) {
+            game.players[player_index].add_to_hand(fortress);
+        }
+    }
+}
+
+fn ironmonger(game: &mut Game, source: Card) -> Result<(), GameError> {
+    let player = game.current_player();
+    let Some(top) = player.take_from_deck() else {
+        return Ok(());
+    };
+    player.put_on_deck(top);
+    if top.is(CardType::Action) {
+        player.actions += 1;
+    }
+    if top.is(CardType::Treasure) {
+        player.coins += 1;
+    }
+    game.ask(
+        game.curr_player_index,
+        source,
+        Prompt::Options {
+            options: vec![format!("Discard {}", top.name()), "Put it back".to_owned()],
+        },
+        move |game, player_index, answer| {
+            let player = &mut game.players[player_index];
+            if answer.option() == 0 {
+                if let Some(top) = player.deck.pop() {
+                    player.add_to_discard(top);
+                }
+            }
+            // The card is drawn after the choice, so a kept Victory card draws itself
+            if top.is(CardType::Victory) {
+                player.draw(1);
+            }
+            Ok(())
+        },
+    );
+    Ok(())
+}
+
+fn marauder(game: &mut Game, _source: Card) -> Result<(), GameError> {
+    game.gain_if_available(
+        game.curr_player_index,
+        card("Spoils"),
+        GainDestination::Discard,
+    )?;
+    for player_index in game.attacked_player_indices() {
+        gain_ruins(game, player_index)?;
+    }
+    Ok(())
+}
+
+fn procession(game: &mut Game, source: Card) -> Result<(), GameError> {
+    let actions = game
+        .current_player_read_only()
+        .hand
+        .iter()
+        .copied()
+        .filter(|card| card.is(CardType::Action) && !card.is(CardType::Duration))
+        .collect();
+    choose_to_play(game, source, actions, move |game, chosen| {
+        from_hand_of(game.current_player(), chosen);
+        play_taken(game, chosen)?;
+        game.then(move |game| game.resolve_play(chosen));
+        game.then(move |game| {
+            let player_index = game.curr_player_index;
+            let max_cost = game.cost_of(chosen).plus_coins(1);
+            if let Some(trashed) = game.players[player_index].take_from_play(chosen) {
+                game.trash_card(player_index, trashed);
+            }
+            game.ask(
+                player_index,
+                source,
+                Prompt::CardFromSupply {
+                    max_cost,
+                    exact: true,
+                    filter: CardFilter::OfType(CardType::Action),
+                },
+                |game, player_index, answer| {
+                    game.gain_card(player_index, answer.supply_card(), GainDestination::Discard)
+                },
+            );
+            Ok(())
+        });
+        Ok(())
+    });
+    Ok(())
+}
+
+fn rats(game: &mut Game, source: Card) -> Result<(), GameError> {
+    let player_index = game.curr_player_index;
+    game.gain_if_available(player_index, source, GainDestination::Discard)?;
+    let others = distinct(
+        game.players[player_index]
+            .hand
+            .iter()
+            .copied()
+            .filter(|&card| card != source),
+    );
+    let options = others
+        .iter()
+        .map(|card| format!("Trash {}", card.name()))
+        .collect();
+    game.ask(
+        player_index,
+        source,
+        Prompt::Options { options },
+        move |game, player_index, answer| {
+            trash_from_hand(game, player_index, others[answer.option()]);
+            Ok(())
+        },
+    );
+    Ok(())
+}
+
+fn rats_trashed(game: &mut Game, player_index: usize, _card: Card) {
+    game.players[player_index].draw(1);
+}
+
+fn scavenger(game: &mut Game, source: Card) -> Result<(), GameError> {
+    game.ask(
+        game.curr_player_index,
+        source,
+        options(&["Put your deck into your discard pile", "Keep your deck"]),
+        move |game, player_index, answer| {
+            let player = &mut game.players[player_index];
+            if answer.option() == 0 {
+                let deck = std::mem::take(&mut player.deck);
+                player.discard.extend(deck);
+            }
+            let choices = distinct(player.discard.iter().copied());
+            let options = choices
+                .iter()
+                .map(|card| format!("Put {} onto your deck", card.name()))
+                .collect();
+            game.ask(
+                player_index,
+                source,
+                Prompt::Options { options },
+                move |game, player_index, answer| {
+                    let player = &mut game.players[player_index];
+                    let chosen = choices[answer.option()];
+                    if let Some(index) = player.discard.iter().rposition(|&c| c == chosen) {
+                        let card = player.discard.remove(index);
+                        player.put_on_deck(card);
+                    }
+                    Ok(())
+                },
+            );
+            Ok(())
+        },
+    );
+    Ok(())
+}
+
+fn wandering_minstrel(game: &mut Game, _source: Card) -> Result<(), GameError> {
+    let player = game.current_player();
+    let revealed = player.reveal_from_deck(3);
+    // The Actions go back in the order they were revealed
+    for &card in revealed.iter().rev() {
+        if card.is(CardType::Action) {
+            player.put_on_deck(card);
+        } else {
+            player.add_to_discard(card);
+        }
+    }
+    Ok(())
+}
+
+fn band_of_misfits(game: &mut Game, source: Card) -> Result<(), GameError> {
+    let cost = game.cost_of(source);
+    if cost.coins > 0 {
+        play_from_supply(game, source, Cost::coins(cost.coins - 1));
+    }
+    Ok(())
+}
+
+fn bandit_camp(game: &mut Game, _source: Card) -> Result<(), GameError> {
+    game.gain_if_available(
+        game.curr_player_index,
+        card("Spoils"),
+        GainDestination::Discard,
+    )
+}
+
+fn catacombs(game: &mut Game, source: Card) -> Result<(), GameError> {
+    let player = game.current_player();
+    let looked_at = player.reveal_from_deck(3);
+    for &card in looked_at.iter().rev() {
+        player.put_on_deck(card);
+    }
+    let names: Vec<&str> = looked_at.iter().map(|card| card.name()).collect();
+    choose_one(
+        game,
+        source,
+        &[
+            &format!("Put {} into your hand", names.join(", ")),
+            "Discard them and +3 Cards",
+        ],
+        move |game, player_index, ability| {
+            let to_hand = ability == 0;
+            let player = &mut game.players[player_index];
+            for _ in 0..looked_at.len() {
+                if let Some(card) = player.deck.pop() {
+                    if to_hand {
+                        player.add_to_hand(card);
+                    } else {
+                        player.add_to_discard(card);
+                    }
+                }
+            }
+            if !to_hand {
+                player.draw(3);
+            }
+            Ok(())
+        },
+    );
+    Ok(())
+}
+
+fn catacombs_trashed(game: &mut Game, player_index: usize, catacombs: Card) {
+    let cost = game.cost_of(catacombs);
+    if cost.coins == 0 {
+        return;
+    }
+    game.ask(
+        player_index,
+        catacombs,
+        Prompt::CardFromSupply {
+            max_cost: cost.minus_coins(1),
+            exact: false,
+            filter: CardFilter::Any,
+        },
+        |game, player_index, answer| {
+            game.gain_card(player_index, answer.supply_card(), GainDestination::Discard)
+        },
+    );
+}
+
+fn count(game: &mut Game, source: Card) -> Result<(), GameError> {
+    choose_one(
+        game,
+        source,
+        &[
+            "Discard 2 cards",
+            "Put a card from your hand onto your deck",
+            "Gain a Copper",
+        ],
+        move |game, player_index, ability| {
+            match ability {
+                0 => game.ask(
+                    player_index,
+                    source,
+                    from_hand(2, 2),
+                    |game, player_index, answer| {
+                        for card in answer.cards() {
+                            game.players[player_index].add_to_discard(card);
+                        }
+                        Ok(())
+                    },
+                ),
+                1 => game.ask(
+                    player_index,
+                    source,
+                    from_hand(1, 1),
+                    |game, player_index, answer| {
+                        for card in answer.cards() {
+                            game.players[player_index].put_on_deck(card);
+                        }
+                        Ok(())
+                    },
+                ),
+                _ => {
+                    game.gain_if_available(player_index, card("Copper"), GainDestination::Discard)?
+                }
+            }
+            Ok(())
+        },
+    );
+    choose_one(
+        game,
+        source,
+        &["+$3", "Trash your hand", "Gain a Duchy"],
+        |game, player_index, ability| {
+            match ability {
+                0 => game.players[player_index].coins += 3,
+                1 => {
+                    for card in std::mem::take(&mut game.players[player_index].hand) {
+                        game.trash_card(player_index, card);
+                    }
+                }
+                _ => {
+                    game.gain_if_available(player_index, card("Duchy"), GainDestination::Discard)?
+                }
+            }
+            Ok(())
+        },
+    );
+    Ok(())
+}
+
+fn counterfeit(game: &mut Game, source: Card) -> Result<(), GameError> {
+    let treasures = game
+        .current_player_read_only()
+        .hand
+        .iter()
+        .copied()
+        .filter(|card| card.is(CardType::Treasure) && !card.is(CardType::Duration))
+        .collect();
+    choose_to_play(game, source, treasures, |game, chosen| {
+        from_hand_of(game.current_player(), chosen);
+        play_taken(game, chosen)?;
+        game.then(move |game| game.resolve_play(chosen));
+        game.then(move |game| {
+            let player_index = game.curr_player_index;
+            if let Some(trashed) = game.players[player_index].take_from_play(chosen) {
+                game.trash_card(player_index, trashed);
+            }
+            Ok(())
+        });
+        Ok(())
+    });
+    Ok(())
+}
+
+fn cultist(game: &mut Game, source: Card) -> Result<(), GameError> {
+    for player_index in game.attacked_player_indices() {
+        gain_ruins(game, player_index)?;
+    }
+    if !game.current_player_read_only().hand.contains(&source) {
+        return Ok(());
+    }
+    game.ask(
+        game.curr_player_index,
+        source,
+        options(&["Play a Cultist from your hand", "Don't"]),
+        move |game, player_index, answer| {
+            let hand = &mut game.players[player_index].hand;
+            let in_hand = hand.iter().position(|&card| card == source);
+            if let (0, Some(index)) = (answer.option(), in_hand) {
+                let cultist = hand.remove(index);
+                game.play_card(cultist)?;
+                game.resolve_play(cultist)?;
+            }
+            Ok(())
+        },
+    );
+    Ok(())
+}
+
+fn cultist_trashed(game: &mut Game, player_index: usize, _card: Card) {
+    game.players[player_index].draw(3);
+}
+
+fn graverobber(game: &mut Game, source: Card) -> Result<(), GameError> {
+    choose_one(
+        game,
+        source,
+        &[
+            "Gain a card from the trash costing from $3 to $6 onto your deck",
+            "Trash an Action card from your hand and gain a card costing up to $3 more",
+        ],
+        move |game, player_index, ability| {
+            if ability == 0 {
+                let choices = distinct(
+                    game.trash_cards_costing(3, 6)
+                        .into_iter()
+                        .map(|(_, card)| card),
+                );
+                let options = choices
+                    .iter()
+                    .map(|card| format!("Gain {} from the trash", card.name()))
+                    .collect();
+                game.ask(
+                    player_index,
+                    source,
+                    Prompt::Options { options },
+                    move |game, player_index, answer| {
+                        game.gain_from_trash(
+                            player_index,
+                            choices[answer.option()],
+                            GainDestination::Deck,
+                        )
+                    },
+                );
+            } else {
+                game.ask(
+                    player_index,
+                    source,
+                    Prompt::CardsFromHand {
+                        min: 1,
+                        max: 1,
+                        filter: CardFilter::OfType(CardType::Action),
+                    },
+                    move |game, player_index, answer| {
+                        for trashed in answer.cards() {
+                            let max_cost = game.cost_of(trashed).plus_coins(3);
+                            game.trash_card(player_index, trashed);
+                            game.ask(
+                                player_index,
+                                source,
+                                gain_from_supply(max_cost, CardFilter::Any),
+                                |game, player_index, answer| {
+                                    game.gain_card(
+                                        player_index,
+                                        answer.supply_card(),
+                                        GainDestination::Discard,
+                                    )
+                                },
+                            );
+                        }
+                        Ok(())
+                    },
+                );
+            }
+            Ok(())
+        },
+    );
+    Ok(())
+}
+
+fn junk_dealer(game: &mut Game, source: Card) -> Result<(), GameError> {
+    game.ask(
+        game.curr_player_index,
+        source,
+        from_hand(1, 1),
+        |game, player_index, answer| {
+            for card in answer.cards() {
+                game.trash_card(player_index, card);
+            }
+            Ok(())
+        },
+    );
+    Ok(())
+}
+
+fn mystic(game: &mut Game, source: Card) -> Result<(), GameError> {
+    let names = game.supply.all_cards();
+    let options = names
+        .iter()
+        .map(|card| format!("Name {}", card.name()))
+        .collect();
+    game.ask(
+        game.curr_player_index,
+        source,
+        Prompt::Options { options },
+        move |game, player_index, answer| {
+            let named = names[answer.option()];
+            let player = &mut game.players[player_index];
+            for card in player.reveal_from_deck(1) {
+                if card == named {
+                    player.add_to_hand(card);
+                } else {
+                    player.put_on_deck(card);
+                }
+            }
+            Ok(())
+        },
+    );
+    Ok(())
+}
+
+fn pillage(game: &mut Game, source: Card) -> Result<(), GameError> {
+    let owner = game.curr_player_index;
+    if !trash_from_play(game, source) {
+        return Ok(());
+    }
+    for _ in 0..2 {
+        game.gain_if_available(owner, card("Spoils"), GainDestination::Discard)?;
+    }
+    for target in game.attacked_player_indices() {
+        let hand = &game.players[target].hand;
+        if hand.len() < 5 {
+            continue;
+        }
+        let choices = distinct(hand.iter().copied());
+        let options = choices
+            .iter()
+            .map(|card| format!("Player {target} discards {}", card.name()))
+            .collect();
+        game.ask(
+            owner,
+            source,
+            Prompt::Options { options },
+            move |game, _, answer| {
+                let hand = &mut game.players[target].hand;
+                let chosen = choices[answer.option()];
+                if let Some(index) = hand.iter().position(|&card| card == chosen) {
+                    let card = hand.remove(index);
+                    game.players[target].add_to_discard(card);
+                }
+                Ok(())
+            },
+        );
+    }
+    Ok(())
+}
+
+fn rebuild(game: &mut Game, source: Card) -> Result<(), GameError> {
+    let names = game.supply.all_cards();
+    let options = names
+        .iter()
+        .map(|card| format!("Name {}", card.name()))
+        .collect();
+    game.ask(
+        game.curr_player_index,
+        source,
+        Prompt::Options { options },
+        move |game, player_index, answer| {
+            let named = names[answer.option()];
+            let (found, revealed) = reveal_until(game, player_index, |_, card| {
+                card.is(CardType::Victory) && card != named
+            });
+            game.players[player_index].discard.extend(revealed);
+            let Some(victory) = found else {
+                return Ok(());
+            };
+            let max_cost = game.cost_of(victory).plus_coins(3);
+            game.trash_card(player_index, victory);
+            game.ask(
+                player_index,
+                source,
+                gain_from_supply(max_cost, CardFilter::OfType(CardType::Victory)),
+                |game, player_index, answer| {
+                    game.gain_card(player_index, answer.supply_card(), GainDestination::Discard)
+                },
+            );
+            Ok(())
+        },
+    );
+    Ok(())
+}
+
+/// Has a player reveal the top 2 cards of their deck, trash one of them
+/// costing from $3 to $6 (their choice if there are two different ones) and
+/// discard the rest, then calls `after` with the trashed card.
+fn trash_revealed(
+    game: &mut Game,
+    player_index: usize,
+    source: Card,
+    after: impl FnOnce(&mut Game, Card) -> Result<(), GameError> + Send + 'static,
+) {
+    let revealed = game.players[player_index].reveal_from_deck(2);
+    let trashable = |game: &Game, card: Card| {
+        let cost = game.cost_of(card);
+        cost.fits_within(&Cost::coins(6)) && cost.coins >= 3
+    };
+    let choices = distinct(
+        revealed
+            .iter()
+            .copied()
+            .filter(|&card| trashable(game, card)),
+    );
+    let resolve = move |game: &mut Game, trashed: Option<Card>| {
+        let mut kept_back = trashed;
+        for card in revealed {
+            if kept_back == Some(card) {
+                kept_back = None;
+                game.trash_card(player_index, card);
+            } else {
+                game.players[player_index].add_to_discard(card);
+            }
+        }
+        match trashed {
+            Some(trashed) => after(game, trashed),
+            None => Ok(()),
+        }
+    };
+    match choices[..] {
+        [] => game.then(move |game| resolve(game, None)),
+        [only] => game.then(move |game| resolve(game, Some(only))),
+        _ => {
+            let options = choices
+                .iter()
+                .map(|card| format!("Trash {}", card.name()))
+                .collect();
+            game.ask(
+                player_index,
+                source,
+                Prompt::Options { options },
+                move |game, _, answer| resolve(game, Some(choices[answer.option()])),
+            );
+        }
+    }
+}
+
+fn rogue(game: &mut Game, source: Card) -> Result<(), GameError> {
+    let in_trash = distinct(
+        game.trash_cards_costing(3, 6)
+            .into_iter()
+            .map(|(_, card)| card),
+    );
+    if in_trash.is_empty() {
+        for player_index in game.attacked_player_indices() {
+            trash_revealed(game, player_index, source, |_, _| Ok(()));
+        }
+        return Ok(());
+    }
+    let options = in_trash
+        .iter()
+        .map(|card| format!("Gain {} from the trash", card.name()))
+        .collect();
+    game.ask(
+        game.curr_player_index,
+        source,
+        Prompt::Options { options },
+        move |game, player_index, answer| {
+            game.gain_from_trash(
+                player_index,
+                in_trash[answer.option()],
+                GainDestination::Discard,
+            )
+        },
+    );
+    Ok(())
+}
+
+fn altar(game: &mut Game, source: Card) -> Result<(), GameError> {
+    let player_index = game.curr_player_index;
+    game.ask(
+        player_index,
+        source,
+        from_hand(1, 1),
+        |game, player_index, answer| {
+            for card in answer.cards() {
+                game.trash_card(player_index, card);
+            }
+            Ok(())
+        },
+    );
+    game.ask(
+        player_index,
+        source,
+        gain_from_supply(Cost::coins(5), CardFilter::Any),
+        |game, player_index, answer| {
+            game.gain_card(player_index, answer.supply_card(), GainDestination::Discard)
+        },
+    );
+    Ok(())
+}
+
+fn hunting_grounds_trashed(game: &mut Game, player_index: usize, hunting_grounds: Card) {
+    game.ask(
+        player_index,
+        hunting_grounds,
+        options(&["Gain a Duchy", "Gain 3 Estates"]),
+        |game, player_index, answer| {
+            let (gained, copies) = match answer.option() {
+                0 => ("Duchy", 1),
+                _ => ("Estate", 3),
+            };
+            for _ in 0..copies {
+                game.gain_if_available(player_index, card(gained), GainDestination::Discard)?;
+            }
+            Ok(())
+        },
+    );
+}
+
+/// The attack every Knight shares. A Knight trashing another Knight is
+/// trashed as well.
+fn knight(game: &mut Game, source: Card) -> Result<(), GameError> {
+    for player_index in game.attacked_player_indices() {
+        trash_revealed(game, player_index, source, move |game, trashed| {
+            if trashed.is(CardType::Knight) {
+                trash_from_play(game, source);
+            }
+            Ok(())
+        });
+    }
+    Ok(())
+}
+
+fn dame_anna(game: &mut Game, source: Card) -> Result<(), GameError> {
+    game.ask(
+        game.curr_player_index,
+        source,
+        from_hand(0, 2),
+        |game, player_index, answer| {
+            for card in answer.cards() {
+                game.trash_card(player_index, card);
+            }
+            Ok(())
+        },
+    );
+    knight(game, source)
+}
+
+fn dame_natalie(game: &mut Game, source: Card) -> Result<(), GameError> {
+    game.ask(
+        game.curr_player_index,
+        source,
+        options(&["Gain a card costing up to $3", "Don't gain a card"]),
+        move |game, player_index, answer| {
+            if answer.option() == 0 {
+                game.ask(
+                    player_index,
+                    source,
+                    gain_from_supply(Cost::coins(3), CardFilter::Any),
+                    |game, player_index, answer| {
+                        game.gain_card(player_index, answer.supply_card(), GainDestination::Discard)
+                    },
+                );
+            }
+            Ok(())
+        },
+    );
+    knight(game, source)
+}
+
+fn sir_michael(game: &mut Game, source: Card) -> Result<(), GameError> {
+    for player_index in game.attacked_player_indices() {
+        discard_down_to(game, player_index, source, 3);
+    }
+    knight(game, source)
+}
+
+fn sir_vander_trashed(game: &mut Game, player_index: usize, _card: Card) {
+    game.then(move |game| {
+        game.gain_if_available(player_index, card("Gold"), GainDestination::Discard)
+    });
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::cards::testing::{choose_option, play, scenario};
+    use crate::{GameMove, GamePhase};
+
+    const KINGDOM: [&str; 10] = [
+        "Vagrant",
+        "Forager",
+        "Hermit",
+        "Sage",
+        "Squire",
+        "Storeroom",
+        "Death Cart",
+        "Marauder",
+        "Cultist",
+        "Sir Bailey",
+    ];
+
+    fn count(cards: &[Card], name: &str) -> usize {
+        cards.iter().filter(|card| card.name() == name).count()
+    }
+
+    #[test]
+    fn ruins_are_a_mixed_pile_of_ten_per_other_player() {
+        for num_players in [2, 3] {
+            let kingdom: Vec<Card> = KINGDOM.iter().map(|&name| card(name)).collect();
+            let game = Game::initialise_game_with_kingdom(num_players, 1, &kingdom).unwrap();
+            let ruins = &game.supply.pile_named("Ruins").unwrap().cards;
+            assert_eq!(ruins.len(), 10 * (num_players - 1));
+            assert!(ruins.iter().all(|ruin| ruin.is(CardType::Ruins)));
+            assert!(distinct(ruins.iter().copied()).len() > 1);
+        }
+    }
+
+    #[test]
+    fn cultist_gives_each_other_player_a_ruins() {
+        let mut game = scenario(&KINGDOM, [&["Cultist"], &[]]);
+        let other = (game.curr_player_index + 1) % 2;
+
+        play(&mut game, "Cultist");
+
+        let discard = &game.players[other].discard;
+        assert_eq!(discard.len(), 1);
+        assert!(discard[0].is(CardType::Ruins));
+        assert_eq!(game.supply.pile_named("Ruins").unwrap().cards.len(), 9);
+    }
+
+    #[test]
+    fn gaining_death_cart_gains_two_ruins() {
+        let mut game = scenario(&KINGDOM, [&[], &[]]);
+        let player_index = game.curr_player_index;
+
+        game.gain_card(player_index, card("Death Cart"), GainDestination::Discard)
+            .unwrap();
+        game.run_pending().unwrap();
+
+        let discard = &game.players[player_index].discard;
+        assert_eq!(discard[0], card("Death Cart"));
+        assert_eq!(discard.len(), 3);
+        assert!(discard[1..].iter().all(|ruin| ruin.is(CardType::Ruins)));
+    }
+
+    #[test]
+    fn shelters_replace_the_starting_estates_with_dark_ages() {
+        let deck = |kingdom: &[&str]| {
+            let kingdom: Vec<Card> = kingdom.iter().map(|&name| card(name)).collect();
+            let game = Game::initialise_game_with_kingdom(2, 1, &kingdom).unwrap();
+            let mut cards: Vec<Card> = game.players[0].all_cards().collect();
+            cards.sort_by_key(|card| card.name());
+            cards
+        };
+
+        let cards = deck(&KINGDOM);
+        assert_eq!(count(&cards, "Estate"), 0);
+        for shelter in ["Hovel", "Necropolis", "Overgrown Estate"] {
+            assert_eq!(count(&cards, shelter), 1);
+        }
+
+        // A full kingdom, so that none of it is picked at random
+        let mut kingdom = crate::DEFAULT_KINGDOM.to_vec();
+        kingdom.extend(["Witch", "Mine"]);
+        let cards = deck(&kingdom);
+        assert_eq!(count(&cards, "Estate"), 3);
+        assert!(cards.iter().all(|card| !card.is(CardType::Shelter)));
+    }
+
+    #[test]
+    fn overgrown_estate_draws_a_card_when_trashed() {
+        let mut game = scenario(&KINGDOM, [&[], &[]]);
+        let player_index = game.curr_player_index;
+
+        game.trash_card(player_index, card("Overgrown Estate"));
+
+        assert_eq!(game.current_player_read_only().hand, [card("Silver")]);
+    }
+
+    #[test]
+    fn hovel_can_be_trashed_when_buying_a_victory_card() {
+        let mut game = scenario(&KINGDOM, [&["Hovel"], &[]]);
+        let player_index = game.curr_player_index;
+        game.game_phase = GamePhase::BuyPhase;
+        game.current_player().coins = 2;
+
+        game.accept_move(
+            player_index,
+            GameMove::BuyCard {
+                card: card("Estate"),
+            },
+        )
+        .unwrap();
+        choose_option(&mut game, 0);
+
+        assert!(game.current_player_read_only().hand.is_empty());
+        assert_eq!(game.trash.cards, [card("Hovel")]);
+    }
+
+    #[test]
+    fn the_knights_share_one_pile() {
+        let game = scenario(&KINGDOM, [&[], &[]]);
+
+        let knights = &game.supply.pile_named("Knights").unwrap().cards;
+        assert_eq!(knights.len(), 10);
+        assert!(knights.iter().all(|knight| knight.is(CardType::Knight)));
+        assert_eq!(distinct(knights.iter().copied()).len(), 10);
+    }
+
+    #[test]
+    fn a_knight_that_trashes_a_knight_is_trashed_too() {
+        let mut game = scenario(&KINGDOM, [&["Sir Bailey"], &[]]);
+        let other = (game.curr_player_index + 1) % 2;
+        game.players[other].deck = vec![card("Copper"), card("Sir Destry")];
+
+        play(&mut game, "Sir Bailey");
+
+        assert_eq!(game.trash.cards, [card("Sir Destry"), card("Sir Bailey")]);
+        assert!(game.current_player_read_only().played.is_empty());
+        assert_eq!(game.players[other].discard, [card("Copper")]);
+    }
+}
@@ -0,0 +1,1630 @@
+//! Behaviours for the Empires kingdom cards, Events and Landmarks. Gathering
+//! cards and several Landmarks keep VP tokens in `Supply::vp_tokens`, under
+//! the name of their pile or Landmark. Landmarks watch events through
+//! `Behaviour::on_event` and change the final scores through `Behaviour::score`.
+
+use std::{collections::HashMap, mem::take};
+
+use super::{
+    before_clean_up, card, choose_one, choose_to_play, discard_all, discard_down_to, distinct,
+    from_hand, from_hand_of, gain_from_supply, next_turn, options, play_from_supply, play_taken,
+    Behaviour, Card, CardFilter, CardType, Cost,
+};
+use crate::{Event, Expiry, GainDestination, Game, GameError, GamePhase, Player, Prompt};
+
+pub(super) fn behaviours() -> Vec<(&'static str, Behaviour)> {
+    let play = |on_play| Behaviour {
+        on_play: Some(on_play),
+        ..Behaviour::default()
+    };
+    let event = |on_buy| Behaviour {
+        on_buy: Some(on_buy),
+        ..Behaviour::default()
+    };
+    let landmark = |on_event| Behaviour {
+        on_event: Some(on_event),
+        ..Behaviour::default()
+    };
+    let scoring = |score| Behaviour {
+        score: Some(score),
+        ..Behaviour::default()
+    };
+    vec![
+        ("Encampment", play(encampment)),
+        ("Plunder", play(plunder)),
+        ("Patrician", play(patrician)),
+        (
+            "Emporium",
+            Behaviour {
+                on_gain: Some(emporium),
+                ..Behaviour::default()
+            },
+        ),
+        ("Settlers", play(settlers)),
+        ("Bustling Village", play(bustling_village)),
+        ("Catapult", play(catapult)),
+        (
+            "Rocks",
+            Behaviour {
+                on_gain: Some(rocks),
+                on_trash: Some(rocks),
+                ..Behaviour::default()
+            },
+        ),
+        ("Gladiator", play(gladiator)),
+        (
+            "Fortune",
+            Behaviour {
+                on_gain: Some(fortune_on_gain),
+                ..play(fortune)
+            },
+        ),
+        ("Engineer", play(engineer)),
+        ("City Quarter", play(city_quarter)),
+        ("Overlord", play(overlord)),
+        ("Royal Blacksmith", play(royal_blacksmith)),
+        ("Chariot Race", play(chariot_race)),
+        ("Enchantress", play(enchantress)),
+        ("Farmers' Market", play(farmers_market)),
+        ("Sacrifice", play(sacrifice)),
+        (
+            "Temple",
+            Behaviour {
+                on_gain: Some(temple_on_gain),
+                ..play(temple)
+            },
+        ),
+        (
+            "Villa",
+            Behaviour {
+                on_gain: Some(villa),
+                ..Behaviour::default()
+            },
+        ),
+        ("Archive", play(archive)),
+        ("Capital", play(capital)),
+        ("Charm", play(charm)),
+        ("Crown", play(crown)),
+        (
+            "Forum",
+            Behaviour {
+                on_buy: Some(forum_on_buy),
+                ..play(forum)
+            },
+        ),
+        ("Groundskeeper", play(groundskeeper)),
+        ("Legionary", play(legionary)),
+        ("Wild Hunt", play(wild_hunt)),
+        // Castles
+        (
+            "Humble Castle",
+            Behaviour {
+                victory_points: Some(humble_castle),
+                ..Behaviour::default()
+            },
+        ),
+        (
+            "Crumbling Castle",
+            Behaviour {
+                on_gain: Some(crumbling_castle),
+                on_trash: Some(crumbling_castle),
+                ..Behaviour::default()
+            },
+        ),
+        ("Small Castle", play(small_castle)),
+        (
+            "Haunted Castle",
+            Behaviour {
+                on_gain: Some(haunted_castle),
+                ..Behaviour::default()
+            },
+        ),
+        ("Opulent Castle", play(opulent_castle)),
+        (
+            "Sprawling Castle",
+            Behaviour {
+                on_gain: Some(sprawling_castle),
+                ..Behaviour::default()
+            },
+        ),
+        (
+            "Grand Castle",
+            Behaviour {
+                on_gain: Some(grand_castle),
+                ..Behaviour::default()
+            },
+        ),
+        (
+            "King's Castle",
+            Behaviour {
+                victory_points: Some(kings_castle),
+                ..Behaviour::default()
+            },
+        ),
+        // Events
+        ("Triumph", event(triumph)),
+        ("Annex", event(annex)),
+        ("Donate", event(donate)),
+        ("Advance", event(advance)),
+        ("Delve", event(delve)),
+        ("Tax", event(tax)),
+        ("Banquet", event(banquet)),
+        ("Ritual", event(ritual)),
+        ("Salt the Earth", event(salt_the_earth)),
+        ("Wedding", event(wedding)),
+        ("Windfall", event(windfall)),
+        ("Conquest", event(conquest)),
+        ("Dominate", event(dominate)),
+        // Landmarks
+        ("Aqueduct", landmark(aqueduct)),
+        ("Arena", landmark(arena)),
+        ("Basilica", landmark(basilica)),
+        ("Baths", landmark(baths)),
+        ("Battlefield", landmark(battlefield)),
+        ("Colonnade", landmark(colonnade)),
+        ("Defiled Shrine", landmark(defiled_shrine)),
+        ("Labyrinth", landmark(labyrinth)),
+        ("Mountain Pass", landmark(mountain_pass)),
+        ("Tomb", landmark(tomb)),
+        ("Bandit Fort", scoring(bandit_fort)),
+        ("Fountain", scoring(fountain)),
+        ("Keep", scoring(keep)),
+        ("Museum", scoring(museum)),
+        ("Obelisk", scoring(obelisk)),
+        ("Orchard", scoring(orchard)),
+        ("Palace", scoring(palace)),
+        ("Tower", scoring(tower)),
+        ("Triumphal Arch", scoring(triumphal_arch)),
+        ("Wall", scoring(wall)),
+        ("Wolf Den", scoring(wolf_den)),
+    ]
+}
+
+/// The player to the left of the current player.
+fn left_player_index(game: &Game) -> usize {
+    (game.curr_player_index + 1) % game.players.len()
+}
+
+/// Moves up to `vp` VP tokens from a pile or Landmark to a player.
+fn take_vp(game: &mut Game, player_index: usize, from: &str, vp: u32) {
+    let taken = game.supply.take_vp(from, vp);
+    game.players[player_index].vp_tokens += taken;
+}
+
+/// How many copies of each card the player has, by name.
+fn card_counts(player: &Player) -> HashMap<&'static str, usize> {
+    let mut counts = HashMap::new();
+    for card in player.all_cards() {
+        *counts.entry(card.name()).or_default() += 1;
+    }
+    counts
+}
+
+fn count_named(player: &Player, name: &str) -> usize {
+    player
+        .all_cards()
+        .filter(|card| card.name() == name)
+        .count()
+}
+
+fn encampment(game: &mut Game, source: Card) -> Result<(), GameError> {
+    let can_reveal = game
+        .current_player_read_only()
+        .hand
+        .iter()
+        .any(|card| matches!(card.name(), "Gold" | "Plunder"));
+    if !can_reveal {
+        set_aside_encampment(game, source);
+        return Ok(());
+    }
+    game.ask(
+        game.curr_player_index,
+        source,
+        options(&["Reveal a Gold or Plunder", "Set Encampment aside"]),
+        move |game, _, answer| {
+            if answer.option() == 1 {
+                set_aside_encampment(game, source);
+            }
+            Ok(())
+        },
+    );
+    Ok(())
+}
+
+/// Sets Encampment aside until it returns to its pile at the start of clean-up.
+fn set_aside_encampment(game: &mut Game, source: Card) {
+    let owner = game.curr_player_index;
+    let Some(encampment) = game.current_player().take_from_play(source) else {
+        return;
+    };
+    let mut set_aside = Some(encampment);
+    game.add_trigger(Expiry::EndOfTurn, move |game, event| {
+        if !matches!(event, Event::BuyPhaseEnd { player_index } if player_index == owner) {
+            return Ok(true);
+        }
+        if let Some(encampment) = set_aside.take() {
+            game.supply.return_card(encampment)?;
+        }
+        Ok(false)
+    });
+}
+
+fn plunder(game: &mut Game, _source: Card) -> Result<(), GameError> {
+    game.current_player().vp_tokens += 1;
+    Ok(())
+}
+
+fn patrician(game: &mut Game, _source: Card) -> Result<(), GameError> {
+    let Some(top) = game.current_player().take_from_deck() else {
+        return Ok(());
+    };
+    let costly = game.cost_of(top).coins >= 5;
+    let player = game.current_player();
+    if costly {
+        player.add_to_hand(top);
+    } else {
+        player.put_on_deck(top);
+    }
+    Ok(())
+}
+
+fn emporium(game: &mut Game, player_index: usize, _card: Card) {
+    let actions_in_play = game.players[player_index]
+        .played
+        .iter()
+        .filter(|card| card.is(CardType::Action))
+        .count();
+    if actions_in_play >= 5 {
+        game.players[player_index].vp_tokens += 2;
+    }
+}
+
+/// Lets the current player put a card with the given name from their
+/// discard pile into their hand.
+fn take_from_discard(game: &mut Game, source: Card, name: &'static str) {
+    if !game
+        .current_player_read_only()
+        .discard
+        .iter()
+        .any(|card| card.name() == name)
+    {
+        return;
+    }
+    game.ask(
+        game.curr_player_index,
+        source,
+        Prompt::Options {
+            options: vec![
+                format!("Put a {name} from your discard pile into your hand"),
+                "Leave your discard pile alone".to_owned(),
+            ],
+        },
+        move |game, player_index, answer| {
+            let player = &mut game.players[player_index];
+            if answer.option() == 0 {
+                if let Some(index) = player.discard.iter().rposition(|c| c.name() == name) {
+                    let taken = player.discard.remove(index);
+                    player.add_to_hand(taken);
+                }
+            }
+            Ok(())
+        },
+    );
+}
+
+fn settlers(game: &mut Game, source: Card) -> Result<(), GameError> {
+    take_from_discard(game, source, "Copper");
+    Ok(())
+}
+
+fn bustling_village(game: &mut Game, source: Card) -> Result<(), GameError> {
+    take_from_discard(game, source, "Settlers");
+    Ok(())
+}
+
+fn catapult(game: &mut Game, source: Card) -> Result<(), GameError> {
+    let attacked = game.attacked_player_indices();
+    game.ask(
+        game.curr_player_index,
+        source,
+        from_hand(1, 1),
+        move |game, player_index, answer| {
+            for trashed in answer.cards() {
+                let costly = game.cost_of(trashed).coins >= 3;
+                game.trash_card(player_index, trashed);
+                for &other in &attacked {
+                    if costly {
+                        game.gain_if_available(other, card("Curse"), GainDestination::Discard)?;
+                    }
+                    if trashed.is(CardType::Treasure) {
+                        discard_down_to(game, other, source, 3);
+                    }
+                }
+            }
+            Ok(())
+        },
+    );
+    Ok(())
+}
+
+fn rocks(game: &mut Game, player_index: usize, _card: Card) {
+    let buy_phase =
+        player_index == game.curr_player_index && matches!(game.game_phase, GamePhase::BuyPhase);
+    let destination = if buy_phase {
+        GainDestination::Deck
+    } else {
+        GainDestination::Hand
+    };
+    game.then(move |game| game.gain_if_available(player_index, card("Silver"), destination));
+}
+
+fn gladiator(game: &mut Game, source: Card) -> Result<(), GameError> {
+    game.ask(
+        game.curr_player_index,
+        source,
+        from_hand(1, 1),
+        move |game, player_index, answer| {
+            let Some(&revealed) = answer.cards().first() else {
+                return Ok(());
+            };
+            game.players[player_index].add_to_hand(revealed);
+            let left = left_player_index(game);
+            if !game.players[left].hand.contains(&revealed) {
+                gladiator_unmatched(game);
+                return Ok(());
+            }
+            game.ask(
+                left,
+                source,
+                Prompt::Options {
+                    options: vec![
+                        format!("Reveal a {}", revealed.name()),
+                        "Don't reveal it".to_owned(),
+                    ],
+                },
+                |game, _, answer| {
+                    if answer.option() == 1 {
+                        gladiator_unmatched(game);
+                    }
+                    Ok(())
+                },
+            );
+            Ok(())
+        },
+    );
+    Ok(())
+}
+
+fn gladiator_unmatched(game: &mut Game) {
+    game.current_player().coins += 1;
+    let gladiator = card("Gladiator");
+    if game.supply.take_card(gladiator).is_ok() {
+        game.trash_card(game.curr_player_index, gladiator);
+    }
+}
+
+fn fortune(game: &mut Game, _source: Card) -> Result<(), GameError> {
+    if game.history.current_turn.played_count("Fortune") == 1 {
+        game.current_player().coins *= 2;
+    }
+    Ok(())
+}
+
+fn fortune_on_gain(game: &mut Game, player_index: usize, _card: Card) {
+    let gladiators = game.players[player_index]
+        .played
+        .iter()
+        .filter(|card| card.name() == "Gladiator")
+        .count();
+    game.then(move |game| {
+        for _ in 0..gladiators {
+            game.gain_if_available(player_index, card("Gold"), GainDestination::Discard)?;
+        }
+        Ok(())
+    });
+}
+
+fn engineer(game: &mut Game, source: Card) -> Result<(), GameError> {
+    let gain = move |game: &mut Game, player_index: usize| {
+        game.ask(
+            player_index,
+            source,
+            gain_from_supply(Cost::coins(4), CardFilter::Any),
+            |game, player_index, answer| {
+                game.gain_card(player_index, answer.supply_card(), GainDestination::Discard)
+            },
+        );
+    };
+    gain(game, game.curr_player_index);
+    game.ask(
+        game.curr_player_index,
+        source,
+        options(&["Trash Engineer to gain another card", "Keep Engineer"]),
+        move |game, player_index, answer| {
+            if answer.option() == 0 {
+                if let Some(engineer) = game.players[player_index].take_from_play(source) {
+                    game.trash_card(player_index, engineer);
+                    gain(game, player_index);
+                }
+            }
+            Ok(())
+        },
+    );
+    Ok(())
+}
+
+fn city_quarter(game: &mut Game, _source: Card) -> Result<(), GameError> {
+    let player = game.current_player();
+    let actions = player
+        .hand
+        .iter()
+        .filter(|card| card.is(CardType::Action))
+        .count();
+    player.draw(actions);
+    Ok(())
+}
+
+fn overlord(game: &mut Game, source: Card) -> Result<(), GameError> {
+    play_from_supply(game, source, Cost::coins(5));
+    Ok(())
+}
+
+fn royal_blacksmith(game: &mut Game, _source: Card) -> Result<(), GameError> {
+    let player = game.current_player();
+    let (coppers, rest) = std::mem::take(&mut player.hand)
+        .into_iter()
+        .partition(|card| card.name() == "Copper");
+    player.hand = rest;
+    player.discard.extend::<Vec<Card>>(coppers);
+    Ok(())
+}
+
+fn chariot_race(game: &mut Game, _source: Card) -> Result<(), GameError> {
+    let Some(mine) = game.current_player().take_from_deck() else {
+        return Ok(());
+    };
+    game.current_player().add_to_hand(mine);
+    let left = left_player_index(game);
+    let theirs = game.players[left].take_from_deck();
+    if let Some(theirs) = theirs {
+        game.players[left].put_on_deck(theirs);
+    }
+    let their_cost = theirs.map_or(0, |theirs| game.cost_of(theirs).coins);
+    if theirs.is_none() || game.cost_of(mine).coins > their_cost {
+        let player = game.current_player();
+        player.coins += 1;
+        player.vp_tokens += 1;
+    }
+    Ok(())
+}
+
+fn enchantress(game: &mut Game, source: Card) -> Result<(), GameError> {
+    let owner = game.curr_player_index;
+    for victim in game.attacked_player_indices() {
+        game.add_trigger(Expiry::StartOfTurn(owner), move |game, event| {
+            if let Event::Play { player_index, card } = event {
+                let actions_played = game
+                    .history
+                    .current_turn
+                    .played
+                    .iter()
+                    .filter(|card| card.is(CardType::Action))
+                    .count();
+                if player_index == victim && card.is(CardType::Action) && actions_played == 1 {
+                    game.players[victim].enchanted = true;
+                }
+            }
+            Ok(true)
+        });
+    }
+    next_turn(game, source, |game, player_index| {
+        game.players[player_index].draw(2);
+        Ok(())
+    });
+    Ok(())
+}
+
+fn farmers_market(game: &mut Game, source: Card) -> Result<(), GameError> {
+    let owner = game.curr_player_index;
+    let pile = source.name();
+    if game.supply.vp_tokens.get(pile).copied().unwrap_or(0) >= 4 {
+        take_vp(game, owner, pile, u32::MAX);
+        if let Some(market) = game.current_player().take_from_play(source) {
+            game.trash_card(owner, market);
+        }
+    } else {
+        game.supply.add_vp(pile, 1);
+        game.current_player().coins += game.supply.vp_tokens[pile];
+    }
+    Ok(())
+}
+
+fn sacrifice(game: &mut Game, source: Card) -> Result<(), GameError> {
+    game.ask(
+        game.curr_player_index,
+        source,
+        from_hand(1, 1),
+        |game, player_index, answer| {
+            for trashed in answer.cards() {
+                game.trash_card(player_index, trashed);
+                let player = &mut game.players[player_index];
+                if trashed.is(CardType::Action) {
+                    player.draw(2);
+                    player.actions += 2;
+                }
+                if trashed.is(CardType::Treasure) {
+                    player.coins += 2;
+                }
+                if trashed.is(CardType::Victory) {
+                    player.vp_tokens += 2;
+                }
+            }
+            Ok(())
+        },
+    );
+    Ok(())
+}
+
+fn temple(game: &mut Game, source: Card) -> Result<(), GameError> {
+    game.current_player().vp_tokens += 1;
+    game.ask(
+        game.curr_player_index,
+        source,
+        from_hand(1, 3),
+        |game, player_index, answer| {
+            let mut trashed: Vec<Card> = Vec::new();
+            for card in answer.cards() {
+                // Only differently named cards can be trashed; the rest stay in hand
+                if trashed.contains(&card) {
+                    game.players[player_index].add_to_hand(card);
+                } else {
+                    trashed.push(card);
+                    game.trash_card(player_index, card);
+                }
+            }
+            Ok(())
+        },
+    );
+    game.supply.add_vp(source.name(), 1);
+    Ok(())
+}
+
+fn temple_on_gain(game: &mut Game, player_index: usize, temple: Card) {
+    take_vp(game, player_index, temple.name(), u32::MAX);
+}
+
+/// Villa is gained to hand by the engine; this is the rest of its gain effect.
+fn villa(game: &mut Game, player_index: usize, _villa: Card) {
+    if player_index == game.curr_player_index {
+        game.players[player_index].actions += 1;
+        if matches!(game.game_phase, GamePhase::BuyPhase) {
+            game.game_phase = GamePhase::ActionPhase;
+        }
+    }
+}
+
+fn archive(game: &mut Game, source: Card) -> Result<(), GameError> {
+    let set_aside = game.current_player().reveal_from_deck(3);
+    archive_take(game, game.curr_player_index, source, set_aside);
+    Ok(())
+}
+
+/// Puts one of Archive's set aside cards into the player's hand, keeping
+/// Archive in play until the start of the turn after the last one is taken.
+fn archive_take(game: &mut Game, player_index: usize, source: Card, set_aside: Vec<Card>) {
+    if set_aside.is_empty() {
+        return;
+    }
+    game.ask(
+        player_index,
+        source,
+        Prompt::Options {
+            options: set_aside
+                .iter()
+                .map(|card| format!("Put {} into your hand", card.name()))
+                .collect(),
+        },
+        move |game, player_index, answer| {
+            let mut set_aside = set_aside;
+            let taken = set_aside.remove(answer.option());
+            game.players[player_index].add_to_hand(taken);
+            if !set_aside.is_empty() {
+                game.at_start_of_turn(player_index, source, move |game| {
+                    archive_take(game, player_index, source, set_aside);
+                    Ok(())
+                });
+            }
+            Ok(())
+        },
+    );
+}
+
+/// Capital's Debt is taken when it is discarded from play, so only if it is
+/// still in play at clean-up. Playing it twice still only discards it once.
+fn capital(game: &mut Game, _source: Card) -> Result<(), GameError> {
+    before_clean_up(game, |game, player_index| {
+        let player = &mut game.players[player_index];
+        while let Some(capital) = player.take_from_play(card("Capital")) {
+            player.discard.push(capital);
+            player.debt += 6;
+        }
+        // Clean-up pays off as much Debt as it can with the $ left
+    });
+    Ok(())
+}
+
+fn charm(game: &mut Game, source: Card) -> Result<(), GameError> {
+    game.ask(
+        game.curr_player_index,
+        source,
+        options(&[
+            "+1 Buy and +$2",
+            "Gain another card costing the same as the next card you buy",
+        ]),
+        move |game, owner, answer| {
+            if answer.option() == 0 {
+                let player = &mut game.players[owner];
+                player.buys += 1;
+                player.coins += 2;
+                return Ok(());
+            }
+            game.add_trigger(Expiry::EndOfTurn, move |game, event| {
+                let Event::Buy { player_index, card } = event else {
+                    return Ok(true);
+                };
+                if player_index != owner {
+                    return Ok(true);
+                }
+                charm_gain(game, owner, source, card);
+                Ok(false)
+            });
+            Ok(())
+        },
+    );
+    Ok(())
+}
+
+fn charm_gain(game: &mut Game, owner: usize, source: Card, bought: Card) {
+    let cost = game.cost_of(bought);
+    let candidates: Vec<Card> = game
+        .gainable_cards(&cost, true, &CardFilter::Any)
+        .into_iter()
+        .filter(|&card| card.name() != bought.name())
+        .collect();
+    if candidates.is_empty() {
+        return;
+    }
+    let mut choices: Vec<String> = candidates
+        .iter()
+        .map(|card| format!("Gain {}", card.name()))
+        .collect();
+    choices.push("Don't gain a card".to_owned());
+    game.ask(
+        owner,
+        source,
+        Prompt::Options { options: choices },
+        move |game, player_index, answer| match candidates.get(answer.option()) {
+            Some(&gained) => game.gain_card(player_index, gained, GainDestination::Discard),
+            None => Ok(()),
+        },
+    );
+}
+
+fn crown(game: &mut Game, source: Card) -> Result<(), GameError> {
+    let card_type = match game.game_phase {
+        GamePhase::ActionPhase => CardType::Action,
+        _ => CardType::Treasure,
+    };
+    let candidates = game
+        .current_player_read_only()
+        .hand
+        .iter()
+        .copied()
+        .filter(|card| card.is(card_type))
+        .collect();
+    choose_to_play(game, source, candidates, |game, chosen| {
+        from_hand_of(game.current_player(), chosen);
+        play_taken(game, chosen)?;
+        game.then(move |game| game.resolve_play(chosen));
+        Ok(())
+    });
+    Ok(())
+}
+
+fn forum(game: &mut Game, source: Card) -> Result<(), GameError> {
+    discard_down_to(
+        game,
+        game.curr_player_index,
+        source,
+        game.current_player_read_only().hand.len().saturating_sub(2),
+    );
+    Ok(())
+}
+
+fn forum_on_buy(game: &mut Game, _source: Card) -> Result<(), GameError> {
+    game.current_player().buys += 1;
+    Ok(())
+}
+
+fn groundskeeper(game: &mut Game, source: Card) -> Result<(), GameError> {
+    let owner = game.curr_player_index;
+    game.add_trigger(Expiry::EndOfTurn, move |game, event| {
+        if let Event::Gain { player_index, card } = event {
+            let in_play = game.players[owner].played.contains(&source);
+            if player_index == owner && in_play && card.is(CardType::Victory) {
+                game.players[owner].vp_tokens += 1;
+            }
+        }
+        Ok(true)
+    });
+    Ok(())
+}
+
+fn legionary(game: &mut Game, source: Card) -> Result<(), GameError> {
+    if !game.current_player_read_only().hand.contains(&card("Gold")) {
+        return Ok(());
+    }
+    let attacked = game.attacked_player_indices();
+    game.ask(
+        game.curr_player_index,
+        source,
+        options(&["Reveal a Gold", "Don't reveal"]),
+        move |game, _, answer| {
+            if answer.option() == 1 {
+                return Ok(());
+            }
+            for player_index in attacked {
+                discard_down_to(game, player_index, source, 2);
+                game.then(move |game| {
+                    game.players[player_index].draw(1);
+                    Ok(())
+                });
+            }
+            Ok(())
+        },
+    );
+    Ok(())
+}
+
+fn wild_hunt(game: &mut Game, source: Card) -> Result<(), GameError> {
+    choose_one(
+        game,
+        source,
+        &[
+            "+3 Cards and add 1VP to the Wild Hunt pile",
+            "Gain an Estate and take the VP from the pile",
+        ],
+        move |game, player_index, ability| {
+            if ability == 0 {
+                game.players[player_index].draw(3);
+                game.supply.add_vp(source.name(), 1);
+            } else if game.supply.count(card("Estate")).unwrap_or(0) > 0 {
+                game.gain_card(player_index, card("Estate"), GainDestination::Discard)?;
+                take_vp(game, player_index, source.name(), u32::MAX);
+            }
+            Ok(())
+        },
+    );
+    Ok(())
+}
+
+fn castles_owned(player: &Player) -> i32 {
+    player
+        .all_cards()
+        .filter(|card| card.is(CardType::Castle))
+        .count() as i32
+}
+
+fn humble_castle(player: &Player) -> i32 {
+    castles_owned(player)
+}
+
+fn kings_castle(player: &Player) -> i32 {
+    2 * castles_owned(player)
+}
+
+/// Gains whichever Castle is on top of the Castles pile, if any are left.
+fn gain_castle(game: &mut Game, player_index: usize) -> Result<(), GameError> {
+    match game
+        .supply
+        .pile_named("Castles")
+        .and_then(|pile| pile.top())
+    {
+        Some(castle) => game.gain_card(player_index, castle, GainDestination::Discard),
+        None => Ok(()),
+    }
+}
+
+fn crumbling_castle(game: &mut Game, player_index: usize, _card: Card) {
+    game.players[player_index].vp_tokens += 1;
+    game.then(move |game| {
+        game.gain_if_available(player_index, card("Silver"), GainDestination::Discard)
+    });
+}
+
+fn small_castle(game: &mut Game, source: Card) -> Result<(), GameError> {
+    let player = game.current_player_read_only();
+    // `None` stands for this Small Castle, from play
+    let mut targets: Vec<Option<Card>> = Vec::new();
+    if player.played.contains(&source) {
+        targets.push(None);
+    }
+    let castles = distinct(
+        player
+            .hand
+            .iter()
+            .copied()
+            .filter(|card| card.is(CardType::Castle)),
+    );
+    targets.extend(castles.into_iter().map(Some));
+    if targets.is_empty() {
+        return Ok(());
+    }
+    let mut choices: Vec<String> = targets
+        .iter()
+        .map(|target| match target {
+            None => format!("Trash {} from play", source.name()),
+            Some(castle) => format!("Trash {} from your hand", castle.name()),
+        })
+        .collect();
+    choices.push("Don't trash anything".to_owned());
+    game.ask(
+        game.curr_player_index,
+        source,
+        Prompt::Options { options: choices },
+        move |game, player_index, answer| {
+            let player = &mut game.players[player_index];
+            let trashed = match targets.get(answer.option()) {
+                Some(None) => player.take_from_play(source),
+                Some(&Some(castle)) => player
+                    .hand
+                    .iter()
+                    .position(|&card| card == castle)
+                    .map(|index| player.hand.remove(index)),
+                None => None,
+            };
+            if let Some(trashed) = trashed {
+                game.trash_card(player_index, trashed);
+                gain_castle(game, player_index)?;
+            }
+            Ok(())
+        },
+    );
+    Ok(())
+}
+
+fn haunted_castle(game: &mut Game, player_index: usize, source: Card) {
+    if player_index != game.curr_player_index {
+        return;
+    }
+    game.then(move |game| {
+        game.gain_if_available(player_index, card("Gold"), GainDestination::Discard)?;
+        let num_players = game.players.len();
+        for offset in 1..num_players {
+            let other = (player_index + offset) % num_players;
+            if game.players[other].hand.len() < 5 {
+                continue;
+            }
+            game.ask(
+                other,
+                source,
+                from_hand(2, 2),
+                |game, player_index, answer| {
+                    for card in answer.cards() {
+                        game.players[player_index].put_on_deck(card);
+                    }
+                    Ok(())
+                },
+            );
+        }
+        Ok(())
+    });
+}
+
+fn opulent_castle(game: &mut Game, source: Card) -> Result<(), GameError> {
+    let hand_size = game.current_player_read_only().hand.len();
+    game.ask(
+        game.curr_player_index,
+        source,
+        Prompt::CardsFromHand {
+            min: 0,
+            max: hand_size,
+            filter: CardFilter::OfType(CardType::Victory),
+        },
+        |game, player_index, answer| {
+            let discarded = answer.cards();
+            game.players[player_index].coins += 2 * discarded.len() as u32;
+            discard_all(game, player_index, discarded);
+            Ok(())
+        },
+    );
+    Ok(())
+}
+
+fn sprawling_castle(game: &mut Game, player_index: usize, source: Card) {
+    game.ask(
+        player_index,
+        source,
+        options(&["Gain a Duchy", "Gain 3 Estates"]),
+        |game, player_index, answer| {
+            if answer.option() == 0 {
+                return game.gain_if_available(
+                    player_index,
+                    card("Duchy"),
+                    GainDestination::Discard,
+                );
+            }
+            for _ in 0..3 {
+                game.gain_if_available(player_index, card("Estate"), GainDestination::Discard)?;
+            }
+            Ok(())
+        },
+    );
+}
+
+fn grand_castle(game: &mut Game, player_index: usize, _card: Card) {
+    let player = &mut game.players[player_index];
+    let victory_cards = player
+        .hand
+        .iter()
+        .chain(player.played.iter())
+        .chain(player.durations.iter())
+        .filter(|card| card.is(CardType::Victory))
+        .count();
+    player.vp_tokens += victory_cards as u32;
+}
+
+/// Gains a card for the current player if there is one left, returning whether it was gained.
+fn gain_if_left(game: &mut Game, gained: &str) -> Result<bool, GameError> {
+    let gained = card(gained);
+    if game.supply.count(gained).unwrap_or(0) == 0 {
+        return Ok(false);
+    }
+    game.gain_card(game.curr_player_index, gained, GainDestination::Discard)?;
+    Ok(true)
+}
+
+fn triumph(game: &mut Game, _source: Card) -> Result<(), GameError> {
+    if gain_if_left(game, "Estate")? {
+        let owner = game.curr_player_index;
+        let gained = game.history.current_turn.gained_by(owner).count();
+        game.players[owner].vp_tokens += gained as u32;
+    }
+    Ok(())
+}
+
+fn annex(game: &mut Game, source: Card) -> Result<(), GameError> {
+    let owner = game.curr_player_index;
+    let discard = take(&mut game.players[owner].discard);
+    annex_keep(game, owner, source, Vec::new(), discard);
+    game.then(move |game| game.gain_if_available(owner, card("Duchy"), GainDestination::Discard));
+    Ok(())
+}
+
+/// Has the player pick the cards to leave in their discard pile one at a time,
+/// up to 5, then shuffles the rest into their deck.
+fn annex_keep(
+    game: &mut Game,
+    player_index: usize,
+    source: Card,
+    kept: Vec<Card>,
+    rest: Vec<Card>,
+) {
+    let candidates = if kept.len() < 5 {
+        distinct(rest.iter().copied())
+    } else {
+        Vec::new()
+    };
+    if candidates.is_empty() {
+        annex_shuffle(&mut game.players[player_index], kept, rest);
+        return;
+    }
+    let mut choices: Vec<String> = candidates
+        .iter()
+        .map(|card| format!("Leave a {} in your discard pile", card.name()))
+        .collect();
+    choices.push("Shuffle the rest into your deck".to_owned());
+    game.ask(
+        player_index,
+        source,
+        Prompt::Options { options: choices },
+        move |game, player_index, answer| {
+            let (mut kept, mut rest) = (kept, rest);
+            match candidates.get(answer.option()) {
+                Some(&left) => {
+                    let index = rest.iter().position(|&card| card == left).unwrap();
+                    kept.push(rest.remove(index));
+                    annex_keep(game, player_index, source, kept, rest);
+                }
+                None => annex_shuffle(&mut game.players[player_index], kept, rest),
+            }
+            Ok(())
+        },
+    );
+}
+
+fn annex_shuffle(player: &mut Player, kept: Vec<Card>, rest: Vec<Card>) {
+    player.deck.extend(rest);
+    player.shuffle_deck();
+    player.discard = kept;
+}
+
+fn donate(game: &mut Game, source: Card) -> Result<(), GameError> {
+    let owner = game.curr_player_index;
+    game.after_turn(move |game| {
+        let player = &mut game.players[owner];
+        let deck = take(&mut player.deck);
+        let discard = take(&mut player.discard);
+        player.hand.extend(deck);
+        player.hand.extend(discard);
+        donate_trash(game, owner, source);
+        Ok(())
+    });
+    Ok(())
+}
+
+/// Has the player trash cards from their hand one at a time, as it holds
+/// their whole deck, then shuffles it into their deck and draws 5 cards.
+fn donate_trash(game: &mut Game, player_index: usize, source: Card) {
+    let candidates = distinct(game.players[player_index].hand.iter().copied());
+    let mut choices: Vec<String> = candidates
+        .iter()
+        .map(|card| format!("Trash a {}", card.name()))
+        .collect();
+    choices.push("Shuffle your hand into your deck".to_owned());
+    game.ask(
+        player_index,
+        source,
+        Prompt::Options { options: choices },
+        move |game, player_index, answer| {
+            if let Some(&trashed) = candidates.get(answer.option()) {
+                from_hand_of(&mut game.players[player_index], trashed);
+                game.trash_card(player_index, trashed);
+                donate_trash(game, player_index, source);
+                return Ok(());
+            }
+            let player = &mut game.players[player_index];
+            let hand = take(&mut player.hand);
+            player.deck.extend(hand);
+            player.shuffle_deck();
+            player.draw(5);
+            Ok(())
+        },
+    );
+}
+
+fn tax(game: &mut Game, source: Card) -> Result<(), GameError> {
+    let piles: Vec<String> = game
+        .supply
+        .piles
+        .iter()
+        .map(|pile| pile.name.clone())
+        .collect();
+    game.ask(
+        game.curr_player_index,
+        source,
+        Prompt::Options {
+            options: piles
+                .iter()
+                .map(|pile| format!("Add 2 Debt to the {pile} pile"))
+                .collect(),
+        },
+        move |game, _, answer| {
+            game.supply.add_debt(&piles[answer.option()], 2);
+            Ok(())
+        },
+    );
+    Ok(())
+}
+
+fn advance(game: &mut Game, source: Card) -> Result<(), GameError> {
+    game.ask(
+        game.curr_player_index,
+        source,
+        Prompt::CardsFromHand {
+            min: 0,
+            max: 1,
+            filter: CardFilter::OfType(CardType::Action),
+        },
+        move |game, player_index, answer| {
+            for trashed in answer.cards() {
+                game.trash_card(player_index, trashed);
+                game.ask(
+                    player_index,
+                    source,
+                    gain_from_supply(Cost::coins(6), CardFilter::OfType(CardType::Action)),
+                    |game, player_index, answer| {
+                        game.gain_card(player_index, answer.supply_card(), GainDestination::Discard)
+                    },
+                );
+            }
+            Ok(())
+        },
+    );
+    Ok(())
+}
+
+fn delve(game: &mut Game, _source: Card) -> Result<(), GameError> {
+    game.current_player().buys += 1;
+    gain_if_left(game, "Silver")?;
+    Ok(())
+}
+
+fn banquet(game: &mut Game, source: Card) -> Result<(), GameError> {
+    gain_if_left(game, "Copper")?;
+    gain_if_left(game, "Copper")?;
+    game.ask(
+        game.curr_player_index,
+        source,
+        gain_from_supply(Cost::coins(5), CardFilter::NotOfType(CardType::Victory)),
+        |game, player_index, answer| {
+            game.gain_card(player_index, answer.supply_card(), GainDestination::Discard)
+        },
+    );
+    Ok(())
+}
+
+fn ritual(game: &mut Game, source: Card) -> Result<(), GameError> {
+    if !gain_if_left(game, "Curse")? {
+        return Ok(());
+    }
+    game.ask(
+        game.curr_player_index,
+        source,
+        from_hand(1, 1),
+        |game, player_index, answer| {
+            for trashed in answer.cards() {
+                let vp = game.cost_of(trashed).coins;
+                game.trash_card(player_index, trashed);
+                game.players[player_index].vp_tokens += vp;
+            }
+            Ok(())
+        },
+    );
+    Ok(())
+}
+
+fn salt_the_earth(game: &mut Game, source: Card) -> Result<(), GameError> {
+    game.current_player().vp_tokens += 1;
+    let victories: Vec<Card> = game
+        .supply
+        .available_cards()
+        .into_iter()
+        .filter(|card| card.is(CardType::Victory))
+        .collect();
+    game.ask(
+        game.curr_player_index,
+        source,
+        Prompt::Options {
+            options: victories
+                .iter()
+                .map(|card| format!("Trash a {} from the supply", card.name()))
+                .collect(),
+        },
+        move |game, player_index, answer| {
+            let trashed = victories[answer.option()];
+            game.supply.take_card(trashed)?;
+            game.trash_card(player_index, trashed);
+            Ok(())
+        },
+    );
+    Ok(())
+}
+
+fn wedding(game: &mut Game, _source: Card) -> Result<(), GameError> {
+    game.current_player().vp_tokens += 1;
+    gain_if_left(game, "Gold")?;
+    Ok(())
+}
+
+fn windfall(game: &mut Game, _source: Card) -> Result<(), GameError> {
+    let player = game.current_player_read_only();
+    if player.deck.is_empty() && player.discard.is_empty() {
+        for _ in 0..3 {
+            gain_if_left(game, "Gold")?;
+        }
+    }
+    Ok(())
+}
+
+fn conquest(game: &mut Game, _source: Card) -> Result<(), GameError> {
+    gain_if_left(game, "Silver")?;
+    gain_if_left(game, "Silver")?;
+    let owner = game.curr_player_index;
+    let silvers = game
+        .history
+        .current_turn
+        .gained_by(owner)
+        .filter(|card| card.name() == "Silver")
+        .count();
+    game.players[owner].vp_tokens += silvers as u32;
+    Ok(())
+}
+
+fn dominate(game: &mut Game, _source: Card) -> Result<(), GameError> {
+    if gain_if_left(game, "Province")? {
+        game.current_player().vp_tokens += 9;
+    }
+    Ok(())
+}
+
+/// Moves 1VP from the pile a gained card came from onto a Landmark.
+fn move_vp_from_pile(game: &mut Game, landmark: Card, gained: Card) {
+    let Some(pile) = game.supply.pile(gained).map(|pile| pile.name.clone()) else {
+        return;
+    };
+    let moved = game.supply.take_vp(&pile, 1);
+    game.supply.add_vp(landmark.name(), moved);
+}
+
+fn aqueduct(game: &mut Game, landmark: Card, event: Event) {
+    if let Event::Gain { player_index, card } = event {
+        if card.is(CardType::Treasure) {
+            move_vp_from_pile(game, landmark, card);
+        }
+        if card.is(CardType::Victory) {
+            take_vp(game, player_index, landmark.name(), u32::MAX);
+        }
+    }
+}
+
+fn arena(game: &mut Game, landmark: Card, event: Event) {
+    let Event::BuyPhaseStart { player_index } = event else {
+        return;
+    };
+    if game
+        .supply
+        .vp_tokens
+        .get(landmark.name())
+        .copied()
+        .unwrap_or(0)
+        == 0
+    {
+        return;
+    }
+    game.ask(
+        player_index,
+        landmark,
+        Prompt::CardsFromHand {
+            min: 0,
+            max: 1,
+            filter: CardFilter::OfType(CardType::Action),
+        },
+        move |game, player_index, answer| {
+            let discarded = answer.cards();
+            if !discarded.is_empty() {
+                discard_all(game, player_index, discarded);
+                take_vp(game, player_index, landmark.name(), 2);
+            }
+            Ok(())
+        },
+    );
+}
+
+fn basilica(game: &mut Game, landmark: Card, event: Event) {
+    if let Event::Buy { player_index, .. } = event {
+        if game.players[player_index].coins >= 2 {
+            take_vp(game, player_index, landmark.name(), 2);
+        }
+    }
+}
+
+fn baths(game: &mut Game, landmark: Card, event: Event) {
+    if let Event::BuyPhaseEnd { player_index } = event {
+        let gained = game.history.current_turn.gained_by(player_index).count();
+        if gained == 0 {
+            take_vp(game, player_index, landmark.name(), 2);
+        }
+    }
+}
+
+fn battlefield(game: &mut Game, landmark: Card, event: Event) {
+    if let Event::Gain { player_index, card } = event {
+        if card.is(CardType::Victory) {
+            take_vp(game, player_index, landmark.name(), 2);
+        }
+    }
+}
+
+fn colonnade(game: &mut Game, landmark: Card, event: Event) {
+    if let Event::Buy { player_index, card } = event {
+        if card.is(CardType::Action) && game.players[player_index].played.contains(&card) {
+            take_vp(game, player_index, landmark.name(), 2);
+        }
+    }
+}
+
+fn defiled_shrine(game: &mut Game, landmark: Card, event: Event) {
+    match event {
+        // Gathering piles keep their own VP
+        Event::Gain { card, .. } if card.is(CardType::Action) && !card.is(CardType::Gathering) => {
+            move_vp_from_pile(game, landmark, card);
+        }
+        Event::Buy { player_index, card } if card.name() == "Curse" => {
+            take_vp(game, player_index, landmark.name(), u32::MAX);
+        }
+        _ => {}
+    }
+}
+
+fn labyrinth(game: &mut Game, landmark: Card, event: Event) {
+    if let Event::Gain { player_index, .. } = event {
+        let this_turn = &game.history.current_turn;
+        if player_index == this_turn.player_index && this_turn.gained_by(player_index).count() == 2
+        {
+            take_vp(game, player_index, landmark.name(), 2);
+        }
+    }
+}
+
+/// Mountain Pass's VP stay on it until the first Province is gained, when
+/// they are taken off to be bid for after that turn.
+fn mountain_pass(game: &mut Game, landmark: Card, event: Event) {
+    let Event::Gain { player_index, card } = event else {
+        return;
+    };
+    if card.name() != "Province" {
+        return;
+    }
+    let vp = game.supply.take_vp(landmark.name(), u32::MAX);
+    if vp == 0 {
+        return;
+    }
+    // Bidding goes round from the player's left, ending with them
+    let num_players = game.players.len();
+    let bidders: Vec<usize> = (1..=num_players)
+        .map(|offset| (player_index + offset) % num_players)
+        .collect();
+    game.after_turn(move |game| {
+        mountain_pass_bid(game, landmark, bidders, vp, None);
+        Ok(())
+    });
+}
+
+/// Asks the next bidder whether to outbid the high bid so far, up to 40 Debt.
+/// Once everyone has had their say, the high bidder gets the VP and the Debt.
+fn mountain_pass_bid(
+    game: &mut Game,
+    landmark: Card,
+    mut bidders: Vec<usize>,
+    vp: u32,
+    high_bid: Option<(usize, u32)>,
+) {
+    if bidders.is_empty() {
+        if let Some((winner, debt)) = high_bid {
+            let player = &mut game.players[winner];
+            player.vp_tokens += vp;
+            player.debt += debt;
+        }
+        return;
+    }
+    let bidder = bidders.remove(0);
+    let lowest = high_bid.map_or(1, |(_, debt)| debt + 1);
+    let bids: Vec<u32> = (lowest..=40).collect();
+    let mut choices = vec!["Don't bid".to_owned()];
+    choices.extend(bids.iter().map(|debt| format!("Bid {debt} Debt")));
+    game.ask(
+        bidder,
+        landmark,
+        Prompt::Options { options: choices },
+        move |game, bidder, answer| {
+            let high_bid = match answer.option() {
+                0 => high_bid,
+                option => Some((bidder, bids[option - 1])),
+            };
+            mountain_pass_bid(game, landmark, bidders, vp, high_bid);
+            Ok(())
+        },
+    );
+}
+
+fn tomb(game: &mut Game, _landmark: Card, event: Event) {
+    if let Event::Trash { player_index } = event {
+        game.players[player_index].vp_tokens += 1;
+    }
+}
+
+fn bandit_fort(game: &Game, player_index: usize) -> i32 {
+    let player = &game.players[player_index];
+    -2 * (count_named(player, "Silver") + count_named(player, "Gold")) as i32
+}
+
+fn fountain(game: &Game, player_index: usize) -> i32 {
+    if count_named(&game.players[player_index], "Copper") >= 10 {
+        15
+    } else {
+        0
+    }
+}
+
+fn keep(game: &Game, player_index: usize) -> i32 {
+    let mut treasures: Vec<Card> = game.players[player_index]
+        .all_cards()
+        .filter(|card| card.is(CardType::Treasure))
+        .collect();
+    treasures.sort_by_key(|card| card.name());
+    treasures.dedup();
+    let most = treasures
+        .into_iter()
+        .filter(|treasure| {
+            let mine = count_named(&game.players[player_index], treasure.name());
+            game.players
+                .iter()
+                .all(|other| count_named(other, treasure.name()) <= mine)
+        })
+        .count();
+    5 * most as i32
+}
+
+fn museum(game: &Game, player_index: usize) -> i32 {
+    2 * card_counts(&game.players[player_index]).len() as i32
+}
+
+fn obelisk(game: &Game, player_index: usize) -> i32 {
+    let Some(pile) = game
+        .supply
+        .obelisk
+        .as_deref()
+        .and_then(|name| game.supply.pile_named(name))
+    else {
+        return 0;
+    };
+    let from_pile = game.players[player_index]
+        .all_cards()
+        .filter(|&card| pile.holds(card))
+        .count();
+    2 * from_pile as i32
+}
+
+fn orchard(game: &Game, player_index: usize) -> i32 {
+    let player = &game.players[player_index];
+    let counts = card_counts(player);
+    let actions = player
+        .all_cards()
+        .filter(|card| card.is(CardType::Action) && counts[card.name()] >= 3)
+        .map(|card| card.name())
+        .collect::<std::collections::HashSet<_>>()
+        .len();
+    4 * actions as i32
+}
+
+fn palace(game: &Game, player_index: usize) -> i32 {
+    let player = &game.players[player_index];
+    let sets = ["Copper", "Silver", "Gold"]
+        .iter()
+        .map(|name| count_named(player, name))
+        .min()
+        .unwrap_or(0);
+    3 * sets as i32
+}
+
+fn tower(game: &Game, player_index: usize) -> i32 {
+    game.players[player_index]
+        .all_cards()
+        .filter(|&card| !card.is(CardType::Victory))
+        .filter(|&card| {
+            game.supply
+                .piles
+                .iter()
+                .any(|pile| pile.holds(card) && pile.is_empty())
+        })
+        .count() as i32
+}
+
+fn triumphal_arch(game: &Game, player_index: usize) -> i32 {
+    let player = &game.players[player_index];
+    let mut action_counts: Vec<usize> = card_counts(player)
+        .into_iter()
+        .filter(|&(name, _)| card(name).is(CardType::Action))
+        .map(|(_, count)| count)
+        .collect();
+    action_counts.sort_unstable_by(|a, b| b.cmp(a));
+    3 * action_counts.get(1).copied().unwrap_or(0) as i32
+}
+
+fn wall(game: &Game, player_index: usize) -> i32 {
+    let cards = game.players[player_index].all_cards().count();
+    -(cards.saturating_sub(15) as i32)
+}
+
+fn wolf_den(game: &Game, player_index: usize) -> i32 {
+    let singles = card_counts(&game.players[player_index])
+        .values()
+        .filter(|&&count| count == 1)
+        .count();
+    -3 * singles as i32
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{AutoPlayPolicy, GameMove};
+
+    fn castles_game(num_players: usize) -> Game {
+        let kingdom = [card("Small Castle"), card("Smithy")];
+        let mut game = Game::initialise_game_with_kingdom(num_players, 3, &kingdom).unwrap();
+        for player in &mut game.players {
+            player.auto_play = AutoPlayPolicy::manual();
+        }
+        game.game_phase = GamePhase::ActionPhase;
+        game
+    }
+
+    fn castles_pile(game: &Game) -> Vec<&'static str> {
+        let pile = game.supply.pile_named("Castles").unwrap();
+        pile.cards.iter().rev().map(|card| card.name()).collect()
+    }
+
+    #[test]
+    fn castles_are_stacked_cheapest_first() {
+        assert_eq!(
+            castles_pile(&castles_game(2)),
+            [
+                "Humble Castle",
+                "Crumbling Castle",
+                "Small Castle",
+                "Haunted Castle",
+                "Opulent Castle",
+                "Sprawling Castle",
+                "Grand Castle",
+                "King's Castle",
+            ]
+        );
+
+        let castles = castles_pile(&castles_game(3));
+        assert_eq!(castles.len(), 12);
+        assert_eq!(
+            &castles[..3],
+            ["Humble Castle", "Humble Castle", "Crumbling Castle"]
+        );
+    }
+
+    #[test]
+    fn humble_and_kings_castles_count_castles() {
+        let mut game = castles_game(2);
+        let player = game.current_player();
+        player.deck = vec![
+            card("Humble Castle"),
+            card("King's Castle"),
+            card("Small Castle"),
+        ];
+        player.hand.clear();
+        player.discard.clear();
+
+        // 3 for Humble Castle, 6 for King's Castle and 2 for Small Castle
+        assert_eq!(player.get_victory_points(), 11);
+    }
+
+    #[test]
+    fn small_castle_trades_a_castle_for_the_next_one() {
+        let mut game = castles_game(2);
+        let player_index = game.curr_player_index;
+        game.current_player().hand = vec![card("Small Castle"), card("Humble Castle")];
+        game.gain_card(
+            player_index,
+            card("Humble Castle"),
+            GainDestination::Discard,
+        )
+        .unwrap();
+        game.accept_move(
+            player_index,
+            GameMove::PlayCard {
+                card_index: 0,
+                way: None,
+            },
+        )
+        .unwrap();
+
+        // Trash the Humble Castle in hand rather than the Small Castle in play
+        game.accept_move(player_index, GameMove::ChooseOption { option_index: 1 })
+            .unwrap();
+
+        assert_eq!(game.trash.cards, [card("Humble Castle")]);
+        // Crumbling Castle gives a VP token and a Silver as it is gained
+        let player = game.current_player_read_only();
+        assert!(player
+            .discard
+            .ends_with(&[card("Crumbling Castle"), card("Silver")]));
+        assert_eq!(player.vp_tokens, 1);
+    }
+}
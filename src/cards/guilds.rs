@@ -0,0 +1,564 @@
+//! Behaviours for the Guilds kingdom cards. Coffers work as in Renaissance.
+//! Overpaying is offered by the engine right after a card with `on_overpay`
+//! is bought, which is then told how much was overpaid.
+
+use super::{
+    card, distinct, from_hand, gain_from_supply, Behaviour, Card, CardFilter, CardType, Cost,
+};
+use crate::{Event, Expiry, GainDestination, Game, GameError, Prompt};
+
+pub(super) fn behaviours() -> Vec<(&'static str, Behaviour)> {
+    let play = |on_play| Behaviour {
+        on_play: Some(on_play),
+        ..Behaviour::default()
+    };
+    vec![
+        ("Candlestick Maker", play(candlestick_maker)),
+        (
+            "Stonemason",
+            Behaviour {
+                on_overpay: Some(stonemason_overpay),
+                ..play(stonemason)
+            },
+        ),
+        (
+            "Doctor",
+            Behaviour {
+                on_overpay: Some(doctor_overpay),
+                ..play(doctor)
+            },
+        ),
+        (
+            "Masterpiece",
+            Behaviour {
+                on_overpay: Some(masterpiece_overpay),
+                ..Behaviour::default()
+            },
+        ),
+        ("Advisor", play(advisor)),
+        ("Plaza", play(plaza)),
+        ("Taxman", play(taxman)),
+        (
+            "Herald",
+            Behaviour {
+                on_overpay: Some(herald_overpay),
+                ..play(herald)
+            },
+        ),
+        ("Baker", play(baker)),
+        ("Butcher", play(butcher)),
+        ("Journeyman", play(journeyman)),
+        ("Merchant Guild", play(merchant_guild)),
+        ("Soothsayer", play(soothsayer)),
+    ]
+}
+
+/// Asks the player to name one of the given cards, or something else, which
+/// is answered with None.
+fn name_a_card(
+    game: &mut Game,
+    player_index: usize,
+    source: Card,
+    candidates: Vec<Card>,
+    then: impl FnOnce(&mut Game, usize, Option<Card>) -> Result<(), GameError> + Send + 'static,
+) {
+    let mut choices: Vec<String> = candidates
+        .iter()
+        .map(|card| format!("Name {}", card.name()))
+        .collect();
+    choices.push("Name something else".to_owned());
+    game.ask(
+        player_index,
+        source,
+        Prompt::Options { options: choices },
+        move |game, player_index, answer| {
+            let named = candidates.get(answer.option()).copied();
+            then(game, player_index, named)
+        },
+    );
+}
+
+fn candlestick_maker(game: &mut Game, _source: Card) -> Result<(), GameError> {
+    game.current_player().coffers += 1;
+    Ok(())
+}
+
+fn stonemason(game: &mut Game, source: Card) -> Result<(), GameError> {
+    game.ask(
+        game.curr_player_index,
+        source,
+        from_hand(1, 1),
+        move |game, player_index, answer| {
+            for trashed in answer.cards() {
+                let cost = game.cost_of(trashed);
+                game.trash_card(player_index, trashed);
+                if cost.coins == 0 {
+                    continue;
+                }
+                for _ in 0..2 {
+                    game.ask(
+                        player_index,
+                        source,
+                        gain_from_supply(cost.minus_coins(1), CardFilter::Any),
+                        |game, player_index, answer| {
+                            game.gain_card(
+                                player_index,
+                                answer.supply_card(),
+                                GainDestination::Discard,
+                            )
+                        },
+                    );
+                }
+            }
+            Ok(())
+        },
+    );
+    Ok(())
+}
+
+fn stonemason_overpay(game: &mut Game, source: Card, amount: u32) -> Result<(), GameError> {
+    stonemason_gain(game, game.curr_player_index, source, Cost::coins(amount), 2);
+    Ok(())
+}
+
+/// Gains an Action costing exactly what was overpaid for Stonemason, then
+/// asks for the next one, so that a gain that empties a pile is accounted for.
+fn stonemason_gain(game: &mut Game, player_index: usize, source: Card, cost: Cost, remaining: u32) {
+    if remaining == 0 {
+        return;
+    }
+    game.ask(
+        player_index,
+        source,
+        Prompt::CardFromSupply {
+            max_cost: cost,
+            exact: true,
+            filter: CardFilter::OfType(CardType::Action),
+        },
+        move |game, player_index, answer| {
+            game.gain_card(player_index, answer.supply_card(), GainDestination::Discard)?;
+            stonemason_gain(game, player_index, source, cost, remaining - 1);
+            Ok(())
+        },
+    );
+}
+
+fn doctor(game: &mut Game, source: Card) -> Result<(), GameError> {
+    let revealed = game.current_player().reveal_from_deck(3);
+    // Only naming one of the revealed cards can make a difference
+    let candidates = distinct(revealed.iter().copied());
+    name_a_card(
+        game,
+        game.curr_player_index,
+        source,
+        candidates,
+        move |game, player_index, named| {
+            for card in revealed.into_iter().rev() {
+                if Some(card) == named {
+                    game.trash_card(player_index, card);
+                } else {
+                    game.players[player_index].put_on_deck(card);
+                }
+            }
+            Ok(())
+        },
+    );
+    Ok(())
+}
+
+fn doctor_overpay(game: &mut Game, source: Card, amount: u32) -> Result<(), GameError> {
+    doctor_look(game, game.curr_player_index, source, amount);
+    Ok(())
+}
+
+/// Looks at the top card of the deck once per $1 overpaid for Doctor.
+fn doctor_look(game: &mut Game, player_index: usize, source: Card, remaining: u32) {
+    if remaining == 0 {
+        return;
+    }
+    let Some(top) = game.players[player_index].take_from_deck() else {
+        return;
+    };
+    let options = ["Trash", "Discard", "Put back"]
+        .iter()
+        .map(|option| format!("{} {}", option, top.name()))
+        .collect();
+    game.ask(
+        player_index,
+        source,
+        Prompt::Options { options },
+        move |game, player_index, answer| {
+            match answer.option() {
+                0 => game.trash_card(player_index, top),
+                1 => game.players[player_index].add_to_discard(top),
+                _ => game.players[player_index].put_on_deck(top),
+            }
+            doctor_look(game, player_index, source, remaining - 1);
+            Ok(())
+        },
+    );
+}
+
+fn masterpiece_overpay(game: &mut Game, _source: Card, amount: u32) -> Result<(), GameError> {
+    for _ in 0..amount {
+        game.gain_if_available(
+            game.curr_player_index,
+            card("Silver"),
+            GainDestination::Discard,
+        )?;
+    }
+    Ok(())
+}
+
+fn advisor(game: &mut Game, source: Card) -> Result<(), GameError> {
+    let revealed = game.current_player().reveal_from_deck(3);
+    if revealed.is_empty() {
+        return Ok(());
+    }
+    let left = (game.curr_player_index + 1) % game.players.len();
+    let owner = game.curr_player_index;
+    game.ask(
+        left,
+        source,
+        Prompt::Options {
+            options: revealed
+                .iter()
+                .map(|card| format!("Player {owner} discards {}", card.name()))
+                .collect(),
+        },
+        move |game, _player_index, answer| {
+            let discarded = answer.option();
+            let player = &mut game.players[owner];
+            for (index, card) in revealed.into_iter().enumerate() {
+                if index == discarded {
+                    player.add_to_discard(card);
+                } else {
+                    player.add_to_hand(card);
+                }
+            }
+            Ok(())
+        },
+    );
+    Ok(())
+}
+
+fn plaza(game: &mut Game, source: Card) -> Result<(), GameError> {
+    game.ask(
+        game.curr_player_index,
+        source,
+        Prompt::CardsFromHand {
+            min: 0,
+            max: 1,
+            filter: CardFilter::OfType(CardType::Treasure),
+        },
+        |game, player_index, answer| {
+            let player = &mut game.players[player_index];
+            for treasure in answer.cards() {
+                player.add_to_discard(treasure);
+                player.coffers += 1;
+            }
+            Ok(())
+        },
+    );
+    Ok(())
+}
+
+fn taxman(game: &mut Game, source: Card) -> Result<(), GameError> {
+    let attacked = game.attacked_player_indices();
+    game.ask(
+        game.curr_player_index,
+        source,
+        Prompt::CardsFromHand {
+            min: 0,
+            max: 1,
+            filter: CardFilter::OfType(CardType::Treasure),
+        },
+        move |game, player_index, answer| {
+            for trashed in answer.cards() {
+                let cost = game.cost_of(trashed).plus_coins(3);
+                game.trash_card(player_index, trashed);
+                for &other in &attacked {
+                    let hand = &mut game.players[other].hand;
+                    if hand.len() < 5 {
+                        continue;
+                    }
+                    if let Some(index) = hand.iter().position(|&card| card == trashed) {
+                        let copy = hand.remove(index);
+                        game.players[other].add_to_discard(copy);
+                    }
+                }
+                game.ask(
+                    player_index,
+                    source,
+                    gain_from_supply(cost, CardFilter::OfType(CardType::Treasure)),
+                    |game, player_index, answer| {
+                        game.gain_card(player_index, answer.supply_card(), GainDestination::Deck)
+                    },
+                );
+            }
+            Ok(())
+        },
+    );
+    Ok(())
+}
+
+fn herald(game: &mut Game, _source: Card) -> Result<(), GameError> {
+    let Some(top) = game.current_player().take_from_deck() else {
+        return Ok(());
+    };
+    if top.is(CardType::Action) && top.is_implemented() {
+        game.play_card(top)?;
+        game.resolve_play(top)
+    } else {
+        game.current_player().put_on_deck(top);
+        Ok(())
+    }
+}
+
+fn herald_overpay(game: &mut Game, source: Card, amount: u32) -> Result<(), GameError> {
+    herald_topdeck(game, game.curr_player_index, source, amount);
+    Ok(())
+}
+
+/// Puts a card from the discard pile onto the deck once per $1 overpaid for Herald.
+fn herald_topdeck(game: &mut Game, player_index: usize, source: Card, remaining: u32) {
+    let discard = distinct(game.players[player_index].discard.iter().copied());
+    if remaining == 0 || discard.is_empty() {
+        return;
+    }
+    game.ask(
+        player_index,
+        source,
+        Prompt::Options {
+            options: discard
+                .iter()
+                .map(|card| format!("Put {} onto your deck", card.name()))
+                .collect(),
+        },
+        move |game, player_index, answer| {
+            let chosen = discard[answer.option()];
+            let player = &mut game.players[player_index];
+            if let Some(index) = player.discard.iter().rposition(|&card| card == chosen) {
+                player.discard.remove(index);
+                player.put_on_deck(chosen);
+            }
+            herald_topdeck(game, player_index, source, remaining - 1);
+            Ok(())
+        },
+    );
+}
+
+fn baker(game: &mut Game, _source: Card) -> Result<(), GameError> {
+    game.current_player().coffers += 1;
+    Ok(())
+}
+
+fn butcher(game: &mut Game, source: Card) -> Result<(), GameError> {
+    game.current_player().coffers += 2;
+    game.ask(
+        game.curr_player_index,
+        source,
+        from_hand(0, 1),
+        move |game, player_index, answer| {
+            for trashed in answer.cards() {
+                let cost = game.cost_of(trashed);
+                game.trash_card(player_index, trashed);
+                let coffers = game.players[player_index].coffers;
+                game.ask(
+                    player_index,
+                    source,
+                    Prompt::Options {
+                        options: (0..=coffers)
+                            .map(|removed| format!("Remove {removed} Coffers"))
+                            .collect(),
+                    },
+                    move |game, player_index, answer| {
+                        let removed = answer.option() as u32;
+                        game.players[player_index].coffers -= removed;
+                        game.ask(
+                            player_index,
+                            source,
+                            gain_from_supply(cost.plus_coins(removed), CardFilter::Any),
+                            |game, player_index, answer| {
+                                game.gain_card(
+                                    player_index,
+                                    answer.supply_card(),
+                                    GainDestination::Discard,
+                                )
+                            },
+                        );
+                        Ok(())
+                    },
+                );
+            }
+            Ok(())
+        },
+    );
+    Ok(())
+}
+
+fn journeyman(game: &mut Game, source: Card) -> Result<(), GameError> {
+    // Only naming a card the player owns can make a difference
+    let candidates = distinct(game.current_player_read_only().all_cards());
+    name_a_card(
+        game,
+        game.curr_player_index,
+        source,
+        candidates,
+        |game, player_index, named| {
+            let player = &mut game.players[player_index];
+            let mut kept = Vec::new();
+            let mut discarded = Vec::new();
+            while kept.len() < 3 {
+                let Some(card) = player.take_from_deck() else {
+                    break;
+                };
+                if Some(card) == named {
+                    discarded.push(card);
+                } else {
+                    kept.push(card);
+                }
+            }
+            for card in kept {
+                player.add_to_hand(card);
+            }
+            for card in discarded {
+                player.add_to_discard(card);
+            }
+            Ok(())
+        },
+    );
+    Ok(())
+}
+
+fn merchant_guild(game: &mut Game, _source: Card) -> Result<(), GameError> {
+    let owner = game.curr_player_index;
+    game.add_trigger(Expiry::EndOfTurn, move |game, event| {
+        if let Event::Buy { player_index, .. } = event {
+            if player_index == owner {
+                game.players[owner].coffers += 1;
+            }
+        }
+        Ok(true)
+    });
+    Ok(())
+}
+
+fn soothsayer(game: &mut Game, _source: Card) -> Result<(), GameError> {
+    game.gain_if_available(
+        game.curr_player_index,
+        card("Gold"),
+        GainDestination::Discard,
+    )?;
+    let curse = card("Curse");
+    for player_index in game.attacked_player_indices() {
+        if game.supply.count(curse).unwrap_or(0) > 0 {
+            game.gain_card(player_index, curse, GainDestination::Discard)?;
+            game.players[player_index].draw(1);
+        }
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::cards::testing::{choose_cards, choose_option, gain, play, scenario};
+    use crate::{GameMove, GamePhase, Pending};
+
+    fn buy(game: &mut Game, name: &str, coins: u32) {
+        let player_index = game.curr_player_index;
+        game.game_phase = GamePhase::BuyPhase;
+        game.current_player().coins = coins;
+        game.accept_move(player_index, GameMove::BuyCard { card: card(name) })
+            .unwrap();
+    }
+
+    #[test]
+    fn stonemason_overpay_asks_for_the_second_action_after_the_first() {
+        let mut game = scenario(&["Stonemason", "Village", "Smithy"], [&[], &[]]);
+        game.supply
+            .pile_mut(card("Village"))
+            .unwrap()
+            .cards
+            .truncate(1);
+        buy(&mut game, "Stonemason", 5);
+
+        // Overpaying $3 leaves only the one Village to gain
+        choose_option(&mut game, 3);
+        let decisions = game
+            .pending
+            .iter()
+            .filter(|pending| matches!(pending, Pending::Decision(_)))
+            .count();
+        assert_eq!(decisions, 1);
+        gain(&mut game, "Village");
+
+        assert!(game.pending_decision().is_none());
+        assert_eq!(
+            game.current_player_read_only().discard,
+            [card("Stonemason"), card("Village")]
+        );
+    }
+
+    #[test]
+    fn masterpiece_gains_a_silver_per_coin_overpaid() {
+        let mut game = scenario(&["Masterpiece", "Baker", "Plaza"], [&[], &[]]);
+        let player_index = game.curr_player_index;
+
+        buy(&mut game, "Masterpiece", 5);
+        // Overpaying is offered from $0 up to what's left
+        assert_eq!(game.legal_moves().len(), 3);
+        choose_option(&mut game, 2);
+
+        assert_eq!(
+            game.players[player_index].discard,
+            [card("Masterpiece"), card("Silver"), card("Silver")]
+        );
+        assert_eq!(game.players[player_index].coins, 0);
+    }
+
+    #[test]
+    fn overpaying_nothing_skips_the_bonus() {
+        let mut game = scenario(&["Masterpiece", "Baker", "Plaza"], [&[], &[]]);
+        let player_index = game.curr_player_index;
+
+        buy(&mut game, "Masterpiece", 5);
+        choose_option(&mut game, 0);
+
+        assert_eq!(game.players[player_index].discard, [card("Masterpiece")]);
+        assert_eq!(game.players[player_index].coins, 2);
+    }
+
+    #[test]
+    fn baker_in_the_kingdom_gives_everyone_a_coffers_to_start() {
+        let game = scenario(&["Baker", "Masterpiece", "Plaza"], [&[], &[]]);
+
+        assert!(game.players.iter().all(|player| player.coffers == 1));
+    }
+
+    #[test]
+    fn coffers_are_spent_for_coins_in_the_buy_phase() {
+        let mut game = scenario(
+            &["Plaza", "Masterpiece", "Stonemason"],
+            [&["Plaza", "Copper"], &[]],
+        );
+        let player_index = game.curr_player_index;
+        game.current_player().coffers = 0;
+
+        play(&mut game, "Plaza");
+        choose_cards(&mut game, &["Copper"]);
+        assert_eq!(game.players[player_index].coffers, 1);
+
+        game.game_phase = GamePhase::BuyPhase;
+        assert!(game.legal_moves().contains(&GameMove::SpendCoffer));
+        game.accept_move(player_index, GameMove::SpendCoffer)
+            .unwrap();
+
+        let player = &game.players[player_index];
+        assert_eq!((player.coffers, player.coins), (0, 1));
+        assert!(game
+            .accept_move(player_index, GameMove::SpendCoffer)
+            .is_err());
+    }
+}
@@ -0,0 +1,337 @@
+//! Helpers shared by the behaviours of the expansions' cards. Which of them are
+//! used depends on the expansions being built, so none are flagged as dead code;
+//! the few that wrap a gated engine hook are only built along with the hook.
+#![allow(dead_code)]
+
+use super::{Card, CardFilter, CardType, Cost};
+use crate::{GainDestination, Game, GameError, Player, Prompt};
+
+impl Cost {
+    /// A cost of only coins.
+    pub(crate) const fn coins(coins: u32) -> Self {
+        Cost {
+            coins,
+            potions: 0,
+            debt: 0,
+        }
+    }
+}
+
+/// Reveals cards from the top of a player's deck until one matches, returning
+/// it (if any turned up) and the cards revealed before it. The revealed cards
+/// are set aside rather than discarded, so that they can't be shuffled back
+/// into the deck being revealed; callers discard them once they're done.
+pub(super) fn reveal_until(
+    game: &mut Game,
+    player_index: usize,
+    found: impl Fn(&Game, Card) -> bool,
+) -> (Option<Card>, Vec<Card>) {
+    let mut revealed = Vec::new();
+    while let Some(card) = game.players[player_index].take_from_deck() {
+        if found(game, card) {
+            return (Some(card), revealed);
+        }
+        revealed.push(card);
+    }
+    (None, revealed)
+}
+
+/// A choice between the given options.
+pub(super) fn options(options: &[&str]) -> Prompt {
+    Prompt::Options {
+        options: options.iter().map(|option| option.to_string()).collect(),
+    }
+}
+
+/// A choice of between `min` and `max` cards from hand.
+pub(super) fn from_hand(min: usize, max: usize) -> Prompt {
+    Prompt::CardsFromHand {
+        min,
+        max,
+        filter: CardFilter::Any,
+    }
+}
+
+/// A choice of a card to gain from the supply, costing up to `max_cost`.
+pub(super) fn gain_from_supply(max_cost: Cost, filter: CardFilter) -> Prompt {
+    Prompt::CardFromSupply {
+        max_cost,
+        exact: false,
+        filter,
+    }
+}
+
+/// One of each different card, sorted by name, for offering as options.
+pub(super) fn distinct(cards: impl IntoIterator<Item = Card>) -> Vec<Card> {
+    let mut cards: Vec<Card> = cards.into_iter().collect();
+    cards.sort_by_key(|card| card.name());
+    cards.dedup();
+    cards
+}
+
+/// Discards cards from anywhere but play, letting each react to being discarded.
+pub(super) fn discard_all(game: &mut Game, player_index: usize, cards: Vec<Card>) {
+    for card in cards {
+        game.players[player_index].add_to_discard(card);
+        if let Some(on_discard) = card.behaviour().and_then(|b| b.on_discard) {
+            on_discard(game, player_index, card);
+        }
+    }
+}
+
+/// Has a player discard `count` cards of their choice.
+pub(super) fn discard_chosen(game: &mut Game, player_index: usize, source: Card, count: usize) {
+    game.ask(
+        player_index,
+        source,
+        from_hand(count, count),
+        |game, player_index, answer| {
+            discard_all(game, player_index, answer.cards());
+            Ok(())
+        },
+    );
+}
+
+/// Has a player discard cards of their choice until they have `hand_size` left.
+pub(super) fn discard_down_to(
+    game: &mut Game,
+    player_index: usize,
+    source: Card,
+    hand_size: usize,
+) {
+    let excess = game.players[player_index]
+        .hand
+        .len()
+        .saturating_sub(hand_size);
+    discard_chosen(game, player_index, source, excess);
+}
+
+/// Has a player gain a card of their choice costing up to `max_cost`.
+pub(super) fn gain_to_discard(game: &mut Game, player_index: usize, source: Card, max_cost: Cost) {
+    game.ask(
+        player_index,
+        source,
+        gain_from_supply(max_cost, CardFilter::Any),
+        |game, player_index, answer| {
+            game.gain_card(player_index, answer.supply_card(), GainDestination::Discard)
+        },
+    );
+}
+
+pub(super) fn from_hand_of(player: &mut Player, card: Card) {
+    if let Some(index) = player.hand.iter().position(|&c| c == card) {
+        player.hand.remove(index);
+    }
+}
+
+pub(super) fn from_discard_of(player: &mut Player, card: Card) {
+    if let Some(index) = player.discard.iter().rposition(|&c| c == card) {
+        player.discard.remove(index);
+    }
+}
+
+/// Protection from Attacks while in the player's hand, like Moat.
+pub(super) fn protects_from_hand(player: &Player, card: Card) -> bool {
+    player.hand.contains(&card)
+}
+
+/// Protection from Attacks while staying in play from the player's last
+/// turn, like Lighthouse.
+pub(super) fn protects_from_play(player: &Player, card: Card) -> bool {
+    player.durations.contains(&card)
+}
+
+/// Runs `effect` for the current player once their Buy phase ends, just
+/// before clean-up discards their cards from play.
+// Built along with `Event::BuyPhaseEnd`, which it waits for
+#[cfg(any(
+    feature = "seaside",
+    feature = "alchemy",
+    feature = "dark_ages",
+    feature = "adventures",
+    feature = "empires",
+    feature = "renaissance",
+    feature = "menagerie",
+    feature = "hinterlands",
+    feature = "allies",
+    feature = "plunder"
+))]
+pub(super) fn before_clean_up(
+    game: &mut Game,
+    effect: impl FnOnce(&mut Game, usize) + Send + 'static,
+) {
+    use crate::{Event, Expiry};
+
+    let owner = game.curr_player_index;
+    let mut effect = Some(effect);
+    game.add_trigger(Expiry::EndOfTurn, move |game, event| {
+        let Event::BuyPhaseEnd { player_index } = event else {
+            return Ok(true);
+        };
+        if let (true, Some(effect)) = (player_index == owner, effect.take()) {
+            effect(game, owner);
+        }
+        Ok(false)
+    });
+}
+
+/// Schedules the rest of the current player's Duration card for their next turn.
+// Built along with `Game::at_start_of_turn`, as is `take_extra_turn` below
+#[cfg(any(
+    feature = "seaside",
+    feature = "prosperity",
+    feature = "adventures",
+    feature = "empires",
+    feature = "nocturne",
+    feature = "renaissance",
+    feature = "menagerie",
+    feature = "cornucopia",
+    feature = "allies",
+    feature = "plunder"
+))]
+pub(super) fn next_turn(
+    game: &mut Game,
+    source: Card,
+    effect: impl FnOnce(&mut Game, usize) -> Result<(), GameError> + Send + 'static,
+) {
+    let player_index = game.curr_player_index;
+    game.at_start_of_turn(player_index, source, move |game| effect(game, player_index));
+}
+
+/// Gives the current player an extra turn after this one, unless this turn is
+/// already an extra one, returning whether they get it.
+#[cfg(any(
+    feature = "seaside",
+    feature = "prosperity",
+    feature = "adventures",
+    feature = "empires",
+    feature = "nocturne",
+    feature = "renaissance",
+    feature = "menagerie",
+    feature = "cornucopia",
+    feature = "allies",
+    feature = "plunder"
+))]
+pub(super) fn take_extra_turn(game: &mut Game) -> bool {
+    use crate::ExtraTurn;
+
+    // An extra turn's previous turn was the player's own
+    if game.history.current_turn.extra_turn {
+        return false;
+    }
+    game.extra_turns.push_back(ExtraTurn {
+        player_index: game.curr_player_index,
+        hand_size: 5,
+    });
+    true
+}
+
+/// Plays a card the current player has taken from somewhere other than their
+/// hand, without using up an Action.
+pub(super) fn play_taken(game: &mut Game, card: Card) -> Result<(), GameError> {
+    game.play_card(card)?;
+    game.resolve_play(card)
+}
+
+/// Asks the current player which of the candidates to play, if any.
+pub(super) fn choose_to_play(
+    game: &mut Game,
+    source: Card,
+    candidates: Vec<Card>,
+    then: impl FnOnce(&mut Game, Card) -> Result<(), GameError> + Send + 'static,
+) {
+    let candidates: Vec<Card> = distinct(candidates)
+        .into_iter()
+        .filter(|card| card.is_implemented())
+        .collect();
+    if candidates.is_empty() {
+        return;
+    }
+    let mut choices: Vec<String> = candidates
+        .iter()
+        .map(|card| format!("Play {}", card.name()))
+        .collect();
+    choices.push("Don't play anything".to_owned());
+    game.ask(
+        game.curr_player_index,
+        source,
+        Prompt::Options { options: choices },
+        move |game, _, answer| match candidates.get(answer.option()) {
+            Some(&chosen) => then(game, chosen),
+            None => Ok(()),
+        },
+    );
+}
+
+/// Asks the current player to choose one of a card's abilities and resolves it
+/// with `resolve`, which gets the player and the ability's index.
+pub(super) fn choose_one(
+    game: &mut Game,
+    source: Card,
+    abilities: &[&str],
+    resolve: impl Fn(&mut Game, usize, usize) -> Result<(), GameError> + Clone + Send + 'static,
+) {
+    let abilities: Vec<String> = abilities
+        .iter()
+        .map(|ability| ability.to_string())
+        .collect();
+    game.ask(
+        game.curr_player_index,
+        source,
+        Prompt::Options {
+            options: abilities.clone(),
+        },
+        move |game, player_index, answer| {
+            let chosen = answer.option();
+            resolve(game, player_index, chosen)?;
+            let others = abilities
+                .into_iter()
+                .enumerate()
+                .filter(|&(ability, _)| ability != chosen)
+                .collect();
+            extra_choice(game, player_index, source, others, resolve);
+            Ok(())
+        },
+    );
+}
+
+/// Lets a player choose one more of a card's remaining abilities if Elder
+/// played it this turn.
+pub(super) fn extra_choice(
+    game: &mut Game,
+    player_index: usize,
+    source: Card,
+    remaining: Vec<(usize, String)>,
+    resolve: impl FnOnce(&mut Game, usize, usize) -> Result<(), GameError> + Send + 'static,
+) {
+    if remaining.is_empty() || !game.players[player_index].elder_played.contains(&source) {
+        return;
+    }
+    let mut choices: Vec<String> = remaining
+        .iter()
+        .map(|(_, ability)| ability.clone())
+        .collect();
+    choices.push("No extra choice".to_owned());
+    game.ask(
+        player_index,
+        source,
+        Prompt::Options { options: choices },
+        move |game, player_index, answer| match remaining.get(answer.option()) {
+            Some(&(ability, _)) => resolve(game, player_index, ability),
+            None => Ok(()),
+        },
+    );
+}
+
+/// Has the current player play a non-Command Action card from the supply
+/// costing up to `max_cost`, leaving it there.
+pub(super) fn play_from_supply(game: &mut Game, source: Card, max_cost: Cost) {
+    let candidates = game
+        .gainable_cards(&max_cost, false, &CardFilter::OfType(CardType::Action))
+        .into_iter()
+        .filter(|card| !card.is(CardType::Command))
+        .collect();
+    choose_to_play(game, source, candidates, |game, chosen| {
+        game.resolve_play(chosen)
+    });
+}
@@ -0,0 +1,1001 @@
+//! Behaviours for the Hinterlands kingdom cards. Many of them act when they
+//! are gained, through `on_gain`, or when another card is gained or bought,
+//! through Reactions in hand or triggers while they are in play. Effects that
+//! replace a gain take the gained card back with `Player::take_gained`.
+
+use super::{
+    card, choose_one, discard_all, discard_down_to, distinct, from_hand, gain_from_supply, options,
+    play_taken, Behaviour, Card, CardFilter, CardType, Cost,
+};
+use crate::{CostModifier, Event, Expiry, GainDestination, Game, GameError, Prompt};
+
+pub(super) fn behaviours() -> Vec<(&'static str, Behaviour)> {
+    let play = |on_play| Behaviour {
+        on_play: Some(on_play),
+        ..Behaviour::default()
+    };
+    let gain = |on_gain| Behaviour {
+        on_gain: Some(on_gain),
+        ..Behaviour::default()
+    };
+    vec![
+        ("Crossroads", play(crossroads)),
+        ("Develop", play(develop)),
+        (
+            "Fool's Gold",
+            Behaviour {
+                on_gain_in_hand: Some(fools_gold_reaction),
+                ..play(fools_gold)
+            },
+        ),
+        (
+            "Guard Dog",
+            Behaviour {
+                on_attack: Some(guard_dog_reaction),
+                ..play(guard_dog)
+            },
+        ),
+        ("Oasis", play(oasis)),
+        ("Scheme", play(scheme)),
+        ("Jack of All Trades", play(jack_of_all_trades)),
+        (
+            "Nomads",
+            Behaviour {
+                on_gain: Some(nomads_bonus),
+                on_trash: Some(nomads_bonus),
+                ..Behaviour::default()
+            },
+        ),
+        ("Spice Merchant", play(spice_merchant)),
+        (
+            "Tunnel",
+            Behaviour {
+                on_discard: Some(tunnel),
+                ..Behaviour::default()
+            },
+        ),
+        (
+            "Trader",
+            Behaviour {
+                on_gain_in_hand: Some(trader_reaction),
+                ..play(trader)
+            },
+        ),
+        (
+            "Trail",
+            Behaviour {
+                on_gain: Some(trail_on_gain),
+                on_trash: Some(trail_on_trash),
+                ..Behaviour::default()
+            },
+        ),
+        ("Weaver", play(weaver)),
+        (
+            "Berserker",
+            Behaviour {
+                on_gain: Some(berserker_on_gain),
+                ..play(berserker)
+            },
+        ),
+        ("Cartographer", play(cartographer)),
+        ("Cauldron", play(cauldron)),
+        ("Haggler", play(haggler)),
+        ("Highway", play(highway)),
+        (
+            "Inn",
+            Behaviour {
+                on_gain: Some(inn_on_gain),
+                ..play(inn)
+            },
+        ),
+        ("Margrave", play(margrave)),
+        (
+            "Souk",
+            Behaviour {
+                on_gain: Some(souk_on_gain),
+                ..play(souk)
+            },
+        ),
+        ("Stables", play(stables)),
+        ("Wheelwright", play(wheelwright)),
+        ("Witch's Hut", play(witchs_hut)),
+        ("Border Village", gain(border_village)),
+        ("Farmland", gain(farmland)),
+        ("Cache", gain(cache)),
+        (
+            "Ill-Gotten Gains",
+            Behaviour {
+                on_gain: Some(ill_gotten_gains_on_gain),
+                ..play(ill_gotten_gains)
+            },
+        ),
+    ]
+}
+
+/// Asks the player to gain a card costing less than `cost`, if anything can.
+fn gain_cheaper(
+    game: &mut Game,
+    player_index: usize,
+    source: Card,
+    cost: Cost,
+    filter: CardFilter,
+) {
+    if cost.coins == 0 {
+        return;
+    }
+    game.ask(
+        player_index,
+        source,
+        gain_from_supply(cost.minus_coins(1), filter),
+        |game, player_index, answer| {
+            game.gain_card(player_index, answer.supply_card(), GainDestination::Discard)
+        },
+    );
+}
+
+fn crossroads(game: &mut Game, _source: Card) -> Result<(), GameError> {
+    let first = game.history.current_turn.played_count("Crossroads") == 1;
+    let player = game.current_player();
+    let victory_cards = player
+        .hand
+        .iter()
+        .filter(|card| card.is(CardType::Victory))
+        .count();
+    player.draw(victory_cards);
+    if first {
+        player.actions += 3;
+    }
+    Ok(())
+}
+
+fn develop(game: &mut Game, source: Card) -> Result<(), GameError> {
+    game.ask(
+        game.curr_player_index,
+        source,
+        from_hand(1, 1),
+        move |game, player_index, answer| {
+            for trashed in answer.cards() {
+                let cost = game.cost_of(trashed);
+                game.trash_card(player_index, trashed);
+                let mut costs = vec![cost.plus_coins(1)];
+                if cost.coins > 0 {
+                    costs.push(cost.minus_coins(1));
+                }
+                // The card gained second ends up on top of the deck
+                let first_choices: Vec<String> = costs
+                    .iter()
+                    .map(|cost| format!("Gain the card costing {cost} first"))
+                    .collect();
+                game.ask(
+                    player_index,
+                    source,
+                    Prompt::Options {
+                        options: first_choices,
+                    },
+                    move |game, player_index, answer| {
+                        let mut costs = costs;
+                        costs.swap(0, answer.option());
+                        for cost in costs {
+                            game.ask(
+                                player_index,
+                                source,
+                                Prompt::CardFromSupply {
+                                    max_cost: cost,
+                                    exact: true,
+                                    filter: CardFilter::Any,
+                                },
+                                |game, player_index, answer| {
+                                    game.gain_card(
+                                        player_index,
+                                        answer.supply_card(),
+                                        GainDestination::Deck,
+                                    )
+                                },
+                            );
+                        }
+                        Ok(())
+                    },
+                );
+            }
+            Ok(())
+        },
+    );
+    Ok(())
+}
+
+fn fools_gold(game: &mut Game, _source: Card) -> Result<(), GameError> {
+    let first = game.history.current_turn.played_count("Fool's Gold") == 1;
+    game.current_player().coins += if first { 1 } else { 4 };
+    Ok(())
+}
+
+fn fools_gold_reaction(
+    game: &mut Game,
+    holder: usize,
+    fools_gold: Card,
+    gainer: usize,
+    gained: Card,
+) {
+    if gainer == holder || gained.name() != "Province" {
+        return;
+    }
+    game.ask(
+        holder,
+        fools_gold,
+        options(&["Trash Fool's Gold to gain a Gold onto your deck", "Keep it"]),
+        move |game, player_index, answer| {
+            let hand = &mut game.players[player_index].hand;
+            let Some(index) = hand.iter().position(|&card| card == fools_gold) else {
+                return Ok(());
+            };
+            if answer.option() == 0 {
+                hand.remove(index);
+                game.trash_card(player_index, fools_gold);
+                game.gain_if_available(player_index, card("Gold"), GainDestination::Deck)?;
+            }
+            Ok(())
+        },
+    );
+}
+
+fn guard_dog(game: &mut Game, _source: Card) -> Result<(), GameError> {
+    let player = game.current_player();
+    if player.hand.len() <= 5 {
+        player.draw(2);
+    }
+    Ok(())
+}
+
+fn guard_dog_reaction(game: &mut Game, player_index: usize, guard_dog: Card, _attack: Card) {
+    game.ask(
+        player_index,
+        guard_dog,
+        options(&["Play Guard Dog first", "Don't play it"]),
+        move |game, player_index, answer| {
+            let player = &mut game.players[player_index];
+            let Some(index) = player.hand.iter().position(|&card| card == guard_dog) else {
+                return Ok(());
+            };
+            if answer.option() == 0 {
+                // Played on someone else's turn, it stays in play until its
+                // owner's next Clean-up
+                player.hand.remove(index);
+                player.played.push(guard_dog);
+                player.draw(2);
+                if player.hand.len() <= 5 {
+                    player.draw(2);
+                }
+            }
+            Ok(())
+        },
+    );
+}
+
+fn oasis(game: &mut Game, source: Card) -> Result<(), GameError> {
+    game.ask(
+        game.curr_player_index,
+        source,
+        from_hand(1, 1),
+        |game, player_index, answer| {
+            discard_all(game, player_index, answer.cards());
+            Ok(())
+        },
+    );
+    Ok(())
+}
+
+fn scheme(game: &mut Game, source: Card) -> Result<(), GameError> {
+    let owner = game.curr_player_index;
+    game.add_trigger(Expiry::EndOfTurn, move |game, event| {
+        let Event::BuyPhaseEnd { player_index } = event else {
+            return Ok(true);
+        };
+        if player_index != owner {
+            return Ok(true);
+        }
+        let actions: Vec<Card> = distinct(game.players[owner].played.iter().copied())
+            .into_iter()
+            .filter(|card| card.is(CardType::Action))
+            .collect();
+        let mut choices: Vec<String> = actions
+            .iter()
+            .map(|card| format!("Put {} onto your deck", card.name()))
+            .collect();
+        choices.push("Discard them all as normal".to_owned());
+        game.ask(
+            owner,
+            source,
+            Prompt::Options { options: choices },
+            move |game, player_index, answer| {
+                let Some(&chosen) = actions.get(answer.option()) else {
+                    return Ok(());
+                };
+                let player = &mut game.players[player_index];
+                if let Some(card) = player.take_from_play(chosen) {
+                    player.put_on_deck(card);
+                }
+                Ok(())
+            },
+        );
+        Ok(false)
+    });
+    Ok(())
+}
+
+fn jack_of_all_trades(game: &mut Game, source: Card) -> Result<(), GameError> {
+    let player_index = game.curr_player_index;
+    game.gain_if_available(player_index, card("Silver"), GainDestination::Discard)?;
+    if let Some(top) = game.current_player().take_from_deck() {
+        game.ask(
+            player_index,
+            source,
+            Prompt::Options {
+                options: vec![
+                    format!("Discard {}", top.name()),
+                    format!("Put {} back", top.name()),
+                ],
+            },
+            move |game, player_index, answer| {
+                let player = &mut game.players[player_index];
+                if answer.option() == 0 {
+                    player.add_to_discard(top);
+                } else {
+                    player.put_on_deck(top);
+                }
+                Ok(())
+            },
+        );
+    }
+    game.then(move |game| {
+        let player = &mut game.players[player_index];
+        let missing = 5usize.saturating_sub(player.hand.len());
+        player.draw(missing);
+        game.ask(
+            player_index,
+            source,
+            Prompt::CardsFromHand {
+                min: 0,
+                max: 1,
+                filter: CardFilter::NotOfType(CardType::Treasure),
+            },
+            |game, player_index, answer| {
+                for trashed in answer.cards() {
+                    game.trash_card(player_index, trashed);
+                }
+                Ok(())
+            },
+        );
+        Ok(())
+    });
+    Ok(())
+}
+
+fn nomads_bonus(game: &mut Game, player_index: usize, _nomads: Card) {
+    game.players[player_index].coins += 2;
+}
+
+fn spice_merchant(game: &mut Game, source: Card) -> Result<(), GameError> {
+    game.ask(
+        game.curr_player_index,
+        source,
+        Prompt::CardsFromHand {
+            min: 0,
+            max: 1,
+            filter: CardFilter::OfType(CardType::Treasure),
+        },
+        move |game, player_index, answer| {
+            for trashed in answer.cards() {
+                game.trash_card(player_index, trashed);
+                choose_one(
+                    game,
+                    source,
+                    &["+2 Cards and +1 Action", "+1 Buy and +$2"],
+                    |game, player_index, ability| {
+                        let player = &mut game.players[player_index];
+                        if ability == 0 {
+                            player.draw(2);
+                            player.actions += 1;
+                        } else {
+                            player.buys += 1;
+                            player.coins += 2;
+                        }
+                        Ok(())
+                    },
+                );
+            }
+            Ok(())
+        },
+    );
+    Ok(())
+}
+
+fn trader(game: &mut Game, source: Card) -> Result<(), GameError> {
+    game.ask(
+        game.curr_player_index,
+        source,
+        from_hand(1, 1),
+        |game, player_index, answer| {
+            for trashed in answer.cards() {
+                let silvers = game.cost_of(trashed).coins;
+                game.trash_card(player_index, trashed);
+                for _ in 0..silvers {
+                    game.gain_if_available(player_index, card("Silver"), GainDestination::Discard)?;
+                }
+            }
+            Ok(())
+        },
+    );
+    Ok(())
+}
+
+fn trader_reaction(game: &mut Game, holder: usize, trader: Card, gainer: usize, gained: Card) {
+    if gainer != holder || gained.name() == "Silver" || !game.supply.in_supply(gained) {
+        return;
+    }
+    game.ask(
+        holder,
+        trader,
+        Prompt::Options {
+            options: vec![
+                format!("Reveal Trader to exchange {} for a Silver", gained.name()),
+                "Don't reveal Trader".to_owned(),
+            ],
+        },
+        move |game, player_index, answer| {
+            if answer.option() == 1 || !game.players[player_index].hand.contains(&trader) {
+                return Ok(());
+            }
+            // Exchanging isn't gaining, so the Silver doesn't trigger anything
+            let silver = card("Silver");
+            if game.supply.count(silver).unwrap_or(0) == 0 {
+                return Ok(());
+            }
+            let player = &mut game.players[player_index];
+            let Some(exchanged) = player.take_gained(gained) else {
+                return Ok(());
+            };
+            player.add_to_discard(silver);
+            game.supply.return_card(exchanged)?;
+            game.supply.take_card(silver)
+        },
+    );
+}
+
+/// Offers to play a Trail its owner has just gained or trashed. Only the
+/// current player can play it, and its reaction to being discarded isn't
+/// supported.
+fn offer_trail(
+    game: &mut Game,
+    player_index: usize,
+    trail: Card,
+    take: fn(&mut Game, usize, Card) -> bool,
+) {
+    if player_index != game.curr_player_index {
+        return;
+    }
+    game.ask(
+        player_index,
+        trail,
+        options(&["Play Trail", "Don't play it"]),
+        move |game, player_index, answer| {
+            if answer.option() == 0 && take(game, player_index, trail) {
+                play_taken(game, trail)?;
+            }
+            Ok(())
+        },
+    );
+}
+
+fn trail_on_gain(game: &mut Game, player_index: usize, trail: Card) {
+    offer_trail(game, player_index, trail, |game, player_index, trail| {
+        game.players[player_index].take_gained(trail).is_some()
+    });
+}
+
+fn trail_on_trash(game: &mut Game, player_index: usize, trail: Card) {
+    offer_trail(game, player_index, trail, |game, _player_index, trail| {
+        let index = game.trash.cards.iter().rposition(|&card| card == trail);
+        index.and_then(|index| game.trash.take(index)).is_some()
+    });
+}
+
+fn tunnel(game: &mut Game, player_index: usize, tunnel: Card) {
+    game.ask(
+        player_index,
+        tunnel,
+        options(&["Reveal Tunnel to gain a Gold", "Don't reveal it"]),
+        |game, player_index, answer| {
+            if answer.option() == 0 {
+                game.gain_if_available(player_index, card("Gold"), GainDestination::Discard)?;
+            }
+            Ok(())
+        },
+    );
+}
+
+fn weaver(game: &mut Game, source: Card) -> Result<(), GameError> {
+    // Playing it when it is discarded other than in Clean-up isn't supported
+    game.ask(
+        game.curr_player_index,
+        source,
+        options(&["Gain two Silvers", "Gain a card costing up to $4"]),
+        move |game, player_index, answer| {
+            if answer.option() == 0 {
+                for _ in 0..2 {
+                    game.gain_if_available(player_index, card("Silver"), GainDestination::Discard)?;
+                }
+                return Ok(());
+            }
+            game.ask(
+                player_index,
+                source,
+                gain_from_supply(Cost::coins(4), CardFilter::Any),
+                |game, player_index, answer| {
+                    game.gain_card(player_index, answer.supply_card(), GainDestination::Discard)
+                },
+            );
+            Ok(())
+        },
+    );
+    Ok(())
+}
+
+fn berserker(game: &mut Game, source: Card) -> Result<(), GameError> {
+    let player_index = game.curr_player_index;
+    gain_cheaper(
+        game,
+        player_index,
+        source,
+        game.cost_of(source),
+        CardFilter::Any,
+    );
+    for attacked in game.attacked_player_indices() {
+        discard_down_to(game, attacked, source, 3);
+    }
+    Ok(())
+}
+
+fn berserker_on_gain(game: &mut Game, player_index: usize, berserker: Card) {
+    let has_action_in_play = game.players[player_index]
+        .played
+        .iter()
+        .any(|card| card.is(CardType::Action));
+    if player_index != game.curr_player_index || !has_action_in_play {
+        return;
+    }
+    game.then(
+        move |game| match game.players[player_index].take_gained(berserker) {
+            Some(berserker) => play_taken(game, berserker),
+            None => Ok(()),
+        },
+    );
+}
+
+fn cartographer(game: &mut Game, source: Card) -> Result<(), GameError> {
+    for card in game.current_player().reveal_from_deck(4) {
+        game.ask(
+            game.curr_player_index,
+            source,
+            Prompt::Options {
+                options: vec![
+                    format!("Discard {}", card.name()),
+                    format!("Put {} back", card.name()),
+                ],
+            },
+            move |game, player_index, answer| {
+                let player = &mut game.players[player_index];
+                if answer.option() == 0 {
+                    player.add_to_discard(card);
+                } else {
+                    player.put_on_deck(card);
+                }
+                Ok(())
+            },
+        );
+    }
+    Ok(())
+}
+
+fn cauldron(game: &mut Game, _source: Card) -> Result<(), GameError> {
+    let owner = game.curr_player_index;
+    game.add_trigger(Expiry::EndOfTurn, move |game, event| {
+        let Event::Gain { player_index, card } = event else {
+            return Ok(true);
+        };
+        if player_index != owner || !card.is(CardType::Action) {
+            return Ok(true);
+        }
+        let actions_gained = game
+            .history
+            .current_turn
+            .gained_by(owner)
+            .filter(|card| card.is(CardType::Action))
+            .count();
+        if actions_gained < 3 {
+            return Ok(true);
+        }
+        if actions_gained == 3 {
+            for attacked in game.attacked_player_indices() {
+                game.gain_if_available(attacked, self::card("Curse"), GainDestination::Discard)?;
+            }
+        }
+        Ok(false)
+    });
+    Ok(())
+}
+
+fn haggler(game: &mut Game, source: Card) -> Result<(), GameError> {
+    let owner = game.curr_player_index;
+    game.add_trigger(Expiry::EndOfTurn, move |game, event| {
+        if let Event::Buy { player_index, card } = event {
+            if player_index == owner {
+                let cost = game.cost_of(card);
+                gain_cheaper(
+                    game,
+                    owner,
+                    source,
+                    cost,
+                    CardFilter::NotOfType(CardType::Victory),
+                );
+            }
+        }
+        Ok(true)
+    });
+    Ok(())
+}
+
+fn highway(game: &mut Game, _source: Card) -> Result<(), GameError> {
+    game.cost_modifiers.push(CostModifier {
+        filter: CardFilter::Any,
+        coin_reduction: 1,
+    });
+    Ok(())
+}
+
+fn inn(game: &mut Game, source: Card) -> Result<(), GameError> {
+    game.ask(
+        game.curr_player_index,
+        source,
+        from_hand(2, 2),
+        |game, player_index, answer| {
+            discard_all(game, player_index, answer.cards());
+            Ok(())
+        },
+    );
+    Ok(())
+}
+
+fn inn_on_gain(game: &mut Game, player_index: usize, source: Card) {
+    game.then(move |game| {
+        choose_for_inn(game, player_index, source, Vec::new());
+        Ok(())
+    });
+}
+
+/// Has the player pick Action cards out of their discard pile one at a time,
+/// then shuffles the chosen ones into their deck.
+fn choose_for_inn(game: &mut Game, player_index: usize, source: Card, chosen: Vec<Card>) {
+    let actions: Vec<Card> = distinct(game.players[player_index].discard.iter().copied())
+        .into_iter()
+        .filter(|card| card.is(CardType::Action))
+        .collect();
+    let mut choices: Vec<String> = actions
+        .iter()
+        .map(|card| format!("Shuffle a {} into your deck", card.name()))
+        .collect();
+    choices.push("Done".to_owned());
+    game.ask(
+        player_index,
+        source,
+        Prompt::Options { options: choices },
+        move |game, player_index, answer| {
+            let mut chosen = chosen;
+            let player = &mut game.players[player_index];
+            match actions.get(answer.option()) {
+                Some(&action) => {
+                    if let Some(index) = player.discard.iter().position(|&card| card == action) {
+                        chosen.push(player.discard.remove(index));
+                    }
+                    choose_for_inn(game, player_index, source, chosen);
+                }
+                None => {
+                    player.deck.extend(chosen);
+                    player.shuffle_deck();
+                }
+            }
+            Ok(())
+        },
+    );
+}
+
+fn margrave(game: &mut Game, source: Card) -> Result<(), GameError> {
+    for attacked in game.attacked_player_indices() {
+        game.players[attacked].draw(1);
+        discard_down_to(game, attacked, source, 3);
+    }
+    Ok(())
+}
+
+fn souk(game: &mut Game, _source: Card) -> Result<(), GameError> {
+    let player = game.current_player();
+    player.coins += 7u32.saturating_sub(player.hand.len() as u32);
+    Ok(())
+}
+
+fn souk_on_gain(game: &mut Game, player_index: usize, source: Card) {
+    game.ask(
+        player_index,
+        source,
+        from_hand(0, 2),
+        |game, player_index, answer| {
+            for trashed in answer.cards() {
+                game.trash_card(player_index, trashed);
+            }
+            Ok(())
+        },
+    );
+}
+
+fn stables(game: &mut Game, source: Card) -> Result<(), GameError> {
+    game.ask(
+        game.curr_player_index,
+        source,
+        Prompt::CardsFromHand {
+            min: 0,
+            max: 1,
+            filter: CardFilter::OfType(CardType::Treasure),
+        },
+        |game, player_index, answer| {
+            for treasure in answer.cards() {
+                let player = &mut game.players[player_index];
+                player.add_to_discard(treasure);
+                player.draw(3);
+                player.actions += 1;
+            }
+            Ok(())
+        },
+    );
+    Ok(())
+}
+
+fn wheelwright(game: &mut Game, source: Card) -> Result<(), GameError> {
+    game.ask(
+        game.curr_player_index,
+        source,
+        from_hand(0, 1),
+        move |game, player_index, answer| {
+            for discarded in answer.cards() {
+                game.players[player_index].add_to_discard(discarded);
+                game.ask(
+                    player_index,
+                    source,
+                    gain_from_supply(
+                        game.cost_of(discarded),
+                        CardFilter::OfType(CardType::Action),
+                    ),
+                    |game, player_index, answer| {
+                        game.gain_card(player_index, answer.supply_card(), GainDestination::Discard)
+                    },
+                );
+            }
+            Ok(())
+        },
+    );
+    Ok(())
+}
+
+fn witchs_hut(game: &mut Game, source: Card) -> Result<(), GameError> {
+    let attacked = game.attacked_player_indices();
+    game.ask(
+        game.curr_player_index,
+        source,
+        from_hand(2, 2),
+        move |game, player_index, answer| {
+            let discarded = answer.cards();
+            let both_actions =
+                discarded.len() == 2 && discarded.iter().all(|card| card.is(CardType::Action));
+            discard_all(game, player_index, discarded);
+            if both_actions {
+                for player_index in attacked {
+                    game.gain_if_available(player_index, card("Curse"), GainDestination::Discard)?;
+                }
+            }
+            Ok(())
+        },
+    );
+    Ok(())
+}
+
+fn border_village(game: &mut Game, player_index: usize, source: Card) {
+    let cost = game.cost_of(source);
+    gain_cheaper(game, player_index, source, cost, CardFilter::Any);
+}
+
+fn farmland(game: &mut Game, player_index: usize, source: Card) {
+    game.ask(
+        player_index,
+        source,
+        from_hand(1, 1),
+        move |game, player_index, answer| {
+            for trashed in answer.cards() {
+                let cost = game.cost_of(trashed).plus_coins(2);
+                game.trash_card(player_index, trashed);
+                game.ask(
+                    player_index,
+                    source,
+                    Prompt::CardFromSupply {
+                        max_cost: cost,
+                        exact: true,
+                        filter: CardFilter::NotNamed("Farmland"),
+                    },
+                    |game, player_index, answer| {
+                        game.gain_card(player_index, answer.supply_card(), GainDestination::Discard)
+                    },
+                );
+            }
+            Ok(())
+        },
+    );
+}
+
+fn cache(game: &mut Game, player_index: usize, _source: Card) {
+    game.then(move |game| {
+        for _ in 0..2 {
+            game.gain_if_available(player_index, card("Copper"), GainDestination::Discard)?;
+        }
+        Ok(())
+    });
+}
+
+fn ill_gotten_gains(game: &mut Game, source: Card) -> Result<(), GameError> {
+    if game.supply.count(card("Copper")).unwrap_or(0) == 0 {
+        return Ok(());
+    }
+    game.ask(
+        game.curr_player_index,
+        source,
+        options(&["Gain a Copper into your hand", "Don't gain one"]),
+        |game, player_index, answer| {
+            if answer.option() == 0 {
+                game.gain_card(player_index, card("Copper"), GainDestination::Hand)?;
+            }
+            Ok(())
+        },
+    );
+    Ok(())
+}
+
+fn ill_gotten_gains_on_gain(game: &mut Game, player_index: usize, _source: Card) {
+    game.then(move |game| {
+        for offset in 1..game.players.len() {
+            let other = (player_index + offset) % game.players.len();
+            game.gain_if_available(other, card("Curse"), GainDestination::Discard)?;
+        }
+        Ok(())
+    });
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::cards::testing::{choose_cards, choose_option, gain, play, scenario};
+    use crate::GameMove;
+
+    const KINGDOM: [&str; 10] = [
+        "Fool's Gold",
+        "Trader",
+        "Nomads",
+        "Border Village",
+        "Souk",
+        "Margrave",
+        "Tunnel",
+        "Cauldron",
+        "Inn",
+        "Farmland",
+    ];
+
+    fn gain_to_discard(game: &mut Game, player_index: usize, name: &str) {
+        game.gain_card(player_index, card(name), GainDestination::Discard)
+            .unwrap();
+        game.run_pending().unwrap();
+    }
+
+    #[test]
+    fn fools_gold_reacts_to_another_player_gaining_a_province() {
+        let mut game = scenario(&KINGDOM, [&[], &["Fool's Gold"]]);
+        let gainer = game.curr_player_index;
+        let holder = (gainer + 1) % 2;
+
+        gain_to_discard(&mut game, gainer, "Province");
+        assert_eq!(game.pending_decision().unwrap().player_index, holder);
+        choose_option(&mut game, 0);
+
+        assert!(game.players[holder].hand.is_empty());
+        assert_eq!(game.players[holder].deck.last(), Some(&card("Gold")));
+        assert!(game.trash.cards.contains(&card("Fool's Gold")));
+    }
+
+    #[test]
+    fn fools_gold_ignores_its_holder_gaining_a_province() {
+        let mut game = scenario(&KINGDOM, [&["Fool's Gold"], &[]]);
+        let player_index = game.curr_player_index;
+
+        gain_to_discard(&mut game, player_index, "Province");
+
+        assert!(game.pending_decision().is_none());
+        assert_eq!(game.players[player_index].hand, [card("Fool's Gold")]);
+    }
+
+    #[test]
+    fn trader_exchanges_a_gained_card_for_a_silver() {
+        let mut game = scenario(&KINGDOM, [&["Trader"], &[]]);
+        let player_index = game.curr_player_index;
+        let estates = game.supply.count(card("Estate"));
+
+        gain_to_discard(&mut game, player_index, "Estate");
+        choose_option(&mut game, 0);
+
+        assert_eq!(game.players[player_index].discard, [card("Silver")]);
+        assert_eq!(game.supply.count(card("Estate")), estates);
+    }
+
+    #[test]
+    fn nomads_gives_coins_when_gained() {
+        let mut game = scenario(&KINGDOM, [&[], &[]]);
+        let player_index = game.curr_player_index;
+
+        gain_to_discard(&mut game, player_index, "Nomads");
+
+        assert_eq!(game.players[player_index].coins, 2);
+    }
+
+    #[test]
+    fn border_village_gains_a_cheaper_card_when_gained() {
+        let mut game = scenario(&KINGDOM, [&[], &[]]);
+        let player_index = game.curr_player_index;
+
+        gain_to_discard(&mut game, player_index, "Border Village");
+        assert!(game
+            .accept_move(
+                player_index,
+                GameMove::GainCard {
+                    card: card("Border Village")
+                }
+            )
+            .is_err());
+        gain(&mut game, "Duchy");
+
+        assert_eq!(
+            game.players[player_index].discard,
+            [card("Border Village"), card("Duchy")]
+        );
+    }
+
+    #[test]
+    fn souk_trashes_up_to_two_cards_when_gained() {
+        let mut game = scenario(&KINGDOM, [&["Copper", "Estate", "Estate"], &[]]);
+        let player_index = game.curr_player_index;
+
+        gain_to_discard(&mut game, player_index, "Souk");
+        choose_cards(&mut game, &["Estate", "Estate"]);
+
+        assert_eq!(game.players[player_index].hand, [card("Copper")]);
+        assert_eq!(game.trash.cards, [card("Estate"), card("Estate")]);
+    }
+
+    #[test]
+    fn tunnel_discarded_to_an_attack_gains_a_gold() {
+        let hand = ["Copper", "Copper", "Copper", "Tunnel"];
+        let mut game = scenario(&KINGDOM, [&["Margrave"], &hand]);
+        let attacked = (game.curr_player_index + 1) % 2;
+
+        play(&mut game, "Margrave");
+        choose_cards(&mut game, &["Tunnel", "Copper"]);
+        choose_option(&mut game, 0);
+
+        let discard = &game.players[attacked].discard;
+        assert!(discard.contains(&card("Tunnel")));
+        assert!(discard.contains(&card("Gold")));
+        assert_eq!(game.players[attacked].hand.len(), 3);
+    }
+}
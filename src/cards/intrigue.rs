@@ -0,0 +1,1036 @@
+//! Behaviours for the Intrigue (2nd edition) kingdom cards.
+
+use super::{
+    card, choose_one, discard_all, extra_choice, from_hand, options, Behaviour, Card, CardFilter,
+    CardType, Cost,
+};
+use crate::{CostModifier, GainDestination, Game, GameError, Player, Prompt};
+
+pub(super) fn behaviours() -> Vec<(&'static str, Behaviour)> {
+    let play = |on_play| Behaviour {
+        on_play: Some(on_play),
+        ..Behaviour::default()
+    };
+    vec![
+        ("Courtyard", play(courtyard)),
+        ("Lurker", play(lurker)),
+        ("Pawn", play(pawn)),
+        ("Masquerade", play(masquerade)),
+        ("Shanty Town", play(shanty_town)),
+        ("Steward", play(steward)),
+        ("Swindler", play(swindler)),
+        ("Wishing Well", play(wishing_well)),
+        ("Baron", play(baron)),
+        ("Bridge", play(bridge)),
+        ("Conspirator", play(conspirator)),
+        (
+            "Diplomat",
+            Behaviour {
+                on_attack: Some(diplomat_reaction),
+                ..play(diplomat)
+            },
+        ),
+        ("Ironworks", play(ironworks)),
+        ("Mill", play(mill)),
+        ("Mining Village", play(mining_village)),
+        ("Secret Passage", play(secret_passage)),
+        ("Courtier", play(courtier)),
+        (
+            "Duke",
+            Behaviour {
+                victory_points: Some(duke),
+                ..Behaviour::default()
+            },
+        ),
+        ("Minion", play(minion)),
+        ("Patrol", play(patrol)),
+        ("Replace", play(replace)),
+        ("Torturer", play(torturer)),
+        ("Trading Post", play(trading_post)),
+        ("Upgrade", play(upgrade)),
+        ("Nobles", play(nobles)),
+    ]
+}
+
+fn courtyard(game: &mut Game, source: Card) -> Result<(), GameError> {
+    game.ask(
+        game.curr_player_index,
+        source,
+        from_hand(1, 1),
+        |game, player_index, answer| {
+            for card in answer.cards() {
+                game.players[player_index].put_on_deck(card);
+            }
+            Ok(())
+        },
+    );
+    Ok(())
+}
+
+fn lurker(game: &mut Game, source: Card) -> Result<(), GameError> {
+    choose_one(
+        game,
+        source,
+        &[
+            "Trash an Action card from the Supply",
+            "Gain an Action card from the trash",
+        ],
+        move |game, player_index, ability| {
+            if ability == 0 {
+                let actions: Vec<Card> = game
+                    .supply
+                    .available_cards()
+                    .into_iter()
+                    .filter(|card| card.is(CardType::Action))
+                    .collect();
+                let options = actions
+                    .iter()
+                    .map(|card| format!("Trash {} from the Supply", card.name()))
+                    .collect();
+                game.ask(
+                    player_index,
+                    source,
+                    Prompt::Options { options },
+                    move |game, player_index, answer| {
+                        let card = actions[answer.option()];
+                        game.supply.take_card(card)?;
+                        game.trash_card(player_index, card);
+                        Ok(())
+                    },
+                );
+            } else {
+                let mut actions: Vec<Card> = game
+                    .trash
+                    .cards
+                    .iter()
+                    .copied()
+                    .filter(|card| card.is(CardType::Action))
+                    .collect();
+                actions.sort_by_key(|card| card.name());
+                actions.dedup();
+                let options = actions
+                    .iter()
+                    .map(|card| format!("Gain {} from the trash", card.name()))
+                    .collect();
+                game.ask(
+                    player_index,
+                    source,
+                    Prompt::Options { options },
+                    move |game, player_index, answer| {
+                        let card = actions[answer.option()];
+                        game.gain_from_trash(player_index, card, GainDestination::Discard)
+                    },
+                );
+            }
+            Ok(())
+        },
+    );
+    Ok(())
+}
+
+const PAWN_BONUSES: [&str; 4] = ["+1 Card", "+1 Action", "+1 Buy", "+$1"];
+
+fn pawn(game: &mut Game, source: Card) -> Result<(), GameError> {
+    let mut pairs = Vec::new();
+    for first in 0..PAWN_BONUSES.len() {
+        for second in first + 1..PAWN_BONUSES.len() {
+            pairs.push((first, second));
+        }
+    }
+    let options = pairs
+        .iter()
+        .map(|&(first, second)| format!("{} and {}", PAWN_BONUSES[first], PAWN_BONUSES[second]))
+        .collect();
+    game.ask(
+        game.curr_player_index,
+        source,
+        Prompt::Options { options },
+        move |game, player_index, answer| {
+            let (first, second) = pairs[answer.option()];
+            pawn_bonus(game, player_index, first);
+            pawn_bonus(game, player_index, second);
+            let remaining = (0..PAWN_BONUSES.len())
+                .filter(|&bonus| bonus != first && bonus != second)
+                .map(|bonus| (bonus, PAWN_BONUSES[bonus].to_owned()))
+                .collect();
+            extra_choice(
+                game,
+                player_index,
+                source,
+                remaining,
+                |game, player_index, bonus| {
+                    pawn_bonus(game, player_index, bonus);
+                    Ok(())
+                },
+            );
+            Ok(())
+        },
+    );
+    Ok(())
+}
+
+fn pawn_bonus(game: &mut Game, player_index: usize, bonus: usize) {
+    let player = &mut game.players[player_index];
+    match bonus {
+        0 => {
+            player.draw(1);
+        }
+        1 => player.actions += 1,
+        2 => player.buys += 1,
+        _ => player.coins += 1,
+    }
+}
+
+fn masquerade(game: &mut Game, source: Card) -> Result<(), GameError> {
+    let num_players = game.players.len();
+    let passers = (0..num_players)
+        .map(|offset| (game.curr_player_index + offset) % num_players)
+        .filter(|&player_index| !game.players[player_index].hand.is_empty())
+        .collect();
+    masquerade_pass(game, source, passers, Vec::new());
+    Ok(())
+}
+
+/// Asks each player with cards in hand, in turn order, which card to pass.
+/// The cards only change hands once everyone has chosen.
+fn masquerade_pass(game: &mut Game, source: Card, passers: Vec<usize>, mut passed: Vec<Card>) {
+    let Some(&passer) = passers.get(passed.len()) else {
+        for (position, card) in passed.into_iter().enumerate() {
+            let receiver = passers[(position + 1) % passers.len()];
+            game.players[receiver].add_to_hand(card);
+        }
+        game.ask(
+            game.curr_player_index,
+            source,
+            from_hand(0, 1),
+            |game, player_index, answer| {
+                for card in answer.cards() {
+                    game.trash_card(player_index, card);
+                }
+                Ok(())
+            },
+        );
+        return;
+    };
+    game.ask(passer, source, from_hand(1, 1), move |game, _, answer| {
+        passed.extend(answer.cards());
+        masquerade_pass(game, source, passers, passed);
+        Ok(())
+    });
+}
+
+fn shanty_town(game: &mut Game, _source: Card) -> Result<(), GameError> {
+    let player = game.current_player();
+    if !player.has_card_in_hand(&CardFilter::OfType(CardType::Action)) {
+        player.draw(2);
+    }
+    Ok(())
+}
+
+fn steward(game: &mut Game, source: Card) -> Result<(), GameError> {
+    choose_one(
+        game,
+        source,
+        &["+2 Cards", "+$2", "Trash 2 cards from your hand"],
+        move |game, player_index, ability| {
+            match ability {
+                0 => {
+                    game.players[player_index].draw(2);
+                }
+                1 => game.players[player_index].coins += 2,
+                _ => game.ask(
+                    player_index,
+                    source,
+                    from_hand(2, 2),
+                    |game, player_index, answer| {
+                        for card in answer.cards() {
+                            game.trash_card(player_index, card);
+                        }
+                        Ok(())
+                    },
+                ),
+            }
+            Ok(())
+        },
+    );
+    Ok(())
+}
+
+fn swindler(game: &mut Game, source: Card) -> Result<(), GameError> {
+    swindle(game, source, game.attacked_player_indices());
+    Ok(())
+}
+
+/// Swindles each victim in turn, so that what one gains can't leave the next
+/// with nothing of the right cost.
+fn swindle(game: &mut Game, source: Card, mut victims: Vec<usize>) {
+    if victims.is_empty() {
+        return;
+    }
+    let victim = victims.remove(0);
+    for card in game.players[victim].reveal_from_deck(1) {
+        game.trash_card(victim, card);
+        game.ask(
+            game.curr_player_index,
+            source,
+            Prompt::CardFromSupply {
+                max_cost: game.cost_of(card),
+                exact: true,
+                filter: CardFilter::Any,
+            },
+            move |game, _, answer| {
+                game.gain_card(victim, answer.supply_card(), GainDestination::Discard)
+            },
+        );
+    }
+    game.then(move |game| {
+        swindle(game, source, victims);
+        Ok(())
+    });
+}
+
+fn wishing_well(game: &mut Game, source: Card) -> Result<(), GameError> {
+    let names = game.supply.all_cards();
+    let options = names
+        .iter()
+        .map(|card| format!("Name {}", card.name()))
+        .collect();
+    game.ask(
+        game.curr_player_index,
+        source,
+        Prompt::Options { options },
+        move |game, player_index, answer| {
+            let named = names[answer.option()];
+            let player = &mut game.players[player_index];
+            for card in player.reveal_from_deck(1) {
+                if card == named {
+                    player.add_to_hand(card);
+                } else {
+                    player.put_on_deck(card);
+                }
+            }
+            Ok(())
+        },
+    );
+    Ok(())
+}
+
+fn baron(game: &mut Game, source: Card) -> Result<(), GameError> {
+    let estate = CardFilter::Named("Estate");
+    if !game.current_player_read_only().has_card_in_hand(&estate) {
+        return game.gain_if_available(
+            game.curr_player_index,
+            card("Estate"),
+            GainDestination::Discard,
+        );
+    }
+    game.ask(
+        game.curr_player_index,
+        source,
+        Prompt::CardsFromHand {
+            min: 0,
+            max: 1,
+            filter: estate,
+        },
+        |game, player_index, answer| {
+            let discarded = answer.cards();
+            if discarded.is_empty() {
+                return game.gain_if_available(
+                    player_index,
+                    card("Estate"),
+                    GainDestination::Discard,
+                );
+            }
+            game.players[player_index].coins += 4;
+            discard_all(game, player_index, discarded);
+            Ok(())
+        },
+    );
+    Ok(())
+}
+
+fn bridge(game: &mut Game, _source: Card) -> Result<(), GameError> {
+    game.cost_modifiers.push(CostModifier {
+        filter: CardFilter::Any,
+        coin_reduction: 1,
+    });
+    Ok(())
+}
+
+fn conspirator(game: &mut Game, _source: Card) -> Result<(), GameError> {
+    let actions_played = game
+        .history
+        .current_turn
+        .played
+        .iter()
+        .filter(|card| card.is(CardType::Action))
+        .count();
+    if actions_played >= 3 {
+        let player = game.current_player();
+        player.draw(1);
+        player.actions += 1;
+    }
+    Ok(())
+}
+
+fn diplomat(game: &mut Game, _source: Card) -> Result<(), GameError> {
+    let player = game.current_player();
+    if player.hand.len() <= 5 {
+        player.actions += 2;
+    }
+    Ok(())
+}
+
+fn diplomat_reaction(game: &mut Game, player_index: usize, diplomat: Card, _attack: Card) {
+    if game.players[player_index].hand.len() < 5 {
+        return;
+    }
+    game.ask(
+        player_index,
+        diplomat,
+        options(&["Reveal Diplomat to draw 2 then discard 3", "Don't reveal"]),
+        move |game, player_index, answer| {
+            if answer.option() == 0 {
+                game.players[player_index].draw(2);
+                game.ask(
+                    player_index,
+                    diplomat,
+                    from_hand(3, 3),
+                    |game, player_index, answer| {
+                        discard_all(game, player_index, answer.cards());
+                        Ok(())
+                    },
+                );
+            }
+            Ok(())
+        },
+    );
+}
+
+fn ironworks(game: &mut Game, source: Card) -> Result<(), GameError> {
+    game.ask(
+        game.curr_player_index,
+        source,
+        Prompt::CardFromSupply {
+            max_cost: Cost::coins(4),
+            exact: false,
+            filter: CardFilter::Any,
+        },
+        |game, player_index, answer| {
+            let gained = answer.supply_card();
+            game.gain_card(player_index, gained, GainDestination::Discard)?;
+            let player = &mut game.players[player_index];
+            if gained.is(CardType::Action) {
+                player.actions += 1;
+            }
+            if gained.is(CardType::Treasure) {
+                player.coins += 1;
+            }
+            if gained.is(CardType::Victory) {
+                player.draw(1);
+            }
+            Ok(())
+        },
+    );
+    Ok(())
+}
+
+fn mill(game: &mut Game, source: Card) -> Result<(), GameError> {
+    game.ask(
+        game.curr_player_index,
+        source,
+        from_hand(0, 2),
+        |game, player_index, answer| {
+            let discarded = answer.cards();
+            if discarded.len() == 2 {
+                game.players[player_index].coins += 2;
+            }
+            discard_all(game, player_index, discarded);
+            Ok(())
+        },
+    );
+    Ok(())
+}
+
+fn mining_village(game: &mut Game, source: Card) -> Result<(), GameError> {
+    game.ask(
+        game.curr_player_index,
+        source,
+        options(&["Trash Mining Village for +$2", "Keep it"]),
+        move |game, player_index, answer| {
+            if answer.option() == 0 {
+                if let Some(card) = game.players[player_index].take_from_play(source) {
+                    game.trash_card(player_index, card);
+                    game.players[player_index].coins += 2;
+                }
+            }
+            Ok(())
+        },
+    );
+    Ok(())
+}
+
+fn secret_passage(game: &mut Game, source: Card) -> Result<(), GameError> {
+    game.ask(
+        game.curr_player_index,
+        source,
+        from_hand(1, 1),
+        move |game, player_index, answer| {
+            for card in answer.cards() {
+                let deck_size = game.players[player_index].deck.len();
+                let options = (0..=deck_size)
+                    .map(|depth| match depth {
+                        0 => format!("Put {} on top of your deck", card.name()),
+                        _ if depth == deck_size => {
+                            format!("Put {} on the bottom of your deck", card.name())
+                        }
+                        _ => format!("Put {} under the top {} cards", card.name(), depth),
+                    })
+                    .collect();
+                game.ask(
+                    player_index,
+                    source,
+                    Prompt::Options { options },
+                    move |game, player_index, answer| {
+                        game.players[player_index].insert_into_deck(card, answer.option());
+                        Ok(())
+                    },
+                );
+            }
+            Ok(())
+        },
+    );
+    Ok(())
+}
+
+const COURTIER_BONUSES: [&str; 4] = ["+1 Action", "+1 Buy", "+$3", "Gain a Gold"];
+
+fn courtier(game: &mut Game, source: Card) -> Result<(), GameError> {
+    game.ask(
+        game.curr_player_index,
+        source,
+        from_hand(1, 1),
+        move |game, player_index, answer| {
+            for revealed in answer.cards() {
+                // Revealing doesn't take the card out of hand
+                game.players[player_index].add_to_hand(revealed);
+                let mut picks = revealed.info().types.len();
+                // Elder's extra choice is one more bonus
+                if game.players[player_index].elder_played.contains(&source) {
+                    picks += 1;
+                }
+                let picks = picks.min(COURTIER_BONUSES.len());
+                courtier_bonuses(game, source, (0..COURTIER_BONUSES.len()).collect(), picks);
+            }
+            Ok(())
+        },
+    );
+    Ok(())
+}
+
+/// Asks for one of the Courtier bonuses not yet chosen, `picks` times over.
+fn courtier_bonuses(game: &mut Game, source: Card, remaining: Vec<usize>, picks: usize) {
+    if picks == 0 {
+        return;
+    }
+    let options = remaining
+        .iter()
+        .map(|&bonus| COURTIER_BONUSES[bonus].to_owned())
+        .collect();
+    game.ask(
+        game.curr_player_index,
+        source,
+        Prompt::Options { options },
+        move |game, player_index, answer| {
+            let mut remaining = remaining;
+            match remaining.remove(answer.option()) {
+                0 => game.players[player_index].actions += 1,
+                1 => game.players[player_index].buys += 1,
+                2 => game.players[player_index].coins += 3,
+                _ => {
+                    game.gain_if_available(player_index, card("Gold"), GainDestination::Discard)?
+                }
+            }
+            courtier_bonuses(game, source, remaining, picks - 1);
+            Ok(())
+        },
+    );
+}
+
+fn duke(player: &Player) -> i32 {
+    player
+        .all_cards()
+        .filter(|card| card.name() == "Duchy")
+        .count() as i32
+}
+
+fn minion(game: &mut Game, source: Card) -> Result<(), GameError> {
+    choose_one(
+        game,
+        source,
+        &[
+            "+$2",
+            "Discard your hand and draw 4; each other player with 5 or more cards does the same",
+        ],
+        |game, player_index, ability| {
+            if ability == 0 {
+                game.players[player_index].coins += 2;
+                return Ok(());
+            }
+            game.players[player_index].discard_hand();
+            game.players[player_index].draw(4);
+            for victim in game.attacked_player_indices() {
+                let player = &mut game.players[victim];
+                if player.hand.len() >= 5 {
+                    player.discard_hand();
+                    player.draw(4);
+                }
+            }
+            Ok(())
+        },
+    );
+    Ok(())
+}
+
+fn patrol(game: &mut Game, source: Card) -> Result<(), GameError> {
+    let player_index = game.curr_player_index;
+    let (to_hand, rest): (Vec<Card>, Vec<Card>) = game.players[player_index]
+        .reveal_from_deck(4)
+        .into_iter()
+        .partition(|card| card.is(CardType::Victory) || card.is(CardType::Curse));
+    for card in to_hand {
+        game.players[player_index].add_to_hand(card);
+    }
+    put_back_in_order(game, player_index, source, rest);
+    Ok(())
+}
+
+/// Has the player put `cards` back on their deck, choosing which goes on top first.
+fn put_back_in_order(game: &mut Game, player_index: usize, source: Card, mut cards: Vec<Card>) {
+    if cards.len() <= 1 {
+        for card in cards {
+            game.players[player_index].put_on_deck(card);
+        }
+        return;
+    }
+    let options = cards
+        .iter()
+        .map(|card| format!("Put {} back above the rest", card.name()))
+        .collect();
+    game.ask(
+        player_index,
+        source,
+        Prompt::Options { options },
+        move |game, player_index, answer| {
+            let top = cards.remove(answer.option());
+            put_back_in_order(game, player_index, source, cards);
+            // The rest have been put back (or asked about) by now, so this goes above them
+            game.then(move |game| {
+                game.players[player_index].put_on_deck(top);
+                Ok(())
+            });
+            Ok(())
+        },
+    );
+}
+
+fn replace(game: &mut Game, source: Card) -> Result<(), GameError> {
+    game.ask(
+        game.curr_player_index,
+        source,
+        from_hand(1, 1),
+        move |game, player_index, answer| {
+            for trashed in answer.cards() {
+                let max_cost = game.cost_of(trashed).plus_coins(2);
+                game.trash_card(player_index, trashed);
+                game.ask(
+                    player_index,
+                    source,
+                    Prompt::CardFromSupply {
+                        max_cost,
+                        exact: false,
+                        filter: CardFilter::Any,
+                    },
+                    |game, player_index, answer| {
+                        let gained = answer.supply_card();
+                        let destination =
+                            if gained.is(CardType::Action) || gained.is(CardType::Treasure) {
+                                GainDestination::Deck
+                            } else {
+                                GainDestination::Discard
+                            };
+                        game.gain_card(player_index, gained, destination)?;
+                        if gained.is(CardType::Victory) {
+                            for victim in game.attacked_player_indices() {
+                                game.gain_if_available(
+                                    victim,
+                                    card("Curse"),
+                                    GainDestination::Discard,
+                                )?;
+                            }
+                        }
+                        Ok(())
+                    },
+                );
+            }
+            Ok(())
+        },
+    );
+    Ok(())
+}
+
+fn torturer(game: &mut Game, source: Card) -> Result<(), GameError> {
+    for victim in game.attacked_player_indices() {
+        game.ask(
+            victim,
+            source,
+            options(&["Discard 2 cards", "Gain a Curse to your hand"]),
+            move |game, player_index, answer| {
+                if answer.option() == 1 {
+                    return game.gain_if_available(
+                        player_index,
+                        card("Curse"),
+                        GainDestination::Hand,
+                    );
+                }
+                game.ask(
+                    player_index,
+                    source,
+                    from_hand(2, 2),
+                    |game, player_index, answer| {
+                        discard_all(game, player_index, answer.cards());
+                        Ok(())
+                    },
+                );
+                Ok(())
+            },
+        );
+    }
+    Ok(())
+}
+
+fn trading_post(game: &mut Game, source: Card) -> Result<(), GameError> {
+    game.ask(
+        game.curr_player_index,
+        source,
+        from_hand(2, 2),
+        |game, player_index, answer| {
+            let trashed = answer.cards();
+            let trashed_two = trashed.len() == 2;
+            for card in trashed {
+                game.trash_card(player_index, card);
+            }
+            if trashed_two {
+                game.gain_if_available(player_index, card("Silver"), GainDestination::Hand)?;
+            }
+            Ok(())
+        },
+    );
+    Ok(())
+}
+
+fn upgrade(game: &mut Game, source: Card) -> Result<(), GameError> {
+    game.ask(
+        game.curr_player_index,
+        source,
+        from_hand(1, 1),
+        move |game, player_index, answer| {
+            for card in answer.cards() {
+                let cost = game.cost_of(card).plus_coins(1);
+                game.trash_card(player_index, card);
+                game.ask(
+                    player_index,
+                    source,
+                    Prompt::CardFromSupply {
+                        max_cost: cost,
+                        exact: true,
+                        filter: CardFilter::Any,
+                    },
+                    |game, player_index, answer| {
+                        game.gain_card(player_index, answer.supply_card(), GainDestination::Discard)
+                    },
+                );
+            }
+            Ok(())
+        },
+    );
+    Ok(())
+}
+
+fn nobles(game: &mut Game, source: Card) -> Result<(), GameError> {
+    choose_one(
+        game,
+        source,
+        &["+3 Cards", "+2 Actions"],
+        |game, player_index, ability| {
+            let player = &mut game.players[player_index];
+            if ability == 0 {
+                player.draw(3);
+            } else {
+                player.actions += 2;
+            }
+            Ok(())
+        },
+    );
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::cards::testing::{choose_cards, choose_option, gain, play, scenario};
+    use crate::GameMove;
+
+    const KINGDOM: [&str; 10] = [
+        "Pawn",
+        "Masquerade",
+        "Steward",
+        "Swindler",
+        "Nobles",
+        "Minion",
+        "Replace",
+        "Courtyard",
+        "Mill",
+        "Duke",
+    ];
+
+    /// The names of the cards, sorted so they can be compared as a set.
+    fn names(cards: &[Card]) -> Vec<&'static str> {
+        let mut names: Vec<&str> = cards.iter().map(|card| card.name()).collect();
+        names.sort();
+        names
+    }
+
+    fn other(game: &Game) -> usize {
+        (game.curr_player_index + 1) % 2
+    }
+
+    #[test]
+    fn pawn_gives_the_chosen_pair_of_bonuses() {
+        let mut game = scenario(&KINGDOM, [&["Pawn", "Copper"], &[]]);
+        play(&mut game, "Pawn");
+        let player = game.current_player_read_only();
+        let (actions, buys, coins) = (player.actions, player.buys, player.coins);
+
+        // The pairs are listed in order: Card+Action, Card+Buy, Card+$, ...
+        choose_option(&mut game, 2);
+
+        let player = game.current_player_read_only();
+        assert_eq!(names(&player.hand), ["Copper", "Silver"]);
+        assert_eq!(player.coins, coins + 1);
+        assert_eq!((player.actions, player.buys), (actions, buys));
+    }
+
+    #[test]
+    fn pawn_offers_each_pair_once() {
+        let mut game = scenario(&KINGDOM, [&["Pawn"], &[]]);
+        play(&mut game, "Pawn");
+        let Some(Prompt::Options { options }) = game.pending_decision().map(|d| &d.prompt) else {
+            panic!("Pawn should offer a choice of bonuses");
+        };
+        assert_eq!(options.len(), 6);
+    }
+
+    #[test]
+    fn steward_can_draw_or_take_coins() {
+        let mut game = scenario(&KINGDOM, [&["Steward"], &[]]);
+        play(&mut game, "Steward");
+        choose_option(&mut game, 0);
+        assert_eq!(
+            names(&game.current_player_read_only().hand),
+            ["Silver", "Silver"]
+        );
+
+        let mut game = scenario(&KINGDOM, [&["Steward"], &[]]);
+        play(&mut game, "Steward");
+        let coins = game.current_player_read_only().coins;
+        choose_option(&mut game, 1);
+        assert_eq!(game.current_player_read_only().coins, coins + 2);
+    }
+
+    #[test]
+    fn steward_trashes_exactly_two_cards() {
+        let mut game = scenario(&KINGDOM, [&["Steward", "Copper", "Estate", "Gold"], &[]]);
+        play(&mut game, "Steward");
+        choose_option(&mut game, 2);
+
+        let player_index = game.curr_player_index;
+        let too_few = GameMove::ChooseCards {
+            card_indices: vec![0],
+        };
+        assert!(game.accept_move(player_index, too_few).is_err());
+        choose_cards(&mut game, &["Copper", "Estate"]);
+
+        assert_eq!(names(&game.current_player_read_only().hand), ["Gold"]);
+        assert_eq!(names(&game.trash.cards), ["Copper", "Estate"]);
+    }
+
+    #[test]
+    fn nobles_gives_cards_or_actions() {
+        let mut game = scenario(&KINGDOM, [&["Nobles"], &[]]);
+        play(&mut game, "Nobles");
+        choose_option(&mut game, 0);
+        assert_eq!(game.current_player_read_only().hand.len(), 3);
+
+        let mut game = scenario(&KINGDOM, [&["Nobles"], &[]]);
+        play(&mut game, "Nobles");
+        let actions = game.current_player_read_only().actions;
+        choose_option(&mut game, 1);
+        let player = game.current_player_read_only();
+        assert_eq!(player.actions, actions + 2);
+        assert!(player.hand.is_empty());
+    }
+
+    #[test]
+    fn minion_can_take_coins_without_attacking() {
+        let mut game = scenario(&KINGDOM, [&["Minion", "Copper"], &["Estate"; 5]]);
+        play(&mut game, "Minion");
+        let coins = game.current_player_read_only().coins;
+        choose_option(&mut game, 0);
+
+        assert_eq!(game.current_player_read_only().coins, coins + 2);
+        assert_eq!(game.current_player_read_only().hand.len(), 1);
+        assert_eq!(game.players[other(&game)].hand.len(), 5);
+    }
+
+    #[test]
+    fn minion_cycles_hands_of_five_or_more() {
+        let mut game = scenario(&KINGDOM, [&["Minion", "Copper", "Copper"], &["Estate"; 5]]);
+        play(&mut game, "Minion");
+        choose_option(&mut game, 1);
+
+        let player = game.current_player_read_only();
+        assert_eq!(names(&player.hand), ["Silver"; 4]);
+        assert_eq!(names(&player.discard), ["Copper", "Copper"]);
+        let victim = &game.players[other(&game)];
+        assert_eq!(names(&victim.hand), ["Silver"; 4]);
+        assert_eq!(names(&victim.discard), ["Estate"; 5]);
+    }
+
+    #[test]
+    fn minion_spares_hands_of_four() {
+        let mut game = scenario(&KINGDOM, [&["Minion"], &["Estate"; 4]]);
+        play(&mut game, "Minion");
+        choose_option(&mut game, 1);
+
+        assert_eq!(names(&game.players[other(&game)].hand), ["Estate"; 4]);
+    }
+
+    #[test]
+    fn masquerade_passes_cards_to_the_left_before_trashing() {
+        let mut game = scenario(
+            &KINGDOM,
+            [&["Masquerade", "Copper", "Estate"], &["Gold", "Duchy"]],
+        );
+        play(&mut game, "Masquerade");
+
+        // The player passes first, then the player to their left
+        assert_eq!(
+            game.pending_decision().unwrap().player_index,
+            game.curr_player_index
+        );
+        choose_cards(&mut game, &["Estate"]);
+        assert_eq!(game.pending_decision().unwrap().player_index, other(&game));
+        choose_cards(&mut game, &["Gold"]);
+        // Only then may the player trash a card
+        choose_cards(&mut game, &["Copper"]);
+
+        assert_eq!(
+            names(&game.current_player_read_only().hand),
+            ["Gold", "Silver", "Silver"]
+        );
+        assert_eq!(names(&game.players[other(&game)].hand), ["Duchy", "Estate"]);
+        assert_eq!(names(&game.trash.cards), ["Copper"]);
+    }
+
+    #[test]
+    fn masquerade_skips_players_without_cards() {
+        let mut game = scenario(&KINGDOM, [&["Masquerade"], &[]]);
+        play(&mut game, "Masquerade");
+
+        choose_cards(&mut game, &["Silver"]);
+        // Nobody else passes, so the card comes back to the player
+        let decision = game.pending_decision().unwrap();
+        assert_eq!(decision.player_index, game.curr_player_index);
+        choose_cards(&mut game, &[]);
+
+        assert_eq!(
+            names(&game.current_player_read_only().hand),
+            ["Silver", "Silver"]
+        );
+        assert!(game.players[other(&game)].hand.is_empty());
+    }
+
+    #[test]
+    fn swindler_replaces_the_top_card_with_one_of_the_same_cost() {
+        let mut game = scenario(&KINGDOM, [&["Swindler"], &[]]);
+        play(&mut game, "Swindler");
+
+        let player_index = game.curr_player_index;
+        let dearer = GameMove::GainCard { card: card("Gold") };
+        assert!(game.accept_move(player_index, dearer).is_err());
+        let cheaper = GameMove::GainCard {
+            card: card("Copper"),
+        };
+        assert!(game.accept_move(player_index, cheaper).is_err());
+        gain(&mut game, "Steward");
+
+        let victim = &game.players[other(&game)];
+        assert_eq!(names(&victim.discard), ["Steward"]);
+        assert_eq!(victim.deck.len(), 9);
+        assert_eq!(names(&game.trash.cards), ["Silver"]);
+    }
+
+    #[test]
+    fn swindler_asks_about_each_victim_in_turn() {
+        let mut game = scenario(&KINGDOM, [&["Swindler"], &[], &[]]);
+        let first = (game.curr_player_index + 1) % 3;
+        let second = (game.curr_player_index + 2) % 3;
+        for victim in [first, second] {
+            game.players[victim].deck.push(card("Pawn"));
+        }
+        // Leave a single card costing $2
+        for name in ["Estate", "Pawn"] {
+            game.supply.pile_mut(card(name)).unwrap().cards.clear();
+        }
+        game.supply
+            .pile_mut(card("Courtyard"))
+            .unwrap()
+            .cards
+            .truncate(1);
+        play(&mut game, "Swindler");
+
+        // The second victim's card is only trashed once the first has gained
+        assert_eq!(names(&game.trash.cards), ["Pawn"]);
+        gain(&mut game, "Courtyard");
+
+        assert!(game.pending_decision().is_none());
+        assert_eq!(names(&game.trash.cards), ["Pawn", "Pawn"]);
+        assert_eq!(names(&game.players[first].discard), ["Courtyard"]);
+        assert!(game.players[second].discard.is_empty());
+    }
+
+    #[test]
+    fn replace_puts_gained_treasures_on_the_deck() {
+        let mut game = scenario(&KINGDOM, [&["Replace", "Estate"], &[]]);
+        play(&mut game, "Replace");
+        choose_cards(&mut game, &["Estate"]);
+        gain(&mut game, "Silver");
+
+        let player = game.current_player_read_only();
+        assert_eq!(player.deck.len(), 11);
+        assert_eq!(player.deck.last(), Some(&card("Silver")));
+        assert!(game.players[other(&game)].discard.is_empty());
+    }
+
+    #[test]
+    fn replace_curses_the_others_when_gaining_a_victory_card() {
+        let mut game = scenario(&KINGDOM, [&["Replace", "Silver"], &[]]);
+        play(&mut game, "Replace");
+        choose_cards(&mut game, &["Silver"]);
+        gain(&mut game, "Duchy");
+
+        assert_eq!(names(&game.current_player_read_only().discard), ["Duchy"]);
+        assert_eq!(names(&game.players[other(&game)].discard), ["Curse"]);
+    }
+}
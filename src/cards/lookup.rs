@@ -0,0 +1,206 @@
+//! Forgiving card-name lookup for anything that reads card names typed by a
+//! person or produced by a model. Matching ignores case, spaces and punctuation,
+//! accepts aliases and unique prefixes, and suggests close names on a miss.
+
+use thiserror::Error;
+
+use super::{all_cards, Card};
+
+#[derive(Debug, Error, PartialEq)]
+pub(crate) enum LookupError {
+    #[error("Unknown card '{query}'{}", did_you_mean(.suggestions))]
+    NotFound {
+        query: String,
+        suggestions: Vec<&'static str>,
+    },
+
+    #[error("'{query}' could be any of {}", .matches.join(", "))]
+    Ambiguous {
+        query: String,
+        matches: Vec<&'static str>,
+    },
+}
+
+fn did_you_mean(suggestions: &[&str]) -> String {
+    match suggestions {
+        [] => String::new(),
+        [suggestion] => format!(". Did you mean {suggestion}?"),
+        _ => format!(". Did you mean one of {}?", suggestions.join(", ")),
+    }
+}
+
+/// Lowercase with everything but letters and digits removed, so that
+/// "Throne Room", "throne-room" and "ThroneRoom" all compare equal.
+pub(super) fn normalise(name: &str) -> String {
+    name.chars()
+        .filter(|c| c.is_alphanumeric())
+        .flat_map(char::to_lowercase)
+        .collect()
+}
+
+/// Initials of a multi-word name, e.g. "cr" for Council Room.
+fn acronym(name: &str) -> Option<String> {
+    let words: Vec<&str> = name.split_whitespace().collect();
+    if words.len() < 2 {
+        return None;
+    }
+    Some(normalise(
+        &words
+            .iter()
+            .filter_map(|word| word.chars().next())
+            .collect::<String>(),
+    ))
+}
+
+fn edit_distance(a: &str, b: &str) -> usize {
+    let b: Vec<char> = b.chars().collect();
+    let mut previous: Vec<usize> = (0..=b.len()).collect();
+    for (i, a_char) in a.chars().enumerate() {
+        let mut current = vec![i + 1];
+        for (j, &b_char) in b.iter().enumerate() {
+            let substitution = previous[j] + usize::from(a_char != b_char);
+            current.push(substitution.min(previous[j + 1] + 1).min(current[j] + 1));
+        }
+        previous = current;
+    }
+    previous[b.len()]
+}
+
+/// Looks a card up among every card in the database.
+pub(crate) fn find_card(query: &str) -> Result<Card, LookupError> {
+    find_card_among(query, all_cards())
+}
+
+/// Looks a card up among `candidates`, trying in turn: the exact name, an
+/// alias, the initials of the name, and a unique prefix of the name. Each
+/// step only succeeds if it matches exactly one card, so an alias settles
+/// initials that several cards share.
+pub(crate) fn find_card_among(
+    query: &str,
+    candidates: impl IntoIterator<Item = Card>,
+) -> Result<Card, LookupError> {
+    let candidates: Vec<Card> = candidates.into_iter().collect();
+    let wanted = normalise(query);
+    let ambiguous = |matches: Vec<Card>| LookupError::Ambiguous {
+        query: query.to_owned(),
+        matches: matches.iter().map(|card| card.name()).collect(),
+    };
+
+    let steps: [&dyn Fn(&Card) -> bool; 4] = [
+        &|card| normalise(card.name()) == wanted,
+        &|card| {
+            card.info()
+                .aliases
+                .iter()
+                .any(|alias| normalise(alias) == wanted)
+        },
+        &|card| acronym(card.name()).is_some_and(|initials| initials == wanted),
+        &|card| !wanted.is_empty() && normalise(card.name()).starts_with(&wanted),
+    ];
+    for matches_step in steps {
+        let matches: Vec<Card> = candidates
+            .iter()
+            .copied()
+            .filter(|card| matches_step(card))
+            .collect();
+        match matches[..] {
+            [] => continue,
+            [card] => return Ok(card),
+            _ => return Err(ambiguous(matches)),
+        }
+    }
+
+    // Nothing matched, so suggest the names within a few typos of the query
+    let tolerance = (wanted.len() / 3).max(1);
+    let mut close: Vec<(usize, &'static str)> = candidates
+        .iter()
+        .map(|card| (edit_distance(&wanted, &normalise(card.name())), card.name()))
+        .filter(|&(distance, _)| distance <= tolerance)
+        .collect();
+    close.sort();
+    Err(LookupError::NotFound {
+        query: query.to_owned(),
+        suggestions: close.into_iter().take(3).map(|(_, name)| name).collect(),
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::cards::card;
+
+    fn cards(names: &[&str]) -> Vec<Card> {
+        names.iter().map(|&name| card(name)).collect()
+    }
+
+    #[test]
+    fn finds_a_card_by_its_exact_name() {
+        assert_eq!(find_card("Throne Room"), Ok(card("Throne Room")));
+        assert_eq!(find_card("Village"), Ok(card("Village")));
+    }
+
+    #[test]
+    fn ignores_case_spaces_and_punctuation() {
+        assert_eq!(find_card("throne room"), Ok(card("Throne Room")));
+        assert_eq!(find_card("THRONE-ROOM"), Ok(card("Throne Room")));
+        assert_eq!(find_card("throneroom"), Ok(card("Throne Room")));
+    }
+
+    #[test]
+    fn accepts_aliases() {
+        assert_eq!(find_card("CR"), Ok(card("Council Room")));
+        assert_eq!(find_card("cr"), Ok(card("Council Room")));
+        assert_eq!(find_card("lab"), Ok(card("Laboratory")));
+    }
+
+    #[test]
+    fn accepts_initials_and_unique_prefixes() {
+        let candidates = cards(&["Throne Room", "Council Room", "Village"]);
+
+        assert_eq!(
+            find_card_among("tr", candidates.clone()),
+            Ok(card("Throne Room"))
+        );
+        assert_eq!(find_card_among("vil", candidates), Ok(card("Village")));
+    }
+
+    #[test]
+    fn refuses_a_name_that_matches_several_cards() {
+        let candidates = cards(&["Market", "Militia", "Moat", "Village"]);
+
+        let error = find_card_among("m", candidates).unwrap_err();
+
+        assert_eq!(
+            error,
+            LookupError::Ambiguous {
+                query: "m".to_owned(),
+                matches: vec!["Market", "Militia", "Moat"],
+            }
+        );
+        assert_eq!(
+            error.to_string(),
+            "'m' could be any of Market, Militia, Moat"
+        );
+    }
+
+    #[test]
+    fn suggests_close_names_for_an_unknown_card() {
+        let candidates = cards(&["Village", "Militia", "Smithy"]);
+
+        let error = find_card_among("Vilage", candidates.clone()).unwrap_err();
+        assert_eq!(
+            error,
+            LookupError::NotFound {
+                query: "Vilage".to_owned(),
+                suggestions: vec!["Village"],
+            }
+        );
+        assert_eq!(
+            error.to_string(),
+            "Unknown card 'Vilage'. Did you mean Village?"
+        );
+
+        let error = find_card_among("Xyzzy", candidates).unwrap_err();
+        assert_eq!(error.to_string(), "Unknown card 'Xyzzy'");
+    }
+}
@@ -0,0 +1,1638 @@
+//! Behaviours for the Menagerie kingdom cards, Events and Ways, and the Horse.
+//! A Way's `on_play` is called with the Action card played using it, in place
+//! of that card's own effect. Exiled cards go onto the player's Exile mat
+//! through `Game::exile_from_supply`, and the engine offers to discard them
+//! when the player gains another copy. Invest watches for gains through
+//! `Behaviour::on_event`, like a Landmark.
+
+use super::{
+    card, choose_one, distinct, from_discard_of, from_hand, from_hand_of, gain_from_supply,
+    next_turn, options, play_taken, Behaviour, Card, CardFilter, CardType, Cost,
+};
+use crate::{
+    Event, Expiry, ExtraTurn, GainDestination, Game, GameError, GamePhase, Mat, Player, Prompt,
+};
+
+pub(super) fn behaviours() -> Vec<(&'static str, Behaviour)> {
+    let play = |on_play| Behaviour {
+        on_play: Some(on_play),
+        ..Behaviour::default()
+    };
+    let in_hand = |on_gain_in_hand| Behaviour {
+        on_gain_in_hand: Some(on_gain_in_hand),
+        ..Behaviour::default()
+    };
+    let event = |on_buy| Behaviour {
+        on_buy: Some(on_buy),
+        ..Behaviour::default()
+    };
+    let cost = |cost| Behaviour {
+        cost: Some(cost),
+        ..Behaviour::default()
+    };
+    vec![
+        ("Black Cat", in_hand(black_cat)),
+        (
+            "Sleigh",
+            Behaviour {
+                on_gain_in_hand: Some(sleigh_reaction),
+                ..play(sleigh)
+            },
+        ),
+        ("Supplies", play(supplies)),
+        ("Snowy Village", play(snowy_village)),
+        (
+            "Camel Train",
+            Behaviour {
+                on_gain: Some(camel_train_on_gain),
+                ..play(camel_train)
+            },
+        ),
+        ("Goatherd", play(goatherd)),
+        ("Scrap", play(scrap)),
+        ("Sheepdog", in_hand(sheepdog)),
+        ("Stockpile", play(stockpile)),
+        ("Bounty Hunter", play(bounty_hunter)),
+        ("Cardinal", play(cardinal)),
+        (
+            "Cavalry",
+            Behaviour {
+                on_gain: Some(cavalry_on_gain),
+                ..play(cavalry)
+            },
+        ),
+        ("Groom", play(groom)),
+        (
+            "Hostelry",
+            Behaviour {
+                on_gain: Some(hostelry),
+                ..Behaviour::default()
+            },
+        ),
+        (
+            "Village Green",
+            Behaviour {
+                #[cfg(any(
+                    feature = "intrigue",
+                    feature = "seaside",
+                    feature = "dark_ages",
+                    feature = "adventures",
+                    feature = "empires",
+                    feature = "nocturne",
+                    feature = "hinterlands",
+                    feature = "cornucopia",
+                    feature = "allies",
+                    feature = "plunder"
+                ))]
+                on_discard: Some(village_green_reaction),
+                ..play(village_green)
+            },
+        ),
+        ("Barge", play(barge)),
+        ("Coven", play(coven)),
+        ("Displace", play(displace)),
+        (
+            "Falconer",
+            Behaviour {
+                on_gain_in_hand: Some(falconer_reaction),
+                ..play(falconer)
+            },
+        ),
+        ("Fisherman", cost(fisherman)),
+        ("Gatekeeper", play(gatekeeper)),
+        ("Hunting Lodge", play(hunting_lodge)),
+        ("Kiln", play(kiln)),
+        ("Livery", play(livery)),
+        ("Mastermind", play(mastermind)),
+        ("Paddock", play(paddock)),
+        ("Sanctuary", play(sanctuary)),
+        ("Destrier", cost(destrier)),
+        (
+            "Wayfarer",
+            Behaviour {
+                cost: Some(wayfarer_cost),
+                ..play(wayfarer)
+            },
+        ),
+        ("Animal Fair", play(animal_fair)),
+        ("Horse", play(horse)),
+        // Events
+        ("Delay", event(delay)),
+        ("Desperation", event(desperation)),
+        ("Gamble", event(gamble)),
+        ("Pursue", event(pursue)),
+        ("Ride", event(ride)),
+        ("Seize the Day", event(seize_the_day)),
+        ("Toil", event(toil)),
+        ("Enhance", event(enhance)),
+        ("March", event(march)),
+        ("Transport", event(transport)),
+        (
+            "Invest",
+            Behaviour {
+                on_event: Some(invest_on_gain),
+                ..event(invest)
+            },
+        ),
+        ("Banish", event(banish)),
+        ("Bargain", event(bargain)),
+        ("Commerce", event(commerce)),
+        ("Demand", event(demand)),
+        ("Stampede", event(stampede)),
+        ("Reap", event(reap)),
+        ("Enclave", event(enclave)),
+        ("Alliance", event(alliance)),
+        ("Populate", event(populate)),
+        // Ways
+        ("Way of the Butterfly", play(way_of_the_butterfly)),
+        ("Way of the Camel", play(way_of_the_camel)),
+        ("Way of the Chameleon", play(way_of_the_chameleon)),
+        ("Way of the Frog", play(way_of_the_frog)),
+        ("Way of the Goat", play(way_of_the_goat)),
+        ("Way of the Horse", play(way_of_the_horse)),
+        ("Way of the Mole", play(way_of_the_mole)),
+        ("Way of the Mouse", play(way_of_the_mouse)),
+        ("Way of the Owl", play(way_of_the_owl)),
+        ("Way of the Rat", play(way_of_the_rat)),
+        ("Way of the Seal", play(way_of_the_seal)),
+        ("Way of the Squirrel", play(way_of_the_squirrel)),
+        ("Way of the Turtle", play(way_of_the_turtle)),
+        ("Way of the Worm", play(way_of_the_worm)),
+    ]
+}
+
+fn gain_horses(
+    game: &mut Game,
+    player_index: usize,
+    count: usize,
+    destination: fn() -> GainDestination,
+) -> Result<(), GameError> {
+    for _ in 0..count {
+        game.gain_if_available(player_index, card("Horse"), destination())?;
+    }
+    Ok(())
+}
+
+fn to_discard() -> GainDestination {
+    GainDestination::Discard
+}
+
+fn to_deck() -> GainDestination {
+    GainDestination::Deck
+}
+
+/// Lets a player Exile a card of their choice from the supply.
+fn exile_choice(game: &mut Game, player_index: usize, source: Card, filter: CardFilter) {
+    let choices: Vec<Card> = game
+        .supply
+        .available_cards()
+        .into_iter()
+        .filter(|&card| filter.matches(card))
+        .collect();
+    game.ask(
+        player_index,
+        source,
+        Prompt::Options {
+            options: choices
+                .iter()
+                .map(|card| format!("Exile {}", card.name()))
+                .collect(),
+        },
+        move |game, player_index, answer| {
+            game.exile_from_supply(player_index, choices[answer.option()])?;
+            Ok(())
+        },
+    );
+}
+
+/// Offers to play an Action card the current player has just had a chance to
+/// play, from a list of candidates, without using up an Action.
+fn offer_to_play(
+    game: &mut Game,
+    source: Card,
+    candidates: Vec<Card>,
+    take: fn(&mut Player, Card),
+) {
+    let candidates: Vec<Card> = distinct(candidates)
+        .into_iter()
+        .filter(|card| card.is(CardType::Action) && card.is_implemented())
+        .collect();
+    if candidates.is_empty() {
+        return;
+    }
+    let mut choices: Vec<String> = candidates
+        .iter()
+        .map(|card| format!("Play {}", card.name()))
+        .collect();
+    choices.push("Don't play anything".to_owned());
+    game.ask(
+        game.curr_player_index,
+        source,
+        Prompt::Options { options: choices },
+        move |game, player_index, answer| {
+            let Some(&action) = candidates.get(answer.option()) else {
+                return Ok(());
+            };
+            take(&mut game.players[player_index], action);
+            play_taken(game, action)
+        },
+    );
+}
+
+/// Offers to play a Reaction from hand in response to its holder gaining a
+/// card during their own turn.
+fn offer_reaction_play(game: &mut Game, holder: Card, player_index: usize, gained: Card) {
+    if player_index != game.curr_player_index {
+        return;
+    }
+    game.ask(
+        player_index,
+        holder,
+        Prompt::Options {
+            options: vec![
+                format!("Play {} (you gained {})", holder.name(), gained.name()),
+                "Don't play it".to_owned(),
+            ],
+        },
+        move |game, player_index, answer| {
+            let player = &mut game.players[player_index];
+            if answer.option() == 1 || !player.hand.contains(&holder) {
+                return Ok(());
+            }
+            from_hand_of(player, holder);
+            play_taken(game, holder)
+        },
+    );
+}
+
+fn black_cat(game: &mut Game, holder: usize, cat: Card, gainer: usize, gained: Card) {
+    if gainer == holder || !gained.is(CardType::Victory) {
+        return;
+    }
+    game.ask(
+        holder,
+        cat,
+        options(&["Play Black Cat", "Don't play it"]),
+        move |game, holder, answer| {
+            let player = &mut game.players[holder];
+            if answer.option() == 1 || !player.hand.contains(&cat) {
+                return Ok(());
+            }
+            // Played on someone else's turn, it stays in play until its
+            // owner's next Clean-up
+            from_hand_of(player, cat);
+            player.played.push(cat);
+            player.draw(2);
+            for offset in 1..game.players.len() {
+                let player_index = (holder + offset) % game.players.len();
+                game.gain_if_available(player_index, card("Curse"), GainDestination::Discard)?;
+            }
+            Ok(())
+        },
+    );
+}
+
+fn sleigh(game: &mut Game, _source: Card) -> Result<(), GameError> {
+    gain_horses(game, game.curr_player_index, 2, to_discard)
+}
+
+fn sleigh_reaction(game: &mut Game, holder: usize, sleigh: Card, gainer: usize, gained: Card) {
+    if gainer != holder {
+        return;
+    }
+    game.ask(
+        holder,
+        sleigh,
+        Prompt::Options {
+            options: vec![
+                format!("Discard Sleigh to put {} into your hand", gained.name()),
+                format!("Discard Sleigh to put {} onto your deck", gained.name()),
+                "Don't discard Sleigh".to_owned(),
+            ],
+        },
+        move |game, player_index, answer| {
+            let choice = answer.option();
+            let player = &mut game.players[player_index];
+            if choice == 2 || !player.hand.contains(&sleigh) {
+                return Ok(());
+            }
+            from_hand_of(player, sleigh);
+            player.add_to_discard(sleigh);
+            if let Some(card) = player.take_gained(gained) {
+                if choice == 0 {
+                    player.add_to_hand(card);
+                } else {
+                    player.put_on_deck(card);
+                }
+            }
+            Ok(())
+        },
+    );
+}
+
+fn supplies(game: &mut Game, _source: Card) -> Result<(), GameError> {
+    gain_horses(game, game.curr_player_index, 1, to_deck)
+}
+
+fn camel_train(game: &mut Game, source: Card) -> Result<(), GameError> {
+    exile_choice(
+        game,
+        game.curr_player_index,
+        source,
+        CardFilter::NotOfType(CardType::Victory),
+    );
+    Ok(())
+}
+
+fn camel_train_on_gain(game: &mut Game, player_index: usize, _source: Card) {
+    game.then(move |game| {
+        game.exile_from_supply(player_index, card("Gold"))?;
+        Ok(())
+    });
+}
+
+fn goatherd(game: &mut Game, source: Card) -> Result<(), GameError> {
+    let player_index = game.curr_player_index;
+    let num_players = game.players.len();
+    let right = (player_index + num_players - 1) % num_players;
+    let trashed = game
+        .history
+        .last_turn(right)
+        .map_or(0, |turn| turn.trashed_by(right).count());
+    game.ask(
+        player_index,
+        source,
+        from_hand(0, 1),
+        |game, player_index, answer| {
+            for card in answer.cards() {
+                game.trash_card(player_index, card);
+            }
+            Ok(())
+        },
+    );
+    game.then(move |game| {
+        game.players[player_index].draw(trashed);
+        Ok(())
+    });
+    Ok(())
+}
+
+const SCRAP_CHOICES: [&str; 6] = [
+    "+1 Card",
+    "+1 Action",
+    "+1 Buy",
+    "+$1",
+    "Gain a Silver",
+    "Gain a Horse",
+];
+
+fn scrap(game: &mut Game, source: Card) -> Result<(), GameError> {
+    game.ask(
+        game.curr_player_index,
+        source,
+        from_hand(1, 1),
+        move |game, player_index, answer| {
+            for trashed in answer.cards() {
+                let count = game.cost_of(trashed).coins as usize;
+                game.trash_card(player_index, trashed);
+                scrap_choose(game, player_index, source, SCRAP_CHOICES.to_vec(), count);
+            }
+            Ok(())
+        },
+    );
+    Ok(())
+}
+
+/// Has the player pick `count` of Scrap's choices, one at a time.
+fn scrap_choose(
+    game: &mut Game,
+    player_index: usize,
+    source: Card,
+    remaining: Vec<&'static str>,
+    count: usize,
+) {
+    if count == 0 {
+        return;
+    }
+    game.ask(
+        player_index,
+        source,
+        options(&remaining),
+        move |game, player_index, answer| {
+            let mut remaining = remaining;
+            let chosen = remaining.remove(answer.option());
+            let player = &mut game.players[player_index];
+            match chosen {
+                "+1 Card" => {
+                    player.draw(1);
+                }
+                "+1 Action" => player.actions += 1,
+                "+1 Buy" => player.buys += 1,
+                "+$1" => player.coins += 1,
+                "Gain a Silver" => {
+                    game.gain_if_available(player_index, card("Silver"), GainDestination::Discard)?
+                }
+                _ => gain_horses(game, player_index, 1, to_discard)?,
+            }
+            scrap_choose(game, player_index, source, remaining, count - 1);
+            Ok(())
+        },
+    );
+}
+
+fn sheepdog(game: &mut Game, holder: usize, sheepdog: Card, gainer: usize, gained: Card) {
+    if gainer == holder {
+        offer_reaction_play(game, sheepdog, holder, gained);
+    }
+}
+
+fn stockpile(game: &mut Game, source: Card) -> Result<(), GameError> {
+    let player = game.current_player();
+    if let Some(stockpile) = player.take_from_play(source) {
+        player.mat_mut(Mat::Exile).push(stockpile);
+    }
+    Ok(())
+}
+
+fn bounty_hunter(game: &mut Game, source: Card) -> Result<(), GameError> {
+    game.ask(
+        game.curr_player_index,
+        source,
+        from_hand(1, 1),
+        |game, player_index, answer| {
+            let player = &mut game.players[player_index];
+            for card in answer.cards() {
+                let exile = player.mat_mut(Mat::Exile);
+                let had_copy = exile.contains(&card);
+                exile.push(card);
+                if !had_copy {
+                    player.coins += 3;
+                }
+            }
+            Ok(())
+        },
+    );
+    Ok(())
+}
+
+fn cardinal(game: &mut Game, source: Card) -> Result<(), GameError> {
+    for player_index in game.attacked_player_indices() {
+        let revealed = game.players[player_index].reveal_from_deck(2);
+        let candidates: Vec<Card> = distinct(revealed.iter().copied())
+            .into_iter()
+            .filter(|&card| (3..=6).contains(&game.cost_of(card).coins))
+            .collect();
+        if candidates.is_empty() {
+            for card in revealed {
+                game.players[player_index].add_to_discard(card);
+            }
+            continue;
+        }
+        game.ask(
+            player_index,
+            source,
+            Prompt::Options {
+                options: candidates
+                    .iter()
+                    .map(|card| format!("Exile {}", card.name()))
+                    .collect(),
+            },
+            move |game, player_index, answer| {
+                let mut revealed = revealed;
+                let exiled = candidates[answer.option()];
+                let index = revealed.iter().position(|&card| card == exiled).unwrap();
+                revealed.remove(index);
+                let player = &mut game.players[player_index];
+                player.mat_mut(Mat::Exile).push(exiled);
+                for card in revealed {
+                    player.add_to_discard(card);
+                }
+                Ok(())
+            },
+        );
+    }
+    Ok(())
+}
+
+fn cavalry(game: &mut Game, _source: Card) -> Result<(), GameError> {
+    gain_horses(game, game.curr_player_index, 2, to_discard)
+}
+
+fn cavalry_on_gain(game: &mut Game, player_index: usize, _cavalry: Card) {
+    let player = &mut game.players[player_index];
+    player.draw(2);
+    player.buys += 1;
+    if player_index == game.curr_player_index && matches!(game.game_phase, GamePhase::BuyPhase) {
+        game.game_phase = GamePhase::ActionPhase;
+    }
+}
+
+fn groom(game: &mut Game, source: Card) -> Result<(), GameError> {
+    game.ask(
+        game.curr_player_index,
+        source,
+        gain_from_supply(Cost::coins(4), CardFilter::Any),
+        |game, player_index, answer| {
+            let gained = answer.supply_card();
+            game.gain_card(player_index, gained, GainDestination::Discard)?;
+            if gained.is(CardType::Action) {
+                gain_horses(game, player_index, 1, to_discard)?;
+            }
+            if gained.is(CardType::Treasure) {
+                game.gain_if_available(player_index, card("Silver"), GainDestination::Discard)?;
+            }
+            if gained.is(CardType::Victory) {
+                let player = &mut game.players[player_index];
+                player.draw(1);
+                player.actions += 1;
+            }
+            Ok(())
+        },
+    );
+    Ok(())
+}
+
+fn hostelry(game: &mut Game, player_index: usize, source: Card) {
+    let treasures = game.players[player_index]
+        .hand
+        .iter()
+        .filter(|card| card.is(CardType::Treasure))
+        .count();
+    game.ask(
+        player_index,
+        source,
+        Prompt::CardsFromHand {
+            min: 0,
+            max: treasures,
+            filter: CardFilter::OfType(CardType::Treasure),
+        },
+        |game, player_index, answer| {
+            let discarded = answer.cards();
+            let count = discarded.len();
+            for treasure in discarded {
+                game.players[player_index].add_to_discard(treasure);
+            }
+            gain_horses(game, player_index, count, to_discard)
+        },
+    );
+}
+
+/// Asks whether a Duration card that lets its player choose should give its
+/// bonus now or at the start of their next turn.
+fn now_or_next_turn(game: &mut Game, player_index: usize, source: Card, bonus: fn(&mut Player)) {
+    game.ask(
+        player_index,
+        source,
+        options(&["Now", "At the start of your next turn"]),
+        move |game, player_index, answer| {
+            if answer.option() == 0 {
+                // +Actions on another player's turn are no use to anyone
+                let on_turn = player_index == game.curr_player_index;
+                let player = &mut game.players[player_index];
+                let actions = player.actions;
+                bonus(player);
+                if !on_turn {
+                    player.actions = actions;
+                }
+            } else {
+                game.at_start_of_turn(player_index, source, move |game| {
+                    bonus(&mut game.players[player_index]);
+                    Ok(())
+                });
+            }
+            Ok(())
+        },
+    );
+}
+
+fn snowy_village(game: &mut Game, _source: Card) -> Result<(), GameError> {
+    // Its own +4 Actions count; any more this turn don't
+    let player = game.current_player();
+    if player.max_actions.is_none() {
+        player.max_actions = Some(player.actions);
+    }
+    Ok(())
+}
+
+fn village_green_bonus(player: &mut Player) {
+    player.draw(1);
+    player.actions += 2;
+}
+
+fn village_green(game: &mut Game, source: Card) -> Result<(), GameError> {
+    now_or_next_turn(game, game.curr_player_index, source, village_green_bonus);
+    Ok(())
+}
+
+#[cfg(any(
+    feature = "intrigue",
+    feature = "seaside",
+    feature = "dark_ages",
+    feature = "adventures",
+    feature = "empires",
+    feature = "nocturne",
+    feature = "hinterlands",
+    feature = "cornucopia",
+    feature = "allies",
+    feature = "plunder"
+))]
+fn village_green_reaction(game: &mut Game, player_index: usize, village_green: Card) {
+    game.ask(
+        player_index,
+        village_green,
+        options(&[
+            "Reveal Village Green to play it",
+            "Leave it in your discard pile",
+        ]),
+        move |game, player_index, answer| {
+            let player = &mut game.players[player_index];
+            if answer.option() == 1 || !player.discard.contains(&village_green) {
+                return Ok(());
+            }
+            from_discard_of(player, village_green);
+            if player_index == game.curr_player_index {
+                return play_taken(game, village_green);
+            }
+            // Played on another player's turn, it stays out until its owner's
+            // next Clean-up
+            game.players[player_index].durations.push(village_green);
+            now_or_next_turn(game, player_index, village_green, village_green_bonus);
+            Ok(())
+        },
+    );
+}
+
+fn barge(game: &mut Game, source: Card) -> Result<(), GameError> {
+    now_or_next_turn(game, game.curr_player_index, source, |player| {
+        player.draw(3);
+        player.buys += 1;
+    });
+    Ok(())
+}
+
+fn coven(game: &mut Game, _source: Card) -> Result<(), GameError> {
+    let curse = card("Curse");
+    for player_index in game.attacked_player_indices() {
+        if !game.exile_from_supply(player_index, curse)? {
+            let player = &mut game.players[player_index];
+            let exile = player.mat_mut(Mat::Exile);
+            let curses = exile.iter().filter(|&&card| card == curse).count();
+            exile.retain(|&card| card != curse);
+            for _ in 0..curses {
+                player.add_to_discard(curse);
+            }
+        }
+    }
+    Ok(())
+}
+
+fn displace(game: &mut Game, source: Card) -> Result<(), GameError> {
+    game.ask(
+        game.curr_player_index,
+        source,
+        from_hand(1, 1),
+        move |game, player_index, answer| {
+            for exiled in answer.cards() {
+                game.players[player_index].mat_mut(Mat::Exile).push(exiled);
+                game.ask(
+                    player_index,
+                    source,
+                    gain_from_supply(
+                        game.cost_of(exiled).plus_coins(2),
+                        CardFilter::NotNamed(exiled.name()),
+                    ),
+                    |game, player_index, answer| {
+                        game.gain_card(player_index, answer.supply_card(), GainDestination::Discard)
+                    },
+                );
+            }
+            Ok(())
+        },
+    );
+    Ok(())
+}
+
+fn falconer(game: &mut Game, source: Card) -> Result<(), GameError> {
+    let cost = game.cost_of(source);
+    if cost.coins == 0 {
+        return Ok(());
+    }
+    game.ask(
+        game.curr_player_index,
+        source,
+        gain_from_supply(cost.minus_coins(1), CardFilter::Any),
+        |game, player_index, answer| {
+            game.gain_card(player_index, answer.supply_card(), GainDestination::Hand)
+        },
+    );
+    Ok(())
+}
+
+fn falconer_reaction(game: &mut Game, holder: usize, falconer: Card, _gainer: usize, gained: Card) {
+    // Only offered on the holder's own turn, where it can be played
+    if gained.info().types.len() >= 2 {
+        offer_reaction_play(game, falconer, holder, gained);
+    }
+}
+
+fn fisherman(game: &Game, cost: Cost) -> Cost {
+    if game.current_player_read_only().discard.is_empty() {
+        cost.minus_coins(3)
+    } else {
+        cost
+    }
+}
+
+fn gatekeeper(game: &mut Game, _source: Card) -> Result<(), GameError> {
+    let owner = game.curr_player_index;
+    let attacked = game.attacked_player_indices();
+    next_turn(game, _source, |game, player_index| {
+        game.players[player_index].coins += 3;
+        Ok(())
+    });
+    game.add_trigger(Expiry::StartOfTurn(owner), move |game, event| {
+        let Event::Gain { player_index, card } = event else {
+            return Ok(true);
+        };
+        if !attacked.contains(&player_index)
+            || !(card.is(CardType::Action) || card.is(CardType::Treasure))
+        {
+            return Ok(true);
+        }
+        let player = &mut game.players[player_index];
+        if player.has_on_mat(Mat::Exile, card) {
+            return Ok(true);
+        }
+        if let Some(gained) = player.take_gained(card) {
+            player.mat_mut(Mat::Exile).push(gained);
+        }
+        Ok(true)
+    });
+    Ok(())
+}
+
+fn hunting_lodge(game: &mut Game, source: Card) -> Result<(), GameError> {
+    game.ask(
+        game.curr_player_index,
+        source,
+        options(&["Discard your hand for +5 Cards", "Keep your hand"]),
+        |game, player_index, answer| {
+            if answer.option() == 0 {
+                let player = &mut game.players[player_index];
+                player.discard_hand();
+                player.draw(5);
+            }
+            Ok(())
+        },
+    );
+    Ok(())
+}
+
+fn kiln(game: &mut Game, source: Card) -> Result<(), GameError> {
+    let owner = game.curr_player_index;
+    game.add_trigger(Expiry::EndOfTurn, move |game, event| {
+        let Event::Play { player_index, card } = event else {
+            return Ok(true);
+        };
+        if player_index != owner {
+            return Ok(true);
+        }
+        if game.supply.count(card).unwrap_or(0) > 0 {
+            game.ask(
+                owner,
+                source,
+                Prompt::Options {
+                    options: vec![
+                        format!("Gain a copy of {}", card.name()),
+                        "Don't gain it".to_owned(),
+                    ],
+                },
+                move |game, player_index, answer| {
+                    if answer.option() == 0 {
+                        game.gain_card(player_index, card, GainDestination::Discard)?;
+                    }
+                    Ok(())
+                },
+            );
+        }
+        Ok(false)
+    });
+    Ok(())
+}
+
+fn livery(game: &mut Game, _source: Card) -> Result<(), GameError> {
+    let owner = game.curr_player_index;
+    game.add_trigger(Expiry::EndOfTurn, move |game, event| {
+        if let Event::Gain { player_index, card } = event {
+            if player_index == owner && game.cost_of(card).coins >= 4 {
+                game.then(move |game| gain_horses(game, owner, 1, to_discard));
+            }
+        }
+        Ok(true)
+    });
+    Ok(())
+}
+
+fn mastermind(game: &mut Game, source: Card) -> Result<(), GameError> {
+    next_turn(game, source, move |game, player_index| {
+        let candidates: Vec<Card> = distinct(game.players[player_index].hand.iter().copied())
+            .into_iter()
+            .filter(|card| card.is(CardType::Action) && card.is_implemented())
+            .collect();
+        let mut choices: Vec<String> = candidates
+            .iter()
+            .map(|card| format!("Play {} three times", card.name()))
+            .collect();
+        choices.push("Don't play anything".to_owned());
+        game.ask(
+            player_index,
+            source,
+            Prompt::Options { options: choices },
+            move |game, player_index, answer| {
+                let Some(&action) = candidates.get(answer.option()) else {
+                    return Ok(());
+                };
+                from_hand_of(&mut game.players[player_index], action);
+                play_taken(game, action)?;
+                game.then(move |game| game.resolve_play(action));
+                game.then(move |game| game.resolve_play(action));
+                Ok(())
+            },
+        );
+        Ok(())
+    });
+    Ok(())
+}
+
+fn paddock(game: &mut Game, _source: Card) -> Result<(), GameError> {
+    gain_horses(game, game.curr_player_index, 2, to_discard)?;
+    let empty = game.supply.num_empty_supply_piles() as u32;
+    game.current_player().actions += empty;
+    Ok(())
+}
+
+fn sanctuary(game: &mut Game, source: Card) -> Result<(), GameError> {
+    game.ask(
+        game.curr_player_index,
+        source,
+        from_hand(0, 1),
+        |game, player_index, answer| {
+            let player = &mut game.players[player_index];
+            for card in answer.cards() {
+                player.mat_mut(Mat::Exile).push(card);
+            }
+            Ok(())
+        },
+    );
+    Ok(())
+}
+
+fn destrier(game: &Game, cost: Cost) -> Cost {
+    let gained = game
+        .history
+        .current_turn
+        .gained_by(game.curr_player_index)
+        .count();
+    cost.minus_coins(gained as u32)
+}
+
+fn wayfarer_cost(game: &Game, cost: Cost) -> Cost {
+    let last_other = game
+        .history
+        .current_turn
+        .gained
+        .iter()
+        .rev()
+        .map(|&(_, card)| card)
+        .find(|card| card.name() != "Wayfarer");
+    last_other.map_or(cost, |card| game.cost_of(card))
+}
+
+fn wayfarer(game: &mut Game, source: Card) -> Result<(), GameError> {
+    if game.supply.count(card("Silver")).unwrap_or(0) == 0 {
+        return Ok(());
+    }
+    game.ask(
+        game.curr_player_index,
+        source,
+        options(&["Gain a Silver", "Don't gain anything"]),
+        |game, player_index, answer| {
+            if answer.option() == 0 {
+                game.gain_card(player_index, card("Silver"), GainDestination::Discard)?;
+            }
+            Ok(())
+        },
+    );
+    Ok(())
+}
+
+fn animal_fair(game: &mut Game, _source: Card) -> Result<(), GameError> {
+    // Buying it by trashing an Action card instead of paying isn't supported
+    let empty = game.supply.num_empty_supply_piles() as u32;
+    game.current_player().buys += empty;
+    Ok(())
+}
+
+fn horse(game: &mut Game, source: Card) -> Result<(), GameError> {
+    if let Some(horse) = game.current_player().take_from_play(source) {
+        game.supply.return_card(horse)?;
+    }
+    Ok(())
+}
+
+// EVENTS
+fn delay(game: &mut Game, source: Card) -> Result<(), GameError> {
+    let candidates: Vec<Card> = distinct(game.current_player_read_only().hand.iter().copied())
+        .into_iter()
+        .filter(|card| card.is(CardType::Action) && card.is_implemented())
+        .collect();
+    let mut choices: Vec<String> = candidates
+        .iter()
+        .map(|card| format!("Set aside {}", card.name()))
+        .collect();
+    choices.push("Don't set anything aside".to_owned());
+    game.ask(
+        game.curr_player_index,
+        source,
+        Prompt::Options { options: choices },
+        move |game, player_index, answer| {
+            let Some(&action) = candidates.get(answer.option()) else {
+                return Ok(());
+            };
+            from_hand_of(&mut game.players[player_index], action);
+            // Delay waits in place of a Duration card, so nothing is kept in play
+            game.at_start_of_turn(player_index, source, move |game| play_taken(game, action));
+            Ok(())
+        },
+    );
+    Ok(())
+}
+
+fn desperation(game: &mut Game, source: Card) -> Result<(), GameError> {
+    if game.supply.count(card("Curse")).unwrap_or(0) == 0 {
+        return Ok(());
+    }
+    game.ask(
+        game.curr_player_index,
+        source,
+        options(&["Gain a Curse for +1 Buy and +$2", "Don't gain a Curse"]),
+        |game, player_index, answer| {
+            if answer.option() == 0 {
+                game.gain_card(player_index, card("Curse"), GainDestination::Discard)?;
+                let player = &mut game.players[player_index];
+                player.buys += 1;
+                player.coins += 2;
+            }
+            Ok(())
+        },
+    );
+    Ok(())
+}
+
+fn gamble(game: &mut Game, source: Card) -> Result<(), GameError> {
+    let player = game.current_player();
+    player.buys += 1;
+    let Some(top) = player.take_from_deck() else {
+        return Ok(());
+    };
+    let playable = (top.is(CardType::Treasure) || top.is(CardType::Action)) && top.is_implemented();
+    if !playable {
+        player.add_to_discard(top);
+        return Ok(());
+    }
+    game.ask(
+        game.curr_player_index,
+        source,
+        Prompt::Options {
+            options: vec![
+                format!("Play {}", top.name()),
+                format!("Discard {}", top.name()),
+            ],
+        },
+        move |game, player_index, answer| {
+            if answer.option() == 0 {
+                play_taken(game, top)
+            } else {
+                game.players[player_index].add_to_discard(top);
+                Ok(())
+            }
+        },
+    );
+    Ok(())
+}
+
+fn pursue(game: &mut Game, source: Card) -> Result<(), GameError> {
+    let player = game.current_player();
+    player.buys += 1;
+    let revealed = player.reveal_from_deck(4);
+    // Only naming one of the revealed cards can make a difference
+    let named = distinct(revealed.iter().copied());
+    let mut choices: Vec<String> = named
+        .iter()
+        .map(|card| format!("Name {}", card.name()))
+        .collect();
+    choices.push("Name something else".to_owned());
+    game.ask(
+        game.curr_player_index,
+        source,
+        Prompt::Options { options: choices },
+        move |game, player_index, answer| {
+            let named = named.get(answer.option()).copied();
+            let player = &mut game.players[player_index];
+            for card in revealed.into_iter().rev() {
+                if Some(card) == named {
+                    player.put_on_deck(card);
+                } else {
+                    player.add_to_discard(card);
+                }
+            }
+            Ok(())
+        },
+    );
+    Ok(())
+}
+
+fn seize_the_day(game: &mut Game, _source: Card) -> Result<(), GameError> {
+    let owner = game.curr_player_index;
+    game.players[owner].seized_the_day = true;
+    game.extra_turns.push_back(ExtraTurn {
+        player_index: owner,
+        hand_size: 5,
+    });
+    Ok(())
+}
+
+fn ride(game: &mut Game, _source: Card) -> Result<(), GameError> {
+    gain_horses(game, game.curr_player_index, 1, to_discard)
+}
+
+fn toil(game: &mut Game, source: Card) -> Result<(), GameError> {
+    game.current_player().buys += 1;
+    let hand = game.current_player_read_only().hand.clone();
+    offer_to_play(game, source, hand, from_hand_of);
+    Ok(())
+}
+
+fn enhance(game: &mut Game, source: Card) -> Result<(), GameError> {
+    game.ask(
+        game.curr_player_index,
+        source,
+        Prompt::CardsFromHand {
+            min: 0,
+            max: 1,
+            filter: CardFilter::NotOfType(CardType::Victory),
+        },
+        move |game, player_index, answer| {
+            for trashed in answer.cards() {
+                let cost = game.cost_of(trashed).plus_coins(2);
+                game.trash_card(player_index, trashed);
+                game.ask(
+                    player_index,
+                    source,
+                    gain_from_supply(cost, CardFilter::Any),
+                    |game, player_index, answer| {
+                        game.gain_card(player_index, answer.supply_card(), GainDestination::Discard)
+                    },
+                );
+            }
+            Ok(())
+        },
+    );
+    Ok(())
+}
+
+fn march(game: &mut Game, source: Card) -> Result<(), GameError> {
+    let discard = game.current_player_read_only().discard.clone();
+    offer_to_play(game, source, discard, from_discard_of);
+    Ok(())
+}
+
+fn transport(game: &mut Game, source: Card) -> Result<(), GameError> {
+    choose_one(
+        game,
+        source,
+        &[
+            "Exile an Action card from the Supply",
+            "Put an Action card from Exile onto your deck",
+        ],
+        move |game, player_index, ability| {
+            if ability == 0 {
+                exile_choice(
+                    game,
+                    player_index,
+                    source,
+                    CardFilter::OfType(CardType::Action),
+                );
+                return Ok(());
+            }
+            let exiled: Vec<Card> = game.players[player_index]
+                .mats
+                .get(&Mat::Exile)
+                .map(|exile| distinct(exile.iter().copied()))
+                .unwrap_or_default()
+                .into_iter()
+                .filter(|card| card.is(CardType::Action))
+                .collect();
+            game.ask(
+                player_index,
+                source,
+                Prompt::Options {
+                    options: exiled
+                        .iter()
+                        .map(|card| format!("Put {} onto your deck", card.name()))
+                        .collect(),
+                },
+                move |game, player_index, answer| {
+                    let chosen = exiled[answer.option()];
+                    let player = &mut game.players[player_index];
+                    let exile = player.mat_mut(Mat::Exile);
+                    if let Some(index) = exile.iter().position(|&card| card == chosen) {
+                        exile.remove(index);
+                        player.put_on_deck(chosen);
+                    }
+                    Ok(())
+                },
+            );
+            Ok(())
+        },
+    );
+    Ok(())
+}
+
+fn invest(game: &mut Game, source: Card) -> Result<(), GameError> {
+    let any_cost = Cost {
+        coins: u32::MAX,
+        potions: u32::MAX,
+        debt: u32::MAX,
+    };
+    game.ask(
+        game.curr_player_index,
+        source,
+        gain_from_supply(any_cost, CardFilter::OfType(CardType::Action)),
+        |game, player_index, answer| {
+            let invested = answer.supply_card();
+            if game.exile_from_supply(player_index, invested)? {
+                game.players[player_index].invested.push(invested);
+            }
+            reward_investors(game, player_index, invested);
+            Ok(())
+        },
+    );
+    Ok(())
+}
+
+fn invest_on_gain(game: &mut Game, _invest: Card, event: Event) {
+    if let Event::Gain { player_index, card } = event {
+        reward_investors(game, player_index, card);
+    }
+}
+
+/// Gives +2 Cards to every other player for each copy of the card they have
+/// Invested in that is still in their Exile.
+fn reward_investors(game: &mut Game, player_index: usize, card: Card) {
+    for (investor, player) in game.players.iter_mut().enumerate() {
+        if investor == player_index {
+            continue;
+        }
+        let invested = player.invested.iter().filter(|&&c| c == card).count();
+        let exiled = player
+            .mat_mut(Mat::Exile)
+            .iter()
+            .filter(|&&c| c == card)
+            .count();
+        player.draw(2 * invested.min(exiled));
+    }
+}
+
+fn banish(game: &mut Game, source: Card) -> Result<(), GameError> {
+    let hand = game.current_player_read_only().hand.clone();
+    let names = distinct(hand.iter().copied());
+    let mut choices: Vec<String> = names
+        .iter()
+        .map(|card| format!("Exile copies of {}", card.name()))
+        .collect();
+    choices.push("Don't Exile anything".to_owned());
+    game.ask(
+        game.curr_player_index,
+        source,
+        Prompt::Options { options: choices },
+        move |game, player_index, answer| {
+            let Some(&chosen) = names.get(answer.option()) else {
+                return Ok(());
+            };
+            let copies = hand.iter().filter(|&&card| card == chosen).count();
+            game.ask(
+                player_index,
+                source,
+                Prompt::Options {
+                    options: (1..=copies)
+                        .map(|count| format!("Exile {count} {}", chosen.name()))
+                        .collect(),
+                },
+                move |game, player_index, answer| {
+                    let player = &mut game.players[player_index];
+                    for _ in 0..=answer.option() {
+                        from_hand_of(player, chosen);
+                        player.mat_mut(Mat::Exile).push(chosen);
+                    }
+                    Ok(())
+                },
+            );
+            Ok(())
+        },
+    );
+    Ok(())
+}
+
+fn bargain(game: &mut Game, source: Card) -> Result<(), GameError> {
+    game.ask(
+        game.curr_player_index,
+        source,
+        gain_from_supply(Cost::coins(5), CardFilter::NotOfType(CardType::Victory)),
+        |game, player_index, answer| {
+            game.gain_card(player_index, answer.supply_card(), GainDestination::Discard)
+        },
+    );
+    for offset in 1..game.players.len() {
+        let player_index = (game.curr_player_index + offset) % game.players.len();
+        gain_horses(game, player_index, 1, to_discard)?;
+    }
+    Ok(())
+}
+
+fn commerce(game: &mut Game, _source: Card) -> Result<(), GameError> {
+    let player_index = game.curr_player_index;
+    let different = distinct(game.history.current_turn.gained_by(player_index)).len();
+    for _ in 0..different {
+        game.gain_if_available(player_index, card("Gold"), GainDestination::Discard)?;
+    }
+    Ok(())
+}
+
+fn demand(game: &mut Game, source: Card) -> Result<(), GameError> {
+    gain_horses(game, game.curr_player_index, 1, to_deck)?;
+    game.ask(
+        game.curr_player_index,
+        source,
+        gain_from_supply(Cost::coins(4), CardFilter::Any),
+        |game, player_index, answer| {
+            game.gain_card(player_index, answer.supply_card(), GainDestination::Deck)
+        },
+    );
+    Ok(())
+}
+
+fn stampede(game: &mut Game, _source: Card) -> Result<(), GameError> {
+    if game.current_player_read_only().played.len() <= 5 {
+        gain_horses(game, game.curr_player_index, 5, to_deck)?;
+    }
+    Ok(())
+}
+
+fn reap(game: &mut Game, source: Card) -> Result<(), GameError> {
+    let gold = card("Gold");
+    if game.supply.count(gold).unwrap_or(0) == 0 {
+        return Ok(());
+    }
+    let player_index = game.curr_player_index;
+    game.gain_card(player_index, gold, GainDestination::SetAside)?;
+    // Reap waits in place of a Duration card, so nothing is kept in play
+    game.at_start_of_turn(player_index, source, move |game| play_taken(game, gold));
+    Ok(())
+}
+
+fn enclave(game: &mut Game, _source: Card) -> Result<(), GameError> {
+    let player_index = game.curr_player_index;
+    game.gain_if_available(player_index, card("Gold"), GainDestination::Discard)?;
+    game.exile_from_supply(player_index, card("Duchy"))?;
+    Ok(())
+}
+
+fn alliance(game: &mut Game, _source: Card) -> Result<(), GameError> {
+    for name in ["Province", "Duchy", "Estate", "Gold", "Silver", "Copper"] {
+        game.gain_if_available(game.curr_player_index, card(name), GainDestination::Discard)?;
+    }
+    Ok(())
+}
+
+fn populate(game: &mut Game, _source: Card) -> Result<(), GameError> {
+    let actions: Vec<Card> = game
+        .supply
+        .available_cards()
+        .into_iter()
+        .filter(|card| card.is(CardType::Action))
+        .collect();
+    for action in actions {
+        game.gain_if_available(game.curr_player_index, action, GainDestination::Discard)?;
+    }
+    Ok(())
+}
+
+// WAYS
+/// Returns a card the current player has in play to its pile, if it has one.
+fn return_to_pile(game: &mut Game, card: Card) -> Result<bool, GameError> {
+    if game.supply.pile(card).is_none() {
+        return Ok(false);
+    }
+    let Some(returned) = game.current_player().take_from_play(card) else {
+        return Ok(false);
+    };
+    game.supply.return_card(returned)?;
+    Ok(true)
+}
+
+fn way_of_the_butterfly(game: &mut Game, played: Card) -> Result<(), GameError> {
+    if game.supply.pile(played).is_none() {
+        return Ok(());
+    }
+    let cost = game.cost_of(played).plus_coins(1);
+    game.ask(
+        game.curr_player_index,
+        card("Way of the Butterfly"),
+        Prompt::Options {
+            options: vec![
+                format!(
+                    "Return {} to gain a card costing exactly {cost}",
+                    played.name()
+                ),
+                format!("Keep {}", played.name()),
+            ],
+        },
+        move |game, player_index, answer| {
+            if answer.option() == 1 || !return_to_pile(game, played)? {
+                return Ok(());
+            }
+            game.ask(
+                player_index,
+                card("Way of the Butterfly"),
+                Prompt::CardFromSupply {
+                    max_cost: cost,
+                    exact: true,
+                    filter: CardFilter::Any,
+                },
+                |game, player_index, answer| {
+                    game.gain_card(player_index, answer.supply_card(), GainDestination::Discard)
+                },
+            );
+            Ok(())
+        },
+    );
+    Ok(())
+}
+
+fn way_of_the_camel(game: &mut Game, _played: Card) -> Result<(), GameError> {
+    game.exile_from_supply(game.curr_player_index, card("Gold"))?;
+    Ok(())
+}
+
+fn way_of_the_chameleon(game: &mut Game, played: Card) -> Result<(), GameError> {
+    let player = game.current_player();
+    let coins_before = player.coins;
+    player.chameleon_draws = Some(0);
+    game.resolve_play(played)?;
+    // Swap what it gave once it has done everything, asked questions included
+    game.then(move |game| {
+        let player = game.current_player();
+        let draws = player.chameleon_draws.take().unwrap_or(0);
+        let coins = player.coins.saturating_sub(coins_before);
+        player.coins = player.coins - coins + draws;
+        player.draw(coins as usize);
+        Ok(())
+    });
+    Ok(())
+}
+
+fn way_of_the_frog(game: &mut Game, played: Card) -> Result<(), GameError> {
+    let owner = game.curr_player_index;
+    // Cards are discarded from play at Clean-up, straight after the Buy phase
+    game.add_trigger(Expiry::EndOfTurn, move |game, event| {
+        let Event::BuyPhaseEnd { player_index } = event else {
+            return Ok(true);
+        };
+        if player_index == owner {
+            let player = &mut game.players[owner];
+            if let Some(card) = player.take_from_play(played) {
+                player.put_on_deck(card);
+            }
+        }
+        Ok(false)
+    });
+    Ok(())
+}
+
+fn way_of_the_goat(game: &mut Game, _played: Card) -> Result<(), GameError> {
+    game.ask(
+        game.curr_player_index,
+        card("Way of the Goat"),
+        from_hand(1, 1),
+        |game, player_index, answer| {
+            for card in answer.cards() {
+                game.trash_card(player_index, card);
+            }
+            Ok(())
+        },
+    );
+    Ok(())
+}
+
+fn way_of_the_horse(game: &mut Game, played: Card) -> Result<(), GameError> {
+    return_to_pile(game, played)?;
+    Ok(())
+}
+
+fn way_of_the_mole(game: &mut Game, _played: Card) -> Result<(), GameError> {
+    let player = game.current_player();
+    player.discard_hand();
+    player.draw(3);
+    Ok(())
+}
+
+fn way_of_the_mouse(game: &mut Game, _played: Card) -> Result<(), GameError> {
+    match game.supply.mouse {
+        Some(mouse) => game.resolve_play(mouse),
+        None => Ok(()),
+    }
+}
+
+fn way_of_the_owl(game: &mut Game, _played: Card) -> Result<(), GameError> {
+    let player = game.current_player();
+    player.draw(6usize.saturating_sub(player.hand.len()));
+    Ok(())
+}
+
+fn way_of_the_rat(game: &mut Game, played: Card) -> Result<(), GameError> {
+    if game.supply.count(played).unwrap_or(0) == 0 {
+        return Ok(());
+    }
+    game.ask(
+        game.curr_player_index,
+        card("Way of the Rat"),
+        Prompt::CardsFromHand {
+            min: 0,
+            max: 1,
+            filter: CardFilter::OfType(CardType::Treasure),
+        },
+        move |game, player_index, answer| {
+            for treasure in answer.cards() {
+                game.players[player_index].add_to_discard(treasure);
+                game.gain_card(player_index, played, GainDestination::Discard)?;
+            }
+            Ok(())
+        },
+    );
+    Ok(())
+}
+
+fn way_of_the_seal(game: &mut Game, _played: Card) -> Result<(), GameError> {
+    let owner = game.curr_player_index;
+    game.add_trigger(Expiry::EndOfTurn, move |game, event| {
+        let Event::Gain { player_index, card } = event else {
+            return Ok(true);
+        };
+        if player_index != owner {
+            return Ok(true);
+        }
+        game.ask(
+            owner,
+            self::card("Way of the Seal"),
+            Prompt::Options {
+                options: vec![
+                    format!("Put {} onto your deck", card.name()),
+                    "Leave it".to_owned(),
+                ],
+            },
+            move |game, player_index, answer| {
+                let player = &mut game.players[player_index];
+                if answer.option() == 0 {
+                    if let Some(gained) = player.take_gained(card) {
+                        player.put_on_deck(gained);
+                    }
+                }
+                Ok(())
+            },
+        );
+        Ok(true)
+    });
+    Ok(())
+}
+
+fn way_of_the_squirrel(game: &mut Game, _played: Card) -> Result<(), GameError> {
+    game.current_player().next_hand_bonus += 2;
+    Ok(())
+}
+
+fn way_of_the_turtle(game: &mut Game, played: Card) -> Result<(), GameError> {
+    let player_index = game.curr_player_index;
+    let Some(turtle) = game.current_player().take_from_play(played) else {
+        return Ok(());
+    };
+    // The Way waits in place of a Duration card, so nothing is kept in play
+    game.at_start_of_turn(player_index, card("Way of the Turtle"), move |game| {
+        play_taken(game, turtle)
+    });
+    Ok(())
+}
+
+fn way_of_the_worm(game: &mut Game, _played: Card) -> Result<(), GameError> {
+    game.exile_from_supply(game.curr_player_index, card("Estate"))?;
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::cards::testing::{play, scenario};
+    use crate::GameMove;
+
+    const KINGDOM: [&str; 12] = [
+        "Black Cat",
+        "Sleigh",
+        "Supplies",
+        "Scrap",
+        "Stockpile",
+        "Groom",
+        "Barge",
+        "Kiln",
+        "Livery",
+        "Paddock",
+        "Way of the Ox",
+        "Way of the Horse",
+    ];
+
+    fn play_using(game: &mut Game, name: &str, way: &str) {
+        let player_index = game.curr_player_index;
+        let card_index = game.players[player_index]
+            .hand
+            .iter()
+            .position(|card| card.name() == name)
+            .unwrap();
+        let way = Some(card(way));
+        game.accept_move(player_index, GameMove::PlayCard { card_index, way })
+            .unwrap();
+    }
+
+    #[test]
+    fn a_way_replaces_the_effect_of_the_action_played_using_it() {
+        let mut game = scenario(&KINGDOM, [&["Sleigh"], &[]]);
+        let using_ox = GameMove::PlayCard {
+            card_index: 0,
+            way: Some(card("Way of the Ox")),
+        };
+        assert!(game.legal_moves().contains(&using_ox));
+
+        play_using(&mut game, "Sleigh", "Way of the Ox");
+
+        let player = game.current_player_read_only();
+        assert_eq!(player.played, [card("Sleigh")]);
+        assert_eq!(player.actions, 2);
+        // No Horses from Sleigh itself
+        assert!(player.discard.is_empty());
+    }
+
+    #[test]
+    fn way_of_the_horse_returns_the_card_to_its_pile() {
+        let mut game = scenario(&KINGDOM, [&["Sleigh"], &[]]);
+        let sleighs = game.supply.count(card("Sleigh")).unwrap();
+
+        play_using(&mut game, "Sleigh", "Way of the Horse");
+
+        let player = game.current_player_read_only();
+        assert!(player.played.is_empty());
+        assert_eq!((player.hand.len(), player.actions), (2, 1));
+        assert_eq!(game.supply.count(card("Sleigh")), Some(sleighs + 1));
+    }
+
+    #[test]
+    fn horses_come_from_their_own_pile_and_go_back_when_played() {
+        let mut game = scenario(&KINGDOM, [&["Sleigh"], &[]]);
+        let horse = card("Horse");
+        let horses = game.supply.count(horse).unwrap();
+        play(&mut game, "Sleigh");
+        assert_eq!(game.current_player_read_only().discard, [horse, horse]);
+        assert_eq!(game.supply.count(horse), Some(horses - 2));
+
+        let player = game.current_player();
+        player.hand.push(horse);
+        player.actions = 1;
+        play(&mut game, "Horse");
+
+        let player = game.current_player_read_only();
+        assert!(player.played.iter().all(|&played| played != horse));
+        assert_eq!(player.hand.len(), 2);
+        assert_eq!(game.supply.count(horse), Some(horses - 1));
+    }
+
+    #[test]
+    fn supplies_puts_a_horse_onto_the_deck() {
+        let mut game = scenario(&KINGDOM, [&["Supplies"], &[]]);
+        let player_index = game.curr_player_index;
+        game.accept_move(player_index, GameMove::EndActions)
+            .unwrap();
+
+        play(&mut game, "Supplies");
+
+        let player = game.current_player_read_only();
+        assert_eq!(player.coins, 1);
+        assert_eq!(player.deck.last(), Some(&card("Horse")));
+    }
+}
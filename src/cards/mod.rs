@@ -0,0 +1,779 @@
+//! The card database. Card metadata lives in the bundled TOML files next to this
+//! module, so a card whose effect is only vanilla bonuses (+Cards, +Actions,
+//! +Buys, +$) needs no Rust code at all. Cards marked `special` also register a
+//! `Behaviour` from their expansion's module.
+
+#[cfg(feature = "adventures")]
+mod adventures;
+#[cfg(feature = "alchemy")]
+mod alchemy;
+#[cfg(feature = "allies")]
+mod allies;
+#[cfg(feature = "base")]
+mod base;
+#[cfg(feature = "cornucopia")]
+mod cornucopia;
+#[cfg(feature = "dark_ages")]
+mod dark_ages;
+#[cfg(feature = "empires")]
+mod empires;
+#[cfg(feature = "guilds")]
+mod guilds;
+mod helpers;
+#[cfg(feature = "hinterlands")]
+mod hinterlands;
+#[cfg(feature = "intrigue")]
+mod intrigue;
+mod lookup;
+#[cfg(feature = "menagerie")]
+mod menagerie;
+#[cfg(feature = "nocturne")]
+mod nocturne;
+#[cfg(feature = "plunder")]
+mod plunder;
+#[cfg(feature = "prosperity")]
+mod prosperity;
+#[cfg(feature = "renaissance")]
+mod renaissance;
+#[cfg(feature = "seaside")]
+mod seaside;
+
+use std::{collections::HashMap, fmt, sync::OnceLock};
+
+use serde::Deserialize;
+
+use crate::{Event, Game, GameError, Player};
+
+use helpers::*;
+pub(crate) use lookup::{find_card, find_card_among, LookupError};
+
+// The engine sets up the basic supply (Copper, Estate, Curse, ...) from the Base set
+#[cfg(not(feature = "base"))]
+compile_error!("the `base` feature is required, as the basic cards are part of it");
+
+/// An expansion compiled into the binary, each behind the cargo feature named
+/// after its module: its card file and the behaviours of its special cards.
+struct Expansion {
+    name: &'static str,
+    file_name: &'static str,
+    contents: &'static str,
+    behaviours: fn() -> Vec<(&'static str, Behaviour)>,
+}
+
+const EXPANSIONS: &[Expansion] = &[
+    #[cfg(feature = "base")]
+    Expansion {
+        name: "Base",
+        file_name: "base.toml",
+        contents: include_str!("base.toml"),
+        behaviours: base::behaviours,
+    },
+    #[cfg(feature = "intrigue")]
+    Expansion {
+        name: "Intrigue",
+        file_name: "intrigue.toml",
+        contents: include_str!("intrigue.toml"),
+        behaviours: intrigue::behaviours,
+    },
+    #[cfg(feature = "seaside")]
+    Expansion {
+        name: "Seaside",
+        file_name: "seaside.toml",
+        contents: include_str!("seaside.toml"),
+        behaviours: seaside::behaviours,
+    },
+    #[cfg(feature = "prosperity")]
+    Expansion {
+        name: "Prosperity",
+        file_name: "prosperity.toml",
+        contents: include_str!("prosperity.toml"),
+        behaviours: prosperity::behaviours,
+    },
+    #[cfg(feature = "alchemy")]
+    Expansion {
+        name: "Alchemy",
+        file_name: "alchemy.toml",
+        contents: include_str!("alchemy.toml"),
+        behaviours: alchemy::behaviours,
+    },
+    #[cfg(feature = "dark_ages")]
+    Expansion {
+        name: "Dark Ages",
+        file_name: "dark_ages.toml",
+        contents: include_str!("dark_ages.toml"),
+        behaviours: dark_ages::behaviours,
+    },
+    #[cfg(feature = "adventures")]
+    Expansion {
+        name: "Adventures",
+        file_name: "adventures.toml",
+        contents: include_str!("adventures.toml"),
+        behaviours: adventures::behaviours,
+    },
+    #[cfg(feature = "empires")]
+    Expansion {
+        name: "Empires",
+        file_name: "empires.toml",
+        contents: include_str!("empires.toml"),
+        behaviours: empires::behaviours,
+    },
+    #[cfg(feature = "nocturne")]
+    Expansion {
+        name: "Nocturne",
+        file_name: "nocturne.toml",
+        contents: include_str!("nocturne.toml"),
+        behaviours: nocturne::behaviours,
+    },
+    #[cfg(feature = "renaissance")]
+    Expansion {
+        name: "Renaissance",
+        file_name: "renaissance.toml",
+        contents: include_str!("renaissance.toml"),
+        behaviours: renaissance::behaviours,
+    },
+    #[cfg(feature = "menagerie")]
+    Expansion {
+        name: "Menagerie",
+        file_name: "menagerie.toml",
+        contents: include_str!("menagerie.toml"),
+        behaviours: menagerie::behaviours,
+    },
+    #[cfg(feature = "hinterlands")]
+    Expansion {
+        name: "Hinterlands",
+        file_name: "hinterlands.toml",
+        contents: include_str!("hinterlands.toml"),
+        behaviours: hinterlands::behaviours,
+    },
+    #[cfg(feature = "cornucopia")]
+    Expansion {
+        name: "Cornucopia",
+        file_name: "cornucopia.toml",
+        contents: include_str!("cornucopia.toml"),
+        behaviours: cornucopia::behaviours,
+    },
+    #[cfg(feature = "guilds")]
+    Expansion {
+        name: "Guilds",
+        file_name: "guilds.toml",
+        contents: include_str!("guilds.toml"),
+        behaviours: guilds::behaviours,
+    },
+    #[cfg(feature = "allies")]
+    Expansion {
+        name: "Allies",
+        file_name: "allies.toml",
+        contents: include_str!("allies.toml"),
+        behaviours: allies::behaviours,
+    },
+    #[cfg(feature = "plunder")]
+    Expansion {
+        name: "Plunder",
+        file_name: "plunder.toml",
+        contents: include_str!("plunder.toml"),
+        behaviours: plunder::behaviours,
+    },
+];
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Deserialize)]
+pub(crate) enum CardType {
+    Treasure,
+    Action,
+    Victory,
+    Curse,
+    Attack,
+    Reaction,
+    Duration,
+    Looter,
+    Ruins,
+    Shelter,
+    Knight,
+    Reserve,
+    // Exchanged for the next card in its line when discarded from play
+    Traveller,
+    Gathering,
+    // The cards of Empires' Castles pile, counted by Humble Castle and King's Castle
+    Castle,
+    // Plays other cards from the supply, which are never Commands themselves
+    Command,
+    // Start the game in the trash, for Necromancer to play
+    Zombie,
+    // Not a card at all: bought in the Buy phase for its effect, never gained
+    Event,
+    // Not a card either: a scoring rule that is in play for the whole game
+    Landmark,
+    Night,
+    // Replaces one of each player's starting Coppers, and has no pile
+    Heirloom,
+    // Cards that receive Boons or Hexes, which decides whether those decks are set up
+    Fate,
+    Doom,
+    Spirit,
+    // Boons and Hexes are received from their shared decks, States are taken
+    // by a player; none of them are cards
+    Boon,
+    Hex,
+    State,
+    // Bought once per player with a cube, for an effect lasting the rest of the game
+    Project,
+    // Held by one player at a time, changing hands when someone else takes it
+    Artifact,
+    // An effect any Action card can be played for instead of its own
+    Way,
+    // Set aside in a pile of its own outside the supply, to be won with Tournament
+    Prize,
+    // Gives Favors, which players spend on the game's one Ally
+    Liaison,
+    // Not a card: an ability players spend their Favors on, set out when a
+    // Liaison is in the kingdom
+    Ally,
+    // The cards of each of Allies' rotating split piles share a type
+    Augur,
+    Clash,
+    Fort,
+    Odyssey,
+    Townsfolk,
+    Wizard,
+    // Not a card either: put on a kingdom pile at setup, changing all its cards
+    Trait,
+    // Treasures that only come from the shuffled Loot pile outside the supply
+    Loot,
+}
+
+/// What a card costs. Most cards only cost coins, but some also cost
+/// Potions or Debt, and each component has to be covered separately.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct Cost {
+    pub(crate) coins: u32,
+    pub(crate) potions: u32,
+    pub(crate) debt: u32,
+}
+
+impl Cost {
+    pub(crate) fn plus_coins(self, coins: u32) -> Self {
+        Cost {
+            coins: self.coins + coins,
+            ..self
+        }
+    }
+
+    pub(crate) fn minus_coins(self, coins: u32) -> Self {
+        Cost {
+            coins: self.coins.saturating_sub(coins),
+            ..self
+        }
+    }
+
+    /// Whether this cost is no more than `limit` in every component, which is
+    /// what "a card costing up to ..." means.
+    pub(crate) fn fits_within(&self, limit: &Cost) -> bool {
+        self.coins <= limit.coins && self.potions <= limit.potions && self.debt <= limit.debt
+    }
+}
+
+impl fmt::Display for Cost {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "${}", self.coins)?;
+        if self.potions > 0 {
+            write!(f, " {}P", self.potions)?;
+        }
+        if self.debt > 0 {
+            write!(f, " {}D", self.debt)?;
+        }
+        Ok(())
+    }
+}
+
+/// One entry of the card database.
+#[derive(Debug, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub(crate) struct CardInfo {
+    pub(crate) name: String,
+    pub(crate) expansion: String,
+    pub(crate) types: Vec<CardType>,
+    // Cost in coins, plus any Potions or Debt
+    #[serde(default)]
+    cost: u32,
+    #[serde(default)]
+    potion_cost: u32,
+    #[serde(default)]
+    debt_cost: u32,
+    #[serde(default)]
+    pub(crate) text: String,
+    // Other names the card is commonly known by, e.g. "Lab"
+    #[serde(default)]
+    pub(crate) aliases: Vec<String>,
+    // Vanilla bonuses
+    #[serde(default)]
+    pub(crate) cards: u32,
+    #[serde(default)]
+    pub(crate) actions: u32,
+    #[serde(default)]
+    pub(crate) buys: u32,
+    #[serde(default)]
+    pub(crate) coins: u32,
+    #[serde(default)]
+    pub(crate) potions: u32,
+    #[serde(default)]
+    pub(crate) vp: i32,
+    // Basic cards (Copper, Estate, Curse, ...) are in the supply of every game
+    #[serde(default)]
+    pub(crate) basic: bool,
+    // Whether the card needs a behaviour beyond its vanilla bonuses
+    #[serde(default)]
+    pub(crate) special: bool,
+    // Cards only gained from their own pile outside the supply, e.g. Spoils
+    #[serde(default)]
+    pub(crate) non_supply: bool,
+    // Other piles to set up when this card is in the kingdom: the names of
+    // non-supply cards, "Ruins" or "Loot"
+    #[serde(default)]
+    pub(crate) extra_piles: Vec<String>,
+    // Events that can only be bought once per turn, e.g. Alms
+    #[serde(default)]
+    pub(crate) once_per_turn: bool,
+    // The split pile this card shares with others, e.g. "Encampment/Plunder",
+    // or one of Allies' rotating piles of four, e.g. "Wizards"
+    #[serde(default)]
+    pub(crate) pile: String,
+    // VP tokens set out on a Landmark for each player, e.g. Basilica
+    #[cfg(feature = "empires")]
+    #[serde(default)]
+    pub(crate) vp_per_player: u32,
+    // Cards that are gained to their owner's hand rather than the discard pile
+    #[serde(default)]
+    pub(crate) gain_to_hand: bool,
+    // The Heirloom that replaces a starting Copper when this is in the kingdom
+    #[serde(default)]
+    pub(crate) heirloom: String,
+    // How many copies a pile outside the supply starts with, if not 10, or
+    // how many of this card go in its split pile, e.g. for the Castles
+    #[serde(default)]
+    pub(crate) pile_size: u8,
+    // Artifacts set out when this card is in the kingdom, e.g. Flag Bearer's Flag
+    #[serde(default)]
+    pub(crate) artifacts: Vec<String>,
+}
+
+#[derive(Deserialize)]
+struct CardFile {
+    card: Vec<CardInfo>,
+}
+
+/// A card is a cheap handle to its entry in the card database.
+#[derive(Clone, Copy)]
+pub(crate) struct Card(&'static CardInfo);
+
+impl Card {
+    pub(crate) fn named(name: &str) -> Option<Card> {
+        let database = database();
+        database
+            .by_name
+            .get(name)
+            .map(|&index| Card(&database.cards[index]))
+    }
+
+    pub(crate) fn name(&self) -> &'static str {
+        &self.0.name
+    }
+
+    pub(crate) fn info(&self) -> &'static CardInfo {
+        self.0
+    }
+
+    pub(crate) fn is(&self, card_type: CardType) -> bool {
+        self.0.types.contains(&card_type)
+    }
+
+    /// The printed cost. Use `Game::cost_of` for the cost after modifiers.
+    pub(crate) fn cost(&self) -> Cost {
+        Cost {
+            coins: self.0.cost,
+            potions: self.0.potion_cost,
+            debt: self.0.debt_cost,
+        }
+    }
+
+    pub(crate) fn behaviour(&self) -> Option<&'static Behaviour> {
+        database().behaviours.get(self.name())
+    }
+
+    /// Cards whose whole effect is described by their vanilla bonuses and VP.
+    pub(crate) fn is_vanilla(&self) -> bool {
+        !self.0.special
+    }
+
+    /// Special cards are only usable once their behaviour has been written.
+    pub(crate) fn is_implemented(&self) -> bool {
+        self.is_vanilla() || self.behaviour().is_some()
+    }
+}
+
+impl PartialEq for Card {
+    fn eq(&self, other: &Self) -> bool {
+        std::ptr::eq(self.0, other.0)
+    }
+}
+
+impl Eq for Card {}
+
+impl fmt::Debug for Card {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.name())
+    }
+}
+
+/// Looks up a card the engine refers to by name, which must be in the database.
+pub(crate) fn card(name: &str) -> Card {
+    Card::named(name).unwrap_or_else(|| panic!("{name} is not in the card database"))
+}
+
+pub(crate) fn all_cards() -> impl Iterator<Item = Card> {
+    database().cards.iter().map(Card)
+}
+
+/// The names of the expansions compiled into this binary, Base first.
+pub(crate) fn expansions() -> impl Iterator<Item = &'static str> {
+    EXPANSIONS.iter().map(|expansion| expansion.name)
+}
+
+/// Finds a compiled-in expansion by name, ignoring case, spaces and
+/// punctuation, so that "dark ages" and "dark_ages" both find Dark Ages.
+pub(crate) fn find_expansion(query: &str) -> Option<&'static str> {
+    let query = lookup::normalise(query);
+    expansions().find(|name| lookup::normalise(name) == query)
+}
+
+/// The cards of one expansion, in the order its card file lists them.
+pub(crate) fn cards_in(expansion: &str) -> impl Iterator<Item = Card> + '_ {
+    all_cards().filter(move |card| card.info().expansion == expansion)
+}
+
+#[derive(Debug, Clone)]
+pub(crate) enum CardFilter {
+    Any,
+    OfType(CardType),
+    #[cfg(any(
+        feature = "empires",
+        feature = "menagerie",
+        feature = "hinterlands",
+        feature = "allies"
+    ))]
+    NotOfType(CardType),
+    Named(&'static str),
+    #[cfg(any(feature = "nocturne", feature = "menagerie", feature = "hinterlands"))]
+    NotNamed(&'static str),
+}
+
+impl CardFilter {
+    pub(crate) fn matches(&self, card: Card) -> bool {
+        match self {
+            CardFilter::Any => true,
+            CardFilter::OfType(card_type) => card.is(*card_type),
+            #[cfg(any(
+                feature = "empires",
+                feature = "menagerie",
+                feature = "hinterlands",
+                feature = "allies"
+            ))]
+            CardFilter::NotOfType(card_type) => !card.is(*card_type),
+            CardFilter::Named(name) => card.name() == *name,
+            #[cfg(any(feature = "nocturne", feature = "menagerie", feature = "hinterlands"))]
+            CardFilter::NotNamed(name) => card.name() != *name,
+        }
+    }
+}
+
+/// Called after a card's vanilla bonuses when it is played.
+pub(crate) type PlayFn = fn(&mut Game, Card) -> Result<(), GameError>;
+/// Victory points a card is worth to the player who owns it.
+pub(crate) type ScoreFn = fn(&Player) -> i32;
+/// Called when another player plays an Attack (the last argument) while this
+/// Reaction is in the hand of the player given by the index.
+pub(crate) type ReactFn = fn(&mut Game, usize, Card, Card);
+/// Called when any player (the second index) gains a card (the last argument)
+/// while this card is in the hand of the player given by the first index.
+pub(crate) type GainReactFn = fn(&mut Game, usize, Card, usize, Card);
+/// Called when the player given by the index buys a card (the last argument)
+/// while this card is in their hand.
+pub(crate) type BuyReactFn = fn(&mut Game, usize, Card, Card);
+/// Called when the player given by the index gains, trashes or discards this card.
+pub(crate) type OwnCardFn = fn(&mut Game, usize, Card);
+/// Called when the current player overpays for this card by the given amount.
+pub(crate) type OverpayFn = fn(&mut Game, Card, u32) -> Result<(), GameError>;
+/// Called for every event while this card is on the Tavern mat of the player
+/// given by the index, so that it can offer to be called.
+pub(crate) type TavernFn = fn(&mut Game, usize, Card, Event);
+/// Called for every event while this Landmark, Ally or Trait is in the game,
+/// or while any player has this State, Project or Artifact. A kingdom card
+/// with a rule for the whole game, like Changeling, is called while its pile
+/// is in the supply.
+pub(crate) type LandmarkFn = fn(&mut Game, Card, Event);
+/// What a Landmark or Ally adds to (or takes from) the score of the player given by the index.
+pub(crate) type LandmarkScoreFn = fn(&Game, usize) -> i32;
+/// Called when the player given by the index receives this Boon or Hex.
+#[cfg(feature = "nocturne")]
+pub(crate) type ReceiveFn = fn(&mut Game, usize, Card) -> Result<(), GameError>;
+/// What this card costs right now, given its printed cost.
+pub(crate) type CostFn = fn(&Game, Cost) -> Cost;
+/// Whether this card, where the player has it, keeps them from being affected
+/// by other players' Attacks.
+pub(crate) type ProtectFn = fn(&Player, Card) -> bool;
+/// Whether the current player may buy this card at the moment, whatever it costs.
+pub(crate) type BuyableFn = fn(&Game) -> bool;
+
+/// The Rust side of a special card.
+#[derive(Default)]
+pub(crate) struct Behaviour {
+    // For a Way, called with the card played using it
+    pub(crate) on_play: Option<PlayFn>,
+    pub(crate) victory_points: Option<ScoreFn>,
+    pub(crate) on_attack: Option<ReactFn>,
+    pub(crate) on_gain_in_hand: Option<GainReactFn>,
+    pub(crate) on_buy_in_hand: Option<BuyReactFn>,
+    // For a Trait, called with each card gained from its pile
+    pub(crate) on_gain: Option<OwnCardFn>,
+    pub(crate) on_trash: Option<OwnCardFn>,
+    // A card's "when you discard this other than during Clean-up", for
+    // cards discarded through `discard_all`
+    pub(crate) on_discard: Option<OwnCardFn>,
+    // When the current player buys this: an Event's whole effect, or a
+    // card's "when you buy this"
+    pub(crate) on_buy: Option<PlayFn>,
+    pub(crate) on_tavern: Option<TavernFn>,
+    pub(crate) on_event: Option<LandmarkFn>,
+    pub(crate) score: Option<LandmarkScoreFn>,
+    #[cfg(feature = "nocturne")]
+    pub(crate) on_receive: Option<ReceiveFn>,
+    // For a Trait, applied to the cost of every card in its pile
+    pub(crate) cost: Option<CostFn>,
+    // For a card in play or a Project, applied to the cost of every card
+    // during its owner's turns
+    pub(crate) reduces_costs: Option<CostFn>,
+    pub(crate) on_overpay: Option<OverpayFn>,
+    pub(crate) protects: Option<ProtectFn>,
+    pub(crate) buyable: Option<BuyableFn>,
+}
+
+struct CardDatabase {
+    cards: Vec<CardInfo>,
+    by_name: HashMap<String, usize>,
+    behaviours: HashMap<&'static str, Behaviour>,
+}
+
+fn database() -> &'static CardDatabase {
+    static DATABASE: OnceLock<CardDatabase> = OnceLock::new();
+    DATABASE.get_or_init(|| {
+        let mut cards = Vec::new();
+        for expansion in EXPANSIONS {
+            let file: CardFile = toml::from_str(expansion.contents).unwrap_or_else(|e| {
+                panic!("Failed to parse card database {}: {e}", expansion.file_name)
+            });
+            cards.extend(file.card);
+        }
+        let by_name = cards
+            .iter()
+            .enumerate()
+            .map(|(index, info)| (info.name.clone(), index))
+            .collect();
+        let behaviours = EXPANSIONS
+            .iter()
+            .flat_map(|expansion| (expansion.behaviours)())
+            .collect();
+        CardDatabase {
+            cards,
+            by_name,
+            behaviours,
+        }
+    })
+}
+
+/// Checks the database against the registered behaviours, returning a
+/// description of every problem found.
+pub(crate) fn validate() -> Vec<String> {
+    let database = database();
+    let mut problems = Vec::new();
+
+    let mut seen = HashMap::new();
+    for info in &database.cards {
+        if let Some(expansion) = seen.insert(&info.name, &info.expansion) {
+            problems.push(format!(
+                "{} is defined in both {} and {}",
+                info.name, expansion, info.expansion
+            ));
+        }
+    }
+
+    for info in &database.cards {
+        for alias in &info.aliases {
+            if let Ok(other) = find_card(alias) {
+                if other.name() != info.name {
+                    problems.push(format!(
+                        "Alias {} of {} is also the name of {}",
+                        alias,
+                        info.name,
+                        other.name()
+                    ));
+                }
+            }
+        }
+    }
+
+    for info in &database.cards {
+        for extra in &info.extra_piles {
+            if extra != "Ruins"
+                && extra != "Loot"
+                && Card::named(extra).is_none_or(|card| !card.info().non_supply)
+            {
+                problems.push(format!(
+                    "{} needs a pile of {}, which is not a non-supply card",
+                    info.name, extra
+                ));
+            }
+        }
+    }
+
+    for info in &database.cards {
+        if !info.heirloom.is_empty()
+            && Card::named(&info.heirloom).is_none_or(|card| !card.is(CardType::Heirloom))
+        {
+            problems.push(format!(
+                "{} comes with {}, which is not an Heirloom",
+                info.name, info.heirloom
+            ));
+        }
+    }
+
+    for info in &database.cards {
+        for artifact in &info.artifacts {
+            if Card::named(artifact).is_none_or(|card| !card.is(CardType::Artifact)) {
+                problems.push(format!(
+                    "{} comes with {}, which is not an Artifact",
+                    info.name, artifact
+                ));
+            }
+        }
+    }
+
+    let mut split_piles: HashMap<&str, usize> = HashMap::new();
+    for info in &database.cards {
+        if !info.pile.is_empty() {
+            *split_piles.entry(&info.pile).or_default() += 1;
+        }
+    }
+    for (pile, parts) in split_piles {
+        if ![2, 4, 8].contains(&parts) {
+            problems.push(format!(
+                "Split pile {pile} has {parts} parts instead of 2, 4 or 8"
+            ));
+        }
+    }
+
+    let mut missing: Vec<&str> = all_cards()
+        .filter(|card| !card.is_implemented())
+        .map(|card| card.name())
+        .collect();
+    missing.sort();
+    if !missing.is_empty() {
+        problems.push(format!(
+            "Missing implementations for {}",
+            missing.join(", ")
+        ));
+    }
+
+    let mut unknown: Vec<&str> = database
+        .behaviours
+        .keys()
+        .filter(|name| Card::named(name).is_none_or(|card| card.is_vanilla()))
+        .copied()
+        .collect();
+    unknown.sort();
+    if !unknown.is_empty() {
+        problems.push(format!(
+            "Behaviours registered for cards that are not special: {}",
+            unknown.join(", ")
+        ));
+    }
+
+    problems
+}
+
+/// Fixtures shared by the tests of the card modules.
+#[cfg(test)]
+pub(crate) mod testing {
+    use super::{card, Card};
+    use crate::{AutoPlayPolicy, Game, GameMove, GamePhase};
+
+    /// A game with the given kingdom and a player for each of the given hands
+    /// (the current player's first), in the current player's Action phase,
+    /// with a deck of Silvers each. Nothing happens automatically, so each
+    /// step can be checked.
+    pub(crate) fn scenario<const N: usize>(kingdom: &[&str], hands: [&[&str]; N]) -> Game {
+        let kingdom: Vec<Card> = kingdom.iter().map(|&name| card(name)).collect();
+        let mut game = Game::initialise_game_with_kingdom(N, 11, &kingdom).unwrap();
+        for (offset, hand) in hands.into_iter().enumerate() {
+            let player = &mut game.players[(game.curr_player_index + offset) % N];
+            player.hand = hand.iter().map(|&name| card(name)).collect();
+            player.deck = vec![card("Silver"); 10];
+            player.discard.clear();
+            player.auto_play = AutoPlayPolicy::manual();
+        }
+        // Setting up may have moved past an Action phase with nothing to play
+        game.game_phase = GamePhase::ActionPhase;
+        game
+    }
+
+    /// Plays the named card from the current player's hand.
+    pub(crate) fn play(game: &mut Game, name: &str) {
+        let player_index = game.curr_player_index;
+        let card_index = game.players[player_index]
+            .hand
+            .iter()
+            .position(|card| card.name() == name)
+            .unwrap();
+        game.accept_move(
+            player_index,
+            GameMove::PlayCard {
+                card_index,
+                way: None,
+            },
+        )
+        .unwrap();
+    }
+
+    /// Answers the decision play is waiting on.
+    pub(crate) fn answer(game: &mut Game, game_move: GameMove) {
+        let player_index = game.pending_decision().unwrap().player_index;
+        game.accept_move(player_index, game_move).unwrap();
+    }
+
+    pub(crate) fn choose_option(game: &mut Game, option_index: usize) {
+        answer(game, GameMove::ChooseOption { option_index });
+    }
+
+    /// Chooses the named cards from the hand of the player being asked.
+    pub(crate) fn choose_cards(game: &mut Game, names: &[&str]) {
+        let player_index = game.pending_decision().unwrap().player_index;
+        let mut hand = game.players[player_index].hand.clone();
+        let mut card_indices: Vec<usize> = names
+            .iter()
+            .map(|name| {
+                let card_index = hand.iter().position(|card| card.name() == *name).unwrap();
+                // So that the same card isn't picked twice
+                hand[card_index] = card("Curse");
+                card_index
+            })
+            .collect();
+        card_indices.sort();
+        answer(game, GameMove::ChooseCards { card_indices });
+    }
+
+    pub(crate) fn gain(game: &mut Game, name: &str) {
+        answer(game, GameMove::GainCard { card: card(name) });
+    }
+
+    pub(crate) fn end_turn(game: &mut Game) {
+        let player_index = game.curr_player_index;
+        game.accept_move(player_index, GameMove::EndTurn).unwrap();
+    }
+}
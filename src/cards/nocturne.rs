@@ -0,0 +1,1682 @@
+//! Behaviours for the Nocturne kingdom cards, Heirlooms and Spirits, and for the
+//! Boons, Hexes and States. Fate cards receive Boons and Doom cards give out
+//! Hexes through `Game::receive_boon` and `Game::receive_hex`; each Boon and Hex
+//! does its work in `Behaviour::on_receive`. States watch for the start of their
+//! owner's Buy or turn through `Behaviour::on_event`, as does Changeling for
+//! its rule about exchanging gained cards.
+
+use super::{
+    card, choose_to_play, discard_all, discard_chosen, distinct, from_discard_of, from_hand,
+    gain_from_supply, next_turn, options, protects_from_play, reveal_until, Behaviour, Card,
+    CardFilter, CardType, Cost,
+};
+use crate::{Event, Expiry, GainDestination, Game, GameError, GamePhase, Player, Prompt};
+
+pub(super) fn behaviours() -> Vec<(&'static str, Behaviour)> {
+    let play = |on_play| Behaviour {
+        on_play: Some(on_play),
+        ..Behaviour::default()
+    };
+    let receive = |on_receive| Behaviour {
+        on_receive: Some(on_receive),
+        ..Behaviour::default()
+    };
+    let state = |on_event| Behaviour {
+        on_event: Some(on_event),
+        ..Behaviour::default()
+    };
+    vec![
+        ("Druid", play(druid)),
+        (
+            "Faithful Hound",
+            Behaviour {
+                on_discard: Some(faithful_hound),
+                ..Behaviour::default()
+            },
+        ),
+        (
+            "Guardian",
+            Behaviour {
+                protects: Some(protects_from_play),
+                ..play(guardian)
+            },
+        ),
+        ("Monastery", play(monastery)),
+        ("Pixie", play(pixie)),
+        ("Tracker", play(tracker)),
+        ("Ghost Town", play(ghost_town)),
+        ("Leprechaun", play(leprechaun)),
+        ("Night Watchman", play(night_watchman)),
+        ("Secret Cave", play(secret_cave)),
+        (
+            "Changeling",
+            Behaviour {
+                on_event: Some(changeling_exchange),
+                ..play(changeling)
+            },
+        ),
+        ("Fool", play(fool)),
+        ("Bard", play(bard)),
+        (
+            "Blessed Village",
+            Behaviour {
+                on_gain: Some(blessed_village),
+                ..Behaviour::default()
+            },
+        ),
+        (
+            "Cemetery",
+            Behaviour {
+                on_gain: Some(cemetery),
+                ..Behaviour::default()
+            },
+        ),
+        ("Conclave", play(conclave)),
+        ("Crypt", play(crypt)),
+        ("Devil's Workshop", play(devils_workshop)),
+        ("Exorcist", play(exorcist)),
+        ("Necromancer", play(necromancer)),
+        ("Shepherd", play(shepherd)),
+        (
+            "Skulk",
+            Behaviour {
+                on_gain: Some(skulk_on_gain),
+                ..play(skulk)
+            },
+        ),
+        ("Cobbler", play(cobbler)),
+        (
+            "Cursed Village",
+            Behaviour {
+                on_gain: Some(cursed_village_on_gain),
+                ..play(cursed_village)
+            },
+        ),
+        ("Den of Sin", play(den_of_sin)),
+        ("Idol", play(idol)),
+        ("Pooka", play(pooka)),
+        ("Sacred Grove", play(sacred_grove)),
+        ("Tormentor", play(tormentor)),
+        ("Tragic Hero", play(tragic_hero)),
+        ("Vampire", play(vampire)),
+        ("Werewolf", play(werewolf)),
+        ("Raider", play(raider)),
+        // Heirlooms
+        (
+            "Haunted Mirror",
+            Behaviour {
+                on_trash: Some(haunted_mirror),
+                ..Behaviour::default()
+            },
+        ),
+        ("Magic Lamp", play(magic_lamp)),
+        ("Goat", play(goat)),
+        (
+            "Pasture",
+            Behaviour {
+                victory_points: Some(pasture),
+                ..Behaviour::default()
+            },
+        ),
+        ("Cursed Gold", play(cursed_gold)),
+        ("Lucky Coin", play(lucky_coin)),
+        // Non-supply cards
+        ("Will-o'-Wisp", play(will_o_wisp)),
+        ("Imp", play(imp)),
+        ("Ghost", play(ghost)),
+        ("Bat", play(bat)),
+        ("Wish", play(wish)),
+        // Zombies
+        ("Zombie Apprentice", play(zombie_apprentice)),
+        ("Zombie Mason", play(zombie_mason)),
+        ("Zombie Spy", play(zombie_spy)),
+        // Boons
+        ("The Earth's Gift", receive(earths_gift)),
+        ("The Field's Gift", receive(fields_gift)),
+        ("The Flame's Gift", receive(flames_gift)),
+        ("The Forest's Gift", receive(forests_gift)),
+        ("The Moon's Gift", receive(moons_gift)),
+        ("The Mountain's Gift", receive(mountains_gift)),
+        ("The River's Gift", receive(rivers_gift)),
+        ("The Sea's Gift", receive(seas_gift)),
+        ("The Sky's Gift", receive(skys_gift)),
+        ("The Sun's Gift", receive(suns_gift)),
+        ("The Swamp's Gift", receive(swamps_gift)),
+        ("The Wind's Gift", receive(winds_gift)),
+        // Hexes
+        ("Bad Omens", receive(bad_omens)),
+        ("Delusion", receive(delusion)),
+        ("Envy", receive(envy)),
+        ("Famine", receive(famine)),
+        ("Fear", receive(fear)),
+        ("Greed", receive(greed)),
+        ("Haunting", receive(haunting)),
+        ("Locusts", receive(locusts)),
+        ("Misery", receive(misery)),
+        ("Plague", receive(plague)),
+        ("Poverty", receive(poverty)),
+        ("War", receive(war)),
+        // States
+        ("Deluded", state(deluded)),
+        ("Envious", state(envious)),
+        ("Lost in the Woods", state(lost_in_the_woods)),
+    ]
+}
+
+fn trash_chosen(game: &mut Game, player_index: usize, source: Card, max: usize) {
+    game.ask(
+        player_index,
+        source,
+        from_hand(0, max),
+        |game, player_index, answer| {
+            for trashed in answer.cards() {
+                game.trash_card(player_index, trashed);
+            }
+            Ok(())
+        },
+    );
+}
+
+/// Looks at the top cards of a player's deck, letting them discard any and
+/// put the rest back.
+fn sift(game: &mut Game, player_index: usize, source: Card, count: usize) {
+    for card in game.players[player_index].reveal_from_deck(count) {
+        game.ask(
+            player_index,
+            source,
+            Prompt::Options {
+                options: vec![
+                    format!("Discard {}", card.name()),
+                    format!("Put {} back", card.name()),
+                ],
+            },
+            move |game, player_index, answer| {
+                let player = &mut game.players[player_index];
+                if answer.option() == 0 {
+                    player.add_to_discard(card);
+                } else {
+                    player.put_on_deck(card);
+                }
+                Ok(())
+            },
+        );
+    }
+}
+
+/// Returns a card the current player has in play to its pile and takes one
+/// from another pile in its place. Exchanging is neither gaining nor trashing.
+fn exchange(game: &mut Game, source: Card, for_card: Card) -> Result<(), GameError> {
+    if game.supply.count(for_card).unwrap_or(0) == 0 {
+        return Ok(());
+    }
+    let Some(returned) = game.current_player().take_from_play(source) else {
+        return Ok(());
+    };
+    game.supply.return_card(returned)?;
+    game.supply.take_card(for_card)?;
+    game.current_player().add_to_discard(for_card);
+    Ok(())
+}
+
+/// Lets the current player play an Action card from hand that they have no
+/// copy of in play.
+fn play_new_action(game: &mut Game, source: Card, bonus_action: bool) {
+    let player = game.current_player_read_only();
+    let candidates: Vec<Card> = distinct(player.hand.iter().copied())
+        .into_iter()
+        .filter(|card| card.is(CardType::Action) && card.is_implemented())
+        .filter(|card| !player.played.contains(card))
+        .collect();
+    if candidates.is_empty() {
+        return;
+    }
+    let mut choices: Vec<String> = candidates
+        .iter()
+        .map(|card| format!("Play {}", card.name()))
+        .collect();
+    choices.push("Don't play anything".to_owned());
+    game.ask(
+        game.curr_player_index,
+        source,
+        Prompt::Options { options: choices },
+        move |game, player_index, answer| {
+            let Some(&action) = candidates.get(answer.option()) else {
+                return Ok(());
+            };
+            let player = &mut game.players[player_index];
+            let index = player.hand.iter().position(|&card| card == action).unwrap();
+            player.hand.remove(index);
+            if bonus_action {
+                player.actions += 1;
+            }
+            game.play_card(action)?;
+            game.resolve_play(action)
+        },
+    );
+}
+
+fn druid(game: &mut Game, source: Card) -> Result<(), GameError> {
+    let boons = game.supply.druid_boons.clone();
+    game.ask(
+        game.curr_player_index,
+        source,
+        Prompt::Options {
+            options: boons
+                .iter()
+                .map(|boon| format!("Receive {}", boon.name()))
+                .collect(),
+        },
+        move |game, player_index, answer| game.receive(player_index, boons[answer.option()]),
+    );
+    Ok(())
+}
+
+fn faithful_hound(game: &mut Game, player_index: usize, hound: Card) {
+    game.ask(
+        player_index,
+        hound,
+        options(&[
+            "Set Faithful Hound aside until the end of the turn",
+            "Leave it in your discard pile",
+        ]),
+        move |game, player_index, answer| {
+            let player = &mut game.players[player_index];
+            if answer.option() == 1 || !player.discard.contains(&hound) {
+                return Ok(());
+            }
+            from_discard_of(player, hound);
+            game.after_turn(move |game| {
+                game.players[player_index].add_to_hand(hound);
+                Ok(())
+            });
+            Ok(())
+        },
+    );
+}
+
+fn guardian(game: &mut Game, source: Card) -> Result<(), GameError> {
+    next_turn(game, source, |game, player_index| {
+        game.players[player_index].coins += 1;
+        Ok(())
+    });
+    Ok(())
+}
+
+fn monastery(game: &mut Game, source: Card) -> Result<(), GameError> {
+    let owner = game.curr_player_index;
+    let gained = game.history.current_turn.gained_by(owner).count();
+    game.ask(
+        owner,
+        source,
+        from_hand(0, gained),
+        move |game, player_index, answer| {
+            let trashed = answer.cards();
+            let left = gained - trashed.len();
+            for card in trashed {
+                game.trash_card(player_index, card);
+            }
+            trash_coppers_in_play(game, source, left);
+            Ok(())
+        },
+    );
+    Ok(())
+}
+
+fn trash_coppers_in_play(game: &mut Game, source: Card, max: usize) {
+    let copper = card("Copper");
+    let coppers = game
+        .current_player_read_only()
+        .played
+        .iter()
+        .filter(|&&played| played == copper)
+        .count()
+        .min(max);
+    if coppers == 0 {
+        return;
+    }
+    game.ask(
+        game.curr_player_index,
+        source,
+        Prompt::Options {
+            options: (0..=coppers)
+                .map(|count| format!("Trash {count} Copper from play"))
+                .collect(),
+        },
+        move |game, player_index, answer| {
+            for _ in 0..answer.option() {
+                if let Some(copper) = game.players[player_index].take_from_play(copper) {
+                    game.trash_card(player_index, copper);
+                }
+            }
+            Ok(())
+        },
+    );
+}
+
+fn pixie(game: &mut Game, source: Card) -> Result<(), GameError> {
+    let Some(boon) = game.supply.boons.draw() else {
+        return Ok(());
+    };
+    game.supply.boons.discard(boon);
+    game.ask(
+        game.curr_player_index,
+        source,
+        Prompt::Options {
+            options: vec![
+                format!("Trash Pixie to receive {} twice", boon.name()),
+                "Keep Pixie".to_owned(),
+            ],
+        },
+        move |game, player_index, answer| {
+            if answer.option() == 1 {
+                return Ok(());
+            }
+            let Some(pixie) = game.players[player_index].take_from_play(source) else {
+                return Ok(());
+            };
+            game.trash_card(player_index, pixie);
+            game.receive(player_index, boon)?;
+            game.then(move |game| game.receive(player_index, boon));
+            Ok(())
+        },
+    );
+    Ok(())
+}
+
+fn tracker(game: &mut Game, source: Card) -> Result<(), GameError> {
+    let owner = game.curr_player_index;
+    game.receive_boon(owner)?;
+    game.add_trigger(Expiry::EndOfTurn, move |game, event| {
+        let Event::Gain { player_index, card } = event else {
+            return Ok(true);
+        };
+        if player_index != owner || !game.players[owner].played.contains(&source) {
+            return Ok(true);
+        }
+        game.ask(
+            owner,
+            source,
+            Prompt::Options {
+                options: vec![
+                    format!("Put {} onto your deck", card.name()),
+                    "Leave it".to_owned(),
+                ],
+            },
+            move |game, player_index, answer| {
+                if answer.option() == 1 {
+                    return Ok(());
+                }
+                let player = &mut game.players[player_index];
+                let found = if let Some(index) = player.discard.iter().rposition(|&c| c == card) {
+                    Some(player.discard.remove(index))
+                } else if let Some(index) = player.hand.iter().rposition(|&c| c == card) {
+                    Some(player.hand.remove(index))
+                } else {
+                    None
+                };
+                if let Some(gained) = found {
+                    player.put_on_deck(gained);
+                }
+                Ok(())
+            },
+        );
+        Ok(true)
+    });
+    Ok(())
+}
+
+fn ghost_town(game: &mut Game, source: Card) -> Result<(), GameError> {
+    next_turn(game, source, |game, player_index| {
+        let player = &mut game.players[player_index];
+        player.draw(1);
+        player.actions += 1;
+        Ok(())
+    });
+    Ok(())
+}
+
+fn leprechaun(game: &mut Game, _source: Card) -> Result<(), GameError> {
+    let player_index = game.curr_player_index;
+    game.gain_if_available(player_index, card("Gold"), GainDestination::Discard)?;
+    if game.current_player_read_only().played.len() == 7 {
+        game.gain_if_available(player_index, card("Wish"), GainDestination::Discard)
+    } else {
+        game.receive_hex(&[player_index])
+    }
+}
+
+fn night_watchman(game: &mut Game, source: Card) -> Result<(), GameError> {
+    sift(game, game.curr_player_index, source, 5);
+    Ok(())
+}
+
+fn secret_cave(game: &mut Game, source: Card) -> Result<(), GameError> {
+    if game.current_player_read_only().hand.len() < 3 {
+        return Ok(());
+    }
+    game.ask(
+        game.curr_player_index,
+        source,
+        options(&["Discard 3 cards for +$3 next turn", "Don't discard"]),
+        move |game, player_index, answer| {
+            if answer.option() == 1 {
+                return Ok(());
+            }
+            discard_chosen(game, player_index, source, 3);
+            next_turn(game, source, |game, player_index| {
+                game.players[player_index].coins += 3;
+                Ok(())
+            });
+            Ok(())
+        },
+    );
+    Ok(())
+}
+
+fn changeling(game: &mut Game, source: Card) -> Result<(), GameError> {
+    let owner = game.curr_player_index;
+    if let Some(changeling) = game.current_player().take_from_play(source) {
+        game.trash_card(owner, changeling);
+    }
+    let in_play: Vec<Card> = distinct(game.current_player_read_only().played.iter().copied())
+        .into_iter()
+        .filter(|&card| game.supply.count(card).unwrap_or(0) > 0)
+        .collect();
+    if in_play.is_empty() {
+        return Ok(());
+    }
+    game.ask(
+        owner,
+        source,
+        Prompt::Options {
+            options: in_play
+                .iter()
+                .map(|card| format!("Gain a {}", card.name()))
+                .collect(),
+        },
+        move |game, player_index, answer| {
+            game.gain_if_available(
+                player_index,
+                in_play[answer.option()],
+                GainDestination::Discard,
+            )
+        },
+    );
+    Ok(())
+}
+
+/// In games with Changeling, a player gaining a card costing $3 or more may
+/// exchange it for a Changeling.
+fn changeling_exchange(game: &mut Game, changeling: Card, event: Event) {
+    let Event::Gain { player_index, card } = event else {
+        return;
+    };
+    if card == changeling || game.cost_of(card).coins < 3 || game.supply.pile(card).is_none() {
+        return;
+    }
+    if game.supply.count(changeling).unwrap_or(0) == 0 {
+        return;
+    }
+    game.ask(
+        player_index,
+        changeling,
+        Prompt::Options {
+            options: vec![
+                format!("Keep {}", card.name()),
+                format!("Exchange {} for a Changeling", card.name()),
+            ],
+        },
+        move |game, player_index, answer| {
+            if answer.option() == 0 || game.supply.count(changeling).unwrap_or(0) == 0 {
+                return Ok(());
+            }
+            // Exchanging isn't gaining, so the Changeling doesn't trigger anything
+            let player = &mut game.players[player_index];
+            let Some(exchanged) = player.take_gained(card) else {
+                return Ok(());
+            };
+            player.add_to_discard(changeling);
+            game.supply.return_card(exchanged)?;
+            game.supply.take_card(changeling)
+        },
+    );
+}
+
+fn fool(game: &mut Game, source: Card) -> Result<(), GameError> {
+    let owner = game.curr_player_index;
+    let lost_in_the_woods = card("Lost in the Woods");
+    if game.players[owner].states.contains(&lost_in_the_woods) {
+        return Ok(());
+    }
+    for player in &mut game.players {
+        player.states.retain(|&state| state != lost_in_the_woods);
+    }
+    game.players[owner].states.push(lost_in_the_woods);
+    let boons: Vec<Card> = (0..3).filter_map(|_| game.supply.boons.draw()).collect();
+    receive_in_any_order(game, owner, source, boons);
+    Ok(())
+}
+
+/// Has a player receive the Boons they have taken one at a time, in the order
+/// they choose.
+fn receive_in_any_order(game: &mut Game, player_index: usize, source: Card, boons: Vec<Card>) {
+    if boons.is_empty() {
+        return;
+    }
+    game.ask(
+        player_index,
+        source,
+        Prompt::Options {
+            options: boons
+                .iter()
+                .map(|boon| format!("Receive {}", boon.name()))
+                .collect(),
+        },
+        move |game, player_index, answer| {
+            let mut boons = boons;
+            let boon = boons.remove(answer.option());
+            game.supply.boons.discard(boon);
+            game.receive(player_index, boon)?;
+            game.then(move |game| {
+                receive_in_any_order(game, player_index, source, boons);
+                Ok(())
+            });
+            Ok(())
+        },
+    );
+}
+
+fn bard(game: &mut Game, _source: Card) -> Result<(), GameError> {
+    game.receive_boon(game.curr_player_index)?;
+    Ok(())
+}
+
+fn blessed_village(game: &mut Game, player_index: usize, source: Card) {
+    game.then(move |game| {
+        let Some(boon) = game.supply.boons.draw() else {
+            return Ok(());
+        };
+        game.supply.boons.discard(boon);
+        game.ask(
+            player_index,
+            source,
+            Prompt::Options {
+                options: vec![
+                    format!("Receive {} now", boon.name()),
+                    format!("Receive {} at the start of your next turn", boon.name()),
+                ],
+            },
+            move |game, player_index, answer| {
+                if answer.option() == 0 {
+                    return game.receive(player_index, boon);
+                }
+                // The Boon waits in place of a Duration card, so nothing is kept in play
+                game.at_start_of_turn(player_index, boon, move |game| {
+                    game.receive(player_index, boon)
+                });
+                Ok(())
+            },
+        );
+        Ok(())
+    });
+}
+
+fn cemetery(game: &mut Game, player_index: usize, source: Card) {
+    trash_chosen(game, player_index, source, 4);
+}
+
+fn conclave(game: &mut Game, source: Card) -> Result<(), GameError> {
+    play_new_action(game, source, true);
+    Ok(())
+}
+
+fn crypt(game: &mut Game, source: Card) -> Result<(), GameError> {
+    crypt_set_aside(game, source, Vec::new());
+    Ok(())
+}
+
+/// Has the current player set aside Treasures from play one at a time, then
+/// schedules them coming back.
+fn crypt_set_aside(game: &mut Game, source: Card, set_aside: Vec<Card>) {
+    let owner = game.curr_player_index;
+    let candidates: Vec<Card> = distinct(game.players[owner].played.iter().copied())
+        .into_iter()
+        .filter(|card| card.is(CardType::Treasure) && !card.is(CardType::Duration))
+        .collect();
+    if candidates.is_empty() {
+        crypt_return(game, owner, source, set_aside);
+        return;
+    }
+    let mut choices: Vec<String> = candidates
+        .iter()
+        .map(|card| format!("Set aside {}", card.name()))
+        .collect();
+    choices.push("Don't set aside any more".to_owned());
+    game.ask(
+        owner,
+        source,
+        Prompt::Options { options: choices },
+        move |game, player_index, answer| {
+            let mut set_aside = set_aside;
+            match candidates.get(answer.option()) {
+                Some(&treasure) => {
+                    if let Some(treasure) = game.players[player_index].take_from_play(treasure) {
+                        set_aside.push(treasure);
+                    }
+                    crypt_set_aside(game, source, set_aside);
+                }
+                None => crypt_return(game, player_index, source, set_aside),
+            }
+            Ok(())
+        },
+    );
+}
+
+/// Puts one of Crypt's Treasures into its owner's hand at the start of their
+/// next turn, keeping Crypt in play until the last one is taken.
+fn crypt_return(game: &mut Game, player_index: usize, source: Card, set_aside: Vec<Card>) {
+    if set_aside.is_empty() {
+        return;
+    }
+    game.at_start_of_turn(player_index, source, move |game| {
+        let candidates = distinct(set_aside.iter().copied());
+        game.ask(
+            player_index,
+            source,
+            Prompt::Options {
+                options: candidates
+                    .iter()
+                    .map(|card| format!("Put {} into your hand", card.name()))
+                    .collect(),
+            },
+            move |game, player_index, answer| {
+                let mut set_aside = set_aside;
+                let chosen = candidates[answer.option()];
+                let index = set_aside.iter().position(|&card| card == chosen).unwrap();
+                game.players[player_index].add_to_hand(set_aside.remove(index));
+                crypt_return(game, player_index, source, set_aside);
+                Ok(())
+            },
+        );
+        Ok(())
+    });
+}
+
+fn devils_workshop(game: &mut Game, source: Card) -> Result<(), GameError> {
+    let player_index = game.curr_player_index;
+    match game.history.current_turn.gained_by(player_index).count() {
+        0 => game.gain_if_available(player_index, card("Gold"), GainDestination::Discard)?,
+        1 => game.ask(
+            player_index,
+            source,
+            gain_from_supply(Cost::coins(4), CardFilter::Any),
+            |game, player_index, answer| {
+                game.gain_card(player_index, answer.supply_card(), GainDestination::Discard)
+            },
+        ),
+        _ => game.gain_if_available(player_index, card("Imp"), GainDestination::Discard)?,
+    }
+    Ok(())
+}
+
+fn exorcist(game: &mut Game, source: Card) -> Result<(), GameError> {
+    game.ask(
+        game.curr_player_index,
+        source,
+        from_hand(1, 1),
+        move |game, player_index, answer| {
+            for trashed in answer.cards() {
+                let limit = game.cost_of(trashed).coins;
+                game.trash_card(player_index, trashed);
+                let spirits: Vec<Card> = ["Will-o'-Wisp", "Imp", "Ghost"]
+                    .into_iter()
+                    .map(card)
+                    .filter(|&spirit| game.cost_of(spirit).coins < limit)
+                    .filter(|&spirit| game.supply.count(spirit).unwrap_or(0) > 0)
+                    .collect();
+                game.ask(
+                    player_index,
+                    source,
+                    Prompt::Options {
+                        options: spirits
+                            .iter()
+                            .map(|spirit| format!("Gain {}", spirit.name()))
+                            .collect(),
+                    },
+                    move |game, player_index, answer| {
+                        let spirit = spirits[answer.option()];
+                        game.gain_card(player_index, spirit, GainDestination::Discard)
+                    },
+                );
+            }
+            Ok(())
+        },
+    );
+    Ok(())
+}
+
+fn necromancer(game: &mut Game, source: Card) -> Result<(), GameError> {
+    let trash = &game.trash;
+    let face_up = |card: &Card| {
+        let copies = |cards: &[Card]| cards.iter().filter(|&c| c == card).count();
+        copies(&trash.cards) > copies(&trash.face_down)
+    };
+    let candidates: Vec<Card> = distinct(trash.cards.iter().copied())
+        .into_iter()
+        .filter(|card| card.is(CardType::Action) && !card.is(CardType::Duration))
+        .filter(face_up)
+        .collect();
+    choose_to_play(game, source, candidates, |game, chosen| {
+        game.trash.face_down.push(chosen);
+        game.resolve_play(chosen)
+    });
+    Ok(())
+}
+
+fn shepherd(game: &mut Game, source: Card) -> Result<(), GameError> {
+    game.ask(
+        game.curr_player_index,
+        source,
+        Prompt::CardsFromHand {
+            min: 0,
+            max: usize::MAX,
+            filter: CardFilter::OfType(CardType::Victory),
+        },
+        |game, player_index, answer| {
+            let discarded = answer.cards();
+            let player = &mut game.players[player_index];
+            player.draw(2 * discarded.len());
+            player.discard.extend(discarded);
+            Ok(())
+        },
+    );
+    Ok(())
+}
+
+fn skulk(game: &mut Game, _source: Card) -> Result<(), GameError> {
+    let attacked = game.attacked_player_indices();
+    game.receive_hex(&attacked)
+}
+
+fn skulk_on_gain(game: &mut Game, player_index: usize, _card: Card) {
+    game.then(move |game| {
+        game.gain_if_available(player_index, card("Gold"), GainDestination::Discard)
+    });
+}
+
+fn cobbler(game: &mut Game, source: Card) -> Result<(), GameError> {
+    next_turn(game, source, move |game, player_index| {
+        game.ask(
+            player_index,
+            source,
+            gain_from_supply(Cost::coins(4), CardFilter::Any),
+            |game, player_index, answer| {
+                game.gain_card(player_index, answer.supply_card(), GainDestination::Hand)
+            },
+        );
+        Ok(())
+    });
+    Ok(())
+}
+
+fn cursed_village(game: &mut Game, _source: Card) -> Result<(), GameError> {
+    let player = game.current_player();
+    let missing = 6usize.saturating_sub(player.hand.len());
+    player.draw(missing);
+    Ok(())
+}
+
+fn cursed_village_on_gain(game: &mut Game, player_index: usize, _card: Card) {
+    game.then(move |game| game.receive_hex(&[player_index]));
+}
+
+fn den_of_sin(game: &mut Game, source: Card) -> Result<(), GameError> {
+    next_turn(game, source, |game, player_index| {
+        game.players[player_index].draw(2);
+        Ok(())
+    });
+    Ok(())
+}
+
+fn idol(game: &mut Game, source: Card) -> Result<(), GameError> {
+    let idols = game
+        .current_player_read_only()
+        .played
+        .iter()
+        .filter(|&&played| played == source)
+        .count();
+    if idols % 2 == 1 {
+        game.receive_boon(game.curr_player_index)?;
+    } else {
+        for player_index in game.attacked_player_indices() {
+            game.gain_if_available(player_index, card("Curse"), GainDestination::Discard)?;
+        }
+    }
+    Ok(())
+}
+
+fn pooka(game: &mut Game, source: Card) -> Result<(), GameError> {
+    let treasures: Vec<Card> = distinct(game.current_player_read_only().hand.iter().copied())
+        .into_iter()
+        .filter(|card| card.is(CardType::Treasure) && card.name() != "Cursed Gold")
+        .collect();
+    if treasures.is_empty() {
+        return Ok(());
+    }
+    let mut choices: Vec<String> = treasures
+        .iter()
+        .map(|card| format!("Trash {} for +4 Cards", card.name()))
+        .collect();
+    choices.push("Don't trash anything".to_owned());
+    game.ask(
+        game.curr_player_index,
+        source,
+        Prompt::Options { options: choices },
+        move |game, player_index, answer| {
+            let Some(&treasure) = treasures.get(answer.option()) else {
+                return Ok(());
+            };
+            let player = &mut game.players[player_index];
+            let index = player
+                .hand
+                .iter()
+                .position(|&card| card == treasure)
+                .unwrap();
+            player.hand.remove(index);
+            game.trash_card(player_index, treasure);
+            game.players[player_index].draw(4);
+            Ok(())
+        },
+    );
+    Ok(())
+}
+
+fn sacred_grove(game: &mut Game, source: Card) -> Result<(), GameError> {
+    let Some(boon) = game.receive_boon(game.curr_player_index)? else {
+        return Ok(());
+    };
+    if matches!(boon.name(), "The Field's Gift" | "The Forest's Gift") {
+        return Ok(());
+    }
+    let others = (1..game.players.len())
+        .map(|offset| (game.curr_player_index + offset) % game.players.len())
+        .collect::<Vec<_>>();
+    for player_index in others {
+        game.ask(
+            player_index,
+            source,
+            Prompt::Options {
+                options: vec![
+                    format!("Receive {}", boon.name()),
+                    "Don't receive it".to_owned(),
+                ],
+            },
+            move |game, player_index, answer| {
+                if answer.option() == 0 {
+                    game.receive(player_index, boon)?;
+                }
+                Ok(())
+            },
+        );
+    }
+    Ok(())
+}
+
+fn tormentor(game: &mut Game, _source: Card) -> Result<(), GameError> {
+    if game.current_player_read_only().played.len() == 1 {
+        game.gain_if_available(
+            game.curr_player_index,
+            card("Imp"),
+            GainDestination::Discard,
+        )
+    } else {
+        let attacked = game.attacked_player_indices();
+        game.receive_hex(&attacked)
+    }
+}
+
+fn tragic_hero(game: &mut Game, source: Card) -> Result<(), GameError> {
+    let player_index = game.curr_player_index;
+    if game.current_player_read_only().hand.len() < 8 {
+        return Ok(());
+    }
+    let Some(hero) = game.current_player().take_from_play(source) else {
+        return Ok(());
+    };
+    game.trash_card(player_index, hero);
+    let any_cost = Cost {
+        coins: u32::MAX,
+        potions: u32::MAX,
+        debt: u32::MAX,
+    };
+    game.ask(
+        player_index,
+        source,
+        gain_from_supply(any_cost, CardFilter::OfType(CardType::Treasure)),
+        |game, player_index, answer| {
+            game.gain_card(player_index, answer.supply_card(), GainDestination::Discard)
+        },
+    );
+    Ok(())
+}
+
+fn vampire(game: &mut Game, source: Card) -> Result<(), GameError> {
+    let attacked = game.attacked_player_indices();
+    game.receive_hex(&attacked)?;
+    game.ask(
+        game.curr_player_index,
+        source,
+        gain_from_supply(Cost::coins(5), CardFilter::NotNamed("Vampire")),
+        |game, player_index, answer| {
+            game.gain_card(player_index, answer.supply_card(), GainDestination::Discard)
+        },
+    );
+    game.then(move |game| exchange(game, source, card("Bat")));
+    Ok(())
+}
+
+fn werewolf(game: &mut Game, _source: Card) -> Result<(), GameError> {
+    if matches!(game.game_phase, GamePhase::NightPhase) {
+        let attacked = game.attacked_player_indices();
+        game.receive_hex(&attacked)
+    } else {
+        game.current_player().draw(3);
+        Ok(())
+    }
+}
+
+fn raider(game: &mut Game, source: Card) -> Result<(), GameError> {
+    let in_play = distinct(game.current_player_read_only().played.iter().copied());
+    for player_index in game.attacked_player_indices() {
+        let hand = &game.players[player_index].hand;
+        if hand.len() < 5 {
+            continue;
+        }
+        let copies: Vec<Card> = distinct(hand.iter().copied())
+            .into_iter()
+            .filter(|card| in_play.contains(card))
+            .collect();
+        game.ask(
+            player_index,
+            source,
+            Prompt::Options {
+                options: copies
+                    .iter()
+                    .map(|card| format!("Discard {}", card.name()))
+                    .collect(),
+            },
+            move |game, player_index, answer| {
+                let copy = copies[answer.option()];
+                let player = &mut game.players[player_index];
+                let index = player.hand.iter().position(|&card| card == copy).unwrap();
+                let discarded = player.hand.remove(index);
+                player.add_to_discard(discarded);
+                Ok(())
+            },
+        );
+    }
+    next_turn(game, source, |game, player_index| {
+        game.players[player_index].coins += 3;
+        Ok(())
+    });
+    Ok(())
+}
+
+fn haunted_mirror(game: &mut Game, player_index: usize, source: Card) {
+    game.ask(
+        player_index,
+        source,
+        Prompt::CardsFromHand {
+            min: 0,
+            max: 1,
+            filter: CardFilter::OfType(CardType::Action),
+        },
+        |game, player_index, answer| {
+            for discarded in answer.cards() {
+                game.players[player_index].add_to_discard(discarded);
+                game.gain_if_available(player_index, card("Ghost"), GainDestination::Discard)?;
+            }
+            Ok(())
+        },
+    );
+}
+
+fn magic_lamp(game: &mut Game, source: Card) -> Result<(), GameError> {
+    let played = &game.current_player_read_only().played;
+    let singles = distinct(played.iter().copied())
+        .into_iter()
+        .filter(|&card| played.iter().filter(|&&other| other == card).count() == 1)
+        .count();
+    if singles < 6 {
+        return Ok(());
+    }
+    let player_index = game.curr_player_index;
+    let Some(lamp) = game.current_player().take_from_play(source) else {
+        return Ok(());
+    };
+    game.trash_card(player_index, lamp);
+    for _ in 0..3 {
+        game.gain_if_available(player_index, card("Wish"), GainDestination::Discard)?;
+    }
+    Ok(())
+}
+
+fn goat(game: &mut Game, source: Card) -> Result<(), GameError> {
+    trash_chosen(game, game.curr_player_index, source, 1);
+    Ok(())
+}
+
+fn pasture(player: &Player) -> i32 {
+    player
+        .all_cards()
+        .filter(|card| card.name() == "Estate")
+        .count() as i32
+}
+
+fn cursed_gold(game: &mut Game, _source: Card) -> Result<(), GameError> {
+    game.gain_if_available(
+        game.curr_player_index,
+        card("Curse"),
+        GainDestination::Discard,
+    )
+}
+
+fn lucky_coin(game: &mut Game, _source: Card) -> Result<(), GameError> {
+    game.gain_if_available(
+        game.curr_player_index,
+        card("Silver"),
+        GainDestination::Discard,
+    )
+}
+
+fn will_o_wisp(game: &mut Game, _source: Card) -> Result<(), GameError> {
+    let Some(top) = game.current_player().take_from_deck() else {
+        return Ok(());
+    };
+    let cheap = game.cost_of(top).fits_within(&Cost::coins(2));
+    let player = game.current_player();
+    if cheap {
+        player.add_to_hand(top);
+    } else {
+        player.put_on_deck(top);
+    }
+    Ok(())
+}
+
+fn imp(game: &mut Game, source: Card) -> Result<(), GameError> {
+    play_new_action(game, source, false);
+    Ok(())
+}
+
+fn ghost(game: &mut Game, source: Card) -> Result<(), GameError> {
+    let player_index = game.curr_player_index;
+    let (action, revealed) = reveal_until(game, player_index, |_, card| card.is(CardType::Action));
+    game.players[player_index].discard.extend(revealed);
+    let Some(action) = action else {
+        return Ok(());
+    };
+    next_turn(game, source, move |game, _| {
+        game.play_card(action)?;
+        game.resolve_play(action)?;
+        game.then(move |game| game.resolve_play(action));
+        Ok(())
+    });
+    Ok(())
+}
+
+fn bat(game: &mut Game, source: Card) -> Result<(), GameError> {
+    game.ask(
+        game.curr_player_index,
+        source,
+        from_hand(0, 2),
+        move |game, player_index, answer| {
+            let trashed = answer.cards();
+            if trashed.is_empty() {
+                return Ok(());
+            }
+            for card in trashed {
+                game.trash_card(player_index, card);
+            }
+            exchange(game, source, card("Vampire"))
+        },
+    );
+    Ok(())
+}
+
+fn wish(game: &mut Game, source: Card) -> Result<(), GameError> {
+    let Some(wish) = game.current_player().take_from_play(source) else {
+        return Ok(());
+    };
+    game.supply.return_card(wish)?;
+    game.ask(
+        game.curr_player_index,
+        source,
+        gain_from_supply(Cost::coins(6), CardFilter::Any),
+        |game, player_index, answer| {
+            game.gain_card(player_index, answer.supply_card(), GainDestination::Hand)
+        },
+    );
+    Ok(())
+}
+
+// ZOMBIES
+
+fn zombie_apprentice(game: &mut Game, source: Card) -> Result<(), GameError> {
+    game.ask(
+        game.curr_player_index,
+        source,
+        Prompt::CardsFromHand {
+            min: 0,
+            max: 1,
+            filter: CardFilter::OfType(CardType::Action),
+        },
+        |game, player_index, answer| {
+            for trashed in answer.cards() {
+                game.trash_card(player_index, trashed);
+                let player = &mut game.players[player_index];
+                player.draw(3);
+                player.actions += 1;
+            }
+            Ok(())
+        },
+    );
+    Ok(())
+}
+
+fn zombie_mason(game: &mut Game, source: Card) -> Result<(), GameError> {
+    let owner = game.curr_player_index;
+    let Some(top) = game.players[owner].take_from_deck() else {
+        return Ok(());
+    };
+    let max_cost = game.cost_of(top).plus_coins(1);
+    game.trash_card(owner, top);
+    game.ask(
+        owner,
+        source,
+        options(&["Gain a card", "Don't gain a card"]),
+        move |game, player_index, answer| {
+            if answer.option() == 0 {
+                game.ask(
+                    player_index,
+                    source,
+                    gain_from_supply(max_cost, CardFilter::Any),
+                    |game, player_index, answer| {
+                        game.gain_card(player_index, answer.supply_card(), GainDestination::Discard)
+                    },
+                );
+            }
+            Ok(())
+        },
+    );
+    Ok(())
+}
+
+fn zombie_spy(game: &mut Game, source: Card) -> Result<(), GameError> {
+    sift(game, game.curr_player_index, source, 1);
+    Ok(())
+}
+
+// BOONS
+
+fn earths_gift(game: &mut Game, player_index: usize, boon: Card) -> Result<(), GameError> {
+    game.ask(
+        player_index,
+        boon,
+        Prompt::CardsFromHand {
+            min: 0,
+            max: 1,
+            filter: CardFilter::OfType(CardType::Treasure),
+        },
+        move |game, player_index, answer| {
+            for discarded in answer.cards() {
+                game.players[player_index].add_to_discard(discarded);
+                game.ask(
+                    player_index,
+                    boon,
+                    gain_from_supply(Cost::coins(4), CardFilter::Any),
+                    |game, player_index, answer| {
+                        game.gain_card(player_index, answer.supply_card(), GainDestination::Discard)
+                    },
+                );
+            }
+            Ok(())
+        },
+    );
+    Ok(())
+}
+
+fn fields_gift(game: &mut Game, player_index: usize, _boon: Card) -> Result<(), GameError> {
+    let player = &mut game.players[player_index];
+    player.actions += 1;
+    player.coins += 1;
+    Ok(())
+}
+
+fn flames_gift(game: &mut Game, player_index: usize, boon: Card) -> Result<(), GameError> {
+    trash_chosen(game, player_index, boon, 1);
+    Ok(())
+}
+
+fn forests_gift(game: &mut Game, player_index: usize, _boon: Card) -> Result<(), GameError> {
+    let player = &mut game.players[player_index];
+    player.buys += 1;
+    player.coins += 1;
+    Ok(())
+}
+
+fn moons_gift(game: &mut Game, player_index: usize, boon: Card) -> Result<(), GameError> {
+    let discarded = distinct(game.players[player_index].discard.iter().copied());
+    if discarded.is_empty() {
+        return Ok(());
+    }
+    let mut choices: Vec<String> = discarded
+        .iter()
+        .map(|card| format!("Put {} onto your deck", card.name()))
+        .collect();
+    choices.push("Leave your discard pile alone".to_owned());
+    game.ask(
+        player_index,
+        boon,
+        Prompt::Options { options: choices },
+        move |game, player_index, answer| {
+            let Some(&chosen) = discarded.get(answer.option()) else {
+                return Ok(());
+            };
+            let player = &mut game.players[player_index];
+            if let Some(index) = player.discard.iter().rposition(|&card| card == chosen) {
+                let card = player.discard.remove(index);
+                player.put_on_deck(card);
+            }
+            Ok(())
+        },
+    );
+    Ok(())
+}
+
+fn mountains_gift(game: &mut Game, player_index: usize, _boon: Card) -> Result<(), GameError> {
+    game.gain_if_available(player_index, card("Silver"), GainDestination::Discard)
+}
+
+fn rivers_gift(game: &mut Game, player_index: usize, _boon: Card) -> Result<(), GameError> {
+    // Only the current player still has a hand to draw at the end of this turn
+    let player = &mut game.players[player_index];
+    if player_index == game.curr_player_index {
+        player.next_hand_bonus += 1;
+    } else {
+        player.draw(1);
+    }
+    Ok(())
+}
+
+fn seas_gift(game: &mut Game, player_index: usize, _boon: Card) -> Result<(), GameError> {
+    game.players[player_index].draw(1);
+    Ok(())
+}
+
+fn skys_gift(game: &mut Game, player_index: usize, boon: Card) -> Result<(), GameError> {
+    if game.players[player_index].hand.len() < 3 {
+        return Ok(());
+    }
+    game.ask(
+        player_index,
+        boon,
+        options(&["Discard 3 cards to gain a Gold", "Don't discard"]),
+        move |game, player_index, answer| {
+            if answer.option() == 0 {
+                discard_chosen(game, player_index, boon, 3);
+                game.then(move |game| {
+                    game.gain_if_available(player_index, card("Gold"), GainDestination::Discard)
+                });
+            }
+            Ok(())
+        },
+    );
+    Ok(())
+}
+
+fn suns_gift(game: &mut Game, player_index: usize, boon: Card) -> Result<(), GameError> {
+    sift(game, player_index, boon, 4);
+    Ok(())
+}
+
+fn swamps_gift(game: &mut Game, player_index: usize, _boon: Card) -> Result<(), GameError> {
+    game.gain_if_available(player_index, card("Will-o'-Wisp"), GainDestination::Discard)
+}
+
+fn winds_gift(game: &mut Game, player_index: usize, boon: Card) -> Result<(), GameError> {
+    game.players[player_index].draw(2);
+    discard_chosen(game, player_index, boon, 2);
+    Ok(())
+}
+
+// HEXES
+
+fn bad_omens(game: &mut Game, player_index: usize, _hex: Card) -> Result<(), GameError> {
+    let player = &mut game.players[player_index];
+    let deck = std::mem::take(&mut player.deck);
+    player.discard.extend(deck);
+    for _ in 0..2 {
+        if let Some(index) = player
+            .discard
+            .iter()
+            .rposition(|card| card.name() == "Copper")
+        {
+            let copper = player.discard.remove(index);
+            player.put_on_deck(copper);
+        }
+    }
+    Ok(())
+}
+
+/// Takes a State unless the player already has Deluded or Envious.
+fn take_delusion_state(game: &mut Game, player_index: usize, state: &str) {
+    let player = &mut game.players[player_index];
+    if !player.has_state("Deluded") && !player.has_state("Envious") {
+        player.states.push(card(state));
+    }
+}
+
+fn delusion(game: &mut Game, player_index: usize, _hex: Card) -> Result<(), GameError> {
+    take_delusion_state(game, player_index, "Deluded");
+    Ok(())
+}
+
+fn envy(game: &mut Game, player_index: usize, _hex: Card) -> Result<(), GameError> {
+    take_delusion_state(game, player_index, "Envious");
+    Ok(())
+}
+
+fn famine(game: &mut Game, player_index: usize, _hex: Card) -> Result<(), GameError> {
+    let player = &mut game.players[player_index];
+    for card in player.reveal_from_deck(3) {
+        if card.is(CardType::Action) {
+            player.add_to_discard(card);
+        } else {
+            player.put_on_deck(card);
+        }
+    }
+    player.shuffle_deck();
+    Ok(())
+}
+
+fn fear(game: &mut Game, player_index: usize, hex: Card) -> Result<(), GameError> {
+    let hand = &game.players[player_index].hand;
+    if hand.len() < 5 {
+        return Ok(());
+    }
+    let candidates: Vec<Card> = distinct(hand.iter().copied())
+        .into_iter()
+        .filter(|card| card.is(CardType::Action) || card.is(CardType::Treasure))
+        .collect();
+    game.ask(
+        player_index,
+        hex,
+        Prompt::Options {
+            options: candidates
+                .iter()
+                .map(|card| format!("Discard {}", card.name()))
+                .collect(),
+        },
+        move |game, player_index, answer| {
+            let chosen = candidates[answer.option()];
+            let player = &mut game.players[player_index];
+            let index = player.hand.iter().position(|&card| card == chosen).unwrap();
+            let discarded = player.hand.remove(index);
+            player.add_to_discard(discarded);
+            Ok(())
+        },
+    );
+    Ok(())
+}
+
+fn greed(game: &mut Game, player_index: usize, _hex: Card) -> Result<(), GameError> {
+    game.gain_if_available(player_index, card("Copper"), GainDestination::Deck)
+}
+
+fn haunting(game: &mut Game, player_index: usize, hex: Card) -> Result<(), GameError> {
+    if game.players[player_index].hand.len() < 4 {
+        return Ok(());
+    }
+    game.ask(
+        player_index,
+        hex,
+        from_hand(1, 1),
+        |game, player_index, answer| {
+            for card in answer.cards() {
+                game.players[player_index].put_on_deck(card);
+            }
+            Ok(())
+        },
+    );
+    Ok(())
+}
+
+fn locusts(game: &mut Game, player_index: usize, hex: Card) -> Result<(), GameError> {
+    let Some(top) = game.players[player_index].take_from_deck() else {
+        return Ok(());
+    };
+    game.trash_card(player_index, top);
+    if matches!(top.name(), "Copper" | "Estate") {
+        return game.gain_if_available(player_index, card("Curse"), GainDestination::Discard);
+    }
+    let limit = game.cost_of(top);
+    let cheaper: Vec<Card> = game
+        .supply
+        .available_cards()
+        .into_iter()
+        .filter(|&card| {
+            let cost = game.cost_of(card);
+            cost.fits_within(&limit) && cost != limit
+        })
+        .filter(|card| top.info().types.iter().any(|&card_type| card.is(card_type)))
+        .collect();
+    game.ask(
+        player_index,
+        hex,
+        Prompt::Options {
+            options: cheaper
+                .iter()
+                .map(|card| format!("Gain {}", card.name()))
+                .collect(),
+        },
+        move |game, player_index, answer| {
+            game.gain_card(
+                player_index,
+                cheaper[answer.option()],
+                GainDestination::Discard,
+            )
+        },
+    );
+    Ok(())
+}
+
+fn misery(game: &mut Game, player_index: usize, _hex: Card) -> Result<(), GameError> {
+    let player = &mut game.players[player_index];
+    if player.return_state("Miserable") {
+        player.states.push(card("Twice Miserable"));
+    } else if !player.has_state("Twice Miserable") {
+        player.states.push(card("Miserable"));
+    }
+    Ok(())
+}
+
+fn plague(game: &mut Game, player_index: usize, _hex: Card) -> Result<(), GameError> {
+    game.gain_if_available(player_index, card("Curse"), GainDestination::Hand)
+}
+
+fn poverty(game: &mut Game, player_index: usize, hex: Card) -> Result<(), GameError> {
+    let excess = game.players[player_index].hand.len().saturating_sub(3);
+    discard_chosen(game, player_index, hex, excess);
+    Ok(())
+}
+
+fn war(game: &mut Game, player_index: usize, _hex: Card) -> Result<(), GameError> {
+    let (found, revealed) = reveal_until(game, player_index, |game, card| {
+        let cost = game.cost_of(card);
+        cost.fits_within(&Cost::coins(4)) && cost.coins >= 3
+    });
+    if let Some(card) = found {
+        game.trash_card(player_index, card);
+    }
+    game.players[player_index].discard.extend(revealed);
+    Ok(())
+}
+
+// STATES
+
+fn lost_in_the_woods(game: &mut Game, state: Card, event: Event) {
+    let Event::TurnStart { player_index } = event else {
+        return;
+    };
+    if !game.players[player_index].states.contains(&state) {
+        return;
+    }
+    game.ask(
+        player_index,
+        state,
+        from_hand(0, 1),
+        |game, player_index, answer| {
+            let discarded = answer.cards();
+            if !discarded.is_empty() {
+                discard_all(game, player_index, discarded);
+                game.receive_boon(player_index)?;
+            }
+            Ok(())
+        },
+    );
+}
+
+fn deluded(game: &mut Game, _state: Card, event: Event) {
+    if let Event::BuyPhaseStart { player_index } = event {
+        if game.players[player_index].return_state("Deluded") {
+            game.buy_restrictions
+                .push(CardFilter::OfType(CardType::Action));
+        }
+    }
+}
+
+fn envious(game: &mut Game, _state: Card, event: Event) {
+    let Event::BuyPhaseStart {
+        player_index: owner,
+    } = event
+    else {
+        return;
+    };
+    if !game.players[owner].return_state("Envious") {
+        return;
+    }
+    game.add_trigger(Expiry::EndOfTurn, move |game, event| {
+        if let Event::Play { player_index, card } = event {
+            let lost = match card.name() {
+                "Silver" => 1,
+                "Gold" => 2,
+                _ => 0,
+            };
+            // The Treasure's coins are added once it has resolved
+            if player_index == owner && lost > 0 {
+                game.then(move |game| {
+                    let player = &mut game.players[owner];
+                    player.coins = player.coins.saturating_sub(lost);
+                    Ok(())
+                });
+            }
+        }
+        Ok(true)
+    });
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::cards::testing::{end_turn, play, scenario};
+    use crate::{GameMove, GamePhase};
+
+    const KINGDOM: [&str; 10] = [
+        "Druid",
+        "Monastery",
+        "Night Watchman",
+        "Bard",
+        "Conclave",
+        "Ghost Town",
+        "Leprechaun",
+        "Skulk",
+        "Cobbler",
+        "Den of Sin",
+    ];
+
+    #[test]
+    fn bard_receives_the_next_boon() {
+        let mut game = scenario(&KINGDOM, [&["Bard"], &[]]);
+        game.supply.boons.cards = vec![card("The Mountain's Gift")];
+
+        play(&mut game, "Bard");
+
+        let player = game.current_player_read_only();
+        assert_eq!(player.coins, 2);
+        assert_eq!(player.discard, [card("Silver")]);
+        assert!(game.supply.boons.cards.is_empty());
+        assert_eq!(game.supply.boons.discard, [card("The Mountain's Gift")]);
+    }
+
+    #[test]
+    fn skulk_gives_each_other_player_the_next_hex() {
+        let mut game = scenario(&KINGDOM, [&["Skulk"], &[]]);
+        let other = (game.curr_player_index + 1) % 2;
+        game.supply.hexes.cards = vec![card("Greed")];
+
+        play(&mut game, "Skulk");
+
+        assert_eq!(game.players[other].deck.last(), Some(&card("Copper")));
+        assert_eq!(game.supply.hexes.discard, [card("Greed")]);
+        assert!(game.current_player_read_only().discard.is_empty());
+    }
+
+    #[test]
+    fn night_cards_are_played_in_the_night_phase() {
+        let mut game = scenario(&KINGDOM, [&["Ghost Town"], &[]]);
+        let player_index = game.curr_player_index;
+        let play_ghost_town = GameMove::PlayCard {
+            card_index: 0,
+            way: None,
+        };
+        assert!(!game.legal_moves().contains(&play_ghost_town));
+
+        for game_move in [
+            GameMove::EndActions,
+            GameMove::EndTreasures,
+            GameMove::EndBuys,
+        ] {
+            game.accept_move(player_index, game_move).unwrap();
+        }
+        assert!(matches!(game.game_phase, GamePhase::NightPhase));
+        assert!(game.legal_moves().contains(&play_ghost_town));
+    }
+
+    #[test]
+    fn ghost_town_gives_a_card_and_an_action_next_turn() {
+        let mut game = scenario(&KINGDOM, [&["Ghost Town"], &[]]);
+        let owner = game.curr_player_index;
+        game.game_phase = GamePhase::NightPhase;
+        play(&mut game, "Ghost Town");
+
+        end_turn(&mut game);
+        end_turn(&mut game);
+
+        let player = &game.players[owner];
+        assert_eq!((player.hand.len(), player.actions), (6, 2));
+        assert_eq!(player.played, [card("Ghost Town")]);
+    }
+
+    #[test]
+    fn some_night_cards_are_gained_to_hand() {
+        let mut game = scenario(&KINGDOM, [&[], &[]]);
+        let player_index = game.curr_player_index;
+
+        game.gain_card(player_index, card("Ghost Town"), GainDestination::Discard)
+            .unwrap();
+
+        let player = game.current_player_read_only();
+        assert_eq!(player.hand, [card("Ghost Town")]);
+        assert!(player.discard.is_empty());
+    }
+}
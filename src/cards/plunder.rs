@@ -0,0 +1,2130 @@
+//! Behaviours for the Plunder kingdom cards, Events, Traits and Loot. A
+//! Trait's `on_gain` is called with each card gained from the pile it was put
+//! on, its `cost` applies to every card in that pile, and its `on_event` is
+//! told about everything else. Fated and Inherited are handled by the engine,
+//! at shuffles and at setup. Loot is gained from the top of the shuffled
+//! "Loot" pile.
+
+use std::mem::take;
+
+use super::{
+    before_clean_up, card, choose_to_play, discard_all, discard_down_to, distinct, from_discard_of,
+    from_hand, from_hand_of, gain_from_supply, gain_to_discard, next_turn, options, play_taken,
+    protects_from_hand, take_extra_turn, Behaviour, Card, CardFilter, CardType, Cost,
+};
+use crate::{Event, Expiry, GainDestination, Game, GameError, GamePhase, Player, Prompt};
+
+pub(super) fn behaviours() -> Vec<(&'static str, Behaviour)> {
+    let play = |on_play| Behaviour {
+        on_play: Some(on_play),
+        ..Behaviour::default()
+    };
+    let gain = |on_gain| Behaviour {
+        on_gain: Some(on_gain),
+        ..Behaviour::default()
+    };
+    let event = |on_buy| Behaviour {
+        on_buy: Some(on_buy),
+        ..Behaviour::default()
+    };
+    let rule = |on_event| Behaviour {
+        on_event: Some(on_event),
+        ..Behaviour::default()
+    };
+    vec![
+        ("Cage", play(cage)),
+        ("Grotto", play(grotto)),
+        (
+            "Jewelled Egg",
+            Behaviour {
+                on_trash: Some(jewelled_egg_on_trash),
+                ..Behaviour::default()
+            },
+        ),
+        ("Search", play(search)),
+        (
+            "Shaman",
+            Behaviour {
+                on_event: Some(shaman_on_event),
+                ..play(shaman)
+            },
+        ),
+        ("Secluded Shrine", play(secluded_shrine)),
+        (
+            "Siren",
+            Behaviour {
+                on_gain: Some(siren_on_gain),
+                ..play(siren)
+            },
+        ),
+        (
+            "Stowaway",
+            Behaviour {
+                on_gain_in_hand: Some(stowaway_reaction),
+                ..play(stowaway)
+            },
+        ),
+        ("Taskmaster", play(taskmaster)),
+        ("Abundance", play(abundance)),
+        ("Cabin Boy", play(cabin_boy)),
+        ("Crucible", play(crucible)),
+        ("Flagship", play(flagship)),
+        ("Fortune Hunter", play(fortune_hunter)),
+        (
+            "Gondola",
+            Behaviour {
+                on_gain: Some(gondola_on_gain),
+                ..play(gondola)
+            },
+        ),
+        ("Harbor Village", play(harbor_village)),
+        ("Landing Party", play(landing_party)),
+        (
+            "Mapmaker",
+            Behaviour {
+                on_gain_in_hand: Some(mapmaker_reaction),
+                ..play(mapmaker)
+            },
+        ),
+        ("Maroon", play(maroon)),
+        ("Rope", play(rope)),
+        ("Swamp Shacks", play(swamp_shacks)),
+        ("Tools", play(tools)),
+        (
+            "Buried Treasure",
+            Behaviour {
+                on_gain: Some(play_on_gain),
+                ..play(buried_treasure)
+            },
+        ),
+        ("Crew", play(crew)),
+        ("Cutthroat", play(cutthroat)),
+        ("Enlarge", play(enlarge)),
+        ("Figurine", play(figurine)),
+        ("First Mate", play(first_mate)),
+        ("Frigate", play(frigate)),
+        ("Longship", play(longship)),
+        ("Mining Road", play(mining_road)),
+        ("Pendant", play(pendant)),
+        ("Pickaxe", play(pickaxe)),
+        ("Pilgrim", play(pilgrim)),
+        ("Quartermaster", play(quartermaster)),
+        ("Silver Mine", play(silver_mine)),
+        ("Trickster", play(trickster)),
+        ("Wealthy Village", gain(wealthy_village_on_gain)),
+        ("Sack of Loot", play(sack_of_loot)),
+        ("King's Cache", play(kings_cache)),
+        // Events
+        ("Bury", event(bury)),
+        ("Avoid", event(avoid)),
+        ("Deliver", event(deliver)),
+        ("Peril", event(peril)),
+        ("Rush", event(rush)),
+        ("Foray", event(foray)),
+        ("Launch", event(launch)),
+        ("Mirror", event(mirror)),
+        ("Prepare", event(prepare)),
+        ("Scrounge", event(scrounge)),
+        ("Journey", event(journey)),
+        ("Maelstrom", event(maelstrom)),
+        ("Looting", event(looting)),
+        ("Invasion", event(invasion)),
+        ("Prosper", event(prosper)),
+        // Traits
+        (
+            "Cheap",
+            Behaviour {
+                cost: Some(cheap),
+                ..Behaviour::default()
+            },
+        ),
+        ("Cursed", gain(cursed)),
+        ("Fated", Behaviour::default()),
+        ("Fawning", rule(fawning)),
+        ("Friendly", rule(friendly)),
+        ("Hasty", gain(hasty)),
+        ("Inherited", Behaviour::default()),
+        ("Inspiring", rule(inspiring)),
+        ("Nearby", gain(nearby)),
+        ("Patient", rule(patient)),
+        ("Pious", gain(pious)),
+        ("Reckless", rule(reckless)),
+        ("Rich", gain(rich)),
+        ("Shy", rule(shy)),
+        ("Tireless", rule(tireless)),
+        // Loot
+        ("Amphora", play(amphora)),
+        ("Doubloons", gain(doubloons_on_gain)),
+        ("Endless Chalice", play(endless_chalice)),
+        ("Figurehead", play(figurehead)),
+        ("Hammer", play(hammer)),
+        ("Insignia", play(insignia)),
+        ("Jewels", play(jewels)),
+        ("Orb", play(orb)),
+        ("Prize Goat", play(prize_goat)),
+        ("Puzzle Box", play(puzzle_box)),
+        ("Sextant", play(sextant)),
+        (
+            "Shield",
+            Behaviour {
+                protects: Some(protects_from_hand),
+                ..Behaviour::default()
+            },
+        ),
+        ("Spell Scroll", play(spell_scroll)),
+        ("Staff", play(staff)),
+        ("Sword", play(sword)),
+    ]
+}
+
+fn trash_from_hand(game: &mut Game, player_index: usize, source: Card, min: usize) {
+    game.ask(
+        player_index,
+        source,
+        from_hand(min, 1),
+        |game, player_index, answer| {
+            for trashed in answer.cards() {
+                game.trash_card(player_index, trashed);
+            }
+            Ok(())
+        },
+    );
+}
+
+/// Gains the top card of the Loot pile, if there is one left.
+fn gain_loot(
+    game: &mut Game,
+    player_index: usize,
+    destination: GainDestination,
+) -> Result<(), GameError> {
+    match game.supply.top_of("Loot") {
+        Some(loot) => game.gain_card(player_index, loot, destination),
+        None => Ok(()),
+    }
+}
+
+/// Lets the current player play a card of the given type from their hand.
+fn offer_play_from_hand(game: &mut Game, source: Card, card_type: CardType) {
+    let candidates = game
+        .current_player_read_only()
+        .hand
+        .iter()
+        .copied()
+        .filter(|card| card.is(card_type))
+        .collect();
+    choose_to_play(game, source, candidates, |game, chosen| {
+        from_hand_of(game.current_player(), chosen);
+        play_taken(game, chosen)
+    });
+}
+
+/// For the rest of the turn, offers to put each card the player gains onto their deck.
+fn gains_onto_deck(game: &mut Game, owner: usize, source: Card) {
+    game.add_trigger(Expiry::EndOfTurn, move |game, event| {
+        if let Event::Gain { player_index, card } = event {
+            if player_index == owner {
+                game.ask(
+                    owner,
+                    source,
+                    Prompt::Options {
+                        options: vec![
+                            format!("Put {} onto your deck", card.name()),
+                            "Leave it where it is".to_owned(),
+                        ],
+                    },
+                    move |game, player_index, answer| {
+                        let player = &mut game.players[player_index];
+                        if answer.option() == 0 {
+                            if let Some(gained) = player.take_gained(card) {
+                                player.put_on_deck(gained);
+                            }
+                        }
+                        Ok(())
+                    },
+                );
+            }
+        }
+        Ok(true)
+    });
+}
+
+/// Any card at all, however much it costs.
+const ANY_COST: Cost = Cost {
+    coins: u32::MAX,
+    potions: u32::MAX,
+    debt: u32::MAX,
+};
+
+/// Takes back a card that may have stayed in play from an earlier turn, e.g.
+/// when it trashes itself.
+fn take_staying(player: &mut Player, card: Card) -> Option<Card> {
+    player.take_from_play(card).or_else(|| {
+        let index = player
+            .durations
+            .iter()
+            .rposition(|&staying| staying == card)?;
+        Some(player.durations.remove(index))
+    })
+}
+
+/// The cards a player has in play that are discarded at their clean-up,
+/// leaving out any Duration cards staying in play.
+fn leaving_play(game: &Game, player_index: usize) -> Vec<Card> {
+    let mut leaving = game.players[player_index].played.clone();
+    let staying = game
+        .durations
+        .iter()
+        .filter(|duration| duration.player_index == player_index)
+        .map(|duration| duration.source)
+        .chain(
+            game.waiting
+                .iter()
+                .filter(|&&(owner, _)| owner == player_index)
+                .map(|&(_, source)| source),
+        );
+    for card in staying {
+        if let Some(index) = leaving.iter().position(|&leaving| leaving == card) {
+            leaving.remove(index);
+        }
+    }
+    leaving
+}
+
+/// The cards from the pile a Trait is on.
+fn with_trait(game: &Game, trait_card: Card, cards: &[Card]) -> Vec<Card> {
+    cards
+        .iter()
+        .copied()
+        .filter(|&card| game.supply.traits_of(card).contains(&trait_card))
+        .collect()
+}
+
+fn cage(game: &mut Game, source: Card) -> Result<(), GameError> {
+    let owner = game.curr_player_index;
+    // With nothing to set aside there is no question to ask, but it still waits
+    if game.current_player_read_only().hand.is_empty() {
+        cage_wait(game, owner, source, Vec::new());
+        return Ok(());
+    }
+    game.ask(
+        owner,
+        source,
+        from_hand(0, 4),
+        move |game, owner, answer| {
+            cage_wait(game, owner, source, answer.cards());
+            Ok(())
+        },
+    );
+    Ok(())
+}
+
+fn cage_wait(game: &mut Game, owner: usize, source: Card, set_aside: Vec<Card>) {
+    let mut set_aside = Some(set_aside);
+    game.wait_for_next_time(owner, source, move |game, event| {
+        let Event::Gain { player_index, card } = event else {
+            return Ok(true);
+        };
+        if player_index != owner || !card.is(CardType::Victory) {
+            return Ok(true);
+        }
+        let set_aside = set_aside.take().unwrap_or_default();
+        game.then(move |game| {
+            if let Some(cage) = take_staying(&mut game.players[owner], source) {
+                game.trash_card(owner, cage);
+            }
+            game.after_turn(move |game| {
+                for card in set_aside {
+                    game.players[owner].add_to_hand(card);
+                }
+                Ok(())
+            });
+            Ok(())
+        });
+        Ok(false)
+    });
+}
+
+fn grotto(game: &mut Game, source: Card) -> Result<(), GameError> {
+    game.ask(
+        game.curr_player_index,
+        source,
+        from_hand(0, 4),
+        move |game, _, answer| {
+            let set_aside = answer.cards();
+            next_turn(game, source, move |game, player_index| {
+                let count = set_aside.len();
+                discard_all(game, player_index, set_aside);
+                game.players[player_index].draw(count);
+                Ok(())
+            });
+            Ok(())
+        },
+    );
+    Ok(())
+}
+
+fn jewelled_egg_on_trash(game: &mut Game, player_index: usize, _egg: Card) {
+    game.then(move |game| gain_loot(game, player_index, GainDestination::Discard));
+}
+
+fn search(game: &mut Game, source: Card) -> Result<(), GameError> {
+    let owner = game.curr_player_index;
+    let empty_piles = game.supply.num_empty_supply_piles();
+    game.wait_for_next_time(owner, source, move |game, event| {
+        if !matches!(event, Event::Gain { .. })
+            || game.supply.num_empty_supply_piles() <= empty_piles
+        {
+            return Ok(true);
+        }
+        game.then(move |game| {
+            if let Some(search) = take_staying(&mut game.players[owner], source) {
+                game.trash_card(owner, search);
+            }
+            gain_loot(game, owner, GainDestination::Discard)
+        });
+        Ok(false)
+    });
+    Ok(())
+}
+
+fn shaman(game: &mut Game, source: Card) -> Result<(), GameError> {
+    trash_from_hand(game, game.curr_player_index, source, 0);
+    Ok(())
+}
+
+fn shaman_on_event(game: &mut Game, shaman: Card, event: Event) {
+    let Event::TurnStart { player_index } = event else {
+        return;
+    };
+    let candidates = distinct(
+        game.trash
+            .cards
+            .iter()
+            .copied()
+            .filter(|&card| Game::cost_allowed(game.cost_of(card), &Cost::coins(6), false)),
+    );
+    game.ask(
+        player_index,
+        shaman,
+        Prompt::Options {
+            options: candidates
+                .iter()
+                .map(|card| format!("Gain {} from the trash", card.name()))
+                .collect(),
+        },
+        move |game, player_index, answer| {
+            game.gain_from_trash(
+                player_index,
+                candidates[answer.option()],
+                GainDestination::Discard,
+            )
+        },
+    );
+}
+
+fn secluded_shrine(game: &mut Game, source: Card) -> Result<(), GameError> {
+    let owner = game.curr_player_index;
+    game.wait_for_next_time(owner, source, move |game, event| {
+        let Event::Gain { player_index, card } = event else {
+            return Ok(true);
+        };
+        if player_index != owner || !card.is(CardType::Treasure) {
+            return Ok(true);
+        }
+        game.ask(
+            owner,
+            source,
+            from_hand(0, 2),
+            |game, player_index, answer| {
+                for trashed in answer.cards() {
+                    game.trash_card(player_index, trashed);
+                }
+                Ok(())
+            },
+        );
+        Ok(false)
+    });
+    Ok(())
+}
+
+fn siren(game: &mut Game, source: Card) -> Result<(), GameError> {
+    for victim in game.attacked_player_indices() {
+        game.gain_if_available(victim, card("Curse"), GainDestination::Discard)?;
+    }
+    next_turn(game, source, |game, player_index| {
+        let player = &mut game.players[player_index];
+        let missing = 8usize.saturating_sub(player.hand.len());
+        player.draw(missing);
+        Ok(())
+    });
+    Ok(())
+}
+
+fn siren_on_gain(game: &mut Game, player_index: usize, siren: Card) {
+    let has_action = game.players[player_index]
+        .hand
+        .iter()
+        .any(|card| card.is(CardType::Action));
+    if !has_action {
+        game.then(move |game| {
+            trash_gained(game, player_index, siren);
+            Ok(())
+        });
+        return;
+    }
+    game.ask(
+        player_index,
+        siren,
+        Prompt::CardsFromHand {
+            min: 0,
+            max: 1,
+            filter: CardFilter::OfType(CardType::Action),
+        },
+        move |game, player_index, answer| {
+            let trashed = answer.cards();
+            if trashed.is_empty() {
+                trash_gained(game, player_index, siren);
+            }
+            for card in trashed {
+                game.trash_card(player_index, card);
+            }
+            Ok(())
+        },
+    );
+}
+
+fn trash_gained(game: &mut Game, player_index: usize, gained: Card) {
+    if let Some(gained) = game.players[player_index].take_gained(gained) {
+        game.trash_card(player_index, gained);
+    }
+}
+
+fn stowaway(game: &mut Game, source: Card) -> Result<(), GameError> {
+    next_turn(game, source, |game, player_index| {
+        game.players[player_index].draw(2);
+        Ok(())
+    });
+    Ok(())
+}
+
+fn stowaway_reaction(game: &mut Game, holder: usize, stowaway: Card, _gainer: usize, gained: Card) {
+    if !gained.is(CardType::Duration) {
+        return;
+    }
+    game.ask(
+        holder,
+        stowaway,
+        options(&["Play Stowaway", "Don't play it"]),
+        move |game, holder, answer| {
+            let player = &mut game.players[holder];
+            if answer.option() == 1 || !player.hand.contains(&stowaway) {
+                return Ok(());
+            }
+            // Like Mapmaker, played on anyone's turn
+            from_hand_of(player, stowaway);
+            player.played.push(stowaway);
+            game.at_start_of_turn(holder, stowaway, move |game| {
+                game.players[holder].draw(2);
+                Ok(())
+            });
+            Ok(())
+        },
+    );
+}
+
+fn taskmaster(game: &mut Game, source: Card) -> Result<(), GameError> {
+    let owner = game.curr_player_index;
+    taskmaster_watch(game, owner, source);
+    Ok(())
+}
+
+/// Repeats Taskmaster's ability at the start of its owner's next turn if they
+/// gain a card costing exactly $5 this turn.
+fn taskmaster_watch(game: &mut Game, owner: usize, source: Card) {
+    game.add_trigger(Expiry::EndOfTurn, move |game, event| {
+        let Event::Gain { player_index, card } = event else {
+            return Ok(true);
+        };
+        if player_index != owner || game.cost_of(card) != Cost::coins(5) {
+            return Ok(true);
+        }
+        game.at_start_of_turn(owner, source, move |game| {
+            let player = &mut game.players[owner];
+            player.actions += 1;
+            player.coins += 1;
+            taskmaster_watch(game, owner, source);
+            Ok(())
+        });
+        Ok(false)
+    });
+}
+
+fn abundance(game: &mut Game, source: Card) -> Result<(), GameError> {
+    let owner = game.curr_player_index;
+    game.wait_for_next_time(owner, source, move |game, event| {
+        let Event::Gain { player_index, card } = event else {
+            return Ok(true);
+        };
+        if player_index != owner || !card.is(CardType::Action) {
+            return Ok(true);
+        }
+        // On someone else's turn the bonus goes to waste
+        if owner == game.curr_player_index {
+            let player = &mut game.players[owner];
+            player.buys += 1;
+            player.coins += 3;
+        }
+        Ok(false)
+    });
+    Ok(())
+}
+
+fn cabin_boy(game: &mut Game, source: Card) -> Result<(), GameError> {
+    next_turn(game, source, move |game, player_index| {
+        game.ask(
+            player_index,
+            source,
+            options(&["+$2", "Trash Cabin Boy to gain a Duration card"]),
+            move |game, player_index, answer| {
+                if answer.option() == 0 {
+                    game.players[player_index].coins += 2;
+                    return Ok(());
+                }
+                let Some(cabin_boy) = game.players[player_index].take_from_play(source) else {
+                    return Ok(());
+                };
+                game.trash_card(player_index, cabin_boy);
+                game.ask(
+                    player_index,
+                    source,
+                    gain_from_supply(ANY_COST, CardFilter::OfType(CardType::Duration)),
+                    |game, player_index, answer| {
+                        game.gain_card(player_index, answer.supply_card(), GainDestination::Discard)
+                    },
+                );
+                Ok(())
+            },
+        );
+        Ok(())
+    });
+    Ok(())
+}
+
+fn crucible(game: &mut Game, source: Card) -> Result<(), GameError> {
+    game.ask(
+        game.curr_player_index,
+        source,
+        from_hand(1, 1),
+        |game, player_index, answer| {
+            for trashed in answer.cards() {
+                let coins = game.cost_of(trashed).coins;
+                game.trash_card(player_index, trashed);
+                game.players[player_index].coins += coins;
+            }
+            Ok(())
+        },
+    );
+    Ok(())
+}
+
+fn flagship(game: &mut Game, source: Card) -> Result<(), GameError> {
+    let owner = game.curr_player_index;
+    game.wait_for_next_time(owner, source, move |game, event| {
+        let Event::Play { player_index, card } = event else {
+            return Ok(true);
+        };
+        if player_index != owner || !card.is(CardType::Action) || card.is(CardType::Command) {
+            return Ok(true);
+        }
+        replay_once_played(game, card);
+        Ok(false)
+    });
+    Ok(())
+}
+
+/// Follows a card's instructions again once they have been fully resolved,
+/// like Citadel.
+fn replay_once_played(game: &mut Game, card: Card) {
+    game.add_trigger(Expiry::EndOfTurn, move |game, event| {
+        if !matches!(event, Event::Played { card: played, .. } if played == card) {
+            return Ok(true);
+        }
+        game.then(move |game| game.resolve_play(card));
+        Ok(false)
+    });
+}
+
+fn fortune_hunter(game: &mut Game, source: Card) -> Result<(), GameError> {
+    let looked_at = game.current_player().reveal_from_deck(3);
+    let treasures: Vec<Card> = distinct(
+        looked_at
+            .iter()
+            .copied()
+            .filter(|card| card.is(CardType::Treasure) && card.is_implemented()),
+    );
+    if treasures.is_empty() {
+        put_back(game.current_player(), looked_at);
+        return Ok(());
+    }
+    let mut choices: Vec<String> = treasures
+        .iter()
+        .map(|card| format!("Play {}", card.name()))
+        .collect();
+    choices.push("Don't play anything".to_owned());
+    game.ask(
+        game.curr_player_index,
+        source,
+        Prompt::Options { options: choices },
+        move |game, _, answer| {
+            // The rest go back in the order they came off the deck
+            let mut rest = looked_at;
+            let chosen = treasures.get(answer.option()).copied();
+            if let Some(treasure) = chosen {
+                if let Some(index) = rest.iter().position(|&card| card == treasure) {
+                    rest.remove(index);
+                }
+            }
+            put_back(game.current_player(), rest);
+            match chosen {
+                Some(treasure) => play_taken(game, treasure),
+                None => Ok(()),
+            }
+        },
+    );
+    Ok(())
+}
+
+/// Puts cards taken from the top of the deck back in the same order.
+fn put_back(player: &mut Player, cards: Vec<Card>) {
+    for card in cards.into_iter().rev() {
+        player.put_on_deck(card);
+    }
+}
+
+fn gondola(game: &mut Game, source: Card) -> Result<(), GameError> {
+    game.ask(
+        game.curr_player_index,
+        source,
+        options(&["+$2 now", "+$2 at the start of your next turn"]),
+        move |game, player_index, answer| {
+            if answer.option() == 0 {
+                game.players[player_index].coins += 2;
+            } else {
+                next_turn(game, source, |game, player_index| {
+                    game.players[player_index].coins += 2;
+                    Ok(())
+                });
+            }
+            Ok(())
+        },
+    );
+    Ok(())
+}
+
+fn gondola_on_gain(game: &mut Game, player_index: usize, gondola: Card) {
+    // Only the current player can play cards
+    if player_index == game.curr_player_index {
+        offer_play_from_hand(game, gondola, CardType::Action);
+    }
+}
+
+fn harbor_village(game: &mut Game, _source: Card) -> Result<(), GameError> {
+    let owner = game.curr_player_index;
+    game.add_trigger(Expiry::EndOfTurn, move |game, event| {
+        let Event::Play { player_index, card } = event else {
+            return Ok(true);
+        };
+        if player_index != owner || !card.is(CardType::Action) {
+            return Ok(true);
+        }
+        let coins_before = game.players[owner].coins;
+        game.add_trigger(Expiry::EndOfTurn, move |game, event| {
+            if !matches!(event, Event::Played { card: played, .. } if played == card) {
+                return Ok(true);
+            }
+            let player = &mut game.players[owner];
+            if player.coins > coins_before {
+                player.coins += 1;
+            }
+            Ok(false)
+        });
+        Ok(false)
+    });
+    Ok(())
+}
+
+fn landing_party(game: &mut Game, source: Card) -> Result<(), GameError> {
+    let owner = game.curr_player_index;
+    game.wait_for_next_time(owner, source, move |game, event| {
+        let Event::Play { player_index, card } = event else {
+            return Ok(true);
+        };
+        let first = game.history.current_turn.played.len() == 1;
+        if player_index != owner || !first || !card.is(CardType::Treasure) {
+            return Ok(true);
+        }
+        game.add_trigger(Expiry::EndOfTurn, move |game, event| {
+            if !matches!(event, Event::Played { card: played, .. } if played == card) {
+                return Ok(true);
+            }
+            let player = &mut game.players[owner];
+            if let Some(landing_party) = take_staying(player, source) {
+                player.put_on_deck(landing_party);
+            }
+            Ok(false)
+        });
+        Ok(false)
+    });
+    Ok(())
+}
+
+fn mapmaker(game: &mut Game, _source: Card) -> Result<(), GameError> {
+    let player_index = game.curr_player_index;
+    map_out(game, player_index);
+    Ok(())
+}
+
+/// Mapmaker's effect for whoever played it, who may not be the current player.
+fn map_out(game: &mut Game, player_index: usize) {
+    let looked_at = game.players[player_index].reveal_from_deck(4);
+    keep_from_map(game, player_index, looked_at, 2);
+}
+
+fn keep_from_map(game: &mut Game, player_index: usize, looked_at: Vec<Card>, keep: usize) {
+    if keep == 0 || looked_at.is_empty() {
+        discard_all(game, player_index, looked_at);
+        return;
+    }
+    let candidates = distinct(looked_at.iter().copied());
+    game.ask(
+        player_index,
+        card("Mapmaker"),
+        Prompt::Options {
+            options: candidates
+                .iter()
+                .map(|card| format!("Put {} into your hand", card.name()))
+                .collect(),
+        },
+        move |game, player_index, answer| {
+            let mut rest = looked_at;
+            let chosen = candidates[answer.option()];
+            if let Some(index) = rest.iter().position(|&card| card == chosen) {
+                rest.remove(index);
+            }
+            game.players[player_index].add_to_hand(chosen);
+            keep_from_map(game, player_index, rest, keep - 1);
+            Ok(())
+        },
+    );
+}
+
+fn mapmaker_reaction(game: &mut Game, holder: usize, mapmaker: Card, _gainer: usize, gained: Card) {
+    if !gained.is(CardType::Victory) {
+        return;
+    }
+    game.ask(
+        holder,
+        mapmaker,
+        options(&["Play Mapmaker", "Don't play it"]),
+        move |game, holder, answer| {
+            let player = &mut game.players[holder];
+            if answer.option() == 1 || !player.hand.contains(&mapmaker) {
+                return Ok(());
+            }
+            // Like Black Cat, it stays in play until its owner's next Clean-up
+            from_hand_of(player, mapmaker);
+            player.played.push(mapmaker);
+            map_out(game, holder);
+            Ok(())
+        },
+    );
+}
+
+fn maroon(game: &mut Game, source: Card) -> Result<(), GameError> {
+    game.ask(
+        game.curr_player_index,
+        source,
+        from_hand(1, 1),
+        |game, player_index, answer| {
+            for trashed in answer.cards() {
+                game.trash_card(player_index, trashed);
+                game.players[player_index].draw(2 * trashed.info().types.len());
+            }
+            Ok(())
+        },
+    );
+    Ok(())
+}
+
+fn rope(game: &mut Game, source: Card) -> Result<(), GameError> {
+    next_turn(game, source, move |game, player_index| {
+        game.players[player_index].draw(1);
+        trash_from_hand(game, player_index, source, 0);
+        Ok(())
+    });
+    Ok(())
+}
+
+fn swamp_shacks(game: &mut Game, _source: Card) -> Result<(), GameError> {
+    let player = game.current_player();
+    let in_play = player.played.len();
+    player.draw(in_play / 3);
+    Ok(())
+}
+
+fn tools(game: &mut Game, source: Card) -> Result<(), GameError> {
+    let in_play = distinct(
+        game.players
+            .iter()
+            .flat_map(|player| player.played.iter().chain(player.durations.iter()))
+            .copied(),
+    );
+    let choices: Vec<Card> = in_play
+        .into_iter()
+        .filter(|&card| game.supply.count(card).unwrap_or(0) > 0)
+        .collect();
+    game.ask(
+        game.curr_player_index,
+        source,
+        Prompt::Options {
+            options: choices
+                .iter()
+                .map(|card| format!("Gain {}", card.name()))
+                .collect(),
+        },
+        move |game, player_index, answer| {
+            game.gain_card(
+                player_index,
+                choices[answer.option()],
+                GainDestination::Discard,
+            )
+        },
+    );
+    Ok(())
+}
+
+fn buried_treasure(game: &mut Game, source: Card) -> Result<(), GameError> {
+    next_turn(game, source, |game, player_index| {
+        let player = &mut game.players[player_index];
+        player.buys += 1;
+        player.coins += 3;
+        Ok(())
+    });
+    Ok(())
+}
+
+/// "When you gain this, play it." Only the current player can play cards, so
+/// a copy gained on someone else's turn stays where it was gained.
+fn play_on_gain(game: &mut Game, player_index: usize, gained: Card) {
+    if player_index != game.curr_player_index {
+        return;
+    }
+    game.then(
+        move |game| match game.current_player().take_gained(gained) {
+            Some(gained) => play_taken(game, gained),
+            None => Ok(()),
+        },
+    );
+}
+
+fn crew(game: &mut Game, source: Card) -> Result<(), GameError> {
+    next_turn(game, source, move |game, player_index| {
+        let player = &mut game.players[player_index];
+        if let Some(crew) = player.take_from_play(source) {
+            player.put_on_deck(crew);
+        }
+        Ok(())
+    });
+    Ok(())
+}
+
+fn cutthroat(game: &mut Game, source: Card) -> Result<(), GameError> {
+    for victim in game.attacked_player_indices() {
+        discard_down_to(game, victim, source, 3);
+    }
+    let owner = game.curr_player_index;
+    game.wait_for_next_time(owner, source, move |game, event| {
+        let Event::Gain { card, .. } = event else {
+            return Ok(true);
+        };
+        if !card.is(CardType::Treasure) || game.cost_of(card).coins < 5 {
+            return Ok(true);
+        }
+        game.then(move |game| gain_loot(game, owner, GainDestination::Discard));
+        Ok(false)
+    });
+    Ok(())
+}
+
+fn enlarge(game: &mut Game, source: Card) -> Result<(), GameError> {
+    remodel_for_two(game, game.curr_player_index, source);
+    next_turn(game, source, move |game, player_index| {
+        remodel_for_two(game, player_index, source);
+        Ok(())
+    });
+    Ok(())
+}
+
+fn remodel_for_two(game: &mut Game, player_index: usize, source: Card) {
+    game.ask(
+        player_index,
+        source,
+        from_hand(1, 1),
+        move |game, player_index, answer| {
+            for trashed in answer.cards() {
+                let max_cost = game.cost_of(trashed).plus_coins(2);
+                game.trash_card(player_index, trashed);
+                gain_to_discard(game, player_index, source, max_cost);
+            }
+            Ok(())
+        },
+    );
+}
+
+fn figurine(game: &mut Game, source: Card) -> Result<(), GameError> {
+    game.ask(
+        game.curr_player_index,
+        source,
+        Prompt::CardsFromHand {
+            min: 0,
+            max: 1,
+            filter: CardFilter::OfType(CardType::Action),
+        },
+        |game, player_index, answer| {
+            let discarded = answer.cards();
+            if !discarded.is_empty() {
+                let player = &mut game.players[player_index];
+                player.coins += 1;
+                player.buys += 1;
+            }
+            discard_all(game, player_index, discarded);
+            Ok(())
+        },
+    );
+    Ok(())
+}
+
+fn first_mate(game: &mut Game, source: Card) -> Result<(), GameError> {
+    let actions = game
+        .current_player_read_only()
+        .hand
+        .iter()
+        .copied()
+        .filter(|card| card.is(CardType::Action))
+        .collect();
+    choose_to_play(game, source, actions, |game, action| {
+        let copies = game
+            .current_player_read_only()
+            .hand
+            .iter()
+            .filter(|&&card| card == action)
+            .count();
+        for _ in 0..copies {
+            game.then(move |game| {
+                let player = game.current_player();
+                if !player.hand.contains(&action) {
+                    return Ok(());
+                }
+                from_hand_of(player, action);
+                play_taken(game, action)
+            });
+        }
+        Ok(())
+    });
+    game.then(|game| {
+        let player = game.current_player();
+        let missing = 6usize.saturating_sub(player.hand.len());
+        player.draw(missing);
+        Ok(())
+    });
+    Ok(())
+}
+
+fn frigate(game: &mut Game, source: Card) -> Result<(), GameError> {
+    let owner = game.curr_player_index;
+    let victims = game.attacked_player_indices();
+    game.add_trigger(Expiry::StartOfTurn(owner), move |game, event| {
+        if let Event::Played { player_index, card } = event {
+            if victims.contains(&player_index) && card.is(CardType::Action) {
+                discard_down_to(game, player_index, source, 4);
+            }
+        }
+        Ok(true)
+    });
+    // Nothing happens at the start of the next turn, but it stays in play until then
+    next_turn(game, source, |_, _| Ok(()));
+    Ok(())
+}
+
+fn longship(game: &mut Game, source: Card) -> Result<(), GameError> {
+    next_turn(game, source, |game, player_index| {
+        game.players[player_index].draw(2);
+        Ok(())
+    });
+    Ok(())
+}
+
+fn mining_road(game: &mut Game, source: Card) -> Result<(), GameError> {
+    let owner = game.curr_player_index;
+    // Only offered for the first Treasure gained after playing it
+    game.add_trigger(Expiry::EndOfTurn, move |game, event| {
+        let Event::Gain { player_index, card } = event else {
+            return Ok(true);
+        };
+        if player_index != owner || !card.is(CardType::Treasure) || !card.is_implemented() {
+            return Ok(true);
+        }
+        game.ask(
+            owner,
+            source,
+            Prompt::Options {
+                options: vec![format!("Play {}", card.name()), "Don't play it".to_owned()],
+            },
+            move |game, player_index, answer| {
+                if answer.option() == 1 {
+                    return Ok(());
+                }
+                match game.players[player_index].take_gained(card) {
+                    Some(gained) => play_taken(game, gained),
+                    None => Ok(()),
+                }
+            },
+        );
+        Ok(false)
+    });
+    Ok(())
+}
+
+fn pendant(game: &mut Game, _source: Card) -> Result<(), GameError> {
+    let player = game.current_player();
+    let treasures = distinct(
+        player
+            .played
+            .iter()
+            .copied()
+            .filter(|card| card.is(CardType::Treasure)),
+    );
+    player.coins += treasures.len() as u32;
+    Ok(())
+}
+
+fn pickaxe(game: &mut Game, source: Card) -> Result<(), GameError> {
+    game.ask(
+        game.curr_player_index,
+        source,
+        from_hand(1, 1),
+        |game, player_index, answer| {
+            for trashed in answer.cards() {
+                let cost = game.cost_of(trashed);
+                game.trash_card(player_index, trashed);
+                if cost.coins >= 3 {
+                    gain_loot(game, player_index, GainDestination::Hand)?;
+                }
+            }
+            Ok(())
+        },
+    );
+    Ok(())
+}
+
+fn pilgrim(game: &mut Game, source: Card) -> Result<(), GameError> {
+    game.ask(
+        game.curr_player_index,
+        source,
+        from_hand(1, 1),
+        |game, player_index, answer| {
+            for card in answer.cards() {
+                game.players[player_index].put_on_deck(card);
+            }
+            Ok(())
+        },
+    );
+    Ok(())
+}
+
+fn quartermaster(game: &mut Game, source: Card) -> Result<(), GameError> {
+    let player_index = game.curr_player_index;
+    quartermaster_next(game, player_index, source, Vec::new());
+    Ok(())
+}
+
+/// Schedules Quartermaster's choice for the start of its owner's next turn,
+/// carrying the cards set aside on it. It stays in play for the rest of the game.
+fn quartermaster_next(game: &mut Game, player_index: usize, source: Card, set_aside: Vec<Card>) {
+    game.at_start_of_turn(player_index, source, move |game| {
+        let can_gain = !game
+            .gainable_cards(&Cost::coins(4), false, &CardFilter::Any)
+            .is_empty();
+        let kinds = distinct(set_aside.iter().copied());
+        let mut choices = Vec::new();
+        if can_gain {
+            choices.push("Gain a card costing up to $4, setting it aside".to_owned());
+        }
+        choices.extend(
+            kinds
+                .iter()
+                .map(|card| format!("Put {} into your hand", card.name())),
+        );
+        if choices.is_empty() {
+            quartermaster_next(game, player_index, source, set_aside);
+            return Ok(());
+        }
+        game.ask(
+            player_index,
+            source,
+            Prompt::Options { options: choices },
+            move |game, player_index, answer| {
+                let mut set_aside = set_aside;
+                let choice = answer.option();
+                if can_gain && choice == 0 {
+                    game.ask(
+                        player_index,
+                        source,
+                        gain_from_supply(Cost::coins(4), CardFilter::Any),
+                        move |game, player_index, answer| {
+                            let gained = answer.supply_card();
+                            game.gain_card(player_index, gained, GainDestination::SetAside)?;
+                            set_aside.push(gained);
+                            quartermaster_next(game, player_index, source, set_aside);
+                            Ok(())
+                        },
+                    );
+                    return Ok(());
+                }
+                let taken = kinds[choice - usize::from(can_gain)];
+                if let Some(index) = set_aside.iter().position(|&card| card == taken) {
+                    set_aside.remove(index);
+                }
+                game.players[player_index].add_to_hand(taken);
+                quartermaster_next(game, player_index, source, set_aside);
+                Ok(())
+            },
+        );
+        Ok(())
+    });
+}
+
+fn silver_mine(game: &mut Game, source: Card) -> Result<(), GameError> {
+    let max_cost = game.cost_of(source).minus_coins(1);
+    game.ask(
+        game.curr_player_index,
+        source,
+        gain_from_supply(max_cost, CardFilter::OfType(CardType::Treasure)),
+        |game, player_index, answer| {
+            game.gain_card(player_index, answer.supply_card(), GainDestination::Hand)
+        },
+    );
+    Ok(())
+}
+
+fn trickster(game: &mut Game, source: Card) -> Result<(), GameError> {
+    for victim in game.attacked_player_indices() {
+        game.gain_if_available(victim, card("Curse"), GainDestination::Discard)?;
+    }
+    before_clean_up(game, move |game, owner| {
+        let treasures: Vec<Card> = distinct(
+            leaving_play(game, owner)
+                .into_iter()
+                .filter(|card| card.is(CardType::Treasure)),
+        );
+        if treasures.is_empty() {
+            return;
+        }
+        let mut choices: Vec<String> = treasures
+            .iter()
+            .map(|card| format!("Set aside {}", card.name()))
+            .collect();
+        choices.push("Don't set anything aside".to_owned());
+        game.ask(
+            owner,
+            source,
+            Prompt::Options { options: choices },
+            move |game, owner, answer| {
+                let Some(&chosen) = treasures.get(answer.option()) else {
+                    return Ok(());
+                };
+                if let Some(treasure) = game.players[owner].take_from_play(chosen) {
+                    game.after_turn(move |game| {
+                        game.players[owner].add_to_hand(treasure);
+                        Ok(())
+                    });
+                }
+                Ok(())
+            },
+        );
+    });
+    Ok(())
+}
+
+fn wealthy_village_on_gain(game: &mut Game, player_index: usize, _village: Card) {
+    let treasures = distinct(
+        game.players[player_index]
+            .played
+            .iter()
+            .copied()
+            .filter(|card| card.is(CardType::Treasure)),
+    );
+    if treasures.len() >= 3 {
+        game.then(move |game| gain_loot(game, player_index, GainDestination::Discard));
+    }
+}
+
+fn sack_of_loot(game: &mut Game, _source: Card) -> Result<(), GameError> {
+    gain_loot(game, game.curr_player_index, GainDestination::Discard)
+}
+
+fn kings_cache(game: &mut Game, source: Card) -> Result<(), GameError> {
+    let treasures = game
+        .current_player_read_only()
+        .hand
+        .iter()
+        .copied()
+        .filter(|card| card.is(CardType::Treasure))
+        .collect();
+    choose_to_play(game, source, treasures, |game, treasure| {
+        from_hand_of(game.current_player(), treasure);
+        play_taken(game, treasure)?;
+        for _ in 0..2 {
+            game.then(move |game| game.resolve_play(treasure));
+        }
+        Ok(())
+    });
+    Ok(())
+}
+
+// EVENTS
+fn bury(game: &mut Game, source: Card) -> Result<(), GameError> {
+    let player = game.current_player();
+    player.buys += 1;
+    let candidates = distinct(player.discard.iter().copied());
+    game.ask(
+        game.curr_player_index,
+        source,
+        Prompt::Options {
+            options: candidates
+                .iter()
+                .map(|card| format!("Put {} on the bottom of your deck", card.name()))
+                .collect(),
+        },
+        move |game, player_index, answer| {
+            let chosen = candidates[answer.option()];
+            let player = &mut game.players[player_index];
+            from_discard_of(player, chosen);
+            player.deck.insert(0, chosen);
+            Ok(())
+        },
+    );
+    Ok(())
+}
+
+fn avoid(game: &mut Game, _source: Card) -> Result<(), GameError> {
+    let player = game.current_player();
+    player.buys += 1;
+    player.avoiding_shuffle = true;
+    Ok(())
+}
+
+fn deliver(game: &mut Game, _source: Card) -> Result<(), GameError> {
+    let owner = game.curr_player_index;
+    game.current_player().buys += 1;
+    game.add_trigger(Expiry::EndOfTurn, move |game, event| {
+        let Event::Gain { player_index, card } = event else {
+            return Ok(true);
+        };
+        if player_index != owner {
+            return Ok(true);
+        }
+        if let Some(gained) = game.players[owner].take_gained(card) {
+            game.after_turn(move |game| {
+                game.players[owner].add_to_hand(gained);
+                Ok(())
+            });
+        }
+        Ok(true)
+    });
+    Ok(())
+}
+
+fn peril(game: &mut Game, source: Card) -> Result<(), GameError> {
+    game.current_player().buys += 1;
+    game.ask(
+        game.curr_player_index,
+        source,
+        Prompt::CardsFromHand {
+            min: 0,
+            max: 1,
+            filter: CardFilter::OfType(CardType::Action),
+        },
+        |game, player_index, answer| {
+            for trashed in answer.cards() {
+                game.trash_card(player_index, trashed);
+                gain_loot(game, player_index, GainDestination::Discard)?;
+            }
+            Ok(())
+        },
+    );
+    Ok(())
+}
+
+fn rush(game: &mut Game, _source: Card) -> Result<(), GameError> {
+    let owner = game.curr_player_index;
+    game.current_player().buys += 1;
+    game.add_trigger(Expiry::EndOfTurn, move |game, event| {
+        let Event::Gain { player_index, card } = event else {
+            return Ok(true);
+        };
+        if player_index != owner || !card.is(CardType::Action) || !card.is_implemented() {
+            return Ok(true);
+        }
+        play_on_gain(game, owner, card);
+        Ok(false)
+    });
+    Ok(())
+}
+
+fn foray(game: &mut Game, source: Card) -> Result<(), GameError> {
+    game.ask(
+        game.curr_player_index,
+        source,
+        from_hand(3, 3),
+        |game, player_index, answer| {
+            let discarded = answer.cards();
+            let different = discarded.len() == 3 && distinct(discarded.iter().copied()).len() == 3;
+            discard_all(game, player_index, discarded);
+            if different {
+                gain_loot(game, player_index, GainDestination::Discard)?;
+            }
+            Ok(())
+        },
+    );
+    Ok(())
+}
+
+fn launch(game: &mut Game, _source: Card) -> Result<(), GameError> {
+    game.game_phase = GamePhase::ActionPhase;
+    let player = game.current_player();
+    player.draw(1);
+    player.actions += 1;
+    player.buys += 1;
+    Ok(())
+}
+
+fn mirror(game: &mut Game, _source: Card) -> Result<(), GameError> {
+    let owner = game.curr_player_index;
+    game.current_player().buys += 1;
+    game.add_trigger(Expiry::EndOfTurn, move |game, event| {
+        let Event::Gain { player_index, card } = event else {
+            return Ok(true);
+        };
+        if player_index != owner || !card.is(CardType::Action) {
+            return Ok(true);
+        }
+        game.then(move |game| game.gain_if_available(owner, card, GainDestination::Discard));
+        Ok(false)
+    });
+    Ok(())
+}
+
+fn prepare(game: &mut Game, source: Card) -> Result<(), GameError> {
+    let owner = game.curr_player_index;
+    let set_aside = take(&mut game.current_player().hand);
+    game.at_start_of_turn(owner, source, move |game| {
+        play_prepared(game, owner, source, set_aside);
+        Ok(())
+    });
+    Ok(())
+}
+
+/// Plays the cards set aside by Prepare one at a time, in the order the
+/// player picks, then discards whatever can't be played.
+fn play_prepared(game: &mut Game, player_index: usize, source: Card, set_aside: Vec<Card>) {
+    let playable = distinct(set_aside.iter().copied().filter(|card| {
+        (card.is(CardType::Action) || card.is(CardType::Treasure)) && card.is_implemented()
+    }));
+    if playable.is_empty() {
+        discard_all(game, player_index, set_aside);
+        return;
+    }
+    game.ask(
+        player_index,
+        source,
+        Prompt::Options {
+            options: playable
+                .iter()
+                .map(|card| format!("Play {}", card.name()))
+                .collect(),
+        },
+        move |game, player_index, answer| {
+            let mut rest = set_aside;
+            let chosen = playable[answer.option()];
+            if let Some(index) = rest.iter().position(|&card| card == chosen) {
+                rest.remove(index);
+            }
+            play_taken(game, chosen)?;
+            game.then(move |game| {
+                play_prepared(game, player_index, source, rest);
+                Ok(())
+            });
+            Ok(())
+        },
+    );
+}
+
+fn scrounge(game: &mut Game, source: Card) -> Result<(), GameError> {
+    game.ask(
+        game.curr_player_index,
+        source,
+        options(&[
+            "Trash a card from your hand",
+            "Gain an Estate from the trash, then a card costing up to $5",
+        ]),
+        move |game, player_index, answer| {
+            if answer.option() == 0 {
+                trash_from_hand(game, player_index, source, 1);
+                return Ok(());
+            }
+            let estate = card("Estate");
+            if !game.trash.cards.contains(&estate) {
+                return Ok(());
+            }
+            game.gain_from_trash(player_index, estate, GainDestination::Discard)?;
+            gain_to_discard(game, player_index, source, Cost::coins(5));
+            Ok(())
+        },
+    );
+    Ok(())
+}
+
+fn journey(game: &mut Game, _source: Card) -> Result<(), GameError> {
+    // The cards stay in play until the start of the next turn, like Durations
+    before_clean_up(game, |game, owner| {
+        let player = &mut game.players[owner];
+        for card in player.played.clone() {
+            player.keep_in_play(card);
+        }
+    });
+    take_extra_turn(game);
+    Ok(())
+}
+
+fn maelstrom(game: &mut Game, source: Card) -> Result<(), GameError> {
+    let player_index = game.curr_player_index;
+    game.ask(
+        player_index,
+        source,
+        from_hand(3, 3),
+        |game, player_index, answer| {
+            for trashed in answer.cards() {
+                game.trash_card(player_index, trashed);
+            }
+            Ok(())
+        },
+    );
+    let num_players = game.players.len();
+    for offset in 1..num_players {
+        let other = (player_index + offset) % num_players;
+        if game.players[other].hand.len() >= 5 {
+            trash_from_hand(game, other, source, 1);
+        }
+    }
+    Ok(())
+}
+
+fn looting(game: &mut Game, _source: Card) -> Result<(), GameError> {
+    gain_loot(game, game.curr_player_index, GainDestination::Discard)
+}
+
+fn invasion(game: &mut Game, source: Card) -> Result<(), GameError> {
+    let owner = game.curr_player_index;
+    offer_play_from_hand(game, source, CardType::Attack);
+    game.then(move |game| {
+        game.gain_if_available(owner, card("Duchy"), GainDestination::Discard)?;
+        game.ask(
+            owner,
+            source,
+            gain_from_supply(ANY_COST, CardFilter::OfType(CardType::Action)),
+            |game, player_index, answer| {
+                game.gain_card(player_index, answer.supply_card(), GainDestination::Deck)
+            },
+        );
+        game.then(move |game| {
+            let Some(loot) = game.supply.top_of("Loot") else {
+                return Ok(());
+            };
+            game.gain_card(owner, loot, GainDestination::SetAside)?;
+            play_taken(game, loot)
+        });
+        Ok(())
+    });
+    Ok(())
+}
+
+fn prosper(game: &mut Game, source: Card) -> Result<(), GameError> {
+    let player_index = game.curr_player_index;
+    gain_loot(game, player_index, GainDestination::Discard)?;
+    prosper_next(game, player_index, source, Vec::new());
+    Ok(())
+}
+
+/// Offers Treasures named differently from the ones already gained, until the player stops.
+fn prosper_next(game: &mut Game, player_index: usize, source: Card, gained: Vec<Card>) {
+    let treasures: Vec<Card> = game
+        .supply
+        .available_cards()
+        .into_iter()
+        .filter(|card| card.is(CardType::Treasure) && !gained.contains(card))
+        .collect();
+    if treasures.is_empty() {
+        return;
+    }
+    let mut choices: Vec<String> = treasures
+        .iter()
+        .map(|card| format!("Gain {}", card.name()))
+        .collect();
+    choices.push("Stop gaining Treasures".to_owned());
+    game.ask(
+        player_index,
+        source,
+        Prompt::Options { options: choices },
+        move |game, player_index, answer| {
+            let Some(&chosen) = treasures.get(answer.option()) else {
+                return Ok(());
+            };
+            game.gain_card(player_index, chosen, GainDestination::Discard)?;
+            let mut gained = gained;
+            gained.push(chosen);
+            prosper_next(game, player_index, source, gained);
+            Ok(())
+        },
+    );
+}
+
+// TRAITS
+fn cheap(_game: &Game, cost: Cost) -> Cost {
+    cost.minus_coins(1)
+}
+
+fn cursed(game: &mut Game, player_index: usize, _gained: Card) {
+    game.then(move |game| {
+        gain_loot(game, player_index, GainDestination::Discard)?;
+        game.gain_if_available(player_index, card("Curse"), GainDestination::Discard)
+    });
+}
+
+fn fawning(game: &mut Game, fawning: Card, event: Event) {
+    let Event::Gain { player_index, card } = event else {
+        return;
+    };
+    if card.name() != "Province" {
+        return;
+    }
+    let Some(top) = game
+        .supply
+        .pile_with_trait(fawning)
+        .and_then(|pile| pile.top())
+    else {
+        return;
+    };
+    game.then(move |game| game.gain_if_available(player_index, top, GainDestination::Discard));
+}
+
+fn friendly(game: &mut Game, friendly: Card, event: Event) {
+    let Event::BuyPhaseEnd { player_index } = event else {
+        return;
+    };
+    let candidates = distinct(with_trait(game, friendly, &game.players[player_index].hand));
+    if candidates.is_empty() {
+        return;
+    }
+    let mut choices: Vec<String> = candidates
+        .iter()
+        .map(|card| format!("Discard {} to gain a copy", card.name()))
+        .collect();
+    choices.push("Don't discard anything".to_owned());
+    game.ask(
+        player_index,
+        friendly,
+        Prompt::Options { options: choices },
+        move |game, player_index, answer| {
+            let Some(&discarded) = candidates.get(answer.option()) else {
+                return Ok(());
+            };
+            from_hand_of(&mut game.players[player_index], discarded);
+            discard_all(game, player_index, vec![discarded]);
+            let top = game
+                .supply
+                .pile_with_trait(friendly)
+                .and_then(|pile| pile.top());
+            match top {
+                Some(top) => game.gain_card(player_index, top, GainDestination::Discard),
+                None => Ok(()),
+            }
+        },
+    );
+}
+
+fn hasty(game: &mut Game, player_index: usize, gained: Card) {
+    let Some(gained) = game.players[player_index].take_gained(gained) else {
+        return;
+    };
+    // Set aside rather than in play, so the Trait stands in as the source
+    game.at_start_of_turn(player_index, card("Hasty"), move |game| {
+        play_taken(game, gained)
+    });
+}
+
+fn inspiring(game: &mut Game, inspiring: Card, event: Event) {
+    let Event::Play { player_index, card } = event else {
+        return;
+    };
+    if player_index != game.curr_player_index || !game.supply.traits_of(card).contains(&inspiring) {
+        return;
+    }
+    game.then(move |game| {
+        let player = game.current_player_read_only();
+        let candidates = player
+            .hand
+            .iter()
+            .copied()
+            .filter(|card| card.is(CardType::Action) && !player.played.contains(card))
+            .collect();
+        choose_to_play(game, inspiring, candidates, |game, action| {
+            from_hand_of(game.current_player(), action);
+            play_taken(game, action)
+        });
+        Ok(())
+    });
+}
+
+fn nearby(game: &mut Game, player_index: usize, _gained: Card) {
+    game.players[player_index].buys += 1;
+}
+
+fn patient(game: &mut Game, patient: Card, event: Event) {
+    let Event::BuyPhaseEnd { player_index } = event else {
+        return;
+    };
+    set_aside_patient(game, player_index, patient, Vec::new());
+}
+
+/// Lets the player set aside Patient cards one at a time until they stop,
+/// then has them play those at the start of their next turn.
+fn set_aside_patient(game: &mut Game, player_index: usize, patient: Card, set_aside: Vec<Card>) {
+    let candidates = distinct(with_trait(game, patient, &game.players[player_index].hand));
+    if candidates.is_empty() {
+        play_patient(game, player_index, patient, set_aside);
+        return;
+    }
+    let mut choices: Vec<String> = candidates
+        .iter()
+        .map(|card| format!("Set aside {}", card.name()))
+        .collect();
+    choices.push("Don't set aside any more".to_owned());
+    game.ask(
+        player_index,
+        patient,
+        Prompt::Options { options: choices },
+        move |game, player_index, answer| {
+            let mut set_aside = set_aside;
+            let Some(&chosen) = candidates.get(answer.option()) else {
+                play_patient(game, player_index, patient, set_aside);
+                return Ok(());
+            };
+            from_hand_of(&mut game.players[player_index], chosen);
+            set_aside.push(chosen);
+            set_aside_patient(game, player_index, patient, set_aside);
+            Ok(())
+        },
+    );
+}
+
+fn play_patient(game: &mut Game, player_index: usize, patient: Card, set_aside: Vec<Card>) {
+    if set_aside.is_empty() {
+        return;
+    }
+    // Set aside rather than in play, so the Trait stands in as the source
+    game.at_start_of_turn(player_index, patient, move |game| {
+        for card in set_aside {
+            game.then(move |game| play_taken(game, card));
+        }
+        Ok(())
+    });
+}
+
+fn pious(game: &mut Game, player_index: usize, _gained: Card) {
+    trash_from_hand(game, player_index, card("Pious"), 0);
+}
+
+fn reckless(game: &mut Game, reckless: Card, event: Event) {
+    match event {
+        Event::Play { player_index, card }
+            if player_index == game.curr_player_index
+                && game.supply.traits_of(card).contains(&reckless) =>
+        {
+            replay_once_played(game, card);
+        }
+        Event::BuyPhaseEnd { player_index } => {
+            for card in with_trait(game, reckless, &leaving_play(game, player_index)) {
+                game.then(
+                    move |game| match game.players[player_index].take_from_play(card) {
+                        Some(card) => game.supply.return_card(card),
+                        None => Ok(()),
+                    },
+                );
+            }
+        }
+        _ => {}
+    }
+}
+
+fn rich(game: &mut Game, player_index: usize, _gained: Card) {
+    game.then(move |game| {
+        game.gain_if_available(player_index, card("Silver"), GainDestination::Discard)
+    });
+}
+
+fn shy(game: &mut Game, shy: Card, event: Event) {
+    let Event::TurnStart { player_index } = event else {
+        return;
+    };
+    let candidates = distinct(with_trait(game, shy, &game.players[player_index].hand));
+    if candidates.is_empty() {
+        return;
+    }
+    let mut choices: Vec<String> = candidates
+        .iter()
+        .map(|card| format!("Discard {} for +2 Cards", card.name()))
+        .collect();
+    choices.push("Don't discard anything".to_owned());
+    game.ask(
+        player_index,
+        shy,
+        Prompt::Options { options: choices },
+        move |game, player_index, answer| {
+            let Some(&discarded) = candidates.get(answer.option()) else {
+                return Ok(());
+            };
+            from_hand_of(&mut game.players[player_index], discarded);
+            discard_all(game, player_index, vec![discarded]);
+            game.players[player_index].draw(2);
+            Ok(())
+        },
+    );
+}
+
+fn tireless(game: &mut Game, tireless: Card, event: Event) {
+    let Event::BuyPhaseEnd { player_index } = event else {
+        return;
+    };
+    for card in with_trait(game, tireless, &leaving_play(game, player_index)) {
+        if let Some(set_aside) = game.players[player_index].take_from_play(card) {
+            game.after_turn(move |game| {
+                game.players[player_index].put_on_deck(set_aside);
+                Ok(())
+            });
+        }
+    }
+}
+
+// LOOT
+fn amphora(game: &mut Game, source: Card) -> Result<(), GameError> {
+    game.ask(
+        game.curr_player_index,
+        source,
+        options(&[
+            "+$3 and +1 Buy now",
+            "+$3 and +1 Buy at the start of your next turn",
+        ]),
+        move |game, player_index, answer| {
+            if answer.option() == 0 {
+                let player = &mut game.players[player_index];
+                player.coins += 3;
+                player.buys += 1;
+            } else {
+                next_turn(game, source, |game, player_index| {
+                    let player = &mut game.players[player_index];
+                    player.coins += 3;
+                    player.buys += 1;
+                    Ok(())
+                });
+            }
+            Ok(())
+        },
+    );
+    Ok(())
+}
+
+fn doubloons_on_gain(game: &mut Game, player_index: usize, _doubloons: Card) {
+    game.then(move |game| {
+        game.gain_if_available(player_index, card("Gold"), GainDestination::Discard)
+    });
+}
+
+fn endless_chalice(game: &mut Game, source: Card) -> Result<(), GameError> {
+    let player_index = game.curr_player_index;
+    chalice_next(game, player_index, source);
+    Ok(())
+}
+
+/// Endless Chalice stays in play for the rest of the game, giving its bonus
+/// again at the start of each of its owner's turns.
+fn chalice_next(game: &mut Game, player_index: usize, source: Card) {
+    game.at_start_of_turn(player_index, source, move |game| {
+        let player = &mut game.players[player_index];
+        player.coins += 1;
+        player.buys += 1;
+        chalice_next(game, player_index, source);
+        Ok(())
+    });
+}
+
+fn figurehead(game: &mut Game, source: Card) -> Result<(), GameError> {
+    next_turn(game, source, |game, player_index| {
+        game.players[player_index].draw(2);
+        Ok(())
+    });
+    Ok(())
+}
+
+fn hammer(game: &mut Game, source: Card) -> Result<(), GameError> {
+    gain_to_discard(game, game.curr_player_index, source, Cost::coins(4));
+    Ok(())
+}
+
+fn insignia(game: &mut Game, source: Card) -> Result<(), GameError> {
+    gains_onto_deck(game, game.curr_player_index, source);
+    Ok(())
+}
+
+fn jewels(game: &mut Game, source: Card) -> Result<(), GameError> {
+    next_turn(game, source, move |game, player_index| {
+        let player = &mut game.players[player_index];
+        if let Some(jewels) = player.take_from_play(source) {
+            player.deck.insert(0, jewels);
+        }
+        Ok(())
+    });
+    Ok(())
+}
+
+fn orb(game: &mut Game, source: Card) -> Result<(), GameError> {
+    let candidates: Vec<Card> = distinct(
+        game.current_player_read_only()
+            .discard
+            .iter()
+            .copied()
+            .filter(|card| card.is(CardType::Action) || card.is(CardType::Treasure))
+            .filter(|card| card.is_implemented()),
+    );
+    let mut choices: Vec<String> = candidates
+        .iter()
+        .map(|card| format!("Play {} from your discard pile", card.name()))
+        .collect();
+    choices.push("+1 Buy and +$3".to_owned());
+    game.ask(
+        game.curr_player_index,
+        source,
+        Prompt::Options { options: choices },
+        move |game, player_index, answer| {
+            let player = &mut game.players[player_index];
+            let Some(&chosen) = candidates.get(answer.option()) else {
+                player.buys += 1;
+                player.coins += 3;
+                return Ok(());
+            };
+            if let Some(index) = player.discard.iter().rposition(|&card| card == chosen) {
+                player.discard.remove(index);
+            }
+            play_taken(game, chosen)
+        },
+    );
+    Ok(())
+}
+
+fn prize_goat(game: &mut Game, source: Card) -> Result<(), GameError> {
+    trash_from_hand(game, game.curr_player_index, source, 0);
+    Ok(())
+}
+
+fn puzzle_box(game: &mut Game, source: Card) -> Result<(), GameError> {
+    game.ask(
+        game.curr_player_index,
+        source,
+        from_hand(0, 1),
+        |game, player_index, answer| {
+            for set_aside in answer.cards() {
+                game.after_turn(move |game| {
+                    game.players[player_index].add_to_hand(set_aside);
+                    Ok(())
+                });
+            }
+            Ok(())
+        },
+    );
+    Ok(())
+}
+
+fn sextant(game: &mut Game, source: Card) -> Result<(), GameError> {
+    // Decided from the bottom card up, so the ones kept go back in order
+    let looked_at = game.current_player().reveal_from_deck(5);
+    for card in looked_at.into_iter().rev() {
+        game.ask(
+            game.curr_player_index,
+            source,
+            Prompt::Options {
+                options: vec![
+                    format!("Discard {}", card.name()),
+                    format!("Put {} back", card.name()),
+                ],
+            },
+            move |game, player_index, answer| {
+                let player = &mut game.players[player_index];
+                if answer.option() == 0 {
+                    player.add_to_discard(card);
+                } else {
+                    player.put_on_deck(card);
+                }
+                Ok(())
+            },
+        );
+    }
+    Ok(())
+}
+
+fn spell_scroll(game: &mut Game, source: Card) -> Result<(), GameError> {
+    let player_index = game.curr_player_index;
+    let Some(scroll) = game.current_player().take_from_play(source) else {
+        return Ok(());
+    };
+    let max_cost = game.cost_of(scroll).minus_coins(1);
+    game.trash_card(player_index, scroll);
+    game.ask(
+        player_index,
+        source,
+        gain_from_supply(max_cost, CardFilter::Any),
+        move |game, player_index, answer| {
+            let gained = answer.supply_card();
+            game.gain_card(player_index, gained, GainDestination::Discard)?;
+            let playable = gained.is(CardType::Action) || gained.is(CardType::Treasure);
+            if !playable || !gained.is_implemented() {
+                return Ok(());
+            }
+            game.ask(
+                player_index,
+                source,
+                Prompt::Options {
+                    options: vec![
+                        format!("Play {}", gained.name()),
+                        "Don't play it".to_owned(),
+                    ],
+                },
+                move |game, player_index, answer| {
+                    if answer.option() == 1 {
+                        return Ok(());
+                    }
+                    match game.players[player_index].take_gained(gained) {
+                        Some(gained) => play_taken(game, gained),
+                        None => Ok(()),
+                    }
+                },
+            );
+            Ok(())
+        },
+    );
+    Ok(())
+}
+
+fn staff(game: &mut Game, source: Card) -> Result<(), GameError> {
+    offer_play_from_hand(game, source, CardType::Action);
+    Ok(())
+}
+
+fn sword(game: &mut Game, source: Card) -> Result<(), GameError> {
+    for victim in game.attacked_player_indices() {
+        let excess = game.players[victim].hand.len().saturating_sub(4);
+        game.ask(
+            victim,
+            source,
+            from_hand(excess, excess),
+            |game, player_index, answer| {
+                discard_all(game, player_index, answer.cards());
+                Ok(())
+            },
+        );
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::cards::testing::{end_turn, play, scenario};
+    use crate::GameMove;
+
+    #[test]
+    fn search_waits_for_a_pile_to_empty() {
+        let mut game = scenario(&["Search", "Smithy"], [&["Search"], &[]]);
+        let owner = game.curr_player_index;
+        let other = (owner + 1) % 2;
+        play(&mut game, "Search");
+        end_turn(&mut game);
+        assert_eq!(game.players[owner].durations, [card("Search")]);
+
+        // Still waiting after its owner's next turn
+        end_turn(&mut game);
+        end_turn(&mut game);
+        assert_eq!(game.players[owner].durations, [card("Search")]);
+
+        while game.supply.count(card("Curse")) != Some(0) {
+            game.gain_card(other, card("Curse"), GainDestination::Discard)
+                .unwrap();
+        }
+        game.run_pending().unwrap();
+
+        assert_eq!(game.trash.cards, [card("Search")]);
+        assert!(game.players[owner].durations.is_empty());
+        assert!(game.players[owner]
+            .discard
+            .iter()
+            .any(|card| card.is(CardType::Loot)));
+    }
+
+    #[test]
+    fn reckless_cards_play_twice_and_go_back_to_their_pile() {
+        let mut game = scenario(&["Smithy", "Reckless"], [&["Smithy"], &[]]);
+        let owner = game.curr_player_index;
+        let smithies = game.supply.count(card("Smithy"));
+        play(&mut game, "Smithy");
+        assert_eq!(game.current_player_read_only().hand.len(), 6);
+
+        end_turn(&mut game);
+
+        assert!(!game.players[owner].discard.contains(&card("Smithy")));
+        assert_eq!(
+            game.supply.count(card("Smithy")),
+            smithies.map(|count| count + 1)
+        );
+    }
+
+    #[test]
+    fn journey_keeps_cards_in_play_for_an_extra_turn() {
+        let mut game = scenario(&["Smithy", "Journey"], [&["Smithy"], &[]]);
+        let owner = game.curr_player_index;
+        play(&mut game, "Smithy");
+        game.game_phase = GamePhase::BuyPhase;
+        game.current_player().coins = 4;
+        game.accept_move(
+            owner,
+            GameMove::BuyCard {
+                card: card("Journey"),
+            },
+        )
+        .unwrap();
+
+        end_turn(&mut game);
+
+        assert_eq!(game.curr_player_index, owner);
+        assert!(game.history.current_turn.extra_turn);
+        assert_eq!(game.current_player_read_only().played, [card("Smithy")]);
+    }
+}
@@ -0,0 +1,806 @@
+//! Behaviours for the Prosperity kingdom cards. Several Treasures here have
+//! abilities while in play, which listen for buys and gains with
+//! `Game::add_trigger` until the end of the turn. Trade Route moves tokens to
+//! its mat through `Behaviour::on_event` for as long as it is in the supply.
+
+use super::{
+    card, choose_to_play, distinct, from_discard_of, from_hand, from_hand_of, play_taken,
+    reveal_until, Behaviour, Card, CardFilter, CardType, Cost,
+};
+use crate::{CostModifier, Event, Expiry, GainDestination, Game, GameError, GamePhase, Prompt};
+
+pub(super) fn behaviours() -> Vec<(&'static str, Behaviour)> {
+    let play = |on_play| Behaviour {
+        on_play: Some(on_play),
+        ..Behaviour::default()
+    };
+    vec![
+        (
+            "Watchtower",
+            Behaviour {
+                on_gain_in_hand: Some(watchtower_reaction),
+                ..play(watchtower)
+            },
+        ),
+        ("Loan", play(loan)),
+        (
+            "Trade Route",
+            Behaviour {
+                on_event: Some(trade_route_tokens),
+                ..play(trade_route)
+            },
+        ),
+        ("Bishop", play(bishop)),
+        ("Monument", play(monument)),
+        ("Quarry", play(quarry)),
+        (
+            "Grand Market",
+            Behaviour {
+                buyable: Some(grand_market_buyable),
+                ..Behaviour::default()
+            },
+        ),
+        ("Talisman", play(talisman)),
+        ("Contraband", play(contraband)),
+        ("Counting House", play(counting_house)),
+        ("City", play(city)),
+        ("Magnate", play(magnate)),
+        (
+            "Mint",
+            Behaviour {
+                on_buy: Some(mint_on_buy),
+                ..play(mint)
+            },
+        ),
+        ("Mountebank", play(mountebank)),
+        ("Rabble", play(rabble)),
+        ("Royal Seal", play(royal_seal)),
+        ("Vault", play(vault)),
+        ("Venture", play(venture)),
+        ("Goons", play(goons)),
+        ("Hoard", play(hoard)),
+        ("Bank", play(bank)),
+        ("Expand", play(expand)),
+        ("Forge", play(forge)),
+        ("King's Court", play(kings_court)),
+        (
+            "Peddler",
+            Behaviour {
+                cost: Some(peddler_cost),
+                ..Behaviour::default()
+            },
+        ),
+    ]
+}
+
+/// The other players in turn order, starting from the current player's left.
+fn other_player_indices(game: &Game) -> impl Iterator<Item = usize> {
+    let num_players = game.players.len();
+    let current = game.curr_player_index;
+    (1..num_players).map(move |offset| (current + offset) % num_players)
+}
+
+/// Reacts, for the rest of the turn, to each card the current player buys.
+fn on_buy(game: &mut Game, effect: fn(&mut Game, usize, Card) -> Result<(), GameError>) {
+    let owner = game.curr_player_index;
+    game.add_trigger(Expiry::EndOfTurn, move |game, event| {
+        if let Event::Buy { player_index, card } = event {
+            if player_index == owner {
+                effect(game, owner, card)?;
+            }
+        }
+        Ok(true)
+    });
+}
+
+fn watchtower(game: &mut Game, _source: Card) -> Result<(), GameError> {
+    let player = game.current_player();
+    let missing = 6usize.saturating_sub(player.hand.len());
+    player.draw(missing);
+    Ok(())
+}
+
+fn watchtower_reaction(
+    game: &mut Game,
+    player_index: usize,
+    watchtower: Card,
+    gainer_index: usize,
+    gained: Card,
+) {
+    if player_index != gainer_index {
+        return;
+    }
+    game.ask(
+        player_index,
+        watchtower,
+        Prompt::Options {
+            options: vec![
+                format!("Trash {}", gained.name()),
+                format!("Put {} onto your deck", gained.name()),
+                "Don't reveal Watchtower".to_owned(),
+            ],
+        },
+        move |game, player_index, answer| {
+            let option = answer.option();
+            if option == 2 {
+                return Ok(());
+            }
+            let found = game.players[player_index].take_gained(gained);
+            match (found, option) {
+                (Some(gained), 0) => game.trash_card(player_index, gained),
+                (Some(gained), _) => game.players[player_index].put_on_deck(gained),
+                (None, _) => {}
+            }
+            Ok(())
+        },
+    );
+}
+
+fn loan(game: &mut Game, source: Card) -> Result<(), GameError> {
+    let player_index = game.curr_player_index;
+    let (treasure, revealed) =
+        reveal_until(game, player_index, |_, card| card.is(CardType::Treasure));
+    game.players[player_index].discard.extend(revealed);
+    let Some(treasure) = treasure else {
+        return Ok(());
+    };
+    game.ask(
+        player_index,
+        source,
+        Prompt::Options {
+            options: vec![
+                format!("Discard {}", treasure.name()),
+                format!("Trash {}", treasure.name()),
+            ],
+        },
+        move |game, player_index, answer| {
+            if answer.option() == 0 {
+                game.players[player_index].add_to_discard(treasure);
+            } else {
+                game.trash_card(player_index, treasure);
+            }
+            Ok(())
+        },
+    );
+    Ok(())
+}
+
+fn trade_route(game: &mut Game, source: Card) -> Result<(), GameError> {
+    game.current_player().coins += game.supply.trade_route_mat;
+    game.ask(
+        game.curr_player_index,
+        source,
+        from_hand(1, 1),
+        |game, player_index, answer| {
+            for trashed in answer.cards() {
+                game.trash_card(player_index, trashed);
+            }
+            Ok(())
+        },
+    );
+    Ok(())
+}
+
+/// The first card gained from each Victory pile moves its token to the Trade
+/// Route mat.
+fn trade_route_tokens(game: &mut Game, _trade_route: Card, event: Event) {
+    let Event::Gain { card, .. } = event else {
+        return;
+    };
+    let Some(pile) = game.supply.pile(card).map(|pile| pile.name.clone()) else {
+        return;
+    };
+    let supply = &mut game.supply;
+    if let Some(index) = supply
+        .trade_route_piles
+        .iter()
+        .position(|name| *name == pile)
+    {
+        supply.trade_route_piles.remove(index);
+        supply.trade_route_mat += 1;
+    }
+}
+
+fn bishop(game: &mut Game, source: Card) -> Result<(), GameError> {
+    game.current_player().vp_tokens += 1;
+    game.ask(
+        game.curr_player_index,
+        source,
+        from_hand(1, 1),
+        |game, player_index, answer| {
+            for trashed in answer.cards() {
+                let tokens = game.cost_of(trashed).coins / 2;
+                game.trash_card(player_index, trashed);
+                game.players[player_index].vp_tokens += tokens;
+            }
+            Ok(())
+        },
+    );
+    for player_index in other_player_indices(game).collect::<Vec<_>>() {
+        game.ask(
+            player_index,
+            source,
+            from_hand(0, 1),
+            |game, player_index, answer| {
+                for trashed in answer.cards() {
+                    game.trash_card(player_index, trashed);
+                }
+                Ok(())
+            },
+        );
+    }
+    Ok(())
+}
+
+fn monument(game: &mut Game, _source: Card) -> Result<(), GameError> {
+    game.current_player().vp_tokens += 1;
+    Ok(())
+}
+
+/// Grand Market can't be bought while its buyer has a Copper in play.
+fn grand_market_buyable(game: &Game) -> bool {
+    !game
+        .current_player_read_only()
+        .played
+        .contains(&card("Copper"))
+}
+
+fn quarry(game: &mut Game, _source: Card) -> Result<(), GameError> {
+    game.cost_modifiers.push(CostModifier {
+        filter: CardFilter::OfType(CardType::Action),
+        coin_reduction: 2,
+    });
+    Ok(())
+}
+
+fn talisman(game: &mut Game, _source: Card) -> Result<(), GameError> {
+    on_buy(game, |game, player_index, bought| {
+        if !bought.is(CardType::Victory) && game.cost_of(bought).fits_within(&Cost::coins(4)) {
+            game.gain_if_available(player_index, bought, GainDestination::Discard)?;
+        }
+        Ok(())
+    });
+    Ok(())
+}
+
+fn contraband(game: &mut Game, source: Card) -> Result<(), GameError> {
+    // Only a card that is still in the supply is worth naming
+    let candidates = distinct(game.supply.piles.iter().filter_map(|pile| pile.top()));
+    let left = (game.curr_player_index + 1) % game.players.len();
+    game.ask(
+        left,
+        source,
+        Prompt::Options {
+            options: candidates
+                .iter()
+                .map(|card| format!("Name {}", card.name()))
+                .collect(),
+        },
+        move |game, _, answer| {
+            let named = candidates[answer.option()];
+            game.buy_restrictions.push(CardFilter::Named(named.name()));
+            Ok(())
+        },
+    );
+    Ok(())
+}
+
+fn counting_house(game: &mut Game, source: Card) -> Result<(), GameError> {
+    let coppers = game
+        .current_player_read_only()
+        .discard
+        .iter()
+        .filter(|card| card.name() == "Copper")
+        .count();
+    if coppers == 0 {
+        return Ok(());
+    }
+    game.ask(
+        game.curr_player_index,
+        source,
+        Prompt::Options {
+            options: (0..=coppers)
+                .rev()
+                .map(|count| format!("Put {count} Copper(s) into your hand"))
+                .collect(),
+        },
+        move |game, player_index, answer| {
+            let player = &mut game.players[player_index];
+            for _ in 0..coppers - answer.option() {
+                from_discard_of(player, card("Copper"));
+                player.add_to_hand(card("Copper"));
+            }
+            Ok(())
+        },
+    );
+    Ok(())
+}
+
+fn city(game: &mut Game, _source: Card) -> Result<(), GameError> {
+    let empty_piles = game.supply.num_empty_supply_piles();
+    let player = game.current_player();
+    if empty_piles >= 1 {
+        player.draw(1);
+    }
+    if empty_piles >= 2 {
+        player.buys += 1;
+        player.coins += 1;
+    }
+    Ok(())
+}
+
+fn magnate(game: &mut Game, _source: Card) -> Result<(), GameError> {
+    let player = game.current_player();
+    let treasures = player
+        .hand
+        .iter()
+        .filter(|card| card.is(CardType::Treasure))
+        .count();
+    player.draw(treasures);
+    Ok(())
+}
+
+fn mint(game: &mut Game, source: Card) -> Result<(), GameError> {
+    game.ask(
+        game.curr_player_index,
+        source,
+        Prompt::CardsFromHand {
+            min: 0,
+            max: 1,
+            filter: CardFilter::OfType(CardType::Treasure),
+        },
+        |game, player_index, answer| {
+            for revealed in answer.cards() {
+                game.players[player_index].add_to_hand(revealed);
+                game.gain_if_available(player_index, revealed, GainDestination::Discard)?;
+            }
+            Ok(())
+        },
+    );
+    Ok(())
+}
+
+fn mint_on_buy(game: &mut Game, _mint: Card) -> Result<(), GameError> {
+    let player_index = game.curr_player_index;
+    let treasures: Vec<Card> = game
+        .current_player_read_only()
+        .played
+        .iter()
+        .copied()
+        .filter(|card| card.is(CardType::Treasure))
+        .collect();
+    for treasure in treasures {
+        if let Some(treasure) = game.current_player().take_from_play(treasure) {
+            game.trash_card(player_index, treasure);
+        }
+    }
+    Ok(())
+}
+
+fn mountebank(game: &mut Game, source: Card) -> Result<(), GameError> {
+    for player_index in game.attacked_player_indices() {
+        let has_curse = game.players[player_index]
+            .hand
+            .iter()
+            .any(|card| card.is(CardType::Curse));
+        if !has_curse {
+            mountebank_gains(game, player_index)?;
+            continue;
+        }
+        game.ask(
+            player_index,
+            source,
+            Prompt::Options {
+                options: vec![
+                    "Discard a Curse".to_owned(),
+                    "Gain a Curse and a Copper".to_owned(),
+                ],
+            },
+            |game, player_index, answer| {
+                if answer.option() == 1 {
+                    return mountebank_gains(game, player_index);
+                }
+                let player = &mut game.players[player_index];
+                if let Some(index) = player.hand.iter().position(|card| card.is(CardType::Curse)) {
+                    let curse = player.hand.remove(index);
+                    player.add_to_discard(curse);
+                }
+                Ok(())
+            },
+        );
+    }
+    Ok(())
+}
+
+fn mountebank_gains(game: &mut Game, player_index: usize) -> Result<(), GameError> {
+    game.gain_if_available(player_index, card("Curse"), GainDestination::Discard)?;
+    game.gain_if_available(player_index, card("Copper"), GainDestination::Discard)
+}
+
+fn rabble(game: &mut Game, _source: Card) -> Result<(), GameError> {
+    for player_index in game.attacked_player_indices() {
+        let player = &mut game.players[player_index];
+        let revealed = player.reveal_from_deck(3);
+        // Whatever is left goes back in the order it was revealed
+        for &card in revealed.iter().rev() {
+            if card.is(CardType::Action) || card.is(CardType::Treasure) {
+                player.add_to_discard(card);
+            } else {
+                player.put_on_deck(card);
+            }
+        }
+    }
+    Ok(())
+}
+
+fn royal_seal(game: &mut Game, source: Card) -> Result<(), GameError> {
+    // One offer per gain is enough, however many Royal Seals are in play
+    if game.history.current_turn.played_count("Royal Seal") > 1 {
+        return Ok(());
+    }
+    let owner = game.curr_player_index;
+    game.add_trigger(Expiry::EndOfTurn, move |game, event| {
+        let Event::Gain { player_index, card } = event else {
+            return Ok(true);
+        };
+        if player_index != owner || !game.players[owner].played.contains(&source) {
+            return Ok(true);
+        }
+        game.ask(
+            owner,
+            source,
+            Prompt::Options {
+                options: vec![
+                    format!("Put {} onto your deck", card.name()),
+                    "Leave it where it is".to_owned(),
+                ],
+            },
+            move |game, player_index, answer| {
+                let player = &mut game.players[player_index];
+                if answer.option() == 0 {
+                    if let Some(gained) = player.take_gained(card) {
+                        player.put_on_deck(gained);
+                    }
+                }
+                Ok(())
+            },
+        );
+        Ok(true)
+    });
+    Ok(())
+}
+
+fn vault(game: &mut Game, source: Card) -> Result<(), GameError> {
+    let hand_size = game.current_player_read_only().hand.len();
+    game.ask(
+        game.curr_player_index,
+        source,
+        from_hand(0, hand_size),
+        |game, player_index, answer| {
+            let player = &mut game.players[player_index];
+            for card in answer.cards() {
+                player.add_to_discard(card);
+                player.coins += 1;
+            }
+            Ok(())
+        },
+    );
+    for player_index in other_player_indices(game).collect::<Vec<_>>() {
+        game.ask(
+            player_index,
+            source,
+            from_hand(0, 2),
+            |game, player_index, answer| {
+                let discarded = answer.cards();
+                let player = &mut game.players[player_index];
+                if discarded.len() == 2 {
+                    player.draw(1);
+                }
+                for card in discarded {
+                    player.add_to_discard(card);
+                }
+                Ok(())
+            },
+        );
+    }
+    Ok(())
+}
+
+fn venture(game: &mut Game, _source: Card) -> Result<(), GameError> {
+    let player_index = game.curr_player_index;
+    let (treasure, revealed) =
+        reveal_until(game, player_index, |_, card| card.is(CardType::Treasure));
+    game.players[player_index].discard.extend(revealed);
+    match treasure {
+        Some(treasure) => game.play_treasure(treasure),
+        None => Ok(()),
+    }
+}
+
+fn goons(game: &mut Game, source: Card) -> Result<(), GameError> {
+    for player_index in game.attacked_player_indices() {
+        let excess = game.players[player_index].hand.len().saturating_sub(3);
+        game.ask(
+            player_index,
+            source,
+            from_hand(excess, excess),
+            |game, player_index, answer| {
+                for card in answer.cards() {
+                    game.players[player_index].add_to_discard(card);
+                }
+                Ok(())
+            },
+        );
+    }
+    on_buy(game, |game, player_index, _| {
+        game.players[player_index].vp_tokens += 1;
+        Ok(())
+    });
+    Ok(())
+}
+
+fn hoard(game: &mut Game, _source: Card) -> Result<(), GameError> {
+    on_buy(game, |game, player_index, bought| {
+        if bought.is(CardType::Victory) {
+            game.gain_if_available(player_index, card("Gold"), GainDestination::Discard)?;
+        }
+        Ok(())
+    });
+    Ok(())
+}
+
+fn bank(game: &mut Game, _source: Card) -> Result<(), GameError> {
+    let player = game.current_player();
+    let treasures = player
+        .played
+        .iter()
+        .filter(|card| card.is(CardType::Treasure))
+        .count();
+    player.coins += treasures as u32;
+    Ok(())
+}
+
+fn expand(game: &mut Game, source: Card) -> Result<(), GameError> {
+    game.ask(
+        game.curr_player_index,
+        source,
+        from_hand(1, 1),
+        move |game, player_index, answer| {
+            for trashed in answer.cards() {
+                let max_cost = game.cost_of(trashed).plus_coins(3);
+                game.trash_card(player_index, trashed);
+                game.ask(
+                    player_index,
+                    source,
+                    Prompt::CardFromSupply {
+                        max_cost,
+                        exact: false,
+                        filter: CardFilter::Any,
+                    },
+                    |game, player_index, answer| {
+                        game.gain_card(player_index, answer.supply_card(), GainDestination::Discard)
+                    },
+                );
+            }
+            Ok(())
+        },
+    );
+    Ok(())
+}
+
+fn forge(game: &mut Game, source: Card) -> Result<(), GameError> {
+    let hand_size = game.current_player_read_only().hand.len();
+    game.ask(
+        game.curr_player_index,
+        source,
+        from_hand(0, hand_size),
+        move |game, player_index, answer| {
+            let mut total = 0;
+            for trashed in answer.cards() {
+                total += game.cost_of(trashed).coins;
+                game.trash_card(player_index, trashed);
+            }
+            game.ask(
+                player_index,
+                source,
+                Prompt::CardFromSupply {
+                    max_cost: Cost::coins(total),
+                    exact: true,
+                    filter: CardFilter::Any,
+                },
+                |game, player_index, answer| {
+                    game.gain_card(player_index, answer.supply_card(), GainDestination::Discard)
+                },
+            );
+            Ok(())
+        },
+    );
+    Ok(())
+}
+
+fn kings_court(game: &mut Game, source: Card) -> Result<(), GameError> {
+    let actions = game
+        .current_player_read_only()
+        .hand
+        .iter()
+        .copied()
+        .filter(|card| card.is(CardType::Action))
+        .collect();
+    choose_to_play(game, source, actions, move |game, action| {
+        from_hand_of(game.current_player(), action);
+        play_taken(game, action)?;
+        for _ in 0..2 {
+            game.then(move |game| game.resolve_play(action));
+        }
+        // King's Court stays in play for as long as a Duration it played does
+        game.then(move |game| {
+            let owner = game.curr_player_index;
+            let staying = game
+                .durations
+                .iter()
+                .any(|duration| duration.player_index == owner && duration.source == action);
+            if staying {
+                game.at_start_of_turn(owner, source, |_| Ok(()));
+            }
+            Ok(())
+        });
+        Ok(())
+    });
+    Ok(())
+}
+
+/// During its buyer's Buy phase, Peddler costs $2 less per Action they have in play.
+fn peddler_cost(game: &Game, cost: Cost) -> Cost {
+    if !matches!(
+        game.game_phase,
+        GamePhase::TreasurePhase | GamePhase::BuyPhase
+    ) {
+        return cost;
+    }
+    let actions = game
+        .current_player_read_only()
+        .played
+        .iter()
+        .filter(|card| card.is(CardType::Action))
+        .count();
+    cost.minus_coins(2 * actions as u32)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::cards::testing::{choose_option, play, scenario};
+    use crate::{GameMove, GamePhase};
+
+    const KINGDOM: [&str; 10] = [
+        "Loan",
+        "Trade Route",
+        "Contraband",
+        "Counting House",
+        "Mint",
+        "Mountebank",
+        "Royal Seal",
+        "King's Court",
+        "Peddler",
+        "Smithy",
+    ];
+
+    fn count(cards: &[Card], name: &str) -> usize {
+        cards.iter().filter(|card| card.name() == name).count()
+    }
+
+    #[test]
+    fn trade_route_pays_for_each_victory_pile_gained_from() {
+        let mut game = scenario(&KINGDOM, [&["Trade Route", "Copper"], &[]]);
+        let other = (game.curr_player_index + 1) % 2;
+        for name in ["Estate", "Estate", "Duchy"] {
+            game.gain_card(other, card(name), GainDestination::Discard)
+                .unwrap();
+        }
+        assert_eq!(game.supply.trade_route_mat, 2);
+
+        play(&mut game, "Trade Route");
+
+        assert_eq!(game.current_player_read_only().coins, 2);
+        assert!(game.pending_decision().is_some());
+    }
+
+    #[test]
+    fn kings_court_plays_an_action_three_times() {
+        let mut game = scenario(&KINGDOM, [&["King's Court", "Smithy"], &[]]);
+        play(&mut game, "King's Court");
+
+        choose_option(&mut game, 0);
+
+        let player = game.current_player_read_only();
+        assert_eq!(count(&player.hand, "Silver"), 9);
+        assert_eq!(count(&player.played, "Smithy"), 1);
+    }
+
+    #[test]
+    #[cfg(feature = "seaside")]
+    fn kings_court_stays_in_play_with_a_duration_it_played() {
+        use crate::cards::testing::end_turn;
+
+        let mut game = scenario(
+            &["King's Court", "Caravan"],
+            [&["King's Court", "Caravan"], &[]],
+        );
+        let owner = game.curr_player_index;
+        // Enough not to reshuffle the discard pile
+        game.current_player().deck = vec![card("Silver"); 20];
+        play(&mut game, "King's Court");
+        choose_option(&mut game, 0);
+
+        end_turn(&mut game);
+        assert_eq!(
+            game.players[owner].durations,
+            [card("Caravan"), card("King's Court")]
+        );
+
+        end_turn(&mut game);
+        // Both are discarded at the end of the turn Caravan's effects happen on
+        assert_eq!(count(&game.current_player_read_only().hand, "Silver"), 8);
+        end_turn(&mut game);
+        let player = &game.players[owner];
+        assert!(player.durations.is_empty());
+        assert_eq!(count(&player.discard, "King's Court"), 1);
+        assert_eq!(count(&player.discard, "Caravan"), 1);
+    }
+
+    #[test]
+    fn mountebank_curses_only_those_who_keep_their_curse() {
+        let mut game = scenario(&KINGDOM, [&["Mountebank"], &["Curse"]]);
+        let other = (game.curr_player_index + 1) % 2;
+        play(&mut game, "Mountebank");
+        choose_option(&mut game, 0);
+        assert_eq!(game.players[other].discard, [card("Curse")]);
+
+        let mut game = scenario(&KINGDOM, [&["Mountebank"], &[]]);
+        play(&mut game, "Mountebank");
+        assert_eq!(game.players[other].discard, [card("Curse"), card("Copper")]);
+    }
+
+    #[test]
+    fn counting_house_takes_coppers_from_the_discard_pile() {
+        let mut game = scenario(&KINGDOM, [&["Counting House"], &[]]);
+        game.current_player().discard = vec![card("Copper"), card("Estate"), card("Copper")];
+        play(&mut game, "Counting House");
+
+        choose_option(&mut game, 0);
+
+        let player = game.current_player_read_only();
+        assert_eq!(player.hand, [card("Copper"), card("Copper")]);
+        assert_eq!(player.discard, [card("Estate")]);
+    }
+
+    #[test]
+    fn buying_mint_trashes_the_treasures_in_play() {
+        let mut game = scenario(&KINGDOM, [&[], &[]]);
+        game.game_phase = GamePhase::BuyPhase;
+        let player = game.current_player();
+        player.played = vec![card("Copper"), card("Gold"), card("Smithy")];
+        player.coins = 5;
+
+        let player_index = game.curr_player_index;
+        game.accept_move(player_index, GameMove::BuyCard { card: card("Mint") })
+            .unwrap();
+
+        assert_eq!(game.current_player_read_only().played, [card("Smithy")]);
+        assert_eq!(game.trash.cards, [card("Copper"), card("Gold")]);
+    }
+
+    #[test]
+    fn peddler_is_cheaper_in_the_buy_phase_for_each_action_in_play() {
+        let mut game = scenario(&KINGDOM, [&[], &[]]);
+        game.current_player().played = vec![card("Smithy"), card("Mint"), card("Copper")];
+        let peddler = card("Peddler");
+        assert_eq!(game.cost_of(peddler), Cost::coins(8));
+
+        game.game_phase = GamePhase::BuyPhase;
+        assert_eq!(game.cost_of(peddler), Cost::coins(4));
+
+        game.current_player().played.extend([card("Smithy"); 3]);
+        assert_eq!(game.cost_of(peddler), Cost::coins(0));
+    }
+}
@@ -0,0 +1,1171 @@
+//! Behaviours for the Renaissance kingdom cards, Projects and Artifacts.
+//! Projects and Artifacts watch for events through `Behaviour::on_event` like
+//! Landmarks do, and only act for the players who have them. Villagers and
+//! Coffers are spent with their own moves, so cards here only hand them out.
+//! Capitalism, Fleet and Star Chart change the rules themselves, so the engine
+//! checks for them where they apply.
+
+use std::mem::take;
+
+use super::{
+    card, distinct, from_hand, gain_from_supply, next_turn, options, Behaviour, Card, CardFilter,
+    CardType, Cost,
+};
+use crate::{CostModifier, Event, Expiry, GainDestination, Game, GameError, Prompt};
+
+pub(super) fn behaviours() -> Vec<(&'static str, Behaviour)> {
+    let play = |on_play| Behaviour {
+        on_play: Some(on_play),
+        ..Behaviour::default()
+    };
+    let gain = |on_gain| Behaviour {
+        on_gain: Some(on_gain),
+        ..Behaviour::default()
+    };
+    let event = |on_event| Behaviour {
+        on_event: Some(on_event),
+        ..Behaviour::default()
+    };
+    vec![
+        ("Border Guard", play(border_guard)),
+        (
+            "Ducat",
+            Behaviour {
+                on_gain: Some(ducat_on_gain),
+                ..play(ducat)
+            },
+        ),
+        ("Lackeys", gain(lackeys)),
+        ("Acting Troupe", play(acting_troupe)),
+        ("Cargo Ship", play(cargo_ship)),
+        (
+            "Experiment",
+            Behaviour {
+                on_gain: Some(experiment_on_gain),
+                ..play(experiment)
+            },
+        ),
+        ("Improve", play(improve)),
+        (
+            "Flag Bearer",
+            Behaviour {
+                on_gain: Some(flag_bearer),
+                on_trash: Some(flag_bearer),
+                ..Behaviour::default()
+            },
+        ),
+        ("Hideout", play(hideout)),
+        ("Inventor", play(inventor)),
+        ("Mountain Village", play(mountain_village)),
+        ("Patron", play(patron)),
+        ("Priest", play(priest)),
+        ("Research", play(research)),
+        (
+            "Silk Merchant",
+            Behaviour {
+                on_gain: Some(silk_merchant),
+                on_trash: Some(silk_merchant),
+                ..Behaviour::default()
+            },
+        ),
+        ("Old Witch", play(old_witch)),
+        ("Recruiter", play(recruiter)),
+        ("Scepter", play(scepter)),
+        ("Scholar", play(scholar)),
+        ("Sculptor", play(sculptor)),
+        ("Seer", play(seer)),
+        ("Spices", gain(spices)),
+        ("Swashbuckler", play(swashbuckler)),
+        ("Treasurer", play(treasurer)),
+        ("Villain", play(villain)),
+        // Projects
+        ("Cathedral", event(cathedral)),
+        ("City Gate", event(city_gate)),
+        ("Pageant", event(pageant)),
+        ("Sewers", event(sewers)),
+        ("Star Chart", Behaviour::default()),
+        (
+            "Canal",
+            Behaviour {
+                reduces_costs: Some(canal),
+                ..Behaviour::default()
+            },
+        ),
+        ("Exploration", event(exploration)),
+        ("Fair", event(fair)),
+        ("Silos", event(silos)),
+        ("Sinister Plot", event(sinister_plot)),
+        ("Academy", event(academy)),
+        ("Guildhall", event(guildhall)),
+        ("Piazza", event(piazza)),
+        ("Road Network", event(road_network)),
+        ("Capitalism", Behaviour::default()),
+        ("Fleet", Behaviour::default()),
+        ("Barracks", event(barracks)),
+        ("Crop Rotation", event(crop_rotation)),
+        ("Innovation", event(innovation)),
+        ("Citadel", event(citadel)),
+        // Artifacts
+        ("Horn", event(horn)),
+        ("Key", event(key)),
+        ("Treasure Chest", event(treasure_chest)),
+    ]
+}
+
+/// Cards revealed by a Renaissance card; each Patron among them gives its
+/// owner +1 Coffers.
+fn revealed(game: &mut Game, player_index: usize, cards: &[Card]) {
+    let patrons = cards.iter().filter(|card| card.name() == "Patron").count();
+    game.players[player_index].coffers += patrons as u32;
+}
+
+/// Trashes the card a player picks from their hand, then carries on with it.
+fn trash_one(
+    game: &mut Game,
+    player_index: usize,
+    source: Card,
+    then: impl FnOnce(&mut Game, usize, Card) -> Result<(), GameError> + Send + 'static,
+) {
+    game.ask(
+        player_index,
+        source,
+        from_hand(1, 1),
+        move |game, player_index, answer| {
+            let Some(&trashed) = answer.cards().first() else {
+                return Ok(());
+            };
+            game.trash_card(player_index, trashed);
+            then(game, player_index, trashed)
+        },
+    );
+}
+
+fn border_guard(game: &mut Game, source: Card) -> Result<(), GameError> {
+    let player_index = game.curr_player_index;
+    let count = if game.players[player_index].has_artifact("Lantern") {
+        3
+    } else {
+        2
+    };
+    let cards = game.players[player_index].reveal_from_deck(count);
+    revealed(game, player_index, &cards);
+    let all_actions = cards.len() == count && cards.iter().all(|card| card.is(CardType::Action));
+    let choices = distinct(cards.iter().copied());
+    game.ask(
+        player_index,
+        source,
+        Prompt::Options {
+            options: choices
+                .iter()
+                .map(|card| format!("Put {} into your hand", card.name()))
+                .collect(),
+        },
+        move |game, player_index, answer| {
+            let mut cards = cards;
+            let kept = choices[answer.option()];
+            let index = cards.iter().position(|&card| card == kept).unwrap();
+            cards.remove(index);
+            let player = &mut game.players[player_index];
+            player.add_to_hand(kept);
+            for card in cards {
+                player.add_to_discard(card);
+            }
+            Ok(())
+        },
+    );
+    if all_actions {
+        game.ask(
+            player_index,
+            source,
+            options(&["Take the Lantern", "Take the Horn"]),
+            |game, player_index, answer| {
+                let artifact = ["Lantern", "Horn"][answer.option()];
+                game.take_artifact(player_index, artifact);
+                Ok(())
+            },
+        );
+    }
+    Ok(())
+}
+
+fn ducat(game: &mut Game, _source: Card) -> Result<(), GameError> {
+    game.current_player().coffers += 1;
+    Ok(())
+}
+
+fn ducat_on_gain(game: &mut Game, player_index: usize, source: Card) {
+    game.ask(
+        player_index,
+        source,
+        Prompt::CardsFromHand {
+            min: 0,
+            max: 1,
+            filter: CardFilter::Named("Copper"),
+        },
+        |game, player_index, answer| {
+            for copper in answer.cards() {
+                game.trash_card(player_index, copper);
+            }
+            Ok(())
+        },
+    );
+}
+
+fn lackeys(game: &mut Game, player_index: usize, _source: Card) {
+    game.players[player_index].villagers += 2;
+}
+
+fn acting_troupe(game: &mut Game, source: Card) -> Result<(), GameError> {
+    let player_index = game.curr_player_index;
+    game.players[player_index].villagers += 4;
+    if let Some(troupe) = game.players[player_index].take_from_play(source) {
+        game.trash_card(player_index, troupe);
+    }
+    Ok(())
+}
+
+fn cargo_ship(game: &mut Game, source: Card) -> Result<(), GameError> {
+    let owner = game.curr_player_index;
+    game.add_trigger(Expiry::EndOfTurn, move |game, event| {
+        let Event::Gain { player_index, card } = event else {
+            return Ok(true);
+        };
+        if player_index != owner {
+            return Ok(true);
+        }
+        // The offer uses up the "once this turn", whether or not it is taken
+        game.ask(
+            owner,
+            source,
+            Prompt::Options {
+                options: vec![
+                    format!("Set {} aside on Cargo Ship", card.name()),
+                    "Leave it".to_owned(),
+                ],
+            },
+            move |game, player_index, answer| {
+                if answer.option() == 1 {
+                    return Ok(());
+                }
+                let player = &mut game.players[player_index];
+                let found = if let Some(index) = player.discard.iter().rposition(|&c| c == card) {
+                    Some(player.discard.remove(index))
+                } else if let Some(index) = player.hand.iter().rposition(|&c| c == card) {
+                    Some(player.hand.remove(index))
+                } else {
+                    None
+                };
+                // Cargo Ship only stays in play if it is holding a card
+                if let Some(set_aside) = found {
+                    next_turn(game, source, move |game, player_index| {
+                        game.players[player_index].add_to_hand(set_aside);
+                        Ok(())
+                    });
+                }
+                Ok(())
+            },
+        );
+        Ok(false)
+    });
+    Ok(())
+}
+
+fn experiment(game: &mut Game, source: Card) -> Result<(), GameError> {
+    if let Some(returned) = game.current_player().take_from_play(source) {
+        game.supply.return_card(returned)?;
+    }
+    Ok(())
+}
+
+fn experiment_on_gain(game: &mut Game, player_index: usize, source: Card) {
+    // Experiments are gained in pairs, and only the first of each pair brings
+    // another, so count the player's Experiments gained one after the other
+    let in_a_row = game
+        .history
+        .current_turn
+        .gained
+        .iter()
+        .rev()
+        .take_while(|&&(index, gained)| index == player_index && gained == source)
+        .count();
+    if in_a_row % 2 == 1 {
+        game.then(move |game| {
+            game.gain_if_available(player_index, source, GainDestination::Discard)
+        });
+    }
+}
+
+fn improve(game: &mut Game, source: Card) -> Result<(), GameError> {
+    let owner = game.curr_player_index;
+    // The Buy phase ends just before Clean-up starts
+    game.add_trigger(Expiry::EndOfTurn, move |game, event| {
+        let Event::BuyPhaseEnd { player_index } = event else {
+            return Ok(true);
+        };
+        if player_index != owner {
+            return Ok(true);
+        }
+        let staying: Vec<Card> = game
+            .durations
+            .iter()
+            .filter(|duration| duration.player_index == owner)
+            .map(|duration| duration.source)
+            .collect();
+        let actions: Vec<Card> = distinct(game.players[owner].played.iter().copied())
+            .into_iter()
+            .filter(|card| card.is(CardType::Action) && !staying.contains(card))
+            .collect();
+        if actions.is_empty() {
+            return Ok(false);
+        }
+        let mut choices: Vec<String> = actions
+            .iter()
+            .map(|card| format!("Trash {}", card.name()))
+            .collect();
+        choices.push("Don't trash anything".to_owned());
+        game.ask(
+            owner,
+            source,
+            Prompt::Options { options: choices },
+            move |game, player_index, answer| {
+                let Some(&action) = actions.get(answer.option()) else {
+                    return Ok(());
+                };
+                let Some(trashed) = game.players[player_index].take_from_play(action) else {
+                    return Ok(());
+                };
+                let cost = game.cost_of(trashed).plus_coins(1);
+                game.trash_card(player_index, trashed);
+                game.ask(
+                    player_index,
+                    source,
+                    Prompt::CardFromSupply {
+                        max_cost: cost,
+                        exact: true,
+                        filter: CardFilter::Any,
+                    },
+                    |game, player_index, answer| {
+                        game.gain_card(player_index, answer.supply_card(), GainDestination::Discard)
+                    },
+                );
+                Ok(())
+            },
+        );
+        Ok(false)
+    });
+    Ok(())
+}
+
+fn flag_bearer(game: &mut Game, player_index: usize, _source: Card) {
+    game.take_artifact(player_index, "Flag");
+}
+
+fn hideout(game: &mut Game, source: Card) -> Result<(), GameError> {
+    trash_one(
+        game,
+        game.curr_player_index,
+        source,
+        |game, player_index, trashed| {
+            if trashed.is(CardType::Victory) {
+                game.gain_if_available(player_index, card("Curse"), GainDestination::Discard)?;
+            }
+            Ok(())
+        },
+    );
+    Ok(())
+}
+
+fn inventor(game: &mut Game, source: Card) -> Result<(), GameError> {
+    game.ask(
+        game.curr_player_index,
+        source,
+        gain_from_supply(Cost::coins(4), CardFilter::Any),
+        |game, player_index, answer| {
+            game.gain_card(player_index, answer.supply_card(), GainDestination::Discard)
+        },
+    );
+    game.then(|game| {
+        game.cost_modifiers.push(CostModifier {
+            filter: CardFilter::Any,
+            coin_reduction: 1,
+        });
+        Ok(())
+    });
+    Ok(())
+}
+
+fn mountain_village(game: &mut Game, source: Card) -> Result<(), GameError> {
+    let player = game.current_player();
+    if player.discard.is_empty() {
+        player.draw(1);
+        return Ok(());
+    }
+    let choices = distinct(player.discard.iter().copied());
+    game.ask(
+        game.curr_player_index,
+        source,
+        Prompt::Options {
+            options: choices
+                .iter()
+                .map(|card| format!("Put {} into your hand", card.name()))
+                .collect(),
+        },
+        move |game, player_index, answer| {
+            let chosen = choices[answer.option()];
+            let player = &mut game.players[player_index];
+            if let Some(index) = player.discard.iter().rposition(|&card| card == chosen) {
+                player.discard.remove(index);
+                player.add_to_hand(chosen);
+            }
+            Ok(())
+        },
+    );
+    Ok(())
+}
+
+fn patron(game: &mut Game, _source: Card) -> Result<(), GameError> {
+    // Its reaction to being revealed is handled by whatever reveals it
+    game.current_player().villagers += 1;
+    Ok(())
+}
+
+fn priest(game: &mut Game, source: Card) -> Result<(), GameError> {
+    let owner = game.curr_player_index;
+    trash_one(game, owner, source, |_, _, _| Ok(()));
+    // Only trashing after Priest's own counts
+    game.then(move |game| {
+        game.add_trigger(Expiry::EndOfTurn, move |game, event| {
+            if let Event::Trash { player_index, .. } = event {
+                if player_index == owner {
+                    game.players[owner].coins += 2;
+                }
+            }
+            Ok(true)
+        });
+        Ok(())
+    });
+    Ok(())
+}
+
+fn research(game: &mut Game, source: Card) -> Result<(), GameError> {
+    trash_one(
+        game,
+        game.curr_player_index,
+        source,
+        move |game, player_index, trashed| {
+            let count = game.cost_of(trashed).coins as usize;
+            let set_aside = game.players[player_index].reveal_from_deck(count);
+            if !set_aside.is_empty() {
+                next_turn(game, source, move |game, player_index| {
+                    for card in set_aside {
+                        game.players[player_index].add_to_hand(card);
+                    }
+                    Ok(())
+                });
+            }
+            Ok(())
+        },
+    );
+    Ok(())
+}
+
+fn silk_merchant(game: &mut Game, player_index: usize, _source: Card) {
+    let player = &mut game.players[player_index];
+    player.coffers += 1;
+    player.villagers += 1;
+}
+
+fn old_witch(game: &mut Game, source: Card) -> Result<(), GameError> {
+    for player_index in game.attacked_player_indices() {
+        game.gain_if_available(player_index, card("Curse"), GainDestination::Discard)?;
+        game.ask(
+            player_index,
+            source,
+            Prompt::CardsFromHand {
+                min: 0,
+                max: 1,
+                filter: CardFilter::Named("Curse"),
+            },
+            |game, player_index, answer| {
+                for curse in answer.cards() {
+                    game.trash_card(player_index, curse);
+                }
+                Ok(())
+            },
+        );
+    }
+    Ok(())
+}
+
+fn recruiter(game: &mut Game, source: Card) -> Result<(), GameError> {
+    trash_one(
+        game,
+        game.curr_player_index,
+        source,
+        |game, player_index, trashed| {
+            game.players[player_index].villagers += game.cost_of(trashed).coins;
+            Ok(())
+        },
+    );
+    Ok(())
+}
+
+fn scepter(game: &mut Game, source: Card) -> Result<(), GameError> {
+    let actions: Vec<Card> = distinct(game.current_player_read_only().played.iter().copied())
+        .into_iter()
+        .filter(|card| card.is(CardType::Action))
+        .collect();
+    let mut choices = vec!["+$2".to_owned()];
+    choices.extend(actions.iter().map(|card| format!("Replay {}", card.name())));
+    game.ask(
+        game.curr_player_index,
+        source,
+        Prompt::Options { options: choices },
+        move |game, player_index, answer| match answer.option() {
+            0 => {
+                game.players[player_index].coins += 2;
+                Ok(())
+            }
+            option => game.resolve_play(actions[option - 1]),
+        },
+    );
+    Ok(())
+}
+
+fn scholar(game: &mut Game, _source: Card) -> Result<(), GameError> {
+    let player = game.current_player();
+    player.discard_hand();
+    player.draw(7);
+    Ok(())
+}
+
+fn sculptor(game: &mut Game, source: Card) -> Result<(), GameError> {
+    game.ask(
+        game.curr_player_index,
+        source,
+        gain_from_supply(Cost::coins(4), CardFilter::Any),
+        |game, player_index, answer| {
+            let gained = answer.supply_card();
+            game.gain_card(player_index, gained, GainDestination::Hand)?;
+            if gained.is(CardType::Treasure) {
+                game.players[player_index].villagers += 1;
+            }
+            Ok(())
+        },
+    );
+    Ok(())
+}
+
+fn seer(game: &mut Game, _source: Card) -> Result<(), GameError> {
+    let player_index = game.curr_player_index;
+    let cards = game.players[player_index].reveal_from_deck(3);
+    revealed(game, player_index, &cards);
+    let (taken, rest): (Vec<Card>, Vec<Card>) = cards.into_iter().partition(|&card| {
+        let cost = game.cost_of(card);
+        (2..=4).contains(&cost.coins) && cost.fits_within(&Cost::coins(4))
+    });
+    let player = &mut game.players[player_index];
+    for card in taken {
+        player.add_to_hand(card);
+    }
+    for card in rest.into_iter().rev() {
+        player.put_on_deck(card);
+    }
+    Ok(())
+}
+
+fn spices(game: &mut Game, player_index: usize, _source: Card) {
+    game.players[player_index].coffers += 2;
+}
+
+fn swashbuckler(game: &mut Game, _source: Card) -> Result<(), GameError> {
+    let player_index = game.curr_player_index;
+    let player = &mut game.players[player_index];
+    if player.discard.is_empty() {
+        return Ok(());
+    }
+    player.coffers += 1;
+    if player.coffers >= 4 {
+        game.take_artifact(player_index, "Treasure Chest");
+    }
+    Ok(())
+}
+
+fn treasurer(game: &mut Game, source: Card) -> Result<(), GameError> {
+    game.ask(
+        game.curr_player_index,
+        source,
+        options(&[
+            "Trash a Treasure from your hand",
+            "Gain a Treasure from the trash to your hand",
+            "Take the Key",
+        ]),
+        move |game, player_index, answer| {
+            match answer.option() {
+                0 => game.ask(
+                    player_index,
+                    source,
+                    Prompt::CardsFromHand {
+                        min: 1,
+                        max: 1,
+                        filter: CardFilter::OfType(CardType::Treasure),
+                    },
+                    |game, player_index, answer| {
+                        for treasure in answer.cards() {
+                            game.trash_card(player_index, treasure);
+                        }
+                        Ok(())
+                    },
+                ),
+                1 => {
+                    let treasures: Vec<Card> = distinct(game.trash.cards.iter().copied())
+                        .into_iter()
+                        .filter(|card| card.is(CardType::Treasure))
+                        .collect();
+                    game.ask(
+                        player_index,
+                        source,
+                        Prompt::Options {
+                            options: treasures
+                                .iter()
+                                .map(|card| format!("Gain {}", card.name()))
+                                .collect(),
+                        },
+                        move |game, player_index, answer| {
+                            game.gain_from_trash(
+                                player_index,
+                                treasures[answer.option()],
+                                GainDestination::Hand,
+                            )
+                        },
+                    );
+                }
+                _ => game.take_artifact(player_index, "Key"),
+            }
+            Ok(())
+        },
+    );
+    Ok(())
+}
+
+fn villain(game: &mut Game, source: Card) -> Result<(), GameError> {
+    game.current_player().coffers += 2;
+    for player_index in game.attacked_player_indices() {
+        let hand = &game.players[player_index].hand;
+        if hand.len() < 5 {
+            continue;
+        }
+        let choices: Vec<Card> = distinct(hand.iter().copied())
+            .into_iter()
+            .filter(|&card| game.cost_of(card).coins >= 2)
+            .collect();
+        game.ask(
+            player_index,
+            source,
+            Prompt::Options {
+                options: choices
+                    .iter()
+                    .map(|card| format!("Discard {}", card.name()))
+                    .collect(),
+            },
+            move |game, player_index, answer| {
+                let discarded = choices[answer.option()];
+                let player = &mut game.players[player_index];
+                if let Some(index) = player.hand.iter().position(|&card| card == discarded) {
+                    player.hand.remove(index);
+                    player.add_to_discard(discarded);
+                }
+                Ok(())
+            },
+        );
+    }
+    Ok(())
+}
+
+// PROJECTS
+/// The player an event is about, if they have put a cube on this Project.
+fn project_owner(game: &Game, project: Card, event: Event) -> Option<usize> {
+    let player_index = match event {
+        Event::Gain { player_index, .. }
+        | Event::Play { player_index, .. }
+        | Event::Played { player_index, .. }
+        | Event::Buy { player_index, .. }
+        | Event::BuyPhaseStart { player_index }
+        | Event::BuyPhaseEnd { player_index }
+        | Event::TurnStart { player_index }
+        | Event::Trash { player_index } => player_index,
+    };
+    game.players[player_index]
+        .projects
+        .contains(&project)
+        .then_some(player_index)
+}
+
+/// The player who starts their turn, if they have this Project.
+fn turn_start_owner(game: &Game, project: Card, event: Event) -> Option<usize> {
+    match event {
+        Event::TurnStart { .. } => project_owner(game, project, event),
+        _ => None,
+    }
+}
+
+fn cathedral(game: &mut Game, project: Card, event: Event) {
+    if let Some(player_index) = turn_start_owner(game, project, event) {
+        trash_one(game, player_index, project, |_, _, _| Ok(()));
+    }
+}
+
+fn city_gate(game: &mut Game, project: Card, event: Event) {
+    let Some(player_index) = turn_start_owner(game, project, event) else {
+        return;
+    };
+    game.players[player_index].draw(1);
+    game.ask(
+        player_index,
+        project,
+        from_hand(1, 1),
+        |game, player_index, answer| {
+            let player = &mut game.players[player_index];
+            for card in answer.cards() {
+                player.put_on_deck(card);
+            }
+            Ok(())
+        },
+    );
+}
+
+fn pageant(game: &mut Game, project: Card, event: Event) {
+    if !matches!(event, Event::BuyPhaseEnd { .. }) {
+        return;
+    }
+    let Some(player_index) = project_owner(game, project, event) else {
+        return;
+    };
+    if !game.players[player_index].can_afford(&Cost::coins(1)) {
+        return;
+    }
+    game.ask(
+        player_index,
+        project,
+        options(&["Pay $1 for +1 Coffers", "Don't pay"]),
+        |game, player_index, answer| {
+            if answer.option() == 0 {
+                let player = &mut game.players[player_index];
+                player.pay(&Cost::coins(1));
+                player.coffers += 1;
+            }
+            Ok(())
+        },
+    );
+}
+
+fn sewers(game: &mut Game, project: Card, event: Event) {
+    let Event::Trash { player_index } = event else {
+        return;
+    };
+    if project_owner(game, project, event).is_none()
+        || take(&mut game.players[player_index].trashing_with_sewers)
+    {
+        return;
+    }
+    game.ask(
+        player_index,
+        project,
+        from_hand(0, 1),
+        |game, player_index, answer| {
+            for trashed in answer.cards() {
+                game.players[player_index].trashing_with_sewers = true;
+                game.trash_card(player_index, trashed);
+            }
+            Ok(())
+        },
+    );
+}
+
+/// During its owner's turns, cards cost $1 less.
+fn canal(_game: &Game, cost: Cost) -> Cost {
+    cost.minus_coins(1)
+}
+
+fn exploration(game: &mut Game, project: Card, event: Event) {
+    if !matches!(event, Event::BuyPhaseEnd { .. }) {
+        return;
+    }
+    let Some(player_index) = project_owner(game, project, event) else {
+        return;
+    };
+    if game.history.current_turn.bought.is_empty() {
+        let player = &mut game.players[player_index];
+        player.coffers += 1;
+        player.villagers += 1;
+    }
+}
+
+fn fair(game: &mut Game, project: Card, event: Event) {
+    if let Some(player_index) = turn_start_owner(game, project, event) {
+        game.players[player_index].buys += 1;
+    }
+}
+
+fn silos(game: &mut Game, project: Card, event: Event) {
+    let Some(player_index) = turn_start_owner(game, project, event) else {
+        return;
+    };
+    let coppers = game.players[player_index]
+        .hand
+        .iter()
+        .filter(|card| card.name() == "Copper")
+        .count();
+    game.ask(
+        player_index,
+        project,
+        Prompt::CardsFromHand {
+            min: 0,
+            max: coppers,
+            filter: CardFilter::Named("Copper"),
+        },
+        |game, player_index, answer| {
+            let player = &mut game.players[player_index];
+            let discarded = answer.cards();
+            let count = discarded.len();
+            for copper in discarded {
+                player.add_to_discard(copper);
+            }
+            player.draw(count);
+            Ok(())
+        },
+    );
+}
+
+fn sinister_plot(game: &mut Game, project: Card, event: Event) {
+    let Some(player_index) = turn_start_owner(game, project, event) else {
+        return;
+    };
+    let tokens = game.players[player_index].sinister_plot_tokens;
+    let mut choices = vec!["Add a token to Sinister Plot".to_owned()];
+    if tokens > 0 {
+        choices.push(format!("Remove your {tokens} tokens for +{tokens} Cards"));
+    }
+    game.ask(
+        player_index,
+        project,
+        Prompt::Options { options: choices },
+        |game, player_index, answer| {
+            let player = &mut game.players[player_index];
+            if answer.option() == 0 {
+                player.sinister_plot_tokens += 1;
+            } else {
+                let tokens = take(&mut player.sinister_plot_tokens);
+                player.draw(tokens as usize);
+            }
+            Ok(())
+        },
+    );
+}
+
+fn academy(game: &mut Game, project: Card, event: Event) {
+    if let Event::Gain { card, .. } = event {
+        if let Some(player_index) = project_owner(game, project, event) {
+            if card.is(CardType::Action) {
+                game.players[player_index].villagers += 1;
+            }
+        }
+    }
+}
+
+fn guildhall(game: &mut Game, project: Card, event: Event) {
+    if let Event::Gain { card, .. } = event {
+        if let Some(player_index) = project_owner(game, project, event) {
+            if card.is(CardType::Treasure) {
+                game.players[player_index].coffers += 1;
+            }
+        }
+    }
+}
+
+fn piazza(game: &mut Game, project: Card, event: Event) {
+    let Some(player_index) = turn_start_owner(game, project, event) else {
+        return;
+    };
+    let Some(top) = game.players[player_index].take_from_deck() else {
+        return;
+    };
+    revealed(game, player_index, &[top]);
+    if !top.is(CardType::Action) || !top.is_implemented() {
+        game.players[player_index].put_on_deck(top);
+        return;
+    }
+    game.then(move |game| {
+        game.play_card(top)?;
+        game.resolve_play(top)
+    });
+}
+
+fn road_network(game: &mut Game, project: Card, event: Event) {
+    let Event::Gain { player_index, card } = event else {
+        return;
+    };
+    if !card.is(CardType::Victory) {
+        return;
+    }
+    for (owner, player) in game.players.iter_mut().enumerate() {
+        if owner != player_index && player.projects.contains(&project) {
+            player.draw(1);
+        }
+    }
+}
+
+fn barracks(game: &mut Game, project: Card, event: Event) {
+    if let Some(player_index) = turn_start_owner(game, project, event) {
+        game.players[player_index].actions += 1;
+    }
+}
+
+fn crop_rotation(game: &mut Game, project: Card, event: Event) {
+    let Some(player_index) = turn_start_owner(game, project, event) else {
+        return;
+    };
+    game.ask(
+        player_index,
+        project,
+        Prompt::CardsFromHand {
+            min: 0,
+            max: 1,
+            filter: CardFilter::OfType(CardType::Victory),
+        },
+        |game, player_index, answer| {
+            let player = &mut game.players[player_index];
+            for victory in answer.cards() {
+                player.add_to_discard(victory);
+                player.draw(2);
+            }
+            Ok(())
+        },
+    );
+}
+
+fn innovation(game: &mut Game, project: Card, event: Event) {
+    let Event::Gain { player_index, card } = event else {
+        return;
+    };
+    if player_index != game.curr_player_index
+        || !card.is(CardType::Action)
+        || !card.is_implemented()
+        || project_owner(game, project, event).is_none()
+    {
+        return;
+    }
+    let actions_gained = game
+        .history
+        .current_turn
+        .gained_by(player_index)
+        .filter(|gained| gained.is(CardType::Action))
+        .count();
+    if actions_gained != 1 {
+        return;
+    }
+    game.ask(
+        player_index,
+        project,
+        Prompt::Options {
+            options: vec![format!("Play {}", card.name()), "Don't play it".to_owned()],
+        },
+        move |game, player_index, answer| {
+            let player = &mut game.players[player_index];
+            let in_discard = player.discard.iter().rposition(|&gained| gained == card);
+            if let (0, Some(index)) = (answer.option(), in_discard) {
+                player.discard.remove(index);
+                game.play_card(card)?;
+                game.resolve_play(card)?;
+            }
+            Ok(())
+        },
+    );
+}
+
+fn citadel(game: &mut Game, project: Card, event: Event) {
+    let Some(owner) = turn_start_owner(game, project, event) else {
+        return;
+    };
+    // Wait for the turn's first Action, then for it to finish
+    game.add_trigger(Expiry::EndOfTurn, move |game, event| {
+        let Event::Play { player_index, card } = event else {
+            return Ok(true);
+        };
+        if player_index != owner || !card.is(CardType::Action) {
+            return Ok(true);
+        }
+        game.add_trigger(Expiry::EndOfTurn, move |game, event| {
+            if !matches!(event, Event::Played { card: played, .. } if played == card) {
+                return Ok(true);
+            }
+            game.then(move |game| game.resolve_play(card));
+            Ok(false)
+        });
+        Ok(false)
+    });
+}
+
+// ARTIFACTS
+fn holds(game: &Game, player_index: usize, artifact: Card) -> bool {
+    game.players[player_index].artifacts.contains(&artifact)
+}
+
+fn horn(game: &mut Game, artifact: Card, event: Event) {
+    // Border Guard is discarded from play at Clean-up, straight after the Buy phase
+    let Event::BuyPhaseEnd { player_index } = event else {
+        return;
+    };
+    let border_guard = card("Border Guard");
+    if !holds(game, player_index, artifact)
+        || !game.players[player_index].played.contains(&border_guard)
+    {
+        return;
+    }
+    game.ask(
+        player_index,
+        artifact,
+        options(&["Put a Border Guard onto your deck", "Discard it as usual"]),
+        move |game, player_index, answer| {
+            let player = &mut game.players[player_index];
+            if answer.option() == 0 {
+                if let Some(card) = player.take_from_play(border_guard) {
+                    player.put_on_deck(card);
+                }
+            }
+            Ok(())
+        },
+    );
+}
+
+fn key(game: &mut Game, artifact: Card, event: Event) {
+    if let Event::TurnStart { player_index } = event {
+        if holds(game, player_index, artifact) {
+            game.players[player_index].coins += 1;
+        }
+    }
+}
+
+fn treasure_chest(game: &mut Game, artifact: Card, event: Event) {
+    if let Event::BuyPhaseStart { player_index } = event {
+        if holds(game, player_index, artifact) {
+            game.then(move |game| {
+                game.gain_if_available(player_index, card("Copper"), GainDestination::Discard)
+            });
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::cards::testing::{choose_option, end_turn, play, scenario};
+    use crate::{GameMove, GamePhase};
+
+    const KINGDOM: [&str; 12] = [
+        "Border Guard",
+        "Flag Bearer",
+        "Acting Troupe",
+        "Inventor",
+        "Mountain Village",
+        "Patron",
+        "Priest",
+        "Research",
+        "Treasurer",
+        "Villain",
+        "Academy",
+        "Barracks",
+    ];
+
+    #[test]
+    fn villagers_are_saved_up_and_spent_for_actions() {
+        let mut game = scenario(&KINGDOM, [&["Acting Troupe", "Patron"], &[]]);
+        let player_index = game.curr_player_index;
+        play(&mut game, "Acting Troupe");
+        assert_eq!(game.trash.cards, [card("Acting Troupe")]);
+        let player = game.current_player_read_only();
+        assert_eq!((player.villagers, player.actions), (4, 0));
+        assert!(game.legal_moves().contains(&GameMove::SpendVillager));
+
+        game.accept_move(player_index, GameMove::SpendVillager)
+            .unwrap();
+
+        let player = game.current_player_read_only();
+        assert_eq!((player.villagers, player.actions), (3, 1));
+        // Only while there is an Action left to play
+        play(&mut game, "Patron");
+        assert!(!game.legal_moves().contains(&GameMove::SpendVillager));
+    }
+
+    #[test]
+    fn a_project_is_only_bought_once_and_acts_for_its_owner() {
+        let mut game = scenario(&KINGDOM, [&[], &[]]);
+        let owner = game.curr_player_index;
+        game.game_phase = GamePhase::BuyPhase;
+        let player = game.current_player();
+        player.coins = 12;
+        player.buys = 2;
+        let barracks = GameMove::BuyCard {
+            card: card("Barracks"),
+        };
+
+        game.accept_move(owner, barracks.clone()).unwrap();
+        assert!(game.accept_move(owner, barracks).is_err());
+        assert_eq!(game.current_player_read_only().projects, [card("Barracks")]);
+
+        end_turn(&mut game);
+        assert_eq!(game.current_player_read_only().actions, 1);
+        end_turn(&mut game);
+        assert_eq!(game.players[owner].actions, 2);
+    }
+
+    #[test]
+    fn academy_gives_a_villager_for_each_action_gained() {
+        let mut game = scenario(&KINGDOM, [&[], &[]]);
+        let owner = game.curr_player_index;
+        let other = (owner + 1) % 2;
+        game.players[owner].projects.push(card("Academy"));
+
+        for gained in ["Patron", "Silver"] {
+            game.gain_card(owner, card(gained), GainDestination::Discard)
+                .unwrap();
+        }
+        game.gain_card(other, card("Patron"), GainDestination::Discard)
+            .unwrap();
+        game.run_pending().unwrap();
+
+        assert_eq!(game.players[owner].villagers, 1);
+        assert_eq!(game.players[other].villagers, 0);
+    }
+
+    #[test]
+    fn an_artifact_moves_to_whoever_takes_it_last() {
+        let mut game = scenario(&KINGDOM, [&[], &[]]);
+        let player_index = game.curr_player_index;
+        let other = (player_index + 1) % 2;
+        let flag_bearer = card("Flag Bearer");
+
+        game.gain_card(player_index, flag_bearer, GainDestination::Discard)
+            .unwrap();
+        assert!(game.players[player_index].has_artifact("Flag"));
+
+        game.gain_card(other, flag_bearer, GainDestination::Discard)
+            .unwrap();
+        assert!(!game.players[player_index].has_artifact("Flag"));
+        assert!(game.players[other].has_artifact("Flag"));
+    }
+
+    #[test]
+    fn the_key_gives_its_holder_a_coin_at_the_start_of_each_turn() {
+        let mut game = scenario(&KINGDOM, [&["Treasurer"], &[]]);
+        let owner = game.curr_player_index;
+        play(&mut game, "Treasurer");
+        choose_option(&mut game, 2);
+        assert!(game.players[owner].has_artifact("Key"));
+
+        end_turn(&mut game);
+        assert_eq!(game.current_player_read_only().coins, 0);
+        end_turn(&mut game);
+        assert_eq!(game.players[owner].coins, 1);
+    }
+}
@@ -0,0 +1,702 @@
+//! Behaviours for the Seaside (2nd edition) kingdom cards. Most of them are
+//! Duration cards, which schedule the rest of their effect for the start of the
+//! player's next turn with `Game::at_start_of_turn`.
+
+use super::{
+    card, choose_one, discard_all, discard_chosen, from_hand, next_turn, options,
+    protects_from_play, Behaviour, Card, CardFilter, CardType, Cost,
+};
+use crate::{Event, Expiry, ExtraTurn, GainDestination, Game, GameError, Mat, Prompt};
+
+pub(super) fn behaviours() -> Vec<(&'static str, Behaviour)> {
+    let play = |on_play| Behaviour {
+        on_play: Some(on_play),
+        ..Behaviour::default()
+    };
+    vec![
+        ("Haven", play(haven)),
+        (
+            "Lighthouse",
+            Behaviour {
+                protects: Some(protects_from_play),
+                ..play(lighthouse)
+            },
+        ),
+        ("Native Village", play(native_village)),
+        ("Astrolabe", play(astrolabe)),
+        ("Fishing Village", play(fishing_village)),
+        ("Lookout", play(lookout)),
+        ("Monkey", play(monkey)),
+        ("Sea Chart", play(sea_chart)),
+        ("Smugglers", play(smugglers)),
+        ("Warehouse", play(warehouse)),
+        ("Blockade", play(blockade)),
+        ("Caravan", play(caravan)),
+        ("Cutpurse", play(cutpurse)),
+        ("Island", play(island)),
+        ("Salvager", play(salvager)),
+        ("Tide Pools", play(tide_pools)),
+        ("Treasure Map", play(treasure_map)),
+        ("Corsair", play(corsair)),
+        ("Merchant Ship", play(merchant_ship)),
+        ("Outpost", play(outpost)),
+        (
+            "Pirate",
+            Behaviour {
+                on_gain_in_hand: Some(pirate_reaction),
+                ..play(pirate)
+            },
+        ),
+        ("Sailor", play(sailor)),
+        ("Sea Witch", play(sea_witch)),
+        ("Tactician", play(tactician)),
+        ("Treasury", play(treasury)),
+        ("Wharf", play(wharf)),
+    ]
+}
+
+fn haven(game: &mut Game, source: Card) -> Result<(), GameError> {
+    game.ask(
+        game.curr_player_index,
+        source,
+        from_hand(1, 1),
+        move |game, _, answer| {
+            let set_aside = answer.cards();
+            next_turn(game, source, move |game, player_index| {
+                for card in set_aside {
+                    game.players[player_index].add_to_hand(card);
+                }
+                Ok(())
+            });
+            Ok(())
+        },
+    );
+    Ok(())
+}
+
+fn lighthouse(game: &mut Game, source: Card) -> Result<(), GameError> {
+    next_turn(game, source, |game, player_index| {
+        game.players[player_index].coins += 1;
+        Ok(())
+    });
+    Ok(())
+}
+
+fn native_village(game: &mut Game, source: Card) -> Result<(), GameError> {
+    choose_one(
+        game,
+        source,
+        &[
+            "Put the top card of your deck on your Native Village mat",
+            "Put all the cards from your mat into your hand",
+        ],
+        |game, player_index, ability| {
+            let player = &mut game.players[player_index];
+            if ability == 0 {
+                let top = player.reveal_from_deck(1);
+                player.mat_mut(Mat::NativeVillage).extend(top);
+            } else {
+                let cards = std::mem::take(player.mat_mut(Mat::NativeVillage));
+                player.hand.extend(cards);
+            }
+            Ok(())
+        },
+    );
+    Ok(())
+}
+
+fn astrolabe(game: &mut Game, source: Card) -> Result<(), GameError> {
+    next_turn(game, source, |game, player_index| {
+        let player = &mut game.players[player_index];
+        player.coins += 1;
+        player.buys += 1;
+        Ok(())
+    });
+    Ok(())
+}
+
+fn fishing_village(game: &mut Game, source: Card) -> Result<(), GameError> {
+    next_turn(game, source, |game, player_index| {
+        let player = &mut game.players[player_index];
+        player.actions += 1;
+        player.coins += 1;
+        Ok(())
+    });
+    Ok(())
+}
+
+fn lookout(game: &mut Game, source: Card) -> Result<(), GameError> {
+    let revealed = game.current_player().reveal_from_deck(3);
+    lookout_choose(game, source, revealed, true);
+    Ok(())
+}
+
+/// Has the player trash one of the looked-at cards, then discard one, then
+/// puts the last one back.
+fn lookout_choose(game: &mut Game, source: Card, mut cards: Vec<Card>, trashing: bool) {
+    let player_index = game.curr_player_index;
+    let verb = if trashing { "Trash" } else { "Discard" };
+    let options = cards
+        .iter()
+        .map(|card| format!("{} {}", verb, card.name()))
+        .collect();
+    game.ask(
+        player_index,
+        source,
+        Prompt::Options { options },
+        move |game, player_index, answer| {
+            let card = cards.remove(answer.option());
+            if trashing {
+                game.trash_card(player_index, card);
+                lookout_choose(game, source, cards, false);
+            } else {
+                game.players[player_index].add_to_discard(card);
+                for card in cards {
+                    game.players[player_index].put_on_deck(card);
+                }
+            }
+            Ok(())
+        },
+    );
+}
+
+fn monkey(game: &mut Game, source: Card) -> Result<(), GameError> {
+    let owner = game.curr_player_index;
+    let right = (owner + game.players.len() - 1) % game.players.len();
+    game.add_trigger(Expiry::StartOfTurn(owner), move |game, event| {
+        if let Event::Gain { player_index, .. } = event {
+            if player_index == right && right != owner {
+                game.players[owner].draw(1);
+            }
+        }
+        Ok(true)
+    });
+    next_turn(game, source, |game, player_index| {
+        game.players[player_index].draw(1);
+        Ok(())
+    });
+    Ok(())
+}
+
+fn sea_chart(game: &mut Game, _source: Card) -> Result<(), GameError> {
+    let player = game.current_player();
+    for card in player.reveal_from_deck(1) {
+        if player.played.contains(&card) {
+            player.add_to_hand(card);
+        } else {
+            player.put_on_deck(card);
+        }
+    }
+    Ok(())
+}
+
+fn smugglers(game: &mut Game, source: Card) -> Result<(), GameError> {
+    let right = (game.curr_player_index + game.players.len() - 1) % game.players.len();
+    let mut gained: Vec<Card> = game
+        .history
+        .last_turn(right)
+        .map(|turn| turn.gained_by(right).collect())
+        .unwrap_or_default();
+    gained.retain(|&card| {
+        game.cost_of(card).fits_within(&Cost::coins(6)) && game.supply.count(card).unwrap_or(0) > 0
+    });
+    gained.sort_by_key(|card| card.name());
+    gained.dedup();
+    let options = gained
+        .iter()
+        .map(|card| format!("Gain {}", card.name()))
+        .collect();
+    game.ask(
+        game.curr_player_index,
+        source,
+        Prompt::Options { options },
+        move |game, player_index, answer| {
+            game.gain_card(
+                player_index,
+                gained[answer.option()],
+                GainDestination::Discard,
+            )
+        },
+    );
+    Ok(())
+}
+
+fn warehouse(game: &mut Game, source: Card) -> Result<(), GameError> {
+    discard_chosen(game, game.curr_player_index, source, 3);
+    Ok(())
+}
+
+fn blockade(game: &mut Game, source: Card) -> Result<(), GameError> {
+    let owner = game.curr_player_index;
+    let attacked = game.attacked_player_indices();
+    game.ask(
+        owner,
+        source,
+        Prompt::CardFromSupply {
+            max_cost: Cost::coins(4),
+            exact: false,
+            filter: CardFilter::Any,
+        },
+        move |game, player_index, answer| {
+            let set_aside = answer.supply_card();
+            game.gain_card(player_index, set_aside, GainDestination::SetAside)?;
+            game.add_trigger(Expiry::StartOfTurn(owner), move |game, event| {
+                if let Event::Gain { player_index, card } = event {
+                    if card == set_aside
+                        && player_index == game.curr_player_index
+                        && attacked.contains(&player_index)
+                    {
+                        game.gain_if_available(
+                            player_index,
+                            super::card("Curse"),
+                            GainDestination::Discard,
+                        )?;
+                    }
+                }
+                Ok(true)
+            });
+            next_turn(game, source, move |game, player_index| {
+                game.players[player_index].add_to_hand(set_aside);
+                Ok(())
+            });
+            Ok(())
+        },
+    );
+    Ok(())
+}
+
+fn caravan(game: &mut Game, source: Card) -> Result<(), GameError> {
+    next_turn(game, source, |game, player_index| {
+        game.players[player_index].draw(1);
+        Ok(())
+    });
+    Ok(())
+}
+
+fn cutpurse(game: &mut Game, _source: Card) -> Result<(), GameError> {
+    for victim in game.attacked_player_indices() {
+        let player = &mut game.players[victim];
+        if let Some(index) = player.hand.iter().position(|card| card.name() == "Copper") {
+            let copper = player.hand.remove(index);
+            player.add_to_discard(copper);
+        }
+    }
+    Ok(())
+}
+
+fn island(game: &mut Game, source: Card) -> Result<(), GameError> {
+    let player = game.current_player();
+    if let Some(island) = player.take_from_play(source) {
+        player.mat_mut(Mat::Island).push(island);
+    }
+    game.ask(
+        game.curr_player_index,
+        source,
+        from_hand(1, 1),
+        |game, player_index, answer| {
+            let player = &mut game.players[player_index];
+            player.mat_mut(Mat::Island).extend(answer.cards());
+            Ok(())
+        },
+    );
+    Ok(())
+}
+
+fn salvager(game: &mut Game, source: Card) -> Result<(), GameError> {
+    game.ask(
+        game.curr_player_index,
+        source,
+        from_hand(1, 1),
+        |game, player_index, answer| {
+            for card in answer.cards() {
+                let coins = game.cost_of(card).coins;
+                game.trash_card(player_index, card);
+                game.players[player_index].coins += coins;
+            }
+            Ok(())
+        },
+    );
+    Ok(())
+}
+
+fn tide_pools(game: &mut Game, source: Card) -> Result<(), GameError> {
+    next_turn(game, source, move |game, player_index| {
+        discard_chosen(game, player_index, source, 2);
+        Ok(())
+    });
+    Ok(())
+}
+
+fn treasure_map(game: &mut Game, source: Card) -> Result<(), GameError> {
+    let player_index = game.curr_player_index;
+    let player = game.current_player();
+    let this = player.take_from_play(source);
+    let other = player
+        .hand
+        .iter()
+        .position(|&card| card == source)
+        .map(|index| player.hand.remove(index));
+    for map in this.iter().chain(other.iter()) {
+        game.trash_card(player_index, *map);
+    }
+    if this.is_some() && other.is_some() {
+        for _ in 0..4 {
+            game.gain_if_available(player_index, card("Gold"), GainDestination::Deck)?;
+        }
+    }
+    Ok(())
+}
+
+fn corsair(game: &mut Game, source: Card) -> Result<(), GameError> {
+    let owner = game.curr_player_index;
+    let attacked = game.attacked_player_indices();
+    game.add_trigger(Expiry::StartOfTurn(owner), move |game, event| {
+        if let Event::Play { player_index, card } = event {
+            let this_turn = &game.history.current_turn;
+            let is_first = this_turn.played_count("Silver") + this_turn.played_count("Gold") == 1;
+            if attacked.contains(&player_index)
+                && (card.name() == "Silver" || card.name() == "Gold")
+                && is_first
+            {
+                if let Some(card) = game.players[player_index].take_from_play(card) {
+                    game.trash_card(player_index, card);
+                }
+            }
+        }
+        Ok(true)
+    });
+    next_turn(game, source, |game, player_index| {
+        game.players[player_index].draw(1);
+        Ok(())
+    });
+    Ok(())
+}
+
+fn merchant_ship(game: &mut Game, source: Card) -> Result<(), GameError> {
+    next_turn(game, source, |game, player_index| {
+        game.players[player_index].coins += 2;
+        Ok(())
+    });
+    Ok(())
+}
+
+fn outpost(game: &mut Game, source: Card) -> Result<(), GameError> {
+    let this_turn = &game.history.current_turn;
+    if this_turn.played_count("Outpost") > 1 || this_turn.extra_turn {
+        return Ok(());
+    }
+    game.extra_turns.push_back(ExtraTurn {
+        player_index: game.curr_player_index,
+        hand_size: 3,
+    });
+    // Outpost has nothing left to do, but stays in play until the extra turn
+    next_turn(game, source, |_, _| Ok(()));
+    Ok(())
+}
+
+fn pirate(game: &mut Game, source: Card) -> Result<(), GameError> {
+    let player_index = game.curr_player_index;
+    pirate_next_turn(game, player_index, source);
+    Ok(())
+}
+
+fn pirate_next_turn(game: &mut Game, player_index: usize, source: Card) {
+    game.at_start_of_turn(player_index, source, move |game| {
+        game.ask(
+            player_index,
+            source,
+            Prompt::CardFromSupply {
+                max_cost: Cost::coins(6),
+                exact: false,
+                filter: CardFilter::OfType(CardType::Treasure),
+            },
+            |game, player_index, answer| {
+                game.gain_card(player_index, answer.supply_card(), GainDestination::Hand)
+            },
+        );
+        Ok(())
+    });
+}
+
+fn pirate_reaction(
+    game: &mut Game,
+    player_index: usize,
+    pirate: Card,
+    _gainer_index: usize,
+    gained: Card,
+) {
+    if !gained.is(CardType::Treasure) {
+        return;
+    }
+    game.ask(
+        player_index,
+        pirate,
+        options(&["Play Pirate", "Don't play it"]),
+        move |game, player_index, answer| {
+            let player = &mut game.players[player_index];
+            let in_hand = player.hand.iter().position(|&card| card == pirate);
+            if let (0, Some(index)) = (answer.option(), in_hand) {
+                let pirate = player.hand.remove(index);
+                player.play_card(pirate);
+                pirate_next_turn(game, player_index, pirate);
+            }
+            Ok(())
+        },
+    );
+}
+
+fn sailor(game: &mut Game, source: Card) -> Result<(), GameError> {
+    let owner = game.curr_player_index;
+    game.add_trigger(Expiry::EndOfTurn, move |game, event| {
+        let Event::Gain { player_index, card } = event else {
+            return Ok(true);
+        };
+        if player_index != owner || !card.is(CardType::Duration) {
+            return Ok(true);
+        }
+        // The offer uses up the "once this turn", whether or not it is taken
+        game.ask(
+            owner,
+            source,
+            Prompt::Options {
+                options: vec![format!("Play {}", card.name()), "Don't play it".to_owned()],
+            },
+            move |game, player_index, answer| {
+                let player = &mut game.players[player_index];
+                let in_discard = player.discard.iter().rposition(|&gained| gained == card);
+                if let (0, Some(index)) = (answer.option(), in_discard) {
+                    player.discard.remove(index);
+                    game.play_card(card)?;
+                    game.resolve_play(card)?;
+                }
+                Ok(())
+            },
+        );
+        Ok(false)
+    });
+    next_turn(game, source, move |game, player_index| {
+        game.players[player_index].coins += 2;
+        game.ask(
+            player_index,
+            source,
+            from_hand(0, 1),
+            |game, player_index, answer| {
+                for card in answer.cards() {
+                    game.trash_card(player_index, card);
+                }
+                Ok(())
+            },
+        );
+        Ok(())
+    });
+    Ok(())
+}
+
+fn sea_witch(game: &mut Game, source: Card) -> Result<(), GameError> {
+    for player_index in game.attacked_player_indices() {
+        game.gain_if_available(player_index, card("Curse"), GainDestination::Discard)?;
+    }
+    next_turn(game, source, move |game, player_index| {
+        game.players[player_index].draw(2);
+        discard_chosen(game, player_index, source, 2);
+        Ok(())
+    });
+    Ok(())
+}
+
+fn tactician(game: &mut Game, source: Card) -> Result<(), GameError> {
+    if game.current_player_read_only().hand.is_empty() {
+        return Ok(());
+    }
+    let hand = std::mem::take(&mut game.current_player().hand);
+    discard_all(game, game.curr_player_index, hand);
+    next_turn(game, source, |game, player_index| {
+        let player = &mut game.players[player_index];
+        player.draw(5);
+        player.actions += 1;
+        player.buys += 1;
+        Ok(())
+    });
+    Ok(())
+}
+
+fn treasury(game: &mut Game, source: Card) -> Result<(), GameError> {
+    let owner = game.curr_player_index;
+    game.add_trigger(Expiry::EndOfTurn, move |game, event| {
+        let Event::BuyPhaseEnd { player_index } = event else {
+            return Ok(true);
+        };
+        let gained_victory = game
+            .history
+            .current_turn
+            .gained_by(player_index)
+            .any(|card| card.is(CardType::Victory));
+        if player_index == owner && !gained_victory {
+            game.ask(
+                owner,
+                source,
+                options(&["Put Treasury onto your deck", "Discard it as normal"]),
+                move |game, player_index, answer| {
+                    let player = &mut game.players[player_index];
+                    if answer.option() == 0 {
+                        if let Some(treasury) = player.take_from_play(source) {
+                            player.put_on_deck(treasury);
+                        }
+                    }
+                    Ok(())
+                },
+            );
+        }
+        Ok(false)
+    });
+    Ok(())
+}
+
+fn wharf(game: &mut Game, source: Card) -> Result<(), GameError> {
+    next_turn(game, source, |game, player_index| {
+        let player = &mut game.players[player_index];
+        player.draw(2);
+        player.buys += 1;
+        Ok(())
+    });
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::cards::testing::{choose_cards, end_turn, play, scenario};
+
+    const KINGDOM: [&str; 10] = [
+        "Haven",
+        "Lighthouse",
+        "Warehouse",
+        "Caravan",
+        "Cutpurse",
+        "Island",
+        "Merchant Ship",
+        "Outpost",
+        "Tactician",
+        "Wharf",
+    ];
+
+    fn count(cards: &[Card], name: &str) -> usize {
+        cards.iter().filter(|card| card.name() == name).count()
+    }
+
+    #[test]
+    fn durations_stay_in_play_until_the_turn_they_finish_on() {
+        let mut game = scenario(&KINGDOM, [&["Caravan", "Merchant Ship"], &[]]);
+        let owner = game.curr_player_index;
+        // Enough not to reshuffle the discard pile
+        game.current_player().deck = vec![card("Silver"); 20];
+        play(&mut game, "Caravan");
+        play(&mut game, "Merchant Ship");
+
+        end_turn(&mut game);
+        let player = &game.players[owner];
+        assert_eq!(player.durations, [card("Caravan"), card("Merchant Ship")]);
+        assert!(player.played.is_empty());
+
+        end_turn(&mut game);
+        let player = game.current_player_read_only();
+        assert_eq!(player.played, [card("Caravan"), card("Merchant Ship")]);
+        assert_eq!(count(&player.hand, "Silver"), 6);
+        assert_eq!(player.coins, 2);
+
+        end_turn(&mut game);
+        let player = &game.players[owner];
+        assert!(player.durations.is_empty());
+        assert_eq!(count(&player.discard, "Caravan"), 1);
+        assert_eq!(count(&player.discard, "Merchant Ship"), 1);
+    }
+
+    #[test]
+    fn haven_sets_a_card_aside_until_next_turn() {
+        let mut game = scenario(&KINGDOM, [&["Haven", "Gold"], &[]]);
+        let owner = game.curr_player_index;
+        play(&mut game, "Haven");
+        choose_cards(&mut game, &["Gold"]);
+        assert_eq!(count(&game.players[owner].hand, "Gold"), 0);
+
+        end_turn(&mut game);
+        assert_eq!(count(&game.players[owner].hand, "Gold"), 0);
+        end_turn(&mut game);
+
+        assert_eq!(count(&game.players[owner].hand, "Gold"), 1);
+    }
+
+    #[test]
+    fn outpost_gives_one_extra_turn_with_a_three_card_hand() {
+        let mut game = scenario(&KINGDOM, [&["Outpost"], &[]]);
+        let owner = game.curr_player_index;
+        play(&mut game, "Outpost");
+
+        end_turn(&mut game);
+        assert_eq!(game.curr_player_index, owner);
+        assert!(game.history.current_turn.extra_turn);
+        let player = game.current_player_read_only();
+        assert_eq!(player.hand.len(), 3);
+        assert_eq!(player.played, [card("Outpost")]);
+
+        // Outpost's extra turn can't lead to another one
+        game.current_player().hand.push(card("Outpost"));
+        play(&mut game, "Outpost");
+        end_turn(&mut game);
+        assert_ne!(game.curr_player_index, owner);
+    }
+
+    #[test]
+    fn lighthouse_protects_from_attacks_until_next_turn() {
+        let mut game = scenario(&KINGDOM, [&["Lighthouse"], &[]]);
+        play(&mut game, "Lighthouse");
+
+        end_turn(&mut game);
+
+        assert!(game.attacked_player_indices().is_empty());
+    }
+
+    #[test]
+    fn tactician_trades_this_hand_for_more_next_turn() {
+        let mut game = scenario(&KINGDOM, [&["Tactician", "Copper", "Estate"], &[]]);
+        let owner = game.curr_player_index;
+        play(&mut game, "Tactician");
+        let player = game.current_player_read_only();
+        assert!(player.hand.is_empty());
+        assert_eq!(player.discard, [card("Copper"), card("Estate")]);
+
+        end_turn(&mut game);
+        end_turn(&mut game);
+
+        let player = &game.players[owner];
+        assert_eq!(player.hand.len(), 10);
+        assert_eq!((player.actions, player.buys), (2, 2));
+    }
+
+    #[test]
+    fn tactician_with_an_empty_hand_is_discarded_as_usual() {
+        let mut game = scenario(&KINGDOM, [&["Tactician"], &[]]);
+        let owner = game.curr_player_index;
+        play(&mut game, "Tactician");
+
+        end_turn(&mut game);
+
+        let player = &game.players[owner];
+        assert!(player.durations.is_empty());
+        assert_eq!(count(&player.discard, "Tactician"), 1);
+    }
+
+    #[test]
+    #[cfg(feature = "hinterlands")]
+    fn tactician_lets_a_discarded_tunnel_react() {
+        use crate::cards::testing::choose_option;
+
+        let mut game = scenario(&KINGDOM, [&["Tactician", "Tunnel"], &[]]);
+        play(&mut game, "Tactician");
+
+        choose_option(&mut game, 0);
+
+        let player = game.current_player_read_only();
+        assert_eq!(player.discard, [card("Tunnel"), card("Gold")]);
+    }
+}
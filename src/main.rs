@@ -1,12 +1,29 @@
-use std::{any::Any, collections::HashMap, fmt::Debug, mem::take};
+mod cards;
+mod simulate;
+mod strategy;
+
+use std::{
+    collections::{HashMap, HashSet, VecDeque},
+    fmt::Debug,
+    mem::take,
+};
 
 use rand::{
     rng,
+    rngs::StdRng,
     seq::{IteratorRandom, SliceRandom},
+    Rng, SeedableRng,
 };
 
 use thiserror::Error;
 
+use strategy::{PlayerView, Strategy, StrategySpec};
+
+use cards::{
+    all_cards, card, cards_in, expansions, find_card, find_card_among, find_expansion, Card,
+    CardFilter, CardType, Cost, LookupError,
+};
+
 #[derive(Debug, Error)]
 pub enum GameError {
     #[error("Card not found in hand: {0}")]
@@ -19,436 +36,762 @@ pub enum GameError {
     CardSupplyDepleted(String),
 
     #[error("Not enough money: required {required}, had {available}")]
-    NotEnoughMoney { required: u32, available: u32 },
+    NotEnoughMoney { required: Cost, available: Cost },
 
     #[error("Invalid move: {0}")]
     InvalidMove(String),
 
-    #[error("Failed to downcast card type: {0}")]
-    FailedToDowncast(String),
-
     #[error("Supply pile empty: {0}")]
     EmptySupply(String),
-}
-
-fn shuffle_vec_inplace<T>(vec: &mut Vec<T>) {
-    vec.shuffle(&mut rng());
-}
-
-#[derive(Debug, Clone, PartialEq, Eq)]
-enum CardType {
-    Treasure,
-    Action,
-    Victory,
-    Curse,
-}
 
-trait Card: Debug {
-    fn name(&self) -> &str;
-    fn card_type(&self) -> CardType;
-    fn cost(&self) -> u32;
+    #[error("Invalid choice: {0}")]
+    InvalidChoice(String),
 
-    fn as_any(&self) -> &dyn Any;
+    #[error("Invalid kingdom: {0}")]
+    InvalidKingdom(String),
 
-    fn as_treasure(&self) -> Result<&Treasure, GameError> {
-        self.as_any()
-            .downcast_ref()
-            .ok_or(GameError::FailedToDowncast("Treasure".to_owned()))
-    }
-    fn as_action(&self) -> Result<&Action, GameError> {
-        self.as_any()
-            .downcast_ref()
-            .ok_or(GameError::FailedToDowncast("Action".to_owned()))
-    }
-    fn as_victory(&self) -> Result<&Victory, GameError> {
-        self.as_any()
-            .downcast_ref()
-            .ok_or(GameError::FailedToDowncast("Victory".to_owned()))
-    }
-    fn as_curse(&self) -> Result<&Curse, GameError> {
-        self.as_any()
-            .downcast_ref()
-            .ok_or(GameError::FailedToDowncast("Curse".to_owned()))
-    }
-}
+    #[error("Invalid players: {0}")]
+    InvalidPlayers(String),
 
-#[derive(Debug, Clone)]
-enum Treasure {
-    Copper,
-    Silver,
-    Gold,
+    #[error("The game is over")]
+    GameOver,
 }
 
-impl Treasure {
-    fn value(&self) -> u32 {
-        match self {
-            Treasure::Copper => 1,
-            Treasure::Silver => 2,
-            Treasure::Gold => 3,
-        }
-    }
+fn shuffle_vec_inplace<T>(vec: &mut [T], rng: &mut StdRng) {
+    vec.shuffle(rng);
 }
 
-impl Card for Treasure {
-    fn name(&self) -> &str {
-        match self {
-            Treasure::Copper => "Copper",
-            Treasure::Silver => "Silver",
-            Treasure::Gold => "Gold",
-        }
-    }
-
-    fn card_type(&self) -> CardType {
-        CardType::Treasure
+/// All ways of picking `size` items from `items`, preserving their order.
+fn combinations<T: Clone>(items: &[T], size: usize) -> Vec<Vec<T>> {
+    if size == 0 {
+        return vec![Vec::new()];
     }
-
-    fn cost(&self) -> u32 {
-        match self {
-            Treasure::Copper => 0,
-            Treasure::Silver => 3,
-            Treasure::Gold => 6,
-        }
-    }
-
-    fn as_any(&self) -> &dyn Any {
-        self
+    if items.len() < size {
+        return Vec::new();
     }
+    let mut with_first: Vec<Vec<T>> = combinations(&items[1..], size - 1)
+        .into_iter()
+        .map(|mut rest| {
+            rest.insert(0, items[0].clone());
+            rest
+        })
+        .collect();
+    with_first.extend(combinations(&items[1..], size));
+    with_first
 }
 
-#[derive(Debug, Clone)]
-enum Victory {
-    Estate,
-    Duchy,
-    Province,
+/// Whether a card makes an ordinary kingdom pile of its own, with nothing
+/// else to set up alongside it, e.g. to be Young Witch's Bane.
+#[cfg(any(feature = "cornucopia", feature = "menagerie"))]
+fn is_plain_kingdom_card(card: Card) -> bool {
+    let info = card.info();
+    let special_types = [
+        CardType::Ruins,
+        CardType::Shelter,
+        CardType::Knight,
+        CardType::Event,
+        CardType::Landmark,
+        CardType::Heirloom,
+        CardType::Fate,
+        CardType::Doom,
+        CardType::Boon,
+        CardType::Hex,
+        CardType::State,
+        CardType::Project,
+        CardType::Artifact,
+        CardType::Way,
+        CardType::Prize,
+        CardType::Ally,
+        CardType::Trait,
+    ];
+    !info.basic
+        && !info.non_supply
+        && info.pile.is_empty()
+        && info.heirloom.is_empty()
+        && info.extra_piles.is_empty()
+        && info.artifacts.is_empty()
+        && !special_types.iter().any(|&card_type| card.is(card_type))
 }
 
-impl Card for Victory {
-    fn name(&self) -> &str {
-        match self {
-            Victory::Estate => "Estate",
-            Victory::Duchy => "Duchy",
-            Victory::Province => "Province",
-        }
-    }
-
-    fn card_type(&self) -> CardType {
-        CardType::Victory
-    }
+/// Whether a kingdom pile can be made from a card, as opposed to the basic
+/// cards, cards set up alongside others, and Events and the like.
+fn is_kingdom_pile_card(card: Card) -> bool {
+    let info = card.info();
+    let not_piles = [
+        CardType::Ruins,
+        CardType::Shelter,
+        CardType::Heirloom,
+        CardType::Boon,
+        CardType::Hex,
+        CardType::State,
+        CardType::Artifact,
+        CardType::Prize,
+        CardType::Event,
+        CardType::Landmark,
+        CardType::Project,
+        CardType::Way,
+        CardType::Ally,
+        CardType::Trait,
+    ];
+    !info.basic && !info.non_supply && !not_piles.iter().any(|&card_type| card.is(card_type))
+}
 
-    fn cost(&self) -> u32 {
-        match self {
-            Victory::Estate => 2,
-            Victory::Duchy => 5,
-            Victory::Province => 8,
-        }
+/// The kingdom pile a card belongs to, which for split piles and Knights is
+/// shared with other cards.
+fn kingdom_pile_name(card: Card) -> &'static str {
+    let info = card.info();
+    if card.is(CardType::Knight) {
+        "Knights"
+    } else if info.pile.is_empty() {
+        card.name()
+    } else {
+        &info.pile
     }
+}
 
-    fn as_any(&self) -> &dyn Any {
-        self
-    }
+#[cfg(feature = "cornucopia")]
+fn count_differently_named(cards: impl Iterator<Item = Card>) -> usize {
+    cards.map(|card| card.name()).collect::<HashSet<_>>().len()
 }
 
+/// A temporary change to what cards cost, such as "cards cost $1 less this turn".
+/// Modifiers last until the end of the turn.
 #[derive(Debug, Clone)]
-enum Curse {
-    Curse,
+struct CostModifier {
+    filter: CardFilter,
+    coin_reduction: u32,
 }
 
-impl Card for Curse {
-    fn name(&self) -> &str {
-        match self {
-            Curse::Curse => "Curse",
-        }
-    }
+#[derive(Debug)]
+struct Player {
+    index: usize,
+    hand: Vec<Card>,
+    deck: Vec<Card>,
+    discard: Vec<Card>,
+    played: Vec<Card>,
+    // Duration cards staying in play until their effects are done
+    durations: Vec<Card>,
+    mats: HashMap<Mat, Vec<Card>>,
+    actions: u32,
+    buys: u32,
+    coins: u32,
+    potions: u32,
+    // Debt tokens carry over between turns until paid off
+    debt: u32,
+    // Victory point tokens, counted alongside the player's cards at the end
+    vp_tokens: u32,
+    // The supply pile each of the player's pile tokens is on
+    tokens: HashMap<Token, String>,
+    #[cfg(feature = "adventures")]
+    journey_token_face_up: bool,
+    // The -1 Card token on the deck makes the next draw one card short
+    minus_card_token: bool,
+    // The -$1 token takes $1 off the next $ the player gets. It remembers how
+    // much they had when they took it, as only $ they get after that counts
+    minus_coin_token: Option<u32>,
+    // Cards set aside to go into the next hand once it is drawn, e.g. by Save
+    saved: Vec<Card>,
+    // The Action card the player's Estates play as, from Inheritance
+    inherited: Option<Card>,
+    // Extra cards to draw for the next hand, e.g. from Expedition
+    next_hand_bonus: usize,
+    // States the player has taken, e.g. Deluded
+    states: Vec<Card>,
+    // Villagers are spent for +1 Action and Coffers for +$1, whenever the
+    // player likes during their Action or Buy phase
+    villagers: u32,
+    coffers: u32,
+    // Favors are spent on the game's Ally
+    favors: u32,
+    // Set by Lich: the player's next turn is skipped
+    skip_next_turn: bool,
+    // Set by Enchantress: the Action card being played gives +1 Card and
+    // +1 Action instead of doing what it says
+    enchanted: bool,
+    // Set by Snowy Village: further +Actions this turn are ignored, so the
+    // player's Actions can only go down from here
+    max_actions: Option<u32>,
+    // Set while an Action played using Way of the Chameleon resolves: the
+    // cards it would draw, to be given as +$ instead
+    chameleon_draws: Option<u32>,
+    // Cards the player has Exiled with Invest
+    #[cfg(feature = "menagerie")]
+    invested: Vec<Card>,
+    // Seize the Day can only be bought once per game
+    seized_the_day: bool,
+    // Warlords attacking the player: they can't play an Action from their
+    // hand that they have 2 or more copies of in play
+    warlord_attacks: u32,
+    // Highwaymen attacking the player: the first Treasure they play each turn
+    // does nothing
+    highwayman_attacks: u32,
+    // Set by Voyage for its extra turn: how many more cards the player can
+    // play from their hand
+    hand_plays_left: Option<u32>,
+    // Cards Elder played this turn, which give an extra choice of abilities
+    elder_played: Vec<Card>,
+    // The game's Ally, as Order of Astrologers and Order of Masons act while
+    // the player shuffles
+    ally: Option<Card>,
+    // Projects the player has put one of their cubes on
+    projects: Vec<Card>,
+    // The player's tokens on Sinister Plot
+    #[cfg(feature = "renaissance")]
+    sinister_plot_tokens: u32,
+    // Set while Sewers trashes a card, which doesn't set Sewers off again
+    #[cfg(feature = "renaissance")]
+    trashing_with_sewers: bool,
+    // Artifacts the player holds until someone else takes them
+    artifacts: Vec<Card>,
+    // The cards with the Fated Trait, which go on top whenever the player shuffles
+    fated: Vec<Card>,
+    // Set by Avoid: the player's next shuffle this turn leaves some cards out
+    avoiding_shuffle: bool,
+    // Number of times the discard pile has been shuffled to form a new deck
+    shuffle_count: u32,
+    auto_play: AutoPlayPolicy,
+    rng: StdRng,
+}
 
-    fn card_type(&self) -> CardType {
-        CardType::Curse
-    }
+/// Places next to a player's deck where cards are set aside.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+enum Mat {
+    #[cfg(feature = "seaside")]
+    NativeVillage,
+    #[cfg(feature = "seaside")]
+    Island,
+    // Where Reserve cards wait to be called
+    Tavern,
+    // Exiled cards still belong to the player, and can be discarded from here
+    // when they gain another copy
+    Exile,
+}
 
-    fn cost(&self) -> u32 {
-        match self {
-            Curse::Curse => 0,
-        }
-    }
+/// A player's tokens that sit on a supply pile and change what happens when
+/// they play or buy cards from it.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+enum Token {
+    #[cfg(feature = "adventures")]
+    PlusCard,
+    #[cfg(feature = "adventures")]
+    PlusAction,
+    #[cfg(feature = "adventures")]
+    PlusBuy,
+    #[cfg(feature = "adventures")]
+    PlusCoin,
+    // Cards from the pile cost $2 less on the player's turns
+    MinusCost,
+    // Buying a card from the pile lets the player trash a card from hand
+    Trashing,
+}
 
-    fn as_any(&self) -> &dyn Any {
-        self
+impl std::fmt::Display for Token {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(match self {
+            #[cfg(feature = "adventures")]
+            Token::PlusCard => "+1 Card",
+            #[cfg(feature = "adventures")]
+            Token::PlusAction => "+1 Action",
+            #[cfg(feature = "adventures")]
+            Token::PlusBuy => "+1 Buy",
+            #[cfg(feature = "adventures")]
+            Token::PlusCoin => "+$1",
+            Token::MinusCost => "-$2 cost",
+            Token::Trashing => "Trashing",
+        })
     }
 }
 
+/// Per-player settings for which steps the engine takes without an explicit move.
+/// Phases are only ever ended automatically once the player has no meaningful
+/// choice left in them.
 #[derive(Debug, Clone)]
-enum Action {
-    // Cost 2
-    Cellar,
-    Chapel,
-    Moat,
-
-    // Cost 3
-    Harbinger,
-    Merchant,
-    Vassal,
-    Village,
-    Workshop,
-
-    // Cost 4
-    Bureaucrat,
-    Gardens,
-    Militia,
-    Moneylender,
-    Poacher,
-    Remodel,
-    Smithy,
-    ThroneRoom,
-
-    // Cost 5
-    Bandit,
-    CouncilRoom,
-    Festival,
-    Laboratory,
-    Library,
-    Market,
-    Mine,
-    Sentry,
-    Witch,
-
-    // Cost 6
-    Artisan,
+struct AutoPlayPolicy {
+    /// End the Action phase once no Action can be played.
+    end_actions: bool,
+    /// End the Treasure phase once no Treasure can be played.
+    end_treasures: bool,
+    /// End the turn once nothing more can be bought.
+    end_turn: bool,
+    /// Play all basic Treasures on entering the Treasure phase.
+    play_treasures: bool,
 }
 
-impl Card for Action {
-    fn name(&self) -> &str {
-        match self {
-            Action::Cellar => "Cellar",
-            Action::Chapel => "Chapel",
-            Action::Moat => "Moat",
-            Action::Harbinger => "Harbinger",
-            Action::Merchant => "Merchant",
-            Action::Vassal => "Vassal",
-            Action::Village => "Village",
-            Action::Workshop => "Workshop",
-            Action::Bureaucrat => "Bureaucrat",
-            Action::Gardens => "Gardens",
-            Action::Militia => "Militia",
-            Action::Moneylender => "Moneylender",
-            Action::Poacher => "Poacher",
-            Action::Remodel => "Remodel",
-            Action::Smithy => "Smithy",
-            Action::ThroneRoom => "Throne Room",
-            Action::Bandit => "Bandit",
-            Action::CouncilRoom => "Council Room",
-            Action::Festival => "Festival",
-            Action::Laboratory => "Laboratory",
-            Action::Library => "Library",
-            Action::Market => "Market",
-            Action::Mine => "Mine",
-            Action::Sentry => "Sentry",
-            Action::Witch => "Witch",
-            Action::Artisan => "Artisan",
-        }
-    }
-
-    fn card_type(&self) -> CardType {
-        CardType::Action
-    }
-
-    fn cost(&self) -> u32 {
-        match self {
-            Action::Cellar => 2,
-            Action::Chapel => 2,
-            Action::Moat => 2,
-            Action::Harbinger => 3,
-            Action::Merchant => 3,
-            Action::Vassal => 3,
-            Action::Village => 3,
-            Action::Workshop => 3,
-            Action::Bureaucrat => 4,
-            Action::Gardens => 4,
-            Action::Militia => 4,
-            Action::Moneylender => 4,
-            Action::Poacher => 4,
-            Action::Remodel => 4,
-            Action::Smithy => 4,
-            Action::ThroneRoom => 4,
-            Action::Bandit => 5,
-            Action::CouncilRoom => 5,
-            Action::Festival => 5,
-            Action::Laboratory => 5,
-            Action::Library => 5,
-            Action::Market => 5,
-            Action::Mine => 5,
-            Action::Sentry => 5,
-            Action::Witch => 5,
-            Action::Artisan => 6,
-        }
-    }
-
-    fn as_any(&self) -> &dyn Any {
-        self
+impl Default for AutoPlayPolicy {
+    fn default() -> Self {
+        AutoPlayPolicy {
+            end_actions: true,
+            end_treasures: true,
+            end_turn: true,
+            play_treasures: false,
+        }
     }
 }
 
-macro_rules! create_card_map {
-    ($(($enum_type:ident, $($variant:ident),+)),*) => {
-        fn card_name_to_card(card_name: &str) -> Option<Box<dyn Card>> {
-            $(
-                $(
-                    if card_name == $enum_type::$variant.name() {
-                        return Some(Box::new($enum_type::$variant));
-                    }
-                )+
-            )*
-            None
+impl AutoPlayPolicy {
+    /// Nothing happens without an explicit move, so that tests can check each step.
+    #[cfg(test)]
+    fn manual() -> Self {
+        AutoPlayPolicy {
+            end_actions: false,
+            end_treasures: false,
+            end_turn: false,
+            play_treasures: false,
         }
     }
 }
 
-create_card_map!(
-    (Treasure, Copper, Silver, Gold),
-    (Victory, Estate, Duchy, Province),
-    (Curse, Curse),
-    (
-        Action,
-        Cellar,
-        Chapel,
-        Moat,
-        Harbinger,
-        Merchant,
-        Vassal,
-        Village,
-        Workshop,
-        Bureaucrat,
-        Gardens,
-        Militia,
-        Moneylender,
-        Poacher,
-        Remodel,
-        Smithy,
-        ThroneRoom,
-        Bandit,
-        CouncilRoom,
-        Festival,
-        Laboratory,
-        Library,
-        Market,
-        Mine,
-        Sentry,
-        Witch,
-        Artisan
-    )
-);
-
-#[derive(Debug)]
-struct Player {
-    index: usize,
-    hand: Vec<Box<dyn Card>>,
-    deck: Vec<Box<dyn Card>>,
-    discard: Vec<Box<dyn Card>>,
-    played: Vec<Box<dyn Card>>,
-    trashed: Vec<Box<dyn Card>>,
-    last_discarded_card: Option<Box<dyn Card>>,
-    actions: u32,
-    buys: u32,
-    coins: u32,
-}
-
 impl Player {
-    fn new(index: usize) -> Self {
+    /// A player with the usual starting deck of 7 Coppers and 3 Estates, or
+    /// with the three Shelters in place of the Estates. Each Heirloom (or
+    /// Inherited card) replaces one of the Coppers.
+    fn new(index: usize, seed: u64, shelters: bool, heirlooms: &[Card]) -> Self {
         let mut player = Player {
             index,
             hand: Vec::new(),
             deck: Vec::new(),
             discard: Vec::new(),
             played: Vec::new(),
-            trashed: Vec::new(),
-            last_discarded_card: None,
+            durations: Vec::new(),
+            mats: HashMap::new(),
             actions: 1,
             buys: 1,
             coins: 0,
+            potions: 0,
+            debt: 0,
+            vp_tokens: 0,
+            tokens: HashMap::new(),
+            #[cfg(feature = "adventures")]
+            journey_token_face_up: true,
+            minus_card_token: false,
+            minus_coin_token: None,
+            saved: Vec::new(),
+            inherited: None,
+            next_hand_bonus: 0,
+            states: Vec::new(),
+            villagers: 0,
+            coffers: 0,
+            favors: 0,
+            skip_next_turn: false,
+            enchanted: false,
+            max_actions: None,
+            chameleon_draws: None,
+            #[cfg(feature = "menagerie")]
+            invested: Vec::new(),
+            seized_the_day: false,
+            warlord_attacks: 0,
+            highwayman_attacks: 0,
+            hand_plays_left: None,
+            elder_played: Vec::new(),
+            ally: None,
+            projects: Vec::new(),
+            #[cfg(feature = "renaissance")]
+            sinister_plot_tokens: 0,
+            #[cfg(feature = "renaissance")]
+            trashing_with_sewers: false,
+            artifacts: Vec::new(),
+            fated: Vec::new(),
+            avoiding_shuffle: false,
+            shuffle_count: 0,
+            auto_play: AutoPlayPolicy::default(),
+            rng: StdRng::seed_from_u64(seed),
         };
 
-        for _ in 0..7 {
-            player.deck.push(Box::new(Treasure::Copper));
+        for _ in heirlooms.len()..7 {
+            player.deck.push(card("Copper"));
         }
-        for _ in 0..3 {
-            player.deck.push(Box::new(Victory::Estate));
+        player.deck.extend_from_slice(heirlooms);
+        if shelters {
+            for shelter in ["Hovel", "Necropolis", "Overgrown Estate"] {
+                player.deck.push(card(shelter));
+            }
+        } else {
+            for _ in 0..3 {
+                player.deck.push(card("Estate"));
+            }
         }
         player.shuffle_deck();
         player.draw(5);
         player
     }
 
-    fn draw(&mut self, num_cards_to_draw: usize) {
-        if self.deck.len() < num_cards_to_draw {
-            self.shuffle_discard();
-            self.prepend_discard_to_deck();
+    /// Draws up to `num_cards_to_draw` cards, returning how many were actually drawn.
+    /// The remaining deck is drawn first; only once it runs out is the discard pile
+    /// shuffled to form a new deck. Drawing stops early if both are empty.
+    fn draw(&mut self, num_cards_to_draw: usize) -> usize {
+        // The -1 Card token is used up by the next draw, whatever its size
+        let num_cards_to_draw = if num_cards_to_draw > 0 && take(&mut self.minus_card_token) {
+            num_cards_to_draw - 1
+        } else {
+            num_cards_to_draw
+        };
+        if let Some(draws) = &mut self.chameleon_draws {
+            *draws += num_cards_to_draw as u32;
+            return num_cards_to_draw;
         }
-        for _ in 0..num_cards_to_draw {
-            if let Some(card) = self.deck.pop() {
-                self.hand.push(card);
+        for drawn in 0..num_cards_to_draw {
+            match self.take_from_deck() {
+                Some(card) => self.hand.push(card),
+                None => return drawn,
             }
         }
+        num_cards_to_draw
+    }
+
+    /// Takes the top card of the deck, reshuffling the discard pile if the deck is empty.
+    fn take_from_deck(&mut self) -> Option<Card> {
+        if self.deck.is_empty() && !self.reshuffle() {
+            return None;
+        }
+        self.deck.pop()
+    }
+
+    /// Reveals up to `num_cards` from the top of the deck, removing them from it.
+    fn reveal_from_deck(&mut self, num_cards: usize) -> Vec<Card> {
+        (0..num_cards)
+            .map_while(|_| self.take_from_deck())
+            .collect()
+    }
+
+    fn put_on_deck(&mut self, card: Card) {
+        self.deck.push(card);
+    }
+
+    /// Puts a card into the deck with `depth` cards above it.
+    #[cfg(feature = "intrigue")]
+    fn insert_into_deck(&mut self, card: Card, depth: usize) {
+        let index = self.deck.len().saturating_sub(depth);
+        self.deck.insert(index, card);
+    }
+
+    /// Takes a card back out of play, e.g. when it trashes itself.
+    fn take_from_play(&mut self, card: Card) -> Option<Card> {
+        let index = self.played.iter().rposition(|&played| played == card)?;
+        Some(self.played.remove(index))
     }
 
     fn shuffle_deck(&mut self) {
-        shuffle_vec_inplace(&mut self.deck);
+        shuffle_vec_inplace(&mut self.deck, &mut self.rng);
+    }
+
+    /// Shuffles the discard pile to form a new deck. Only valid once the deck is empty;
+    /// returns false if there was nothing to shuffle.
+    fn reshuffle(&mut self) -> bool {
+        if self.discard.is_empty() {
+            return false;
+        }
+        self.deck = take(&mut self.discard);
+        self.shuffle_deck();
+        self.shuffle_count += 1;
+        // Fated cards are always put on top, as nothing can stop to ask in the
+        // middle of drawing
+        if !self.fated.is_empty() {
+            let (fated, rest): (Vec<Card>, Vec<Card>) = take(&mut self.deck)
+                .into_iter()
+                .partition(|card| self.fated.contains(card));
+            self.deck = rest;
+            self.deck.extend(fated);
+        }
+        // Star Chart's pick goes on top. Shuffles happen in the middle of
+        // drawing, so it always picks the card with the highest printed cost
+        if self.has_project("Star Chart") {
+            let top = (0..self.deck.len()).max_by_key(|&index| self.deck[index].cost().coins);
+            if let Some(index) = top {
+                let picked = self.deck.remove(index);
+                self.deck.push(picked);
+            }
+        }
+        self.spend_favors_on_shuffle();
+        // Avoid picks the same cards as Order of Masons would
+        if take(&mut self.avoiding_shuffle) {
+            self.discard_junk_from_deck(3);
+        }
+        true
     }
 
-    fn shuffle_discard(&mut self) {
-        shuffle_vec_inplace(&mut self.discard);
+    /// Spends Favors on Order of Astrologers or Order of Masons, if that is the
+    /// game's Ally. Like Star Chart, they can't stop to ask in the middle of
+    /// drawing, so they follow a fixed rule: Astrologers puts the costliest
+    /// non-Victory cards costing $5 or more on top, one per Favor, and Masons
+    /// puts Victory cards and Curses into the discard pile, two per Favor.
+    fn spend_favors_on_shuffle(&mut self) {
+        let Some(ally) = self.ally else {
+            return;
+        };
+        match ally.name() {
+            "Order of Astrologers" => {
+                let mut picked = Vec::new();
+                while self.favors > 0 {
+                    let best = (0..self.deck.len())
+                        .filter(|&index| {
+                            let card = self.deck[index];
+                            !card.is(CardType::Victory)
+                                && !card.is(CardType::Curse)
+                                && card.cost().coins >= 5
+                        })
+                        .max_by_key(|&index| self.deck[index].cost().coins);
+                    let Some(index) = best else {
+                        break;
+                    };
+                    picked.push(self.deck.remove(index));
+                    self.favors -= 1;
+                }
+                // The costliest card ends up on top
+                self.deck.extend(picked.into_iter().rev());
+            }
+            "Order of Masons" => {
+                let discarded = self.discard_junk_from_deck(2 * self.favors as usize);
+                self.favors -= discarded.div_ceil(2) as u32;
+            }
+            _ => {}
+        }
     }
 
-    fn prepend_discard_to_deck(&mut self) {
-        let mut new_deck = take(&mut self.discard);
-        let old_deck = take(&mut self.deck);
-        new_deck.extend(old_deck);
+    /// Moves up to `max` Curses and Victory cards that do nothing else from
+    /// the freshly shuffled deck to the discard pile, returning how many.
+    fn discard_junk_from_deck(&mut self, max: usize) -> usize {
+        let junk: Vec<usize> = (0..self.deck.len())
+            .filter(|&index| {
+                let card = self.deck[index];
+                card.is(CardType::Curse)
+                    || (card.is(CardType::Victory)
+                        && !card.is(CardType::Action)
+                        && !card.is(CardType::Treasure))
+            })
+            .collect();
+        let count = junk.len().min(max);
+        for &index in junk[..count].iter().rev() {
+            let card = self.deck.remove(index);
+            self.discard.push(card);
+        }
+        count
+    }
 
-        self.deck = new_deck;
+    fn shuffle_count(&self) -> u32 {
+        self.shuffle_count
     }
 
-    fn get_victory_points(&self) -> u32 {
+    /// Every card the player owns, wherever it currently is.
+    fn all_cards(&self) -> impl Iterator<Item = Card> + '_ {
         self.hand
             .iter()
             .chain(self.deck.iter())
             .chain(self.discard.iter())
-            .fold(0, |sum, card| {
-                if card.name() == "Estate" {
-                    sum + 1
-                } else if card.name() == "Duchy" {
-                    sum + 3
-                } else if card.name() == "Province" {
-                    sum + 6
-                } else {
-                    sum
-                }
-            })
+            .chain(self.played.iter())
+            .chain(self.durations.iter())
+            .chain(self.mats.values().flatten())
+            .chain(self.saved.iter())
+            .copied()
+    }
+
+    /// How many differently named cards the player owns, e.g. for Fairgrounds.
+    #[cfg(feature = "cornucopia")]
+    fn differently_named_cards(&self) -> usize {
+        count_differently_named(self.all_cards())
+    }
+
+    /// How many differently named cards the player has in play, e.g. for Horn of Plenty.
+    #[cfg(feature = "cornucopia")]
+    fn differently_named_in_play(&self) -> usize {
+        count_differently_named(self.played.iter().copied())
+    }
+
+    /// Whether any two cards in the player's hand share a name.
+    #[cfg(feature = "cornucopia")]
+    fn has_duplicates_in_hand(&self) -> bool {
+        count_differently_named(self.hand.iter().copied()) < self.hand.len()
     }
 
-    fn get_card_from_hand(&mut self, card_index: usize) -> Result<&Box<dyn Card>, GameError> {
-        if card_index >= self.hand.len() {
-            Err(GameError::CardNotFound("Index out of bounds".to_owned()))
+    /// Takes back a card the player has just gained, from wherever it was
+    /// gained to, so that an effect can replace the gain, e.g. Watchtower.
+    /// Returns None if the card has already moved on.
+    #[cfg(any(
+        feature = "prosperity",
+        feature = "nocturne",
+        feature = "menagerie",
+        feature = "hinterlands",
+        feature = "allies",
+        feature = "plunder"
+    ))]
+    fn take_gained(&mut self, card: Card) -> Option<Card> {
+        if let Some(index) = self.discard.iter().rposition(|&c| c == card) {
+            Some(self.discard.remove(index))
+        } else if let Some(index) = self.hand.iter().rposition(|&c| c == card) {
+            Some(self.hand.remove(index))
+        } else if self.deck.last() == Some(&card) {
+            self.deck.pop()
         } else {
-            Ok(self.hand.get(card_index).unwrap())
+            None
         }
     }
 
-    fn remove_card_from_hand(&mut self, card_index: usize) -> Result<Box<dyn Card>, GameError> {
-        if card_index >= self.hand.len() {
-            Err(GameError::CardNotFound("Index out of bounds".to_owned()))
-        } else {
-            Ok(self.hand.remove(card_index))
+    fn mat_mut(&mut self, mat: Mat) -> &mut Vec<Card> {
+        self.mats.entry(mat).or_default()
+    }
+
+    #[cfg(any(feature = "adventures", feature = "menagerie"))]
+    fn has_on_mat(&self, mat: Mat, card: Card) -> bool {
+        self.mats
+            .get(&mat)
+            .is_some_and(|cards| cards.contains(&card))
+    }
+
+    /// Moves a Reserve card from the Tavern mat into play, returning false if
+    /// there is no such card on the mat.
+    #[cfg(feature = "adventures")]
+    fn call_from_tavern(&mut self, card: Card) -> bool {
+        let tavern = self.mat_mut(Mat::Tavern);
+        let Some(index) = tavern.iter().position(|&reserve| reserve == card) else {
+            return false;
+        };
+        let called = tavern.remove(index);
+        self.played.push(called);
+        true
+    }
+
+    /// Turns the Journey token over, returning whether it is now face up.
+    #[cfg(feature = "adventures")]
+    fn turn_journey_token(&mut self) -> bool {
+        self.journey_token_face_up = !self.journey_token_face_up;
+        self.journey_token_face_up
+    }
+
+    fn get_victory_points(&self) -> i32 {
+        self.all_cards()
+            .map(
+                |card| match card.behaviour().and_then(|b| b.victory_points) {
+                    Some(victory_points) => victory_points(self),
+                    None => card.info().vp,
+                },
+            )
+            .sum::<i32>()
+            + self.states.iter().map(|state| state.info().vp).sum::<i32>()
+            + self.vp_tokens as i32
+            + 4 * self.distant_lands_on_tavern() as i32
+    }
+
+    /// Distant Lands is only worth anything while on the Tavern mat, so it
+    /// is scored here rather than by the card itself.
+    fn distant_lands_on_tavern(&self) -> usize {
+        self.mats.get(&Mat::Tavern).map_or(0, |tavern| {
+            tavern
+                .iter()
+                .filter(|card| card.name() == "Distant Lands")
+                .count()
+        })
+    }
+
+    /// The Action card an Estate plays as on its owner's turns, once they
+    /// have bought Inheritance.
+    fn inherited_by(&self, card: Card) -> Option<Card> {
+        self.inherited.filter(|_| card.name() == "Estate")
+    }
+
+    #[cfg(feature = "nocturne")]
+    fn has_state(&self, name: &str) -> bool {
+        self.states.iter().any(|state| state.name() == name)
+    }
+
+    fn has_project(&self, name: &str) -> bool {
+        self.projects.iter().any(|project| project.name() == name)
+    }
+
+    /// Whether the player can play a card in their Treasure phase. With
+    /// Capitalism, that includes Actions with +$ amounts in their text.
+    fn plays_as_treasure(&self, card: Card) -> bool {
+        card.is(CardType::Treasure)
+            || (card.is(CardType::Action)
+                && self.has_project("Capitalism")
+                && card.info().text.contains("+$"))
+    }
+
+    fn has_artifact(&self, name: &str) -> bool {
+        self.artifacts
+            .iter()
+            .any(|artifact| artifact.name() == name)
+    }
+
+    /// Returns a State, returning false if the player didn't have it.
+    #[cfg(feature = "nocturne")]
+    fn return_state(&mut self, name: &str) -> bool {
+        let Some(index) = self.states.iter().position(|state| state.name() == name) else {
+            return false;
+        };
+        self.states.remove(index);
+        true
+    }
+
+    fn get_card_from_hand(&mut self, card_index: usize) -> Result<Card, GameError> {
+        self.hand
+            .get(card_index)
+            .copied()
+            .ok_or(GameError::CardNotFound("Index out of bounds".to_owned()))
+    }
+
+    /// Takes a card out of hand to play it, as long as nothing stops the
+    /// player playing it.
+    fn take_card_to_play(&mut self, card_index: usize) -> Result<Card, GameError> {
+        let card = self.get_card_from_hand(card_index)?;
+        if !self.can_play_from_hand(card) {
+            return Err(GameError::InvalidMove(format!(
+                "{} can't be played from hand right now",
+                card.name()
+            )));
         }
+        self.use_hand_play();
+        Ok(self.hand.remove(card_index))
     }
 
-    fn play_card(&mut self, card: Box<dyn Card>) {
+    /// Whether the player may play the card from their hand, which they can't
+    /// once Voyage's limit is used up, or while a Warlord attacks them and they
+    /// have 2 or more copies of the Action in play.
+    fn can_play_from_hand(&self, card: Card) -> bool {
+        let copies_in_play = self.played.iter().filter(|&&played| played == card).count();
+        self.hand_plays_left != Some(0)
+            && !(self.warlord_attacks > 0 && card.is(CardType::Action) && copies_in_play >= 2)
+    }
+
+    /// Counts a card played from hand against Voyage's limit, if there is one.
+    fn use_hand_play(&mut self) {
+        if let Some(left) = &mut self.hand_plays_left {
+            *left = left.saturating_sub(1);
+        }
+    }
+
+    fn play_card(&mut self, card: Card) {
         self.played.push(card);
     }
 
-    fn end_turn(&mut self) {
+    fn end_turn(&mut self, hand_size: usize) {
         self.discard_hand();
         self.clear_played();
         self.actions = 1;
+        self.max_actions = None;
+        self.hand_plays_left = None;
+        self.elder_played.clear();
         self.buys = 1;
         self.coins = 0;
-        self.draw(5);
+        self.potions = 0;
+        self.settle_minus_coin_token();
+        self.draw(hand_size);
+        // Drawing the next hand is still part of the turn
+        self.avoiding_shuffle = false;
+        let saved = take(&mut self.saved);
+        self.hand.extend(saved);
+    }
+
+    /// Keeps a Duration card in play through clean-up.
+    fn keep_in_play(&mut self, card: Card) {
+        if let Some(card) = self.take_from_play(card) {
+            self.durations.push(card);
+        }
+    }
+
+    /// Duration cards whose effects are resolving this turn count as played
+    /// this turn again, so they are discarded at its clean-up.
+    fn start_turn(&mut self) {
+        let durations = take(&mut self.durations);
+        self.played.extend(durations);
     }
 
     fn discard_hand(&mut self) {
@@ -461,404 +804,2852 @@ impl Player {
         self.discard.extend(played);
     }
 
-    fn get_starting_game_phase(&self) -> GamePhase {
-        if self.has_action_cards_in_hand() {
-            GamePhase::ActionPhase
-        } else if self.has_treasure_cards_in_hand() {
-            GamePhase::TreasurePhase
-        } else {
-            GamePhase::BuyPhase
-        }
+    /// Basic treasures only produce coins, so playing them never involves a choice.
+    fn is_basic_treasure(card: Card) -> bool {
+        card.is(CardType::Treasure) && card.is_vanilla()
     }
 
-    fn has_action_cards_in_hand(&self) -> bool {
-        self.hand
-            .iter()
-            .any(|card| card.card_type() == CardType::Action)
+    /// Removes every basic Treasure from hand, keeping the rest in their original order.
+    fn take_basic_treasures_from_hand(&mut self) -> Vec<Card> {
+        let (treasures, rest) = take(&mut self.hand)
+            .into_iter()
+            .partition(|&card| Player::is_basic_treasure(card));
+        self.hand = rest;
+        treasures
     }
 
-    fn has_treasure_cards_in_hand(&self) -> bool {
-        self.hand
-            .iter()
-            .any(|card| card.card_type() == CardType::Treasure)
+    fn add_to_discard(&mut self, card: Card) {
+        self.discard.push(card);
     }
 
-    fn add_to_discard(&mut self, card: Box<dyn Card>) {
-        self.discard.push(card);
+    fn add_to_hand(&mut self, card: Card) {
+        self.hand.push(card);
     }
 
-    fn has_moat_in_hand(&self) -> bool {
-        self.hand
-            .iter()
-            .any(|card| card.card_type() == CardType::Action && card.name() == "Moat")
+    /// What the player has available to pay with this turn.
+    fn available_money(&self) -> Cost {
+        Cost {
+            coins: self.coins,
+            potions: self.potions,
+            debt: 0,
+        }
     }
-}
 
-#[derive(Debug)]
-struct Supply {
-    // Maps from card name to quantity
-    treasures: HashMap<String, u8>,
-    actions: HashMap<String, u8>,
-    victories: HashMap<String, u8>,
-    curses: HashMap<String, u8>,
-}
+    /// Debt in a cost is taken on rather than paid, so only coins and potions must
+    /// be covered, but any Debt the player already has must be paid off first.
+    fn can_afford(&self, cost: &Cost) -> bool {
+        self.debt + cost.coins <= self.coins && cost.potions <= self.potions
+    }
 
-impl Supply {
-    fn take_card(&mut self, card_to_take: &Box<dyn Card>) -> Result<(), GameError> {
-        match card_to_take.card_type() {
-            CardType::Treasure => {
-                Supply::take_from_supply_pile(&mut self.treasures, card_to_take.name())
-            }
-            CardType::Victory => {
-                Supply::take_from_supply_pile(&mut self.victories, card_to_take.name())
-            }
-            CardType::Action => {
-                Supply::take_from_supply_pile(&mut self.actions, card_to_take.name())
-            }
-            CardType::Curse => Supply::take_from_supply_pile(&mut self.curses, card_to_take.name()),
-        }
+    fn pay(&mut self, cost: &Cost) {
+        self.pay_off_debt();
+        self.coins -= cost.coins;
+        self.potions -= cost.potions;
+        self.debt += cost.debt;
+        self.settle_minus_coin_token();
     }
 
-    fn take_from_supply_pile(
-        pile: &mut HashMap<String, u8>,
-        card_name: &str,
-    ) -> Result<(), GameError> {
-        if let Some(count) = pile.get_mut(card_name) {
-            if *count <= 0 {
-                Err(GameError::CardSupplyDepleted(card_name.to_owned()))
-            } else {
-                *count -= 1;
-                Ok(())
-            }
+    /// Takes the -$1 token, unless the player already has it.
+    #[cfg(feature = "adventures")]
+    fn take_minus_coin_token(&mut self) {
+        self.minus_coin_token.get_or_insert(self.coins);
+    }
+
+    /// Returns the -$1 token, for $1 less, if the player has got $ since
+    /// taking it. $ spent since then is forgotten about.
+    fn settle_minus_coin_token(&mut self) {
+        let Some(had) = self.minus_coin_token else {
+            return;
+        };
+        if self.coins > had {
+            self.coins -= 1;
+            self.minus_coin_token = None;
         } else {
-            Err(GameError::CardNotFoundInSupply(card_name.to_owned()))
+            self.minus_coin_token = Some(self.coins);
         }
     }
 
-    fn num_empty_supply_piles(&self) -> u8 {
-        self.treasures.values().filter(|&count| *count == 0).count() as u8
-            + self.victories.values().filter(|&count| *count == 0).count() as u8
-            + self.actions.values().filter(|&count| *count == 0).count() as u8
-            + self.curses.values().filter(|&count| *count == 0).count() as u8
+    /// Takes away any +Actions Snowy Village says to ignore.
+    fn settle_max_actions(&mut self) {
+        if let Some(max_actions) = self.max_actions {
+            self.actions = self.actions.min(max_actions);
+            self.max_actions = Some(self.actions);
+        }
     }
 
-    fn check_game_over(&mut self) -> bool {
-        self.victories["Province"] == 0 || self.num_empty_supply_piles() >= 3
+    /// Pays off as much Debt as the player's coins allow.
+    fn pay_off_debt(&mut self) {
+        let paid = self.debt.min(self.coins);
+        self.coins -= paid;
+        self.debt -= paid;
+        self.settle_minus_coin_token();
     }
-}
-
-#[derive(Debug)]
-enum GameMove {
-    PlayCard { card_index: usize },
-    BuyCard { card: Box<dyn Card> },
-    DiscardCard { card: Box<dyn Card> },
-    EndActions,
-    EndTreasures,
-    EndTurn,
-}
 
-#[derive(Debug, Clone)]
-enum GamePhase {
-    ActionPhase,
-    TreasurePhase,
-    BuyPhase,
+    fn has_card_in_hand(&self, filter: &CardFilter) -> bool {
+        self.hand.iter().any(|&card| filter.matches(card))
+    }
 }
 
-struct Game {
-    players: Vec<Player>,
-    supply: Supply,
-    curr_player_index: usize,
-    game_phase: GamePhase,
-    winner: Option<usize>,
+/// A pile of cards. Most piles hold copies of a single card, but some, like
+/// Ruins or Knights, mix different cards, and only the top one can be taken.
+struct Pile {
+    name: String,
+    // The top of the pile is the end of the Vec
+    cards: Vec<Card>,
+    // Traits put on the pile at setup, which apply to every card in it
+    traits: Vec<Card>,
 }
 
-impl Debug for Game {
-    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
-        f.write_fmt(format_args!("Current player: {}\n", self.curr_player_index))?;
-        f.write_fmt(format_args!(
-            "Actions: {}\n",
-            self.current_player_read_only().actions
-        ))?;
-        f.write_fmt(format_args!(
-            "Buys: {}\n",
-            self.current_player_read_only().buys
-        ))?;
-        f.write_fmt(format_args!(
-            "Coins: {}\n",
-            self.current_player_read_only().coins
-        ))?;
-        f.write_fmt(format_args!("Current phase: {:?}\n", self.game_phase))?;
-        f.write_fmt(format_args!("Supply: {:#?}\n", self.supply))?;
-        f.write_fmt(format_args!(
-            "Current player deck: {:#?}\n",
-            self.current_player_read_only().deck
-        ))?;
-        f.write_fmt(format_args!(
-            "Current player discard: {:#?}\n",
-            self.current_player_read_only().discard
-        ))?;
-        f.write_fmt(format_args!(
-            "Current player hand: {:#?}\n",
-            self.current_player_read_only().hand
-        ))?;
-        f.write_fmt(format_args!(
-            "Current player played cards: {:#?}\n",
-            self.current_player_read_only().played
-        ))
+impl Pile {
+    fn of(card: Card, count: u8) -> Self {
+        Pile {
+            name: card.name().to_owned(),
+            cards: vec![card; count as usize],
+            traits: Vec::new(),
+        }
     }
-}
-
-impl Game {
-    fn initialise_game(num_players: usize) -> Self {
-        use Action::*;
-        use Treasure::*;
-        use Victory::*;
-        let supply = Supply {
-            treasures: HashMap::from([
-                (Copper.name().to_owned(), 60),
-                (Silver.name().to_owned(), 40),
-                (Gold.name().to_owned(), 30),
-            ]),
-            actions: HashMap::from([
-                (Moat.name().to_owned(), 10),
-                (Village.name().to_owned(), 10),
-                // (Militia.name().to_owned(), 10),
-                (Smithy.name().to_owned(), 10),
-                // (Remodel.name().to_owned(), 10),
-                (Festival.name().to_owned(), 10),
-                // (Sentry.name().to_owned(), 10),
-                (Market.name().to_owned(), 10),
-                (Laboratory.name().to_owned(), 10),
-                // (Artisan.name().to_owned(), 10),
-            ]),
-            victories: HashMap::from([
-                (Province.name().to_owned(), 10),
-                (Duchy.name().to_owned(), 10),
-                (Estate.name().to_owned(), 10),
-            ]),
-            curses: HashMap::from([(Curse::Curse.name().to_owned(), 10)]),
-        };
 
-        let players: Vec<Player> = (0..num_players).map(|i| Player::new(i)).collect();
-        let curr_player_index = (0..num_players).choose(&mut rng()).unwrap();
-        let game_phase = players[curr_player_index].get_starting_game_phase().clone();
-
-        Game {
-            players,
-            supply,
-            curr_player_index,
-            game_phase,
-            winner: None,
+    fn mixed(name: &str, cards: Vec<Card>) -> Self {
+        Pile {
+            name: name.to_owned(),
+            cards,
+            traits: Vec::new(),
         }
     }
 
-    fn current_player(&mut self) -> &mut Player {
-        &mut self.players[self.curr_player_index]
+    fn top(&self) -> Option<Card> {
+        self.cards.last().copied()
     }
 
-    fn current_player_read_only(&self) -> &Player {
-        &self.players[self.curr_player_index]
+    fn is_empty(&self) -> bool {
+        self.cards.is_empty()
     }
 
-    fn accept_move(&mut self, player_index: usize, game_move: GameMove) -> Result<(), GameError> {
-        if player_index != self.curr_player_index {
-            return Err(GameError::InvalidMove("Wrong player index".to_owned()));
+    /// Whether a card belongs in this pile. An empty mixed pile no longer
+    /// knows what it held, unless it is a split pile.
+    fn holds(&self, card: Card) -> bool {
+        self.name == card.name() || self.name == card.info().pile || self.cards.contains(&card)
+    }
+
+    /// Moves every copy of the top card to the bottom of the pile, as Allies'
+    /// rotating split piles allow. Returns false if that changes nothing.
+    #[cfg(feature = "allies")]
+    fn rotate(&mut self) -> bool {
+        let Some(top) = self.top() else {
+            return false;
+        };
+        let (mut rotated, rest): (Vec<Card>, Vec<Card>) =
+            self.cards.iter().partition(|&&card| card == top);
+        if rest.is_empty() {
+            return false;
         }
-        match (&self.game_phase, game_move) {
+        rotated.extend(rest);
+        self.cards = rotated;
+        true
+    }
+
+    /// One of each different card that belongs in this pile.
+    fn kinds(&self) -> Vec<Card> {
+        let mut kinds: Vec<Card> = Card::named(&self.name).into_iter().collect();
+        for &card in &self.cards {
+            if !kinds.contains(&card) {
+                kinds.push(card);
+            }
+        }
+        kinds
+    }
+}
+
+impl Debug for Pile {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self.top() {
+            Some(top) if top.name() != self.name => {
+                write!(
+                    f,
+                    "{}: {} ({} on top)",
+                    self.name,
+                    self.cards.len(),
+                    top.name()
+                )?;
+            }
+            _ => write!(f, "{}: {}", self.name, self.cards.len())?,
+        }
+        if !self.traits.is_empty() {
+            write!(f, " {:?}", self.traits)?;
+        }
+        Ok(())
+    }
+}
+
+/// The Boons or Hexes, shared by all players. Received ones are discarded,
+/// and the discards are shuffled to form a new deck once it runs out.
+#[cfg(feature = "nocturne")]
+struct SharedDeck {
+    // The top of the deck is the end of the Vec
+    cards: Vec<Card>,
+    discard: Vec<Card>,
+    rng: StdRng,
+}
+
+#[cfg(feature = "nocturne")]
+impl SharedDeck {
+    fn new(mut cards: Vec<Card>, seed: u64) -> Self {
+        let mut rng = StdRng::seed_from_u64(seed);
+        cards.shuffle(&mut rng);
+        SharedDeck {
+            cards,
+            discard: Vec::new(),
+            rng,
+        }
+    }
+
+    fn draw(&mut self) -> Option<Card> {
+        if self.cards.is_empty() {
+            self.cards = take(&mut self.discard);
+            self.cards.shuffle(&mut self.rng);
+        }
+        self.cards.pop()
+    }
+
+    fn discard(&mut self, card: Card) {
+        self.discard.push(card);
+    }
+}
+
+#[cfg(feature = "nocturne")]
+impl Debug for SharedDeck {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "{} in the deck, discarded {:?}",
+            self.cards.len(),
+            self.discard
+        )
+    }
+}
+
+#[derive(Debug)]
+struct Supply {
+    // Basic piles first, then the kingdom, in the order they were set up
+    piles: Vec<Pile>,
+    // Piles outside the supply, such as Spoils. Their cards can only be gained
+    // by the cards that call for them, and they never end the game.
+    non_supply: Vec<Pile>,
+    // Events that can be bought alongside the piles
+    events: Vec<Card>,
+    // Landmarks, which change how the game is scored
+    landmarks: Vec<Card>,
+    // Projects that each player can put a cube on once
+    projects: Vec<Card>,
+    // Ways that any Action card can be played as
+    ways: Vec<Card>,
+    // Artifacts set out by the kingdom cards; who holds them is tracked by the players
+    #[cfg(feature = "renaissance")]
+    artifacts: Vec<Card>,
+    // The extra kingdom pile that protects against Young Witch
+    #[cfg(feature = "cornucopia")]
+    bane: Option<Card>,
+    // The one Ally in the game, whose ability players spend Favors on
+    ally: Option<Card>,
+    // VP tokens sitting on supply piles and Landmarks, by name
+    #[cfg(feature = "empires")]
+    vp_tokens: HashMap<String, u32>,
+    // Debt tokens sitting on supply piles, by pile name, taken by whoever buys from the pile
+    debt_tokens: HashMap<String, u32>,
+    // Favor tokens put on supply piles with Family of Inventors, by pile name
+    favor_tokens: HashMap<String, u32>,
+    // The pile Obelisk scores for
+    #[cfg(feature = "empires")]
+    obelisk: Option<String>,
+    // Piles still holding a Trade Route token, and how many tokens have been
+    // moved from them to the Trade Route mat
+    #[cfg(feature = "prosperity")]
+    trade_route_piles: Vec<String>,
+    #[cfg(feature = "prosperity")]
+    trade_route_mat: u32,
+    // Only set up when a card in the kingdom uses them
+    #[cfg(feature = "nocturne")]
+    boons: SharedDeck,
+    #[cfg(feature = "nocturne")]
+    hexes: SharedDeck,
+    // The unused Action that Way of the Mouse plays, set aside at setup
+    #[cfg(feature = "menagerie")]
+    mouse: Option<Card>,
+    // The Boons Druid set aside at setup, which stay there all game
+    #[cfg(feature = "nocturne")]
+    druid_boons: Vec<Card>,
+}
+
+impl Supply {
+    fn pile(&self, card: Card) -> Option<&Pile> {
+        self.piles
+            .iter()
+            .chain(self.non_supply.iter())
+            .find(|pile| pile.holds(card))
+    }
+
+    fn pile_mut(&mut self, card: Card) -> Option<&mut Pile> {
+        self.piles
+            .iter_mut()
+            .chain(self.non_supply.iter_mut())
+            .find(|pile| pile.holds(card))
+    }
+
+    fn pile_named(&self, name: &str) -> Option<&Pile> {
+        self.piles
+            .iter()
+            .chain(self.non_supply.iter())
+            .find(|pile| pile.name == name)
+    }
+
+    /// The Traits on the pile a card belongs to.
+    fn traits_of(&self, card: Card) -> Vec<Card> {
+        self.pile(card)
+            .map_or_else(Vec::new, |pile| pile.traits.clone())
+    }
+
+    /// The supply pile a Trait was put on.
+    #[cfg(feature = "plunder")]
+    fn pile_with_trait(&self, trait_card: Card) -> Option<&Pile> {
+        self.piles
+            .iter()
+            .find(|pile| pile.traits.contains(&trait_card))
+    }
+
+    /// Rotates the supply pile with the given name, returning false if it
+    /// doesn't exist or nothing moved.
+    #[cfg(feature = "allies")]
+    fn rotate(&mut self, name: &str) -> bool {
+        self.piles
+            .iter_mut()
+            .find(|pile| pile.name == name)
+            .is_some_and(Pile::rotate)
+    }
+
+    /// Whether a card has a pile in the supply proper, so can be bought.
+    fn in_supply(&self, card: Card) -> bool {
+        self.piles.iter().any(|pile| pile.holds(card))
+    }
+
+    /// The top card of each non-empty supply pile.
+    fn available_cards(&self) -> Vec<Card> {
+        let mut cards: Vec<Card> = self.piles.iter().filter_map(Pile::top).collect();
+        cards.sort_by_key(|card| card.name());
+        cards
+    }
+
+    /// One of each card with a pile in the supply, empty or not.
+    fn all_cards(&self) -> Vec<Card> {
+        let mut cards: Vec<Card> = self.piles.iter().flat_map(Pile::kinds).collect();
+        cards.sort_by_key(|card| card.name());
+        cards
+    }
+
+    /// The top card of the pile with the given name, e.g. "Ruins".
+    #[cfg(any(feature = "dark_ages", feature = "allies", feature = "plunder"))]
+    fn top_of(&self, name: &str) -> Option<Card> {
+        self.pile_named(name).and_then(Pile::top)
+    }
+
+    /// Looks up a card by a possibly abbreviated or misspelt name, preferring
+    /// cards in this supply so that e.g. a prefix only has to be unique among them.
+    fn find_card(&self, query: &str) -> Result<Card, LookupError> {
+        let candidates = self
+            .all_cards()
+            .into_iter()
+            .chain(self.events.clone())
+            .chain(self.projects.clone())
+            .chain(self.ways.clone());
+        match find_card_among(query, candidates) {
+            Err(LookupError::NotFound { .. }) => find_card(query),
+            found => found,
+        }
+    }
+
+    /// Number of cards left in the pile a card can be taken from, or None if no
+    /// pile holds it. A card buried in a mixed pile can't be taken, so counts as 0.
+    fn count(&self, card: Card) -> Option<u8> {
+        let pile = self.pile(card)?;
+        Some(if pile.top() == Some(card) {
+            pile.cards.len() as u8
+        } else {
+            0
+        })
+    }
+
+    /// Checks that a card is on top of its pile, so that it can be taken.
+    fn check_can_take(&self, card_to_take: Card) -> Result<(), GameError> {
+        let card_name = card_to_take.name().to_owned();
+        let pile = self
+            .pile(card_to_take)
+            .ok_or(GameError::CardNotFoundInSupply(card_name.clone()))?;
+        match pile.top() {
+            None => Err(GameError::CardSupplyDepleted(card_name)),
+            Some(top) if top != card_to_take => Err(GameError::InvalidMove(format!(
+                "{card_name} is not on top of the {} pile",
+                pile.name
+            ))),
+            Some(_) => Ok(()),
+        }
+    }
+
+    fn take_card(&mut self, card_to_take: Card) -> Result<(), GameError> {
+        self.check_can_take(card_to_take)?;
+        if let Some(pile) = self.pile_mut(card_to_take) {
+            pile.cards.pop();
+        }
+        Ok(())
+    }
+
+    /// Puts a card back on top of its pile, e.g. Spoils after being played.
+    #[cfg(any(
+        feature = "dark_ages",
+        feature = "adventures",
+        feature = "empires",
+        feature = "nocturne",
+        feature = "renaissance",
+        feature = "menagerie",
+        feature = "hinterlands",
+        feature = "allies",
+        feature = "plunder"
+    ))]
+    fn return_card(&mut self, card: Card) -> Result<(), GameError> {
+        let pile = self
+            .pile_mut(card)
+            .ok_or(GameError::CardNotFoundInSupply(card.name().to_owned()))?;
+        pile.cards.push(card);
+        Ok(())
+    }
+
+    #[cfg(feature = "empires")]
+    fn add_vp(&mut self, name: &str, vp: u32) {
+        *self.vp_tokens.entry(name.to_owned()).or_default() += vp;
+    }
+
+    /// Takes up to `max` VP tokens from a pile or Landmark, returning how many there were.
+    #[cfg(feature = "empires")]
+    fn take_vp(&mut self, name: &str, max: u32) -> u32 {
+        let Some(tokens) = self.vp_tokens.get_mut(name) else {
+            return 0;
+        };
+        let taken = (*tokens).min(max);
+        *tokens -= taken;
+        taken
+    }
+
+    #[cfg(feature = "empires")]
+    fn add_debt(&mut self, name: &str, debt: u32) {
+        *self.debt_tokens.entry(name.to_owned()).or_default() += debt;
+    }
+
+    /// Takes all the Debt tokens from the pile a card comes from.
+    fn take_debt(&mut self, card: Card) -> u32 {
+        let Some(pile) = self.pile(card).map(|pile| pile.name.clone()) else {
+            return 0;
+        };
+        self.debt_tokens.remove(&pile).unwrap_or(0)
+    }
+
+    fn num_empty_supply_piles(&self) -> u8 {
+        self.piles.iter().filter(|pile| pile.is_empty()).count() as u8
+    }
+
+    /// The game ends once the Provinces (or the Colonies, if in use) run out,
+    /// or any three supply piles are empty (four with 5 or more players).
+    fn check_game_over(&self, num_players: usize) -> bool {
+        let ran_out = |name| self.pile_named(name).is_some_and(Pile::is_empty);
+        let empty_piles_to_end = if num_players >= 5 { 4 } else { 3 };
+        ran_out("Province")
+            || ran_out("Colony")
+            || self.num_empty_supply_piles() >= empty_piles_to_end
+    }
+}
+
+/// The single trash pile shared by all players.
+#[derive(Debug, Default)]
+struct Trash {
+    cards: Vec<Card>,
+    // Copies turned face down for the rest of the turn, e.g. by Necromancer
+    face_down: Vec<Card>,
+}
+
+impl Trash {
+    fn add(&mut self, card: Card) {
+        self.cards.push(card);
+    }
+
+    /// Removes a card from the trash, e.g. when a player gains it.
+    fn take(&mut self, index: usize) -> Option<Card> {
+        if index < self.cards.len() {
+            Some(self.cards.remove(index))
+        } else {
+            None
+        }
+    }
+}
+
+/// What a player is being asked to choose.
+#[derive(Debug, Clone)]
+enum Prompt {
+    /// Between `min` and `max` cards from hand, answered with `ChooseCards`.
+    CardsFromHand {
+        min: usize,
+        max: usize,
+        filter: CardFilter,
+    },
+    /// A card from the supply costing up to `max_cost` (or exactly `max_cost`
+    /// if `exact`), answered with `GainCard`.
+    CardFromSupply {
+        max_cost: Cost,
+        exact: bool,
+        filter: CardFilter,
+    },
+    /// One of a list of options, answered with `ChooseOption`.
+    Options { options: Vec<String> },
+}
+
+/// What happens once a decision is answered. Any cards set aside while the
+/// decision is pending are captured by the closure.
+type Continuation = Box<dyn FnOnce(&mut Game, usize, Answer) -> Result<(), GameError> + Send>;
+
+/// A step of a card's effect that has to wait until earlier decisions are answered.
+type Effect = Box<dyn FnOnce(&mut Game) -> Result<(), GameError> + Send>;
+
+/// Work the engine still has to do before play can continue.
+enum Pending {
+    Decision(Decision),
+    Effect(Effect),
+}
+
+/// Something that happened which cards in play may respond to.
+#[derive(Debug, Clone, Copy)]
+enum Event {
+    #[cfg(any(
+        feature = "seaside",
+        feature = "prosperity",
+        feature = "adventures",
+        feature = "empires",
+        feature = "nocturne",
+        feature = "renaissance",
+        feature = "menagerie",
+        feature = "hinterlands",
+        feature = "allies",
+        feature = "plunder"
+    ))]
+    Gain { player_index: usize, card: Card },
+    Play { player_index: usize, card: Card },
+    // Once a card's effect has been fully resolved
+    #[cfg(any(feature = "adventures", feature = "renaissance", feature = "plunder"))]
+    Played { player_index: usize, card: Card },
+    #[cfg(any(
+        feature = "prosperity",
+        feature = "adventures",
+        feature = "empires",
+        feature = "renaissance",
+        feature = "hinterlands",
+        feature = "guilds"
+    ))]
+    Buy {
+        player_index: usize,
+        #[cfg(any(feature = "prosperity", feature = "empires", feature = "hinterlands"))]
+        card: Card,
+    },
+    // On entering the Treasure phase, which is where the Buy phase begins
+    #[cfg(any(
+        feature = "empires",
+        feature = "nocturne",
+        feature = "renaissance",
+        feature = "allies"
+    ))]
+    BuyPhaseStart { player_index: usize },
+    #[cfg(any(
+        feature = "seaside",
+        feature = "alchemy",
+        feature = "dark_ages",
+        feature = "adventures",
+        feature = "empires",
+        feature = "renaissance",
+        feature = "menagerie",
+        feature = "hinterlands",
+        feature = "allies",
+        feature = "plunder"
+    ))]
+    BuyPhaseEnd { player_index: usize },
+    #[cfg(any(
+        feature = "adventures",
+        feature = "nocturne",
+        feature = "renaissance",
+        feature = "allies",
+        feature = "plunder"
+    ))]
+    TurnStart { player_index: usize },
+    #[cfg(any(feature = "dark_ages", feature = "empires", feature = "renaissance"))]
+    Trash { player_index: usize },
+}
+
+/// How long a trigger keeps listening for events.
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum Expiry {
+    EndOfTurn,
+    StartOfTurn(usize),
+    // Only once the handler has seen what it was waiting for
+    #[cfg(feature = "plunder")]
+    Never,
+}
+
+/// Handles an event, returning whether the trigger should keep listening.
+type TriggerFn = Box<dyn FnMut(&mut Game, Event) -> Result<bool, GameError> + Send>;
+
+/// A lasting effect of a card, e.g. "until your next turn, when ...".
+struct Trigger {
+    expires: Expiry,
+    handler: TriggerFn,
+}
+
+/// An effect of a Duration card that happens at the start of its owner's next turn.
+struct DurationEffect {
+    player_index: usize,
+    // The card that stays in play until the effect happens
+    source: Card,
+    effect: Effect,
+}
+
+#[derive(Debug, Clone, Copy)]
+struct ExtraTurn {
+    player_index: usize,
+    hand_size: usize,
+}
+
+/// A choice the engine is waiting on before play can continue.
+struct Decision {
+    player_index: usize,
+    // The card whose effect is asking
+    source: Card,
+    prompt: Prompt,
+    then: Continuation,
+}
+
+impl Debug for Decision {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("Decision")
+            .field("player_index", &self.player_index)
+            .field("source", &self.source)
+            .field("prompt", &self.prompt)
+            .finish_non_exhaustive()
+    }
+}
+
+impl Decision {
+    fn describe(&self) -> String {
+        let request = match &self.prompt {
+            Prompt::CardsFromHand { min, max, filter } => {
+                format!("choose {min} to {max} cards ({filter:?}) from hand")
+            }
+            Prompt::CardFromSupply {
+                max_cost,
+                exact,
+                filter,
+            } => {
+                let limit = if *exact { "exactly" } else { "up to" };
+                format!("gain a card ({filter:?}) costing {limit} {max_cost}")
+            }
+            Prompt::Options { options } => format!("choose an option {options:?}"),
+        };
+        format!(
+            "Player {} must {} for {:?}",
+            self.player_index, request, self.source
+        )
+    }
+}
+
+/// A validated answer to a decision.
+enum Answer {
+    Cards(Vec<Card>),
+    SupplyCard(Card),
+    Option(usize),
+}
+
+impl Answer {
+    fn cards(self) -> Vec<Card> {
+        match self {
+            Answer::Cards(cards) => cards,
+            _ => unreachable!("answer validated against prompt"),
+        }
+    }
+
+    fn supply_card(self) -> Card {
+        match self {
+            Answer::SupplyCard(card) => card,
+            _ => unreachable!("answer validated against prompt"),
+        }
+    }
+
+    fn option(self) -> usize {
+        match self {
+            Answer::Option(option_index) => option_index,
+            _ => unreachable!("answer validated against prompt"),
+        }
+    }
+}
+
+#[derive(Debug)]
+enum GainDestination {
+    Discard,
+    Hand,
+    #[cfg(any(
+        feature = "intrigue",
+        feature = "seaside",
+        feature = "dark_ages",
+        feature = "adventures",
+        feature = "empires",
+        feature = "nocturne",
+        feature = "menagerie",
+        feature = "hinterlands",
+        feature = "cornucopia",
+        feature = "guilds",
+        feature = "allies",
+        feature = "plunder"
+    ))]
+    Deck,
+    // The gaining effect holds on to the card itself
+    #[cfg(any(feature = "seaside", feature = "menagerie", feature = "plunder"))]
+    SetAside,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+enum GameMove {
+    // An Action card can be played using a Way's effect instead of its own
+    PlayCard {
+        card_index: usize,
+        way: Option<Card>,
+    },
+    BuyCard {
+        card: Card,
+    },
+    ChooseCards {
+        card_indices: Vec<usize>,
+    },
+    GainCard {
+        card: Card,
+    },
+    ChooseOption {
+        option_index: usize,
+    },
+    PlayAllTreasures,
+    SpendVillager,
+    SpendCoffer,
+    EndActions,
+    EndTreasures,
+    EndBuys,
+    EndTurn,
+}
+
+#[derive(Debug, Clone)]
+#[allow(clippy::enum_variant_names)]
+enum GamePhase {
+    ActionPhase,
+    TreasurePhase,
+    BuyPhase,
+    // After the Buy phase, for playing Night cards
+    NightPhase,
+}
+
+/// Everything that happened during a single turn, for card effects that
+/// depend on what has already been played, gained, bought or trashed.
+#[derive(Debug, Default)]
+struct TurnRecord {
+    player_index: usize,
+    // Which of this player's turns this is, counting from 1
+    turn_number: u32,
+    extra_turn: bool,
+    played: Vec<Card>,
+    bought: Vec<Card>,
+    // Events aren't cards, so buying one isn't buying a card
+    events: Vec<Card>,
+    // Gains and trashes can happen to any player, so they record who was affected
+    gained: Vec<(usize, Card)>,
+    trashed: Vec<(usize, Card)>,
+}
+
+impl TurnRecord {
+    fn played_count(&self, card_name: &str) -> usize {
+        self.played
+            .iter()
+            .filter(|card| card.name() == card_name)
+            .count()
+    }
+
+    fn gained_by(&self, player_index: usize) -> impl Iterator<Item = Card> + '_ {
+        Self::cards_for(&self.gained, player_index)
+    }
+
+    fn trashed_by(&self, player_index: usize) -> impl Iterator<Item = Card> + '_ {
+        Self::cards_for(&self.trashed, player_index)
+    }
+
+    fn cards_for(events: &[(usize, Card)], player_index: usize) -> impl Iterator<Item = Card> + '_ {
+        events
+            .iter()
+            .filter(move |(index, _)| *index == player_index)
+            .map(|&(_, card)| card)
+    }
+
+    fn summary(&self) -> String {
+        format!(
+            "turn {}{} of player {}: played {:?}, bought {:?}, gained {:?}, trashed {:?}",
+            self.turn_number,
+            if self.extra_turn { " (extra)" } else { "" },
+            self.player_index,
+            self.played,
+            self.bought,
+            self.gained_by(self.player_index).collect::<Vec<_>>(),
+            self.trashed_by(self.player_index).collect::<Vec<_>>(),
+        )
+    }
+}
+
+#[derive(Debug)]
+struct GameHistory {
+    current_turn: TurnRecord,
+    // Each player's most recently finished turn
+    last_turns: Vec<Option<TurnRecord>>,
+    turns_taken: Vec<u32>,
+    extra_turns_taken: Vec<u32>,
+}
+
+impl GameHistory {
+    fn new(num_players: usize, first_player_index: usize) -> Self {
+        let mut history = GameHistory {
+            current_turn: TurnRecord::default(),
+            last_turns: (0..num_players).map(|_| None).collect(),
+            turns_taken: vec![0; num_players],
+            extra_turns_taken: vec![0; num_players],
+        };
+        history.start_turn(first_player_index, false);
+        history
+    }
+
+    /// Files away the turn in progress and starts recording a new one.
+    fn start_turn(&mut self, player_index: usize, extra_turn: bool) {
+        self.turns_taken[player_index] += 1;
+        if extra_turn {
+            self.extra_turns_taken[player_index] += 1;
+        }
+        let finished = std::mem::replace(
+            &mut self.current_turn,
+            TurnRecord {
+                player_index,
+                turn_number: self.turns_taken[player_index],
+                extra_turn,
+                ..TurnRecord::default()
+            },
+        );
+        if finished.turn_number > 0 {
+            let finished_player_index = finished.player_index;
+            self.last_turns[finished_player_index] = Some(finished);
+        }
+    }
+
+    fn last_turn(&self, player_index: usize) -> Option<&TurnRecord> {
+        self.last_turns[player_index].as_ref()
+    }
+
+    fn regular_turns_taken(&self, player_index: usize) -> u32 {
+        self.turns_taken[player_index] - self.extra_turns_taken[player_index]
+    }
+}
+
+/// How many Projects each player can buy over the game.
+const PROJECT_CUBES: usize = 2;
+
+/// How many kingdom piles a randomly picked kingdom has, not counting the Bane.
+const KINGDOM_SIZE: usize = 10;
+
+/// The kingdom used when none is given.
+const DEFAULT_KINGDOM: [&str; 8] = [
+    "Moat",
+    "Village",
+    "Smithy",
+    "Remodel",
+    "Festival",
+    "Sentry",
+    "Market",
+    "Laboratory",
+];
+
+struct Game {
+    players: Vec<Player>,
+    supply: Supply,
+    trash: Trash,
+    // Cleared at the end of every turn
+    cost_modifiers: Vec<CostModifier>,
+    // Cards that can't be bought this turn, e.g. Actions while Deluded
+    buy_restrictions: Vec<CardFilter>,
+    // Decisions and deferred effects, in the order they happen
+    pending: VecDeque<Pending>,
+    // Work queued by the effect currently resolving, which happens before
+    // anything already pending
+    staged: Vec<Pending>,
+    triggers: Vec<Trigger>,
+    durations: Vec<DurationEffect>,
+    // Duration cards staying in play until the next time something happens,
+    // e.g. Search, with the players they belong to
+    waiting: Vec<(usize, Card)>,
+    extra_turns: VecDeque<ExtraTurn>,
+    // Effects that happen once the current turn is over, before the next one starts
+    after_turn: Vec<Effect>,
+    // Once the game has ended, the players with Fleet still to take their turn
+    // in the extra round
+    fleet_turns: Option<VecDeque<usize>>,
+    // Players who played Possession, each owed a turn of the player to their
+    // left once their own turns are over
+    possessions: Vec<usize>,
+    // Who makes the current player's decisions during a possessed turn
+    possessed_by: Option<usize>,
+    // The possessed player's cards trashed this turn, which go to their
+    // discard pile once it is over
+    possessed_trashed: Vec<Card>,
+    curr_player_index: usize,
+    game_phase: GamePhase,
+    history: GameHistory,
+    // Set once the game is over; more than one player means a shared victory
+    winners: Option<Vec<usize>>,
+}
+
+impl Debug for Game {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_fmt(format_args!("Current player: {}\n", self.curr_player_index))?;
+        f.write_fmt(format_args!(
+            "This turn: {}\n",
+            self.history.current_turn.summary()
+        ))?;
+        if let Some(last_turn) = self.history.last_turn(self.curr_player_index) {
+            f.write_fmt(format_args!("Last turn: {}\n", last_turn.summary()))?;
+        }
+        f.write_fmt(format_args!(
+            "Actions: {}\n",
+            self.current_player_read_only().actions
+        ))?;
+        f.write_fmt(format_args!(
+            "Buys: {}\n",
+            self.current_player_read_only().buys
+        ))?;
+        f.write_fmt(format_args!(
+            "Coins: {}\n",
+            self.current_player_read_only().coins
+        ))?;
+        if self.current_player_read_only().potions > 0 {
+            f.write_fmt(format_args!(
+                "Potions: {}\n",
+                self.current_player_read_only().potions
+            ))?;
+        }
+        f.write_fmt(format_args!("Current phase: {:?}\n", self.game_phase))?;
+        f.write_fmt(format_args!("Supply: {:#?}\n", self.supply))?;
+        f.write_fmt(format_args!("Trash: {:#?}\n", self.trash.cards))?;
+        if let Some(decision) = self.pending_decision() {
+            f.write_fmt(format_args!("Pending decision: {}\n", decision.describe()))?;
+            if decision.player_index != self.curr_player_index {
+                f.write_fmt(format_args!(
+                    "Player {} hand: {:#?}\n",
+                    decision.player_index, self.players[decision.player_index].hand
+                ))?;
+            }
+        }
+        f.write_fmt(format_args!(
+            "Current player shuffles: {}\n",
+            self.current_player_read_only().shuffle_count()
+        ))?;
+        f.write_fmt(format_args!(
+            "Current player deck: {:#?}\n",
+            self.current_player_read_only().deck
+        ))?;
+        f.write_fmt(format_args!(
+            "Current player discard: {:#?}\n",
+            self.current_player_read_only().discard
+        ))?;
+        f.write_fmt(format_args!(
+            "Current player hand: {:#?}\n",
+            self.current_player_read_only().hand
+        ))?;
+        f.write_fmt(format_args!(
+            "Current player played cards: {:#?}\n",
+            self.current_player_read_only().played
+        ))?;
+        let player = self.current_player_read_only();
+        if !player.durations.is_empty() {
+            f.write_fmt(format_args!(
+                "Current player durations: {:#?}\n",
+                player.durations
+            ))?;
+        }
+        if player.vp_tokens > 0 {
+            f.write_fmt(format_args!(
+                "Current player VP tokens: {}\n",
+                player.vp_tokens
+            ))?;
+        }
+        if player.debt > 0 {
+            f.write_fmt(format_args!("Current player Debt: {}\n", player.debt))?;
+        }
+        if !player.states.is_empty() {
+            f.write_fmt(format_args!("Current player states: {:?}\n", player.states))?;
+        }
+        if player.villagers > 0 || player.coffers > 0 {
+            f.write_fmt(format_args!(
+                "Current player Villagers: {}, Coffers: {}\n",
+                player.villagers, player.coffers
+            ))?;
+        }
+        if player.favors > 0 {
+            f.write_fmt(format_args!("Current player Favors: {}\n", player.favors))?;
+        }
+        if !player.projects.is_empty() {
+            f.write_fmt(format_args!(
+                "Current player projects: {:?}\n",
+                player.projects
+            ))?;
+        }
+        if !player.artifacts.is_empty() {
+            f.write_fmt(format_args!(
+                "Current player artifacts: {:?}\n",
+                player.artifacts
+            ))?;
+        }
+        if player.minus_card_token || player.minus_coin_token.is_some() {
+            f.write_fmt(format_args!(
+                "Current player -1 Card token on deck: {}, -$1 token: {}\n",
+                player.minus_card_token,
+                player.minus_coin_token.is_some()
+            ))?;
+        }
+        if let Some(inherited) = player.inherited {
+            f.write_fmt(format_args!(
+                "Current player Estate token: on {}\n",
+                inherited.name()
+            ))?;
+        }
+        if !player.saved.is_empty() {
+            f.write_fmt(format_args!("Current player saved: {:?}\n", player.saved))?;
+        }
+        for (token, pile) in &player.tokens {
+            f.write_fmt(format_args!(
+                "Current player {} token: on {}\n",
+                token, pile
+            ))?;
+        }
+        for (mat, cards) in &player.mats {
+            f.write_fmt(format_args!("Current player {:?} mat: {:?}\n", mat, cards))?;
+        }
+        Ok(())
+    }
+}
+
+impl Game {
+    fn initialise_game(num_players: usize) -> Self {
+        Game::initialise_game_with_seed(num_players, rng().random())
+    }
+
+    /// Sets up a game with the default kingdom whose shuffles and starting player
+    /// are fully determined by `seed`.
+    fn initialise_game_with_seed(num_players: usize, seed: u64) -> Self {
+        let kingdom: Vec<Card> = DEFAULT_KINGDOM.iter().map(|&name| card(name)).collect();
+        Game::initialise_game_with_kingdom(num_players, seed, &kingdom)
+            .expect("the default kingdom is valid")
+    }
+
+    /// Sets up a game using the given kingdom cards alongside the basic supply.
+    /// Any Knight in the kingdom stands for the whole pile of Knights.
+    fn initialise_game_with_kingdom(
+        num_players: usize,
+        seed: u64,
+        kingdom: &[Card],
+    ) -> Result<Self, GameError> {
+        let expansions: Vec<&str> = expansions().collect();
+        Game::initialise_game_with_expansions(num_players, seed, kingdom, &expansions)
+    }
+
+    /// Like `initialise_game_with_kingdom`, but any extra pile the kingdom
+    /// calls for at random, such as Young Witch's Bane, comes from `expansions`.
+    fn initialise_game_with_expansions(
+        num_players: usize,
+        seed: u64,
+        kingdom: &[Card],
+        // Only the Bane and Way of the Mouse's card are picked from these
+        #[cfg_attr(
+            not(any(feature = "cornucopia", feature = "menagerie")),
+            allow(unused_variables)
+        )]
+        expansions: &[&str],
+    ) -> Result<Self, GameError> {
+        let mut rng = StdRng::seed_from_u64(seed);
+        let from_expansion = |expansion: &str| {
+            kingdom
+                .iter()
+                .any(|card| card.info().expansion == expansion)
+        };
+
+        let mut piles = vec![
+            Pile::of(card("Copper"), 60),
+            Pile::of(card("Silver"), 40),
+            Pile::of(card("Gold"), 30),
+        ];
+        // Platinum and Colony join the basic supply when playing with Prosperity,
+        // and the Potion pile whenever a kingdom card costs a Potion
+        if from_expansion("Prosperity") {
+            piles.push(Pile::of(card("Platinum"), 12));
+        }
+        if kingdom.iter().any(|card| card.cost().potions > 0) {
+            piles.push(Pile::of(card("Potion"), 16));
+        }
+        // Victory piles are smaller in two-player games, and there are extra
+        // Provinces for five or six players
+        let victory_pile_size = if num_players == 2 { 8 } else { 12 };
+        let province_pile_size = match num_players {
+            5 => 15,
+            6 => 18,
+            _ => victory_pile_size,
+        };
+        piles.extend([
+            Pile::of(card("Estate"), victory_pile_size),
+            Pile::of(card("Duchy"), victory_pile_size),
+            Pile::of(card("Province"), province_pile_size),
+        ]);
+        if from_expansion("Prosperity") {
+            piles.push(Pile::of(card("Colony"), victory_pile_size));
+        }
+        // Ten Curses for each player but the first
+        piles.push(Pile::of(card("Curse"), (10 * (num_players.max(2) - 1)) as u8));
+        let num_basic_piles = piles.len();
+
+        let mut non_supply: Vec<Pile> = Vec::new();
+        let mut events: Vec<Card> = Vec::new();
+        let mut landmarks: Vec<Card> = Vec::new();
+        let mut projects: Vec<Card> = Vec::new();
+        let mut ways: Vec<Card> = Vec::new();
+        #[cfg(feature = "renaissance")]
+        let mut artifacts: Vec<Card> = Vec::new();
+        let mut ally: Option<Card> = None;
+        let mut traits: Vec<Card> = Vec::new();
+        #[cfg(feature = "empires")]
+        let mut vp_tokens: HashMap<String, u32> = HashMap::new();
+        // Victory kingdom piles are the same size as the basic Victory piles
+        let kingdom_pile_size = |card: Card| {
+            if card.is(CardType::Victory) {
+                victory_pile_size
+            } else {
+                10
+            }
+        };
+        for &kingdom_card in kingdom {
+            let info = kingdom_card.info();
+            if info.basic
+                || info.non_supply
+                || [
+                    CardType::Ruins,
+                    CardType::Shelter,
+                    CardType::Heirloom,
+                    CardType::Boon,
+                    CardType::Hex,
+                    CardType::State,
+                    CardType::Artifact,
+                    CardType::Prize,
+                ]
+                .iter()
+                .any(|&card_type| kingdom_card.is(card_type))
+            {
+                return Err(GameError::InvalidKingdom(format!(
+                    "{} is not a kingdom card",
+                    kingdom_card.name()
+                )));
+            }
+            if !kingdom_card.is_implemented() {
+                return Err(GameError::InvalidKingdom(format!(
+                    "{} is not implemented yet",
+                    kingdom_card.name()
+                )));
+            }
+            // Events, Landmarks, Projects and Ways are set out next to the supply
+            // rather than as piles
+            if [
+                CardType::Event,
+                CardType::Landmark,
+                CardType::Project,
+                CardType::Way,
+            ]
+            .iter()
+            .any(|&card_type| kingdom_card.is(card_type))
+            {
+                if events.contains(&kingdom_card)
+                    || landmarks.contains(&kingdom_card)
+                    || projects.contains(&kingdom_card)
+                    || ways.contains(&kingdom_card)
+                {
+                    return Err(GameError::InvalidKingdom(format!(
+                        "{} is in the kingdom twice",
+                        kingdom_card.name()
+                    )));
+                }
+                if kingdom_card.is(CardType::Event) {
+                    events.push(kingdom_card);
+                } else if kingdom_card.is(CardType::Project) {
+                    projects.push(kingdom_card);
+                } else if kingdom_card.is(CardType::Way) {
+                    ways.push(kingdom_card);
+                } else {
+                    landmarks.push(kingdom_card);
+                }
+                #[cfg(feature = "empires")]
+                if info.vp_per_player > 0 {
+                    vp_tokens.insert(info.name.clone(), info.vp_per_player * num_players as u32);
+                }
+                continue;
+            }
+            // There is only ever one Ally, while each Trait goes on a kingdom
+            // pile once they are all set up
+            if kingdom_card.is(CardType::Ally) {
+                if let Some(other) = ally.replace(kingdom_card) {
+                    return Err(GameError::InvalidKingdom(format!(
+                        "{} and {} are both Allies, but a game only has one",
+                        other.name(),
+                        kingdom_card.name()
+                    )));
+                }
+                continue;
+            }
+            if kingdom_card.is(CardType::Trait) {
+                if traits.contains(&kingdom_card) {
+                    return Err(GameError::InvalidKingdom(format!(
+                        "{} is in the kingdom twice",
+                        kingdom_card.name()
+                    )));
+                }
+                traits.push(kingdom_card);
+                continue;
+            }
+            let count = kingdom_pile_size(kingdom_card);
+            let pile = if kingdom_card.is(CardType::Knight) {
+                let mut knights: Vec<Card> = all_cards()
+                    .filter(|card| card.is(CardType::Knight))
+                    .collect();
+                knights.shuffle(&mut rng);
+                Pile::mixed("Knights", knights)
+            } else if !info.pile.is_empty() {
+                // A split pile has five of each half, or four of each card if
+                // it is one of Allies' rotating piles, unless its cards say how
+                // many of them there are, like the Castles. The cheapest part
+                // goes on top.
+                let mut parts: Vec<Card> = all_cards()
+                    .filter(|card| card.info().pile == info.pile)
+                    .collect();
+                parts.sort_by_key(|part| part.cost().coins);
+                if let Some(part) = parts.iter().find(|part| !part.is_implemented()) {
+                    return Err(GameError::InvalidKingdom(format!(
+                        "{} is not implemented yet",
+                        part.name()
+                    )));
+                }
+                let copies = |part: Card| match (part.info().pile_size, num_players) {
+                    (0, _) if parts.len() == 2 => 5,
+                    (0, _) => 4,
+                    // Sized parts are all Victory cards, of which a two-player
+                    // game has only one each
+                    (_, 2) => 1,
+                    (count, _) => count as usize,
+                };
+                let cards = parts
+                    .iter()
+                    .rev()
+                    .flat_map(|&part| vec![part; copies(part)])
+                    .collect();
+                Pile::mixed(&info.pile, cards)
+            } else {
+                Pile::of(kingdom_card, count)
+            };
+            if piles[num_basic_piles..]
+                .iter()
+                .any(|existing| existing.name == pile.name)
+            {
+                return Err(GameError::InvalidKingdom(format!(
+                    "{} is in the kingdom twice",
+                    pile.name
+                )));
+            }
+            piles.push(pile);
+            #[cfg(feature = "renaissance")]
+            for artifact in &info.artifacts {
+                let artifact = card(artifact);
+                if !artifacts.contains(&artifact) {
+                    artifacts.push(artifact);
+                }
+            }
+        }
+        // Young Witch adds an eleventh kingdom pile costing $2 or $3, the Bane
+        #[cfg(feature = "cornucopia")]
+        let bane = if kingdom.iter().any(|card| card.name() == "Young Witch") {
+            let bane = all_cards()
+                .filter(|&candidate| {
+                    let cost = candidate.cost();
+                    (2..=3).contains(&cost.coins)
+                        && cost.potions == 0
+                        && cost.debt == 0
+                        && candidate.is_implemented()
+                        && is_plain_kingdom_card(candidate)
+                        && expansions.contains(&candidate.info().expansion.as_str())
+                        && !piles.iter().any(|pile| pile.holds(candidate))
+                })
+                .choose(&mut rng)
+                .ok_or_else(|| {
+                    GameError::InvalidKingdom(format!(
+                        "there is no card from {} to use as the Bane",
+                        expansions.join(", ")
+                    ))
+                })?;
+            piles.push(Pile::of(bane, kingdom_pile_size(bane)));
+            Some(bane)
+        } else {
+            None
+        };
+        // Way of the Mouse sets aside an unused Action costing $2 or $3
+        #[cfg(feature = "menagerie")]
+        let mouse = if ways.iter().any(|way| way.name() == "Way of the Mouse") {
+            let mouse = all_cards()
+                .filter(|&candidate| {
+                    let cost = candidate.cost();
+                    (2..=3).contains(&cost.coins)
+                        && cost.potions == 0
+                        && cost.debt == 0
+                        && candidate.is(CardType::Action)
+                        && candidate.is_implemented()
+                        && is_plain_kingdom_card(candidate)
+                        && expansions.contains(&candidate.info().expansion.as_str())
+                        && !piles.iter().any(|pile| pile.holds(candidate))
+                })
+                .choose(&mut rng)
+                .ok_or_else(|| {
+                    GameError::InvalidKingdom(format!(
+                        "there is no Action from {} for Way of the Mouse",
+                        expansions.join(", ")
+                    ))
+                })?;
+            Some(mouse)
+        } else {
+            None
+        };
+        // Some cards and Events need piles of other cards, e.g. Marauder gives
+        // out Spoils and Ruins
+        for extra in kingdom.iter().flat_map(|card| &card.info().extra_piles) {
+            let set_up = piles
+                .iter()
+                .chain(non_supply.iter())
+                .any(|pile| &pile.name == extra);
+            if set_up {
+                continue;
+            }
+            if extra == "Loot" {
+                // Two of each Loot, shuffled, leaving out any the engine
+                // can't play yet
+                let mut loot: Vec<Card> = all_cards()
+                    .filter(|card| card.is(CardType::Loot) && card.is_implemented())
+                    .flat_map(|loot| [loot; 2])
+                    .collect();
+                loot.shuffle(&mut rng);
+                non_supply.push(Pile::mixed("Loot", loot));
+            } else if extra == "Ruins" {
+                let mut ruins: Vec<Card> = all_cards()
+                    .filter(|card| card.is(CardType::Ruins))
+                    .flat_map(|ruin| [ruin; 10])
+                    .collect();
+                ruins.shuffle(&mut rng);
+                ruins.truncate(10 * (num_players.max(2) - 1));
+                piles.push(Pile::mixed("Ruins", ruins));
+            } else {
+                let extra = card(extra);
+                let count = match extra.info().pile_size {
+                    0 => 10,
+                    count => count,
+                };
+                non_supply.push(Pile::of(extra, count));
+            }
+        }
+        // Each Trait goes on a different Action or Treasure kingdom pile
+        for &trait_card in &traits {
+            let pile = piles[num_basic_piles..]
+                .iter_mut()
+                .filter(|pile| pile.traits.is_empty())
+                .filter(|pile| {
+                    pile.kinds()
+                        .iter()
+                        .any(|card| card.is(CardType::Action) || card.is(CardType::Treasure))
+                })
+                .choose(&mut rng)
+                .ok_or(GameError::InvalidKingdom(format!(
+                    "there is no kingdom pile to put {} on",
+                    trait_card.name()
+                )))?;
+            pile.traits.push(trait_card);
+        }
+        // Each player starts with an Inherited card from its pile in place of
+        // one of their Coppers, like an Heirloom, and Fated cards go on top
+        // whenever their owners shuffle
+        let mut inherited: Vec<Card> = Vec::new();
+        let mut fated: Vec<Card> = Vec::new();
+        for pile in &mut piles[num_basic_piles..] {
+            let trait_names: Vec<&str> = pile.traits.iter().map(|card| card.name()).collect();
+            if trait_names.contains(&"Inherited") {
+                inherited = (0..num_players).filter_map(|_| pile.cards.pop()).collect();
+            }
+            if trait_names.contains(&"Fated") {
+                fated = pile.kinds();
+            }
+        }
+        // Some Landmarks and Events put tokens on supply piles, or pick one out
+        let mut debt_tokens: HashMap<String, u32> = HashMap::new();
+        #[cfg(feature = "empires")]
+        let mut obelisk: Option<String> = None;
+        for set_out in landmarks.iter().chain(&events) {
+            match set_out.name() {
+                #[cfg(feature = "empires")]
+                "Aqueduct" => {
+                    vp_tokens.insert("Silver".to_owned(), 8);
+                    vp_tokens.insert("Gold".to_owned(), 8);
+                }
+                #[cfg(feature = "empires")]
+                "Defiled Shrine" => {
+                    for pile in &piles {
+                        let kinds = pile.kinds();
+                        if kinds.iter().any(|card| card.is(CardType::Action))
+                            && !kinds.iter().any(|card| card.is(CardType::Gathering))
+                        {
+                            vp_tokens.insert(pile.name.clone(), 2);
+                        }
+                    }
+                }
+                // The high bidder in Mountain Pass's auction gets these
+                #[cfg(feature = "empires")]
+                "Mountain Pass" => {
+                    vp_tokens.insert(set_out.name().to_owned(), 8);
+                }
+                #[cfg(feature = "empires")]
+                "Obelisk" => {
+                    obelisk = piles
+                        .iter()
+                        .filter(|pile| pile.kinds().iter().any(|card| card.is(CardType::Action)))
+                        .choose(&mut rng)
+                        .map(|pile| pile.name.clone());
+                }
+                "Tax" => {
+                    for pile in &piles {
+                        debt_tokens.insert(pile.name.clone(), 1);
+                    }
+                }
+                _ => {}
+            }
+        }
+        // Trade Route puts a token on every Victory pile in the supply
+        #[cfg(feature = "prosperity")]
+        let trade_route_piles = if kingdom.iter().any(|card| card.name() == "Trade Route") {
+            piles
+                .iter()
+                .filter(|pile| pile.kinds().iter().any(|card| card.is(CardType::Victory)))
+                .map(|pile| pile.name.clone())
+                .collect()
+        } else {
+            Vec::new()
+        };
+        // Liaisons need an Ally to spend their Favors on, so one is picked at
+        // random if the kingdom doesn't name it
+        if ally.is_none() && kingdom.iter().any(|card| card.is(CardType::Liaison)) {
+            ally = all_cards()
+                .filter(|card| card.is(CardType::Ally) && card.is_implemented())
+                .choose(&mut rng);
+        }
+        // Fate and Doom cards need the Boons and Hexes to receive
+        #[cfg(feature = "nocturne")]
+        let shared_deck = |rng: &mut StdRng, user: CardType, kind: CardType| {
+            let cards = if kingdom.iter().any(|card| card.is(user)) {
+                all_cards().filter(|card| card.is(kind)).collect()
+            } else {
+                Vec::new()
+            };
+            SharedDeck::new(cards, rng.random())
+        };
+        #[cfg(feature = "nocturne")]
+        let mut boons = shared_deck(&mut rng, CardType::Fate, CardType::Boon);
+        #[cfg(feature = "nocturne")]
+        let hexes = shared_deck(&mut rng, CardType::Doom, CardType::Hex);
+        #[cfg(feature = "nocturne")]
+        let druid_boons = if kingdom.iter().any(|card| card.name() == "Druid") {
+            (0..3).filter_map(|_| boons.draw()).collect()
+        } else {
+            Vec::new()
+        };
+        let supply = Supply {
+            piles,
+            non_supply,
+            events,
+            landmarks,
+            projects,
+            ways,
+            #[cfg(feature = "renaissance")]
+            artifacts,
+            #[cfg(feature = "cornucopia")]
+            bane,
+            ally,
+            #[cfg(feature = "empires")]
+            vp_tokens,
+            debt_tokens,
+            favor_tokens: HashMap::new(),
+            #[cfg(feature = "empires")]
+            obelisk,
+            #[cfg(feature = "prosperity")]
+            trade_route_piles,
+            #[cfg(feature = "prosperity")]
+            trade_route_mat: 0,
+            #[cfg(feature = "nocturne")]
+            boons,
+            #[cfg(feature = "nocturne")]
+            hexes,
+            #[cfg(feature = "menagerie")]
+            mouse,
+            #[cfg(feature = "nocturne")]
+            druid_boons,
+        };
+
+        // Shelters replace the starting Estates when playing with Dark Ages
+        let shelters = from_expansion("Dark Ages");
+        let heirlooms: Vec<Card> = kingdom
+            .iter()
+            .filter(|card| !card.info().heirloom.is_empty())
+            .map(|kingdom_card| card(&kingdom_card.info().heirloom))
+            .collect();
+        let mut players: Vec<Player> = (0..num_players)
+            .map(|i| {
+                let mut replacing = heirlooms.clone();
+                replacing.extend(inherited.get(i));
+                let mut player = Player::new(i, rng.random(), shelters, &replacing);
+                player.fated = fated.clone();
+                player
+            })
+            .collect();
+        // Baker gives every player a Coffers to start with
+        if kingdom.iter().any(|card| card.name() == "Baker") {
+            for player in &mut players {
+                player.coffers += 1;
+            }
+        }
+        // With an Ally every player starts with a Favor, and Importer gives
+        // them four more
+        let importer = kingdom.iter().any(|card| card.name() == "Importer");
+        for player in &mut players {
+            player.favors += u32::from(ally.is_some()) + if importer { 4 } else { 0 };
+            player.ally = ally;
+        }
+        let curr_player_index = (0..num_players).choose(&mut rng).unwrap();
+        // Necromancer has the Zombies start in the trash
+        let mut trash = Trash::default();
+        if kingdom.iter().any(|card| card.name() == "Necromancer") {
+            trash.cards = all_cards()
+                .filter(|card| card.is(CardType::Zombie))
+                .collect();
+        }
+
+        let mut game = Game {
+            players,
+            supply,
+            trash,
+            cost_modifiers: Vec::new(),
+            buy_restrictions: Vec::new(),
+            pending: VecDeque::new(),
+            staged: Vec::new(),
+            triggers: Vec::new(),
+            durations: Vec::new(),
+            waiting: Vec::new(),
+            extra_turns: VecDeque::new(),
+            after_turn: Vec::new(),
+            fleet_turns: None,
+            possessions: Vec::new(),
+            possessed_by: None,
+            possessed_trashed: Vec::new(),
+            curr_player_index,
+            game_phase: GamePhase::ActionPhase,
+            history: GameHistory::new(num_players, curr_player_index),
+            winners: None,
+        };
+        game.auto_advance()?;
+        Ok(game)
+    }
+
+    fn current_player(&mut self) -> &mut Player {
+        &mut self.players[self.curr_player_index]
+    }
+
+    fn current_player_read_only(&self) -> &Player {
+        &self.players[self.curr_player_index]
+    }
+
+    /// The player expected to make the next move: whoever owes a pending decision,
+    /// otherwise the player whose turn it is, or whoever possesses them.
+    fn acting_player_index(&self) -> usize {
+        self.controller_of(self.acting_for())
+    }
+
+    /// The player the next move is made for, which is the acting player
+    /// themselves unless they are possessing someone.
+    fn acting_for(&self) -> usize {
+        self.pending_decision()
+            .map_or(self.curr_player_index, |decision| decision.player_index)
+    }
+
+    /// Who makes a player's moves and decisions: the player themselves, unless
+    /// they are taking a possessed turn.
+    fn controller_of(&self, player_index: usize) -> usize {
+        match self.possessed_by {
+            Some(possessor) if player_index == self.curr_player_index => possessor,
+            _ => player_index,
+        }
+    }
+
+    fn accept_move(&mut self, player_index: usize, game_move: GameMove) -> Result<(), GameError> {
+        if self.winners.is_some() {
+            return Err(GameError::GameOver);
+        }
+        if self.pending_decision().is_some() {
+            self.accept_decision(player_index, game_move)?;
+        } else {
+            self.accept_turn_move(player_index, game_move)?;
+        }
+        self.run_pending()?;
+        self.auto_advance()
+    }
+
+    fn accept_turn_move(
+        &mut self,
+        player_index: usize,
+        game_move: GameMove,
+    ) -> Result<(), GameError> {
+        if player_index != self.controller_of(self.curr_player_index) {
+            return Err(GameError::InvalidMove("Wrong player index".to_owned()));
+        }
+        match (&self.game_phase, game_move) {
             // ACTION PHASE
-            (GamePhase::ActionPhase, GameMove::PlayCard { card_index }) => {
-                match self
-                    .current_player()
-                    .get_card_from_hand(card_index)?
-                    .card_type()
-                {
-                    CardType::Treasure => {
-                        return Err(GameError::InvalidMove(
-                            "Cannot play treasure in action phase".to_owned(),
-                        ))
+            (GamePhase::ActionPhase, GameMove::PlayCard { card_index, way }) => {
+                let card = self.current_player().get_card_from_hand(card_index)?;
+                let inherited = self.current_player_read_only().inherited_by(card);
+                if !card.is(CardType::Action) && inherited.is_none() {
+                    return Err(Game::unplayable(card, "action phase"));
+                }
+                // Played using a Way, the card's own effect doesn't matter
+                if way.is_none() && inherited.is_none() && !card.is_implemented() {
+                    return Err(Game::unplayable(card, "action phase"));
+                }
+                if self.current_player_read_only().actions == 0 {
+                    return Err(GameError::InvalidMove("No actions left".to_owned()));
+                }
+                if let Some(way) = way {
+                    if !self.supply.ways.contains(&way) {
+                        return Err(GameError::CardNotFoundInSupply(way.name().to_owned()));
                     }
-                    CardType::Action => {
-                        let card_to_play =
-                            self.current_player().remove_card_from_hand(card_index)?;
-                        if self.current_player_read_only().actions == 0 {
-                            return Err(GameError::InvalidMove("No actions left".to_owned()));
-                        }
-                        self.current_player().actions -= 1;
-                        let action = card_to_play.as_action()?;
-                        self.current_player().play_card(Box::new(action.clone()));
+                }
+                let player = self.current_player();
+                player.take_card_to_play(card_index)?;
+                player.actions -= 1;
+                player.max_actions = player.max_actions.map(|max| max.saturating_sub(1));
+                self.play_card(card)?;
+                match way {
+                    Some(way) => self.resolve_way(way, card)?,
+                    // An Estate plays as the card with its owner's Estate token
+                    None => self.resolve_play(inherited.unwrap_or(card))?,
+                }
+            }
+            (GamePhase::ActionPhase, GameMove::SpendVillager) => {
+                let player = self.current_player();
+                if player.villagers == 0 {
+                    return Err(GameError::InvalidMove("No Villagers left".to_owned()));
+                }
+                player.villagers -= 1;
+                player.actions += 1;
+            }
+            (GamePhase::ActionPhase, GameMove::EndActions) => {
+                self.current_player().actions = 0;
+                self.action_to_treasure_phase()?;
+            }
+
+            // TREASURE PHASE
+            (
+                GamePhase::TreasurePhase,
+                GameMove::PlayCard {
+                    card_index,
+                    way: None,
+                },
+            ) => {
+                let card = self.current_player().get_card_from_hand(card_index)?;
+                let player = self.current_player_read_only();
+                if !player.plays_as_treasure(card) || !card.is_implemented() {
+                    return Err(Game::unplayable(card, "treasure phase"));
+                }
+                self.current_player().take_card_to_play(card_index)?;
+                self.play_treasure(card)?;
+            }
+            (GamePhase::TreasurePhase, GameMove::PlayAllTreasures) => self.play_all_treasures()?,
+            (GamePhase::TreasurePhase, GameMove::EndTreasures) => self.treasure_to_buy_phase()?,
+            (GamePhase::TreasurePhase | GamePhase::BuyPhase, GameMove::SpendCoffer) => {
+                let player = self.current_player();
+                if player.coffers == 0 {
+                    return Err(GameError::InvalidMove("No Coffers left".to_owned()));
+                }
+                player.coffers -= 1;
+                player.coins += 1;
+            }
+
+            // BUY PHASE
+            (GamePhase::BuyPhase, GameMove::EndBuys) => self.buy_to_night_phase()?,
+            (GamePhase::BuyPhase, GameMove::BuyCard { card }) if card.is(CardType::Event) => {
+                self.buy_event(card)?;
+            }
+            (GamePhase::BuyPhase, GameMove::BuyCard { card }) if card.is(CardType::Project) => {
+                self.buy_project(card)?;
+            }
+            (GamePhase::BuyPhase, GameMove::BuyCard { card }) => {
+                let cost = self.cost_of(card);
+                if self.current_player_read_only().buys == 0 {
+                    return Err(GameError::InvalidMove("No buys left".to_owned()));
+                }
+                if !self.supply.in_supply(card) {
+                    return Err(GameError::CardNotFoundInSupply(card.name().to_owned()));
+                }
+                if self.buying_forbidden(card) {
+                    return Err(GameError::InvalidMove(format!(
+                        "Cannot buy {} right now",
+                        card.name()
+                    )));
+                }
+                self.check_affordable(&cost)?;
+                self.supply.check_can_take(card)?;
+                // The card is paid for before it is gained, so that anything
+                // responding to the gain sees what the player has left
+                self.pay_for(&cost);
+                self.current_player().buys -= 1;
+                self.gain_card(self.curr_player_index, card, GainDestination::Discard)?;
+                self.history.current_turn.bought.push(card);
+                let debt = self.supply.take_debt(card);
+                let debtor = self.controller_of(self.curr_player_index);
+                self.players[debtor].debt += debt;
+                #[cfg(any(
+                    feature = "prosperity",
+                    feature = "adventures",
+                    feature = "empires",
+                    feature = "renaissance",
+                    feature = "hinterlands",
+                    feature = "guilds"
+                ))]
+                self.fire(Event::Buy {
+                    player_index: self.curr_player_index,
+                    #[cfg(any(
+                        feature = "prosperity",
+                        feature = "empires",
+                        feature = "hinterlands"
+                    ))]
+                    card,
+                })?;
+                if let Some(on_buy) = card.behaviour().and_then(|b| b.on_buy) {
+                    on_buy(self, card)?;
+                }
+                self.offer_overpay(card);
+                self.offer_buy_reactions(card);
+                if self.has_token_on(self.curr_player_index, Token::Trashing, card) {
+                    self.ask(
+                        self.curr_player_index,
+                        card,
+                        Prompt::CardsFromHand {
+                            min: 0,
+                            max: 1,
+                            filter: CardFilter::Any,
+                        },
+                        |game, player_index, answer| {
+                            for trashed in answer.cards() {
+                                game.trash_card(player_index, trashed);
+                            }
+                            Ok(())
+                        },
+                    );
+                }
+            }
+
+            // NIGHT PHASE
+            (
+                GamePhase::NightPhase,
+                GameMove::PlayCard {
+                    card_index,
+                    way: None,
+                },
+            ) => {
+                let card = self.current_player().get_card_from_hand(card_index)?;
+                if !card.is(CardType::Night) || !card.is_implemented() {
+                    return Err(Game::unplayable(card, "night phase"));
+                }
+                self.current_player().take_card_to_play(card_index)?;
+                self.play_card(card)?;
+                self.resolve_play(card)?;
+            }
+
+            (_, GameMove::EndTurn) => {
+                self.end_turn()?;
+            }
+
+            _ => {
+                return Err(GameError::InvalidMove(
+                    "Invalid move for given game phase".to_owned(),
+                ))
+            }
+        };
+        Ok(())
+    }
+
+    /// Buys an Event: it uses up a Buy and is paid for like a card, but
+    /// nothing is gained, only its effect happens.
+    fn buy_event(&mut self, event: Card) -> Result<(), GameError> {
+        let cost = self.cost_of(event);
+        if self.current_player_read_only().buys == 0 {
+            return Err(GameError::InvalidMove("No buys left".to_owned()));
+        }
+        if !self.supply.events.contains(&event) {
+            return Err(GameError::CardNotFoundInSupply(event.name().to_owned()));
+        }
+        if !self.can_buy_event(event) {
+            return Err(GameError::InvalidMove(format!(
+                "{} can only be bought once per turn",
+                event.name()
+            )));
+        }
+        self.check_affordable(&cost)?;
+        self.history.current_turn.events.push(event);
+        self.pay_for(&cost);
+        self.current_player().buys -= 1;
+        match event.behaviour().and_then(|b| b.on_buy) {
+            Some(on_buy) => on_buy(self, event),
+            None => Ok(()),
+        }
+    }
+
+    /// Buys a Project: like an Event, but each player can only buy each
+    /// Project once, and has just two cubes to mark the ones they have.
+    fn buy_project(&mut self, project: Card) -> Result<(), GameError> {
+        let cost = self.cost_of(project);
+        if self.current_player_read_only().buys == 0 {
+            return Err(GameError::InvalidMove("No buys left".to_owned()));
+        }
+        if !self.supply.projects.contains(&project) {
+            return Err(GameError::CardNotFoundInSupply(project.name().to_owned()));
+        }
+        if !self.can_buy_project(project) {
+            return Err(GameError::InvalidMove(format!(
+                "Cannot put a cube on {}",
+                project.name()
+            )));
+        }
+        self.check_affordable(&cost)?;
+        self.current_player().pay(&cost);
+        self.current_player().buys -= 1;
+        self.current_player().projects.push(project);
+        match project.behaviour().and_then(|b| b.on_buy) {
+            Some(on_buy) => on_buy(self, project),
+            None => Ok(()),
+        }
+    }
+
+    fn can_buy_project(&self, project: Card) -> bool {
+        let projects = &self.current_player_read_only().projects;
+        projects.len() < PROJECT_CUBES && !projects.contains(&project)
+    }
+
+    /// Gives an Artifact to a player, taking it from whoever held it before.
+    #[cfg(feature = "renaissance")]
+    fn take_artifact(&mut self, player_index: usize, name: &str) {
+        let artifact = card(name);
+        if !self.supply.artifacts.contains(&artifact) {
+            return;
+        }
+        for player in &mut self.players {
+            player.artifacts.retain(|&held| held != artifact);
+        }
+        self.players[player_index].artifacts.push(artifact);
+    }
+
+    fn check_affordable(&self, cost: &Cost) -> Result<(), GameError> {
+        let player = self.current_player_read_only();
+        if player.can_afford(cost) {
+            Ok(())
+        } else {
+            Err(GameError::NotEnoughMoney {
+                // Debt has to be paid off before buying anything
+                required: cost.plus_coins(player.debt),
+                available: player.available_money(),
+            })
+        }
+    }
+
+    /// Has the current player pay for what they bought. Any Debt it costs is
+    /// taken on by whoever possesses them.
+    fn pay_for(&mut self, cost: &Cost) {
+        self.current_player().pay(&Cost { debt: 0, ..*cost });
+        let debtor = self.controller_of(self.curr_player_index);
+        self.players[debtor].debt += cost.debt;
+    }
+
+    fn can_buy_event(&self, event: Card) -> bool {
+        // Inheritance is once per game, as there is only the one Estate token
+        if event.name() == "Inheritance" && self.current_player_read_only().inherited.is_some() {
+            return false;
+        }
+        if event.name() == "Seize the Day" && self.current_player_read_only().seized_the_day {
+            return false;
+        }
+        !event.info().once_per_turn || !self.history.current_turn.events.contains(&event)
+    }
+
+    fn unplayable(card: Card, phase: &str) -> GameError {
+        let reason = if !card.is_implemented() {
+            format!("{} is not implemented yet", card.name())
+        } else if card.is(CardType::Night) {
+            format!("Cannot play night card in {phase}")
+        } else if card.is(CardType::Action) {
+            format!("Cannot play action card in {phase}")
+        } else if card.is(CardType::Treasure) {
+            format!("Cannot play treasure in {phase}")
+        } else if card.is(CardType::Curse) {
+            "Cannot play curse".to_owned()
+        } else {
+            "Cannot play victory card".to_owned()
+        };
+        GameError::InvalidMove(reason)
+    }
+
+    /// Applies the effect of a card that has just been put into play: first its
+    /// vanilla bonuses from the card database, then any special behaviour.
+    fn resolve_play(&mut self, card: Card) -> Result<(), GameError> {
+        if self.resolve_enchanted() {
+            return Ok(());
+        }
+        self.add_vanilla_bonuses(card);
+
+        // Tokens the player has put on the card's pile add their own bonuses
+        for token in self.tokens_on(self.curr_player_index, card) {
+            #[cfg(feature = "adventures")]
+            let player = self.current_player();
+            match token {
+                #[cfg(feature = "adventures")]
+                Token::PlusCard => {
+                    player.draw(1);
+                }
+                #[cfg(feature = "adventures")]
+                Token::PlusAction => player.actions += 1,
+                #[cfg(feature = "adventures")]
+                Token::PlusBuy => player.buys += 1,
+                #[cfg(feature = "adventures")]
+                Token::PlusCoin => player.coins += 1,
+                Token::MinusCost | Token::Trashing => {}
+            }
+        }
+        if card.is(CardType::Reserve) {
+            let player = self.current_player();
+            if let Some(reserve) = player.take_from_play(card) {
+                player.mat_mut(Mat::Tavern).push(reserve);
+            }
+        }
+
+        if let Some(on_play) = card.behaviour().and_then(|behaviour| behaviour.on_play) {
+            if card.is(CardType::Attack) {
+                // Other players get to respond with Reactions before the attack resolves
+                self.offer_reactions(card);
+                self.then(move |game| on_play(game, card));
+            } else {
+                on_play(self, card)?;
+            }
+        }
+        // Anything waiting for the card to finish, e.g. Royal Carriage, comes
+        // after whatever its effect asked
+        #[cfg(any(feature = "adventures", feature = "renaissance", feature = "plunder"))]
+        {
+            let player_index = self.curr_player_index;
+            self.then(move |game| game.fire(Event::Played { player_index, card }));
+        }
+        Ok(())
+    }
+
+    /// Applies a Way's effect in place of the effect of the Action card played
+    /// using it: the Way's vanilla bonuses, then its behaviour, which is told
+    /// which card was played.
+    fn resolve_way(&mut self, way: Card, card: Card) -> Result<(), GameError> {
+        if self.resolve_enchanted() {
+            return Ok(());
+        }
+        self.add_vanilla_bonuses(way);
+        match way.behaviour().and_then(|b| b.on_play) {
+            Some(on_play) => on_play(self, card),
+            None => Ok(()),
+        }
+    }
+
+    /// Gives +1 Card and +1 Action in place of the effect of an Action card
+    /// Enchantress has enchanted, returning whether it had.
+    fn resolve_enchanted(&mut self) -> bool {
+        let player = self.current_player();
+        if !take(&mut player.enchanted) {
+            return false;
+        }
+        player.draw(1);
+        player.actions += 1;
+        true
+    }
+
+    fn add_vanilla_bonuses(&mut self, card: Card) {
+        let info = card.info();
+        let player = self.current_player();
+        player.actions += info.actions;
+        player.buys += info.buys;
+        player.coins += info.coins;
+        player.potions += info.potions;
+        player.draw(info.cards as usize);
+    }
+
+    /// Lets each other player, in turn order, respond to an Attack with the
+    /// Reactions in their hand.
+    fn offer_reactions(&mut self, attack: Card) {
+        for offset in 1..self.players.len() {
+            let player_index = (self.curr_player_index + offset) % self.players.len();
+            let mut reactions = self.players[player_index].hand.clone();
+            reactions.sort_by_key(|card| card.name());
+            reactions.dedup();
+            for reaction in reactions {
+                if let Some(on_attack) = reaction.behaviour().and_then(|b| b.on_attack) {
+                    on_attack(self, player_index, reaction, attack);
+                }
+            }
+        }
+    }
+
+    /// The other players, starting from the current player's left, who are
+    /// affected by an attack (i.e. not protected by a Moat or Shield).
+    fn attacked_player_indices(&self) -> Vec<usize> {
+        (1..self.players.len())
+            .map(|offset| (self.curr_player_index + offset) % self.players.len())
+            .filter(|&player_index| {
+                let player = &self.players[player_index];
+                !player.hand.iter().chain(player.durations.iter()).any(|&card| {
+                    card.behaviour()
+                        .and_then(|b| b.protects)
+                        .is_some_and(|protects| protects(player, card))
+                })
+            })
+            .collect()
+    }
+
+    fn gain_card(
+        &mut self,
+        player_index: usize,
+        card: Card,
+        destination: GainDestination,
+    ) -> Result<(), GameError> {
+        self.supply.take_card(card)?;
+        self.receive_gained(player_index, card, destination)
+    }
+
+    /// Gains a card unless its supply pile has run out, in which case nothing happens.
+    fn gain_if_available(
+        &mut self,
+        player_index: usize,
+        card: Card,
+        destination: GainDestination,
+    ) -> Result<(), GameError> {
+        if self.supply.count(card).unwrap_or(0) > 0 {
+            self.gain_card(player_index, card, destination)?;
+        }
+        Ok(())
+    }
+
+    /// Moves a card from the supply onto a player's Exile mat, returning
+    /// false if there were none left.
+    #[cfg(feature = "menagerie")]
+    fn exile_from_supply(&mut self, player_index: usize, card: Card) -> Result<bool, GameError> {
+        if self.supply.count(card).unwrap_or(0) == 0 {
+            return Ok(false);
+        }
+        self.supply.take_card(card)?;
+        self.players[player_index].mat_mut(Mat::Exile).push(card);
+        Ok(true)
+    }
+
+    #[cfg(any(
+        feature = "intrigue",
+        feature = "dark_ages",
+        feature = "renaissance",
+        feature = "allies",
+        feature = "plunder"
+    ))]
+    fn gain_from_trash(
+        &mut self,
+        player_index: usize,
+        card: Card,
+        destination: GainDestination,
+    ) -> Result<(), GameError> {
+        let index = self
+            .trash
+            .cards
+            .iter()
+            .position(|&trashed| trashed == card)
+            .ok_or(GameError::CardNotFound(format!("{} in trash", card.name())))?;
+        self.trash.take(index);
+        self.receive_gained(player_index, card, destination)
+    }
+
+    fn receive_gained(
+        &mut self,
+        player_index: usize,
+        card: Card,
+        destination: GainDestination,
+    ) -> Result<(), GameError> {
+        // Whoever possesses the player gains their cards instead
+        let player_index = self.controller_of(player_index);
+        self.history.current_turn.gained.push((player_index, card));
+        let player = &mut self.players[player_index];
+        match destination {
+            GainDestination::Discard if card.info().gain_to_hand => player.add_to_hand(card),
+            GainDestination::Discard => player.add_to_discard(card),
+            GainDestination::Hand => player.add_to_hand(card),
+            #[cfg(any(
+                feature = "intrigue",
+                feature = "seaside",
+                feature = "dark_ages",
+                feature = "adventures",
+                feature = "empires",
+                feature = "nocturne",
+                feature = "menagerie",
+                feature = "hinterlands",
+                feature = "cornucopia",
+                feature = "guilds",
+                feature = "allies",
+                feature = "plunder"
+            ))]
+            GainDestination::Deck => player.put_on_deck(card),
+            #[cfg(any(feature = "seaside", feature = "menagerie", feature = "plunder"))]
+            GainDestination::SetAside => {}
+        }
+        self.offer_leaving_exile(player_index, card);
+        if let Some(on_gain) = card.behaviour().and_then(|b| b.on_gain) {
+            on_gain(self, player_index, card);
+        }
+        for trait_card in self.supply.traits_of(card) {
+            if let Some(on_gain) = trait_card.behaviour().and_then(|b| b.on_gain) {
+                on_gain(self, player_index, card);
+            }
+        }
+        #[cfg(any(
+            feature = "seaside",
+            feature = "prosperity",
+            feature = "adventures",
+            feature = "empires",
+            feature = "nocturne",
+            feature = "renaissance",
+            feature = "menagerie",
+            feature = "hinterlands",
+            feature = "allies",
+            feature = "plunder"
+        ))]
+        self.fire(Event::Gain { player_index, card })?;
+        self.offer_gain_reactions(player_index, card);
+        Ok(())
+    }
+
+    /// Lets a player who gains a card discard its copies from their Exile mat.
+    fn offer_leaving_exile(&mut self, player_index: usize, gained: Card) {
+        let exiled = self.players[player_index]
+            .mats
+            .get(&Mat::Exile)
+            .map_or(0, |exile| {
+                exile.iter().filter(|&&card| card == gained).count()
+            });
+        if exiled == 0 {
+            return;
+        }
+        self.ask(
+            player_index,
+            gained,
+            Prompt::Options {
+                options: vec![
+                    format!("Discard {exiled} {} from Exile", gained.name()),
+                    "Leave them in Exile".to_owned(),
+                ],
+            },
+            move |game, player_index, answer| {
+                if answer.option() == 0 {
+                    let player = &mut game.players[player_index];
+                    player.mat_mut(Mat::Exile).retain(|&card| card != gained);
+                    for _ in 0..exiled {
+                        player.add_to_discard(gained);
+                    }
+                }
+                Ok(())
+            },
+        );
+    }
+
+    /// Lets players respond to a gain with cards in their hand, e.g. Pirate.
+    fn offer_gain_reactions(&mut self, gainer_index: usize, gained: Card) {
+        for player_index in 0..self.players.len() {
+            let mut reactions = self.players[player_index].hand.clone();
+            reactions.sort_by_key(|card| card.name());
+            reactions.dedup();
+            for reaction in reactions {
+                if let Some(on_gain) = reaction.behaviour().and_then(|b| b.on_gain_in_hand) {
+                    on_gain(self, player_index, reaction, gainer_index, gained);
+                }
+            }
+        }
+    }
+
+    /// Lets the current player pay more than a card they just bought costs,
+    /// for the card's bonus, e.g. Masterpiece.
+    fn offer_overpay(&mut self, bought: Card) {
+        let Some(on_overpay) = bought.behaviour().and_then(|b| b.on_overpay) else {
+            return;
+        };
+        let coins = self.current_player_read_only().coins;
+        if coins == 0 {
+            return;
+        }
+        let options = std::iter::once("Don't overpay".to_owned())
+            .chain((1..=coins).map(|amount| format!("Overpay ${amount}")))
+            .collect();
+        self.ask(
+            self.curr_player_index,
+            bought,
+            Prompt::Options { options },
+            move |game, player_index, answer| {
+                let player = &mut game.players[player_index];
+                let amount = (answer.option() as u32).min(player.coins);
+                if amount == 0 {
+                    return Ok(());
+                }
+                player.coins -= amount;
+                on_overpay(game, bought, amount)
+            },
+        );
+    }
+
+    /// Lets the current player respond to buying a card with cards in their hand, e.g. Hovel.
+    fn offer_buy_reactions(&mut self, bought: Card) {
+        let player_index = self.curr_player_index;
+        let mut reactions = self.players[player_index].hand.clone();
+        reactions.sort_by_key(|card| card.name());
+        reactions.dedup();
+        for reaction in reactions {
+            if let Some(on_buy) = reaction.behaviour().and_then(|b| b.on_buy_in_hand) {
+                on_buy(self, player_index, reaction, bought);
+            }
+        }
+    }
+
+    /// What a card costs right now, after any active cost modifiers.
+    fn cost_of(&self, card: Card) -> Cost {
+        // Some cards work out their own cost, e.g. Fisherman
+        let printed = match card.behaviour().and_then(|b| b.cost) {
+            Some(cost) => cost(self, card.cost()),
+            None => card.cost(),
+        };
+        // So do the Traits on its pile, e.g. Cheap
+        let printed = self
+            .supply
+            .traits_of(card)
+            .into_iter()
+            .filter_map(|trait_card| trait_card.behaviour().and_then(|b| b.cost))
+            .fold(printed, |cost, trait_cost| trait_cost(self, cost));
+        let printed = if self.has_token_on(self.curr_player_index, Token::MinusCost, card) {
+            printed.minus_coins(2)
+        } else {
+            printed
+        };
+        // Cards in play and Projects can make everything cheaper on their
+        // owner's turns, e.g. Bridge Troll or Canal
+        let player = self.current_player_read_only();
+        let printed = player
+            .played
+            .iter()
+            .chain(player.projects.iter())
+            .filter_map(|card| card.behaviour().and_then(|b| b.reduces_costs))
+            .fold(printed, |cost, reduce| reduce(self, cost));
+        // Each Favor token on its pile, from Family of Inventors, takes off $1
+        let favor_tokens = self
+            .supply
+            .pile(card)
+            .and_then(|pile| self.supply.favor_tokens.get(&pile.name))
+            .copied()
+            .unwrap_or(0);
+        let printed = printed.minus_coins(favor_tokens);
+        self.cost_modifiers
+            .iter()
+            .filter(|modifier| modifier.filter.matches(card))
+            .fold(printed, |cost, modifier| {
+                cost.minus_coins(modifier.coin_reduction)
+            })
+    }
+
+    /// The player's tokens on the supply pile a card comes from.
+    fn tokens_on(&self, player_index: usize, card: Card) -> Vec<Token> {
+        let Some(pile) = self.supply.pile(card) else {
+            return Vec::new();
+        };
+        self.players[player_index]
+            .tokens
+            .iter()
+            .filter(|(_, on)| **on == pile.name)
+            .map(|(&token, _)| token)
+            .collect()
+    }
+
+    fn has_token_on(&self, player_index: usize, token: Token, card: Card) -> bool {
+        self.tokens_on(player_index, card).contains(&token)
+    }
+
+    /// Whether a card can't be bought this turn whatever it costs, like
+    /// Grand Market while you have a Copper in play.
+    fn buying_forbidden(&self, to_buy: Card) -> bool {
+        let unbuyable = to_buy
+            .behaviour()
+            .and_then(|b| b.buyable)
+            .is_some_and(|buyable| !buyable(self));
+        unbuyable
+            || self
+                .buy_restrictions
+                .iter()
+                .any(|restriction| restriction.matches(to_buy))
+    }
+
+    /// Cards in the trash whose current cost is between `min_coins` and `max_coins`
+    /// (and involves no Potions or Debt), along with their index in the pile.
+    #[cfg(any(feature = "dark_ages", feature = "allies"))]
+    fn trash_cards_costing(&self, min_coins: u32, max_coins: u32) -> Vec<(usize, Card)> {
+        self.trash
+            .cards
+            .iter()
+            .enumerate()
+            .filter(|&(_, &card)| {
+                let cost = self.cost_of(card);
+                cost.fits_within(&Cost::coins(max_coins)) && cost.coins >= min_coins
+            })
+            .map(|(index, &card)| (index, card))
+            .collect()
+    }
+
+    /// Moves a card to the shared trash. Every trashing effect goes through here.
+    /// Anything responding to the trashing happens once the trashing effect is done.
+    fn trash_card(&mut self, player_index: usize, card: Card) {
+        self.history.current_turn.trashed.push((player_index, card));
+        self.trash.add(card);
+        if self.controller_of(player_index) != player_index {
+            self.possessed_trashed.push(card);
+        }
+        if let Some(on_trash) = card.behaviour().and_then(|b| b.on_trash) {
+            on_trash(self, player_index, card);
+        }
+        #[cfg(any(feature = "dark_ages", feature = "empires", feature = "renaissance"))]
+        self.then(move |game| game.fire(Event::Trash { player_index }));
+    }
 
-                        self.handle_action(action)?;
+    /// Puts a card into play for the current player, remembering that it was played this turn.
+    fn play_card(&mut self, card: Card) -> Result<(), GameError> {
+        self.history.current_turn.played.push(card);
+        self.current_player().play_card(card);
+        self.fire(Event::Play {
+            player_index: self.curr_player_index,
+            card,
+        })
+    }
 
-                        if self.current_player_read_only().actions == 0
-                            || !self.current_player_read_only().has_action_cards_in_hand()
-                        {
-                            self.action_to_treasure_phase()?
-                        }
-                    }
-                    CardType::Victory => {
-                        return Err(GameError::InvalidMove(
-                            "Cannot play victory card".to_owned(),
-                        ))
-                    }
-                    CardType::Curse => {
-                        return Err(GameError::InvalidMove("Cannot play curse".to_owned()))
-                    }
+    fn play_treasure(&mut self, card: Card) -> Result<(), GameError> {
+        self.play_card(card)?;
+        // With a Highwayman attacking, the first Treasure played this turn
+        // does nothing
+        let treasures_played = self
+            .history
+            .current_turn
+            .played
+            .iter()
+            .filter(|played| played.is(CardType::Treasure))
+            .count();
+        if self.current_player_read_only().highwayman_attacks > 0 && treasures_played == 1 {
+            return Ok(());
+        }
+        self.resolve_play(card)
+    }
+
+    // DECISIONS
+    /// Asks a player to make a choice. Anything asked or deferred while a card's
+    /// effect is resolving happens, in order, before whatever was already pending.
+    /// Players do as much as they can: a prompt that can't be met in full is
+    /// scaled down, and one with nothing to choose from is skipped entirely,
+    /// along with its continuation.
+    fn ask(
+        &mut self,
+        player_index: usize,
+        source: Card,
+        mut prompt: Prompt,
+        then: impl FnOnce(&mut Game, usize, Answer) -> Result<(), GameError> + Send + 'static,
+    ) {
+        match &mut prompt {
+            Prompt::CardsFromHand { min, filter, .. } => {
+                let candidates = self.players[player_index]
+                    .hand
+                    .iter()
+                    .filter(|&&card| filter.matches(card))
+                    .count();
+                if candidates == 0 {
+                    return;
                 }
+                *min = (*min).min(candidates);
             }
-            (GamePhase::ActionPhase, GameMove::EndActions) => {
-                self.current_player().actions = 0;
-                self.action_to_treasure_phase()?;
+            Prompt::CardFromSupply {
+                max_cost,
+                exact,
+                filter,
+            } => {
+                if self.gainable_cards(max_cost, *exact, filter).is_empty() {
+                    return;
+                }
+            }
+            Prompt::Options { options } => {
+                if options.is_empty() {
+                    return;
+                }
             }
+        }
+        self.staged.push(Pending::Decision(Decision {
+            player_index,
+            source,
+            prompt,
+            then: Box::new(then),
+        }));
+    }
 
-            // TREASURE PHASE
-            (GamePhase::TreasurePhase, GameMove::PlayCard { card_index }) => {
-                match self
-                    .current_player()
-                    .get_card_from_hand(card_index)?
-                    .card_type()
-                {
-                    CardType::Treasure => {
-                        let card_to_play =
-                            self.current_player().remove_card_from_hand(card_index)?;
-                        self.current_player().coins += card_to_play.as_treasure()?.value();
-                        self.current_player().play_card(card_to_play);
-                        if !self.current_player_read_only().has_treasure_cards_in_hand() {
-                            self.treasure_to_buy_phase()?;
-                        }
-                    }
-                    CardType::Action => {
-                        return Err(GameError::InvalidMove(
-                            "Cannot play action card in treasure phase".to_owned(),
-                        ))
-                    }
-                    CardType::Victory => {
-                        return Err(GameError::InvalidMove(
-                            "Cannot play victory card".to_owned(),
-                        ))
+    /// Defers part of an effect until everything asked before it has been answered.
+    fn then(&mut self, effect: impl FnOnce(&mut Game) -> Result<(), GameError> + Send + 'static) {
+        self.staged.push(Pending::Effect(Box::new(effect)));
+    }
+
+    /// The decision play is waiting on, if any.
+    fn pending_decision(&self) -> Option<&Decision> {
+        match self.pending.front() {
+            Some(Pending::Decision(decision)) => Some(decision),
+            _ => None,
+        }
+    }
+
+    /// Runs deferred effects until play has to wait for a decision, dropping
+    /// any decision that can no longer be answered.
+    fn run_pending(&mut self) -> Result<(), GameError> {
+        loop {
+            for item in self.staged.drain(..).rev() {
+                self.pending.push_front(item);
+            }
+            match self.pending.front() {
+                Some(Pending::Effect(_)) => {
+                    let Some(Pending::Effect(effect)) = self.pending.pop_front() else {
+                        unreachable!()
+                    };
+                    effect(self)?;
+                }
+                // Decisions asked together are checked as they are staged, so
+                // an earlier one may have left a later one nothing to choose
+                Some(Pending::Decision(decision)) if self.decision_moves(decision).is_empty() => {
+                    self.pending.pop_front();
+                }
+                _ => return Ok(()),
+            }
+        }
+    }
+
+    fn accept_decision(
+        &mut self,
+        player_index: usize,
+        game_move: GameMove,
+    ) -> Result<(), GameError> {
+        let decision = self.pending_decision().unwrap();
+        let decider = self.controller_of(decision.player_index);
+        if player_index != decider {
+            return Err(GameError::InvalidMove(format!(
+                "Waiting on a decision from player {decider}"
+            )));
+        }
+        // The answer is about the cards of the player the decision is for
+        let player_index = decision.player_index;
+        self.validate_answer(&decision.prompt, player_index, &game_move)?;
+
+        let Some(Pending::Decision(decision)) = self.pending.pop_front() else {
+            unreachable!("a decision is pending")
+        };
+        let answer = match game_move {
+            GameMove::ChooseCards { mut card_indices } => {
+                // Remove from the back so earlier indices stay valid
+                card_indices.sort_unstable();
+                let hand = &mut self.players[player_index].hand;
+                let mut cards: Vec<_> = card_indices
+                    .into_iter()
+                    .rev()
+                    .map(|index| hand.remove(index))
+                    .collect();
+                cards.reverse();
+                Answer::Cards(cards)
+            }
+            GameMove::GainCard { card } => Answer::SupplyCard(card),
+            GameMove::ChooseOption { option_index } => Answer::Option(option_index),
+            _ => unreachable!("answer validated against prompt"),
+        };
+        (decision.then)(self, player_index, answer)
+    }
+
+    fn validate_answer(
+        &self,
+        prompt: &Prompt,
+        player_index: usize,
+        game_move: &GameMove,
+    ) -> Result<(), GameError> {
+        match (prompt, game_move) {
+            (
+                Prompt::CardsFromHand { min, max, filter },
+                GameMove::ChooseCards { card_indices },
+            ) => {
+                let hand = &self.players[player_index].hand;
+                if card_indices.len() < *min || card_indices.len() > *max {
+                    return Err(GameError::InvalidChoice(format!(
+                        "Choose between {min} and {max} cards"
+                    )));
+                }
+                for (position, &index) in card_indices.iter().enumerate() {
+                    if card_indices[..position].contains(&index) {
+                        return Err(GameError::InvalidChoice(format!(
+                            "Card {index} chosen twice"
+                        )));
                     }
-                    CardType::Curse => {
-                        return Err(GameError::InvalidMove("Cannot play curse".to_owned()))
+                    let card = *hand
+                        .get(index)
+                        .ok_or(GameError::CardNotFound("Index out of bounds".to_owned()))?;
+                    if !filter.matches(card) {
+                        return Err(GameError::InvalidChoice(format!(
+                            "{} does not match {:?}",
+                            card.name(),
+                            filter
+                        )));
                     }
                 }
+                Ok(())
             }
-            (GamePhase::TreasurePhase, GameMove::EndTreasures) => self.treasure_to_buy_phase()?,
-
-            // BUY PHASE
-            (GamePhase::BuyPhase, GameMove::BuyCard { card }) => {
-                let cost = card.cost();
-                if self.current_player_read_only().coins < cost {
-                    return Err(GameError::NotEnoughMoney {
-                        required: cost,
-                        available: self.current_player_read_only().coins,
-                    });
-                }
-                self.current_player().coins -= cost;
-                self.supply.take_card(&card)?;
-                self.current_player().buys -= 1;
-                self.current_player().add_to_discard(card);
-                if self.current_player_read_only().buys == 0 {
-                    self.end_turn()?
+            (
+                Prompt::CardFromSupply {
+                    max_cost,
+                    exact,
+                    filter,
+                },
+                GameMove::GainCard { card },
+            ) => {
+                if !filter.matches(*card) {
+                    return Err(GameError::InvalidChoice(format!(
+                        "{} does not match {:?}",
+                        card.name(),
+                        filter
+                    )));
+                }
+                if !Game::cost_allowed(self.cost_of(*card), max_cost, *exact) {
+                    let limit = if *exact { "exactly" } else { "up to" };
+                    return Err(GameError::InvalidChoice(format!(
+                        "{} does not cost {} {}",
+                        card.name(),
+                        limit,
+                        max_cost
+                    )));
+                }
+                if !self.supply.in_supply(*card) {
+                    return Err(GameError::CardNotFoundInSupply(card.name().to_owned()));
+                }
+                match self.supply.count(*card) {
+                    None => Err(GameError::CardNotFoundInSupply(card.name().to_owned())),
+                    Some(0) => Err(GameError::CardSupplyDepleted(card.name().to_owned())),
+                    Some(_) => Ok(()),
                 }
             }
-
-            (_, GameMove::EndTurn) => {
-                self.end_turn()?;
+            (Prompt::Options { options }, GameMove::ChooseOption { option_index }) => {
+                if *option_index < options.len() {
+                    Ok(())
+                } else {
+                    Err(GameError::InvalidChoice(format!(
+                        "Option {option_index} does not exist"
+                    )))
+                }
             }
+            _ => Err(GameError::InvalidMove(
+                "Move does not answer the pending decision".to_owned(),
+            )),
+        }
+    }
 
-            _ => {
-                return Err(GameError::InvalidMove(
-                    "Invalid move for given game phase".to_owned(),
-                ))
+    fn play_all_treasures(&mut self) -> Result<(), GameError> {
+        for card in self.current_player().take_basic_treasures_from_hand() {
+            let player = self.current_player();
+            if !player.can_play_from_hand(card) {
+                player.add_to_hand(card);
+                continue;
             }
-        };
+            player.use_hand_play();
+            self.play_treasure(card)?;
+        }
         Ok(())
     }
 
-    fn handle_action(&mut self, action: &Action) -> Result<(), GameError> {
-        match action {
-            Action::Cellar => todo!(),
-            Action::Chapel => todo!(),
-            Action::Moat => {
-                self.current_player().draw(2);
-            }
-            Action::Harbinger => todo!(),
-            Action::Merchant => todo!(),
-            Action::Vassal => todo!(),
-            Action::Village => {
-                self.current_player().actions += 2;
-                self.current_player().draw(1);
+    // LEGAL MOVES
+    /// Every move the acting player could make right now.
+    fn legal_moves(&self) -> Vec<GameMove> {
+        if self.winners.is_some() {
+            return Vec::new();
+        }
+        if let Some(decision) = self.pending_decision() {
+            return self.decision_moves(decision);
+        }
+
+        let player = self.current_player_read_only();
+        let mut moves = Vec::new();
+        match self.game_phase {
+            GamePhase::ActionPhase => {
+                if player.actions > 0 {
+                    moves.extend(Game::playable_indices(player, CardType::Action).map(
+                        |card_index| GameMove::PlayCard {
+                            card_index,
+                            way: None,
+                        },
+                    ));
+                    // Any Action card can be played using a Way
+                    for (card_index, _) in player.hand.iter().enumerate().filter(|&(_, &card)| {
+                        card.is(CardType::Action) && player.can_play_from_hand(card)
+                    }) {
+                        moves.extend(self.supply.ways.iter().map(|&way| GameMove::PlayCard {
+                            card_index,
+                            way: Some(way),
+                        }));
+                    }
+                } else if player.villagers > 0
+                    && Game::playable_indices(player, CardType::Action)
+                        .next()
+                        .is_some()
+                {
+                    moves.push(GameMove::SpendVillager);
+                }
+                moves.push(GameMove::EndActions);
             }
-            Action::Workshop => todo!(),
-            Action::Bureaucrat => todo!(),
-            Action::Gardens => {
-                return Err(GameError::InvalidMove(
-                    "Cannot play Gardens as action".to_owned(),
-                ))
+            GamePhase::TreasurePhase => {
+                moves.extend(Game::playable_indices(player, CardType::Treasure).map(
+                    |card_index| GameMove::PlayCard {
+                        card_index,
+                        way: None,
+                    },
+                ));
+                if player
+                    .hand
+                    .iter()
+                    .any(|&card| Player::is_basic_treasure(card) && player.can_play_from_hand(card))
+                {
+                    moves.push(GameMove::PlayAllTreasures);
+                }
+                moves.push(GameMove::EndTreasures);
             }
-            Action::Militia => todo!(),
-            Action::Moneylender => todo!(),
-            Action::Poacher => todo!(),
-            Action::Remodel => todo!(),
-            Action::Smithy => {
-                self.current_player().draw(3);
-            }
-            Action::ThroneRoom => todo!(),
-            Action::Bandit => todo!(),
-            Action::CouncilRoom => {
-                self.current_player().buys += 1;
-                self.current_player().draw(4);
-
-                // Every other player draws one card
-                let current_player_index = self.curr_player_index;
-                for player in self.players.iter_mut() {
-                    if player.index != current_player_index {
-                        player.draw(1);
+            GamePhase::BuyPhase => {
+                if player.buys > 0 {
+                    moves.extend(
+                        self.supply
+                            .available_cards()
+                            .into_iter()
+                            .filter(|&card| player.can_afford(&self.cost_of(card)))
+                            .filter(|&card| !self.buying_forbidden(card))
+                            .map(|card| GameMove::BuyCard { card }),
+                    );
+                    moves.extend(
+                        self.supply
+                            .events
+                            .iter()
+                            .copied()
+                            .filter(|&event| player.can_afford(&self.cost_of(event)))
+                            .filter(|&event| self.can_buy_event(event))
+                            .map(|card| GameMove::BuyCard { card }),
+                    );
+                    moves.extend(
+                        self.supply
+                            .projects
+                            .iter()
+                            .copied()
+                            .filter(|&project| player.can_afford(&self.cost_of(project)))
+                            .filter(|&project| self.can_buy_project(project))
+                            .map(|card| GameMove::BuyCard { card }),
+                    );
+                    if player.coffers > 0 {
+                        moves.push(GameMove::SpendCoffer);
                     }
                 }
+                moves.push(GameMove::EndBuys);
             }
-            Action::Festival => {
-                self.current_player().buys += 1;
-                self.current_player().actions += 2;
-            }
-            Action::Laboratory => {
-                self.current_player().actions += 1;
-                self.current_player().draw(2);
-            }
-            Action::Library => todo!(),
-            Action::Market => {
-                self.current_player().buys += 1;
-                self.current_player().actions += 1;
-                self.current_player().draw(1);
-            }
-            Action::Mine => todo!(),
-            Action::Sentry => todo!(),
-            Action::Witch => {
-                self.current_player().draw(2);
-
-                // Starting from the left of the current player,
-                // each player will gain a curse if there's one left.
-                let mut cursed_player_index = (self.curr_player_index + 1) % self.players.len();
-                for _ in 0..self.players.len() - 1 {
-                    if self.supply.curses[Curse::Curse.name()] > 0
-                        && !self.players[cursed_player_index].has_moat_in_hand()
-                    {
-                        self.players[cursed_player_index].add_to_discard(Box::new(Curse::Curse));
-                        if let Some(curse_count) = self.supply.curses.get_mut(Curse::Curse.name()) {
-                            *curse_count -= 1;
+            GamePhase::NightPhase => {
+                moves.extend(
+                    Game::playable_indices(player, CardType::Night).map(|card_index| {
+                        GameMove::PlayCard {
+                            card_index,
+                            way: None,
                         }
+                    }),
+                );
+            }
+        }
+        moves.push(GameMove::EndTurn);
+        moves
+    }
+
+    fn playable_indices(player: &Player, card_type: CardType) -> impl Iterator<Item = usize> + '_ {
+        player
+            .hand
+            .iter()
+            .enumerate()
+            .filter(move |&(_, &card)| {
+                let playable = match card_type {
+                    CardType::Treasure => player.plays_as_treasure(card),
+                    _ => card.is(card_type),
+                };
+                let playable = (playable && card.is_implemented())
+                    || (card_type == CardType::Action && player.inherited_by(card).is_some());
+                playable && player.can_play_from_hand(card)
+            })
+            .map(|(card_index, _)| card_index)
+    }
+
+    fn decision_moves(&self, decision: &Decision) -> Vec<GameMove> {
+        match &decision.prompt {
+            Prompt::CardsFromHand { min, max, filter } => {
+                let candidates: Vec<usize> = self.players[decision.player_index]
+                    .hand
+                    .iter()
+                    .enumerate()
+                    .filter(|&(_, &card)| filter.matches(card))
+                    .map(|(card_index, _)| card_index)
+                    .collect();
+                let mut moves = Vec::new();
+                for size in *min..=(*max).min(candidates.len()) {
+                    for card_indices in combinations(&candidates, size) {
+                        moves.push(GameMove::ChooseCards { card_indices });
                     }
-                    cursed_player_index = (cursed_player_index + 1) % self.players.len();
                 }
+                moves
             }
-            Action::Artisan => todo!(),
+            Prompt::CardFromSupply {
+                max_cost,
+                exact,
+                filter,
+            } => self
+                .gainable_cards(max_cost, *exact, filter)
+                .into_iter()
+                .map(|card| GameMove::GainCard { card })
+                .collect(),
+            Prompt::Options { options } => (0..options.len())
+                .map(|option_index| GameMove::ChooseOption { option_index })
+                .collect(),
         }
-        Ok(())
+    }
+
+    fn cost_allowed(cost: Cost, max_cost: &Cost, exact: bool) -> bool {
+        if exact {
+            cost == *max_cost
+        } else {
+            cost.fits_within(max_cost)
+        }
+    }
+
+    /// Supply cards that could be gained from a `CardFromSupply` prompt.
+    fn gainable_cards(&self, max_cost: &Cost, exact: bool, filter: &CardFilter) -> Vec<Card> {
+        self.supply
+            .available_cards()
+            .into_iter()
+            .filter(|&card| {
+                filter.matches(card) && Game::cost_allowed(self.cost_of(card), max_cost, exact)
+            })
+            .collect()
+    }
+
+    /// True if the current player could do something other than leave the current phase.
+    fn has_meaningful_choice(&self) -> bool {
+        self.legal_moves().iter().any(|game_move| {
+            !matches!(
+                game_move,
+                GameMove::EndActions
+                    | GameMove::EndTreasures
+                    | GameMove::EndBuys
+                    | GameMove::EndTurn
+            )
+        })
     }
 
     // PHASE TRANSITIONS
+    /// Takes every step the acting player's auto-play policy allows, stopping as
+    /// soon as a decision is pending, the player has a meaningful choice, or the game ends.
+    fn auto_advance(&mut self) -> Result<(), GameError> {
+        loop {
+            self.use_minus_coin_tokens();
+            self.current_player().settle_max_actions();
+            if !self.pending.is_empty() || self.winners.is_some() {
+                return Ok(());
+            }
+            let policy = self.players[self.acting_player_index()].auto_play.clone();
+            match self.game_phase {
+                GamePhase::ActionPhase if policy.end_actions && !self.has_meaningful_choice() => {
+                    self.action_to_treasure_phase()?
+                }
+                GamePhase::TreasurePhase
+                    if policy.play_treasures
+                        && self.current_player_read_only().hand.iter().any(|&card| {
+                            Player::is_basic_treasure(card)
+                                && self.current_player_read_only().can_play_from_hand(card)
+                        }) =>
+                {
+                    self.play_all_treasures()?
+                }
+                GamePhase::TreasurePhase
+                    if policy.end_treasures && !self.has_meaningful_choice() =>
+                {
+                    self.treasure_to_buy_phase()?
+                }
+                GamePhase::BuyPhase if policy.end_turn && !self.has_meaningful_choice() => {
+                    self.buy_to_night_phase()?
+                }
+                GamePhase::NightPhase if policy.end_turn && !self.has_meaningful_choice() => {
+                    self.end_turn()?
+                }
+                _ => return Ok(()),
+            }
+        }
+    }
+
+    /// Takes $1 from any player who has their -$1 token and has got $ since
+    /// taking it. Checking between moves is as soon as the engine can tell.
+    fn use_minus_coin_tokens(&mut self) {
+        for player in &mut self.players {
+            player.settle_minus_coin_token();
+        }
+    }
+
     fn action_to_treasure_phase(&mut self) -> Result<(), GameError> {
         if let GamePhase::ActionPhase = self.game_phase {
             self.game_phase = GamePhase::TreasurePhase;
+            #[cfg(any(
+                feature = "empires",
+                feature = "nocturne",
+                feature = "renaissance",
+                feature = "allies"
+            ))]
+            self.fire(Event::BuyPhaseStart {
+                player_index: self.curr_player_index,
+            })?;
             Ok(())
         } else {
             Err(GameError::InvalidMove(
@@ -876,30 +3667,468 @@ impl Game {
             ))
         }
     }
+    fn buy_to_night_phase(&mut self) -> Result<(), GameError> {
+        if let GamePhase::BuyPhase = self.game_phase {
+            self.game_phase = GamePhase::NightPhase;
+            #[cfg(any(
+                feature = "seaside",
+                feature = "alchemy",
+                feature = "dark_ages",
+                feature = "adventures",
+                feature = "empires",
+                feature = "renaissance",
+                feature = "menagerie",
+                feature = "hinterlands",
+                feature = "allies",
+                feature = "plunder"
+            ))]
+            self.fire(Event::BuyPhaseEnd {
+                player_index: self.curr_player_index,
+            })?;
+            Ok(())
+        } else {
+            Err(GameError::InvalidMove(
+                "Not in buy phase, cannot enter night phase".to_owned(),
+            ))
+        }
+    }
+    /// Ends the Buy phase, unless it is already over, then cleans up once
+    /// anything triggered by that is resolved.
     fn end_turn(&mut self) -> Result<(), GameError> {
-        self.current_player().end_turn();
-        self.curr_player_index = (self.curr_player_index + 1) % self.players.len();
-        self.game_phase = self.current_player_read_only().get_starting_game_phase();
-        if self.supply.check_game_over() {
-            let winner = self
-                .players
+        #[cfg(any(
+            feature = "seaside",
+            feature = "alchemy",
+            feature = "dark_ages",
+            feature = "adventures",
+            feature = "empires",
+            feature = "renaissance",
+            feature = "menagerie",
+            feature = "hinterlands",
+            feature = "allies",
+            feature = "plunder"
+        ))]
+        if !matches!(self.game_phase, GamePhase::NightPhase) {
+            self.fire(Event::BuyPhaseEnd {
+                player_index: self.curr_player_index,
+            })?;
+        }
+        self.then(Game::clean_up);
+        self.run_pending()
+    }
+
+    fn clean_up(&mut self) -> Result<(), GameError> {
+        let player_index = self.curr_player_index;
+        // Coins left over at the end of the turn may as well pay off Debt
+        self.current_player().pay_off_debt();
+        for index in 0..self.durations.len() {
+            let duration = &self.durations[index];
+            if duration.player_index == player_index {
+                let source = duration.source;
+                self.current_player().keep_in_play(source);
+            }
+        }
+        for index in 0..self.waiting.len() {
+            let (owner, source) = self.waiting[index];
+            if owner == player_index {
+                self.current_player().keep_in_play(source);
+            }
+        }
+
+        let extra_turn = self
+            .extra_turns
+            .iter()
+            .position(|extra_turn| extra_turn.player_index == player_index)
+            .and_then(|index| self.extra_turns.remove(index));
+        // The Flag's holder draws an extra card for their next hand
+        let flag = usize::from(self.current_player_read_only().has_artifact("Flag"));
+        let hand_size = extra_turn.map_or(5, |extra_turn| extra_turn.hand_size)
+            + take(&mut self.current_player().next_hand_bonus)
+            + flag;
+        self.current_player().end_turn(hand_size);
+        // A possessed player gets back the cards trashed on their turn
+        for card in take(&mut self.possessed_trashed) {
+            if let Some(index) = self
+                .trash
+                .cards
                 .iter()
-                .max_by_key(|p| p.get_victory_points())
-                .unwrap();
-            println!("Game over! Player {} wins!", winner.index);
-            self.winner = Some(winner.index);
+                .rposition(|&trashed| trashed == card)
+            {
+                self.trash.take(index);
+                self.players[player_index].add_to_discard(card);
+            }
+        }
+        self.cost_modifiers.clear();
+        self.buy_restrictions.clear();
+        self.trash.face_down.clear();
+        self.triggers
+            .retain(|trigger| trigger.expires != Expiry::EndOfTurn);
+        for effect in take(&mut self.after_turn) {
+            self.staged.push(Pending::Effect(effect));
+        }
+        self.then(move |game| game.pass_turn(player_index, extra_turn));
+        Ok(())
+    }
+
+    /// Moves play on to whoever's turn is next, unless the game is over.
+    fn pass_turn(
+        &mut self,
+        player_index: usize,
+        extra_turn: Option<ExtraTurn>,
+    ) -> Result<(), GameError> {
+        let possessor = self.possessed_by.take();
+        // Possessed turns follow all of the possessor's own turns
+        let possession = self
+            .possessions
+            .iter()
+            .position(|&index| index == possessor.unwrap_or(player_index));
+        self.curr_player_index = match (extra_turn.as_ref(), possession) {
+            (Some(_), _) => player_index,
+            (None, Some(index)) => {
+                let possessor = self.possessions.remove(index);
+                self.possessed_by = Some(possessor);
+                (possessor + 1) % self.players.len()
+            }
+            // After being possessed, the player takes their own turn
+            (None, None) if possessor.is_some() => player_index,
+            (None, None) => {
+                let next = (player_index + 1) % self.players.len();
+                if take(&mut self.players[next].skip_next_turn) {
+                    (next + 1) % self.players.len()
+                } else {
+                    next
+                }
+            }
+        };
+        self.game_phase = GamePhase::ActionPhase;
+        // The game ends before the next turn starts, so it doesn't count
+        // towards breaking ties
+        if self.fleet_turns.is_some() || self.supply.check_game_over(self.players.len()) {
+            let Some(fleet_turn) = self.next_fleet_turn(player_index) else {
+                self.winners = Some(self.determine_winners());
+                return Ok(());
+            };
+            self.curr_player_index = fleet_turn;
+            self.possessed_by = None;
+            self.history.start_turn(fleet_turn, false);
+            return self.start_turn();
+        }
+        let extra_turn = extra_turn.is_some() || self.possessed_by.is_some();
+        self.history.start_turn(self.curr_player_index, extra_turn);
+        self.start_turn()
+    }
+
+    /// Once the game has ended, each player with Fleet takes one more turn,
+    /// in turn order from whoever ended it. Returns whose turn is next, if
+    /// anyone's.
+    fn next_fleet_turn(&mut self, player_index: usize) -> Option<usize> {
+        let num_players = self.players.len();
+        let players = &self.players;
+        self.fleet_turns
+            .get_or_insert_with(|| {
+                (1..=num_players)
+                    .map(|offset| (player_index + offset) % num_players)
+                    .filter(|&index| players[index].has_project("Fleet"))
+                    .collect()
+            })
+            .pop_front()
+    }
+
+    /// Resolves the new current player's start-of-turn effects.
+    fn start_turn(&mut self) -> Result<(), GameError> {
+        let player_index = self.curr_player_index;
+        self.current_player().start_turn();
+        self.triggers
+            .retain(|trigger| trigger.expires != Expiry::StartOfTurn(player_index));
+        let (due, later) = take(&mut self.durations)
+            .into_iter()
+            .partition(|duration| duration.player_index == player_index);
+        self.durations = later;
+        for duration in due {
+            self.staged.push(Pending::Effect(duration.effect));
+        }
+        #[cfg(any(
+            feature = "adventures",
+            feature = "nocturne",
+            feature = "renaissance",
+            feature = "allies",
+            feature = "plunder"
+        ))]
+        self.fire(Event::TurnStart { player_index })?;
+        Ok(())
+    }
+
+    // LASTING EFFECTS
+    /// Defers an effect until the current turn is over, e.g. Donate's. It
+    /// happens after clean-up, once the next hand has been drawn.
+    #[cfg(any(feature = "empires", feature = "nocturne", feature = "plunder"))]
+    fn after_turn(
+        &mut self,
+        effect: impl FnOnce(&mut Game) -> Result<(), GameError> + Send + 'static,
+    ) {
+        self.after_turn.push(Box::new(effect));
+    }
+
+    /// Schedules part of a Duration card's effect for the start of the player's next
+    /// turn. The card stays in play until then.
+    #[cfg(any(
+        feature = "seaside",
+        feature = "prosperity",
+        feature = "adventures",
+        feature = "empires",
+        feature = "nocturne",
+        feature = "renaissance",
+        feature = "menagerie",
+        feature = "cornucopia",
+        feature = "allies",
+        feature = "plunder"
+    ))]
+    fn at_start_of_turn(
+        &mut self,
+        player_index: usize,
+        source: Card,
+        effect: impl FnOnce(&mut Game) -> Result<(), GameError> + Send + 'static,
+    ) {
+        self.durations.push(DurationEffect {
+            player_index,
+            source,
+            effect: Box::new(effect),
+        });
+    }
+
+    /// Keeps a Duration card in play, for as many turns as it takes, until the
+    /// next time something happens, e.g. Search waiting for a supply pile to
+    /// run out. `handler` returns whether the card should keep waiting.
+    #[cfg(feature = "plunder")]
+    fn wait_for_next_time(
+        &mut self,
+        player_index: usize,
+        source: Card,
+        mut handler: impl FnMut(&mut Game, Event) -> Result<bool, GameError> + Send + 'static,
+    ) {
+        self.waiting.push((player_index, source));
+        self.add_trigger(Expiry::Never, move |game, event| {
+            let waiting = handler(game, event)?;
+            if !waiting {
+                if let Some(index) = game
+                    .waiting
+                    .iter()
+                    .position(|&entry| entry == (player_index, source))
+                {
+                    game.waiting.remove(index);
+                }
+            }
+            Ok(waiting)
+        });
+    }
+
+    fn add_trigger(
+        &mut self,
+        expires: Expiry,
+        handler: impl FnMut(&mut Game, Event) -> Result<bool, GameError> + Send + 'static,
+    ) {
+        self.triggers.push(Trigger {
+            expires,
+            handler: Box::new(handler),
+        });
+    }
+
+    /// Lets every active trigger respond to an event.
+    fn fire(&mut self, event: Event) -> Result<(), GameError> {
+        let mut triggers = take(&mut self.triggers);
+        let mut result = Ok(());
+        triggers.retain_mut(|trigger| {
+            if result.is_err() {
+                return true;
+            }
+            match (trigger.handler)(self, event) {
+                Ok(keep) => keep,
+                Err(e) => {
+                    result = Err(e);
+                    true
+                }
+            }
+        });
+        // Keep any triggers added while responding
+        triggers.append(&mut self.triggers);
+        self.triggers = triggers;
+        result?;
+        self.offer_calls(event);
+        // States, Projects and Artifacts respond while any player has them
+        let mut held: Vec<Card> = self
+            .players
+            .iter()
+            .flat_map(|player| {
+                player
+                    .states
+                    .iter()
+                    .chain(player.projects.iter())
+                    .chain(player.artifacts.iter())
+                    .copied()
+            })
+            .collect();
+        held.sort_by_key(|card| card.name());
+        held.dedup();
+        let set_out: Vec<Card> = self
+            .supply
+            .landmarks
+            .iter()
+            .chain(self.supply.ally.iter())
+            .chain(self.supply.piles.iter().flat_map(|pile| pile.traits.iter()))
+            // Events with a lasting rule, e.g. Invest
+            .chain(self.supply.events.iter())
+            .copied()
+            // Kingdom cards with a rule for the whole game, e.g. Changeling
+            .chain(
+                self.supply
+                    .piles
+                    .iter()
+                    .filter_map(|pile| Card::named(&pile.name)),
+            )
+            .collect();
+        for card in set_out.into_iter().chain(held) {
+            if let Some(on_event) = card.behaviour().and_then(|b| b.on_event) {
+                on_event(self, card, event);
+            }
+        }
+        Ok(())
+    }
+
+    // BOONS AND HEXES
+    /// Has a player receive a Boon or Hex. Where it goes afterwards is up to
+    /// whoever drew it.
+    #[cfg(feature = "nocturne")]
+    fn receive(&mut self, player_index: usize, boon_or_hex: Card) -> Result<(), GameError> {
+        match boon_or_hex.behaviour().and_then(|b| b.on_receive) {
+            Some(on_receive) => on_receive(self, player_index, boon_or_hex),
+            None => Ok(()),
+        }
+    }
+
+    /// Has a player receive the next Boon, returning which one it was.
+    #[cfg(feature = "nocturne")]
+    fn receive_boon(&mut self, player_index: usize) -> Result<Option<Card>, GameError> {
+        let Some(boon) = self.supply.boons.draw() else {
+            return Ok(None);
+        };
+        self.supply.boons.discard(boon);
+        self.receive(player_index, boon)?;
+        Ok(Some(boon))
+    }
+
+    /// Has each of the given players receive the same, next Hex.
+    #[cfg(feature = "nocturne")]
+    fn receive_hex(&mut self, player_indices: &[usize]) -> Result<(), GameError> {
+        let Some(hex) = self.supply.hexes.draw() else {
+            return Ok(());
+        };
+        self.supply.hexes.discard(hex);
+        for &player_index in player_indices {
+            self.receive(player_index, hex)?;
         }
         Ok(())
     }
+
+    /// Lets the Reserve cards on each player's Tavern mat respond to an event.
+    fn offer_calls(&mut self, event: Event) {
+        for player_index in 0..self.players.len() {
+            let Some(tavern) = self.players[player_index].mats.get(&Mat::Tavern) else {
+                continue;
+            };
+            let mut reserves = tavern.clone();
+            reserves.sort_by_key(|card| card.name());
+            reserves.dedup();
+            for reserve in reserves {
+                if let Some(on_tavern) = reserve.behaviour().and_then(|b| b.on_tavern) {
+                    on_tavern(self, player_index, reserve, event);
+                }
+            }
+        }
+    }
+
+    /// A player's victory points from their cards and tokens, plus whatever
+    /// the Landmarks (or the Ally) in the game give or take away.
+    fn score(&self, player_index: usize) -> i32 {
+        let landmarks: i32 = self
+            .supply
+            .landmarks
+            .iter()
+            .chain(self.supply.ally.iter())
+            .filter_map(|landmark| landmark.behaviour().and_then(|b| b.score))
+            .map(|score| score(self, player_index))
+            .sum();
+        self.players[player_index].get_victory_points() + landmarks
+    }
+
+    /// The players with the most victory points. Ties go to whoever had fewer
+    /// (non-extra) turns; if that is also tied the victory is shared.
+    fn determine_winners(&self) -> Vec<usize> {
+        let best_score = (0..self.players.len())
+            .map(|player_index| self.score(player_index))
+            .max()
+            .unwrap();
+        let leaders: Vec<usize> = (0..self.players.len())
+            .filter(|&player_index| self.score(player_index) == best_score)
+            .collect();
+        let fewest_turns = leaders
+            .iter()
+            .map(|&player_index| self.history.regular_turns_taken(player_index))
+            .min()
+            .unwrap();
+        leaders
+            .into_iter()
+            .filter(|&player_index| self.history.regular_turns_taken(player_index) == fewest_turns)
+            .collect()
+    }
 }
 
 use std::io::{self, Write};
 
 fn main() {
+    // Report cards the engine can't play yet, or data that doesn't match the code
+    for problem in cards::validate() {
+        eprintln!("Card database: {}", problem);
+    }
+
+    let args: Vec<String> = std::env::args().skip(1).collect();
+    if args.first().is_some_and(|arg| arg == "simulate") {
+        if let Err(e) = simulate::run(&args[1..]) {
+            eprintln!("Error: {}", e);
+        }
+        return;
+    }
+
     // Initialize your game
-    let mut game = Game::initialise_game(2);
+    let seed = rng().random();
+    let setup = players_from_args(&args, seed).and_then(|seats| {
+        let num_players = seats.len();
+        let game = match (kingdom_from_args(&args)?, expansions_from_args(&args)?) {
+            (None, None) => Game::initialise_game(num_players),
+            (Some(kingdom), None) => {
+                Game::initialise_game_with_kingdom(num_players, seed, &kingdom)?
+            }
+            (kingdom, Some(expansions)) => {
+                let kingdom = random_kingdom(kingdom.unwrap_or_default(), &expansions, seed)?;
+                Game::initialise_game_with_expansions(num_players, seed, &kingdom, &expansions)?
+            }
+        };
+        Ok((game, seats))
+    });
+    let (mut game, mut seats) = match setup {
+        Ok(setup) => setup,
+        Err(e) => {
+            eprintln!("Error: {}", e);
+            return;
+        }
+    };
 
     loop {
+        // Computer players move until it's up to a human
+        run_bots(&mut game, &mut seats);
+        if let Some(winners) = &game.winners {
+            print_result(&game, &seats, winners);
+            break;
+        }
+
         // Display current game state
         println!("{:#?}", game);
 
@@ -907,25 +4136,281 @@ fn main() {
         print!("> ");
         io::stdout().flush().unwrap(); // Ensure the prompt is displayed before reading input
 
-        // Read input
-        let mut input = String::new();
-        io::stdin()
-            .read_line(&mut input)
-            .expect("Failed to read line");
+        // Read input
+        let mut input = String::new();
+        io::stdin()
+            .read_line(&mut input)
+            .expect("Failed to read line");
+
+        // Trim whitespace
+        let input = input.trim();
+
+        // Check for exit command
+        if input.eq_ignore_ascii_case("quit") || input.eq_ignore_ascii_case("exit") {
+            println!("Thanks for playing!");
+            break;
+        }
+
+        // Process the command
+        process_command(&mut game, input);
+        println!();
+    }
+}
+
+/// Who makes the moves for a player.
+enum Seat {
+    Human,
+    Bot(Box<dyn Strategy>),
+}
+
+/// Reads who sits in each chair from `--players <seat>,<seat>,...`, where a
+/// seat is `human`, the name of a strategy or a file of buy rules. Without
+/// it, two humans play.
+fn players_from_args(args: &[String], seed: u64) -> Result<Vec<Seat>, GameError> {
+    let Some(position) = args.iter().position(|arg| arg == "--players") else {
+        return Ok(vec![Seat::Human, Seat::Human]);
+    };
+    let Some(names) = args.get(position + 1) else {
+        return Err(GameError::InvalidPlayers(
+            "Usage: --players <human|strategy>,<human|strategy>,...".to_owned(),
+        ));
+    };
+    let seats = names
+        .split(',')
+        .enumerate()
+        .map(|(player_index, name)| {
+            let name = name.trim();
+            if name.eq_ignore_ascii_case("human") {
+                return Ok(Seat::Human);
+            }
+            // Each bot gets its own seed, so random ones don't all move alike
+            StrategySpec::load(name)
+                .map(|spec| Seat::Bot(spec.build(seed.wrapping_add(player_index as u64 + 1))))
+                .map_err(|e| GameError::InvalidPlayers(format!("{e}; a seat can also be human")))
+        })
+        .collect::<Result<Vec<_>, _>>()?;
+    if !(2..=6).contains(&seats.len()) {
+        return Err(GameError::InvalidPlayers(format!(
+            "a game needs 2 to 6 players, not {}",
+            seats.len()
+        )));
+    }
+    Ok(seats)
+}
+
+/// Makes the moves of computer players, printing each one, until a human is
+/// to move or the game is over.
+fn run_bots(game: &mut Game, seats: &mut [Seat]) {
+    let mut turn_shown = None;
+    while game.winners.is_none() {
+        let player_index = game.acting_player_index();
+        let Seat::Bot(strategy) = &mut seats[player_index] else {
+            return;
+        };
+        let turn = (
+            game.curr_player_index,
+            game.history.current_turn.turn_number,
+        );
+        if player_index == game.curr_player_index && turn_shown != Some(turn) {
+            println!(
+                "-- Player {} ({}), turn {} --",
+                turn.0,
+                strategy.name(),
+                turn.1
+            );
+            turn_shown = Some(turn);
+        }
+        match play_bot_move(game, strategy.as_mut()) {
+            Ok(line) => println!("{line}"),
+            Err(e) => {
+                // Leave the move to whoever is at the keyboard
+                println!("Error: {} couldn't move: {}", strategy.name(), e);
+                return;
+            }
+        }
+    }
+}
+
+/// Lets a strategy make the acting player's next move, returning a line
+/// describing it for the log.
+fn play_bot_move(game: &mut Game, strategy: &mut dyn Strategy) -> Result<String, GameError> {
+    if game.winners.is_some() {
+        return Err(GameError::GameOver);
+    }
+    let player_index = game.acting_player_index();
+    let game_move = strategy.choose_move(&PlayerView::new(game, player_index));
+    let description = describe_move(game, player_index, &game_move);
+    game.accept_move(player_index, game_move)?;
+    Ok(format!(
+        "Player {player_index} ({}) {description}",
+        strategy.name()
+    ))
+}
+
+/// Puts a move into words, as seen before it is made.
+fn describe_move(game: &Game, player_index: usize, game_move: &GameMove) -> String {
+    let hand = &game.players[player_index].hand;
+    let card_at = |card_index: usize| {
+        hand.get(card_index).map_or_else(
+            || format!("card {card_index}"),
+            |card| card.name().to_owned(),
+        )
+    };
+    let source = game
+        .pending_decision()
+        .map_or("", |decision| decision.source.name());
+    match game_move {
+        GameMove::PlayCard {
+            card_index,
+            way: None,
+        } => format!("plays {}", card_at(*card_index)),
+        GameMove::PlayCard {
+            card_index,
+            way: Some(way),
+        } => format!("plays {} using {}", card_at(*card_index), way.name()),
+        GameMove::BuyCard { card } => format!("buys {}", card.name()),
+        GameMove::ChooseCards { card_indices } if card_indices.is_empty() => {
+            format!("chooses no cards for {source}")
+        }
+        GameMove::ChooseCards { card_indices } => format!(
+            "chooses {} for {source}",
+            card_indices
+                .iter()
+                .map(|&card_index| card_at(card_index))
+                .collect::<Vec<_>>()
+                .join(", ")
+        ),
+        GameMove::GainCard { card } => format!("picks {} for {source}", card.name()),
+        GameMove::ChooseOption { option_index } => {
+            match game.pending_decision().map(|decision| &decision.prompt) {
+                Some(Prompt::Options { options }) if *option_index < options.len() => {
+                    format!("chooses \"{}\" for {source}", options[*option_index])
+                }
+                _ => format!("chooses option {option_index} for {source}"),
+            }
+        }
+        GameMove::PlayAllTreasures => format!(
+            "plays {}",
+            hand.iter()
+                .filter(|&&card| Player::is_basic_treasure(card))
+                .map(|card| card.name())
+                .collect::<Vec<_>>()
+                .join(", ")
+        ),
+        GameMove::SpendVillager => "spends a Villager".to_owned(),
+        GameMove::SpendCoffer => "spends a Coffer".to_owned(),
+        GameMove::EndActions => "ends their Action phase".to_owned(),
+        GameMove::EndTreasures => "ends their Treasure phase".to_owned(),
+        GameMove::EndBuys => "ends their Buy phase".to_owned(),
+        GameMove::EndTurn => "ends their turn".to_owned(),
+    }
+}
+
+/// Announces the winners and every player's final score.
+fn print_result(game: &Game, seats: &[Seat], winners: &[usize]) {
+    if let [winner] = winners[..] {
+        println!("Game over! Player {} wins!", winner);
+    } else {
+        println!("Game over! Players {:?} share the victory!", winners);
+    }
+    for (player_index, seat) in seats.iter().enumerate() {
+        let name = match seat {
+            Seat::Human => "human",
+            Seat::Bot(strategy) => strategy.name(),
+        };
+        println!(
+            "Player {player_index} ({name}): {} VP",
+            game.score(player_index)
+        );
+    }
+}
+
+/// Reads the kingdom from `--kingdom <card>,<card>,...`, if given.
+fn kingdom_from_args(args: &[String]) -> Result<Option<Vec<Card>>, GameError> {
+    let Some(position) = args.iter().position(|arg| arg == "--kingdom") else {
+        return Ok(None);
+    };
+    let Some(names) = args.get(position + 1) else {
+        return Err(GameError::InvalidKingdom(
+            "Usage: --kingdom <card>,<card>,...".to_owned(),
+        ));
+    };
+    names
+        .split(',')
+        .map(|name| find_card(name.trim()).map_err(|e| GameError::InvalidKingdom(e.to_string())))
+        .collect::<Result<Vec<_>, _>>()
+        .map(Some)
+}
 
-        // Trim whitespace
-        let input = input.trim();
+/// Reads the expansions to pick a random kingdom from, from
+/// `--expansions <name>,<name>,...`, if given.
+fn expansions_from_args(args: &[String]) -> Result<Option<Vec<&'static str>>, GameError> {
+    let Some(position) = args.iter().position(|arg| arg == "--expansions") else {
+        return Ok(None);
+    };
+    let Some(names) = args.get(position + 1) else {
+        return Err(GameError::InvalidKingdom(
+            "Usage: --expansions <expansion>,<expansion>,...".to_owned(),
+        ));
+    };
+    names
+        .split(',')
+        .map(|name| {
+            find_expansion(name.trim()).ok_or_else(|| {
+                GameError::InvalidKingdom(format!(
+                    "unknown expansion '{}'; this build has {}",
+                    name.trim(),
+                    expansions().collect::<Vec<_>>().join(", ")
+                ))
+            })
+        })
+        .collect::<Result<Vec<_>, _>>()
+        .map(Some)
+}
 
-        // Check for exit command
-        if input.eq_ignore_ascii_case("quit") || input.eq_ignore_ascii_case("exit") {
-            println!("Thanks for playing!");
-            break;
+/// Fills a kingdom up to ten piles with piles picked at random from the given
+/// expansions. The cards already named stay in it; Events, Landmarks and the
+/// like are only ever in the kingdom when named.
+fn random_kingdom(
+    mut kingdom: Vec<Card>,
+    expansions: &[&str],
+    seed: u64,
+) -> Result<Vec<Card>, GameError> {
+    let named: HashSet<&str> = kingdom
+        .iter()
+        .copied()
+        .filter(|&card| is_kingdom_pile_card(card))
+        .map(kingdom_pile_name)
+        .collect();
+    // One card stands for each pile, which can only be picked if every card
+    // in it has been implemented
+    let mut candidates: Vec<Card> = Vec::new();
+    for &expansion in expansions {
+        for card in cards_in(expansion).filter(|&card| is_kingdom_pile_card(card)) {
+            let pile = kingdom_pile_name(card);
+            let ready = all_cards()
+                .filter(|&other| is_kingdom_pile_card(other) && kingdom_pile_name(other) == pile)
+                .all(|other| other.is_implemented());
+            let seen = candidates
+                .iter()
+                .any(|&other| kingdom_pile_name(other) == pile);
+            if ready && !seen && !named.contains(pile) {
+                candidates.push(card);
+            }
         }
-
-        // Process the command
-        process_command(&mut game, input);
-        println!("");
     }
+    let missing = KINGDOM_SIZE.saturating_sub(named.len());
+    if candidates.len() < missing {
+        return Err(GameError::InvalidKingdom(format!(
+            "{} only have {} more kingdom piles to pick from, but {} are needed",
+            expansions.join(", "),
+            candidates.len(),
+            missing
+        )));
+    }
+    candidates.shuffle(&mut StdRng::seed_from_u64(seed));
+    kingdom.extend(candidates.into_iter().take(missing));
+    Ok(kingdom)
 }
 
 fn process_command(game: &mut Game, command: &str) {
@@ -940,18 +4425,31 @@ fn process_command(game: &mut Game, command: &str) {
     match parts[0].to_lowercase().as_str() {
         "play" => {
             if parts.len() < 2 {
-                println!("Usage: play <card_index>");
+                println!("Usage: play <card_index> [way]");
                 return;
             }
 
+            // Anything after the index names the Way to play the card as
+            let way = if parts.len() > 2 {
+                match game.supply.find_card(&parts[2..].join(" ")) {
+                    Ok(way) => Some(way),
+                    Err(e) => {
+                        println!("Error: {}", e);
+                        return;
+                    }
+                }
+            } else {
+                None
+            };
+
             // Parse card index
             match parts[1].parse::<usize>() {
                 Ok(card_index) => {
                     // Create a play card move
-                    let game_move = GameMove::PlayCard { card_index };
+                    let game_move = GameMove::PlayCard { card_index, way };
 
                     // Execute the move
-                    match game.accept_move(game.curr_player_index, game_move) {
+                    match game.accept_move(game.acting_player_index(), game_move) {
                         Ok(_) => println!("Card played successfully."),
                         Err(e) => println!("Error: {}", e),
                     }
@@ -969,15 +4467,201 @@ fn process_command(game: &mut Game, command: &str) {
             let card_name = parts[1..].join(" ");
 
             // Create a buy card move (you'd need to implement this move)
-            if let Some(card) = card_name_to_card(&card_name) {
-                let game_move = GameMove::BuyCard { card };
-                // Execute the move
-                match game.accept_move(game.curr_player_index, game_move) {
-                    Ok(_) => println!("Card bought successfully."),
+            match game.supply.find_card(&card_name) {
+                Ok(card) => {
+                    let game_move = GameMove::BuyCard { card };
+                    // Execute the move
+                    match game.accept_move(game.acting_player_index(), game_move) {
+                        Ok(_) => println!("Card bought successfully."),
+                        Err(e) => println!("Error: {}", e),
+                    }
+                }
+                Err(e) => println!("Error: {}", e),
+            }
+        }
+        "choose" => {
+            // No indices means choosing no cards
+            let card_indices: Result<Vec<usize>, _> = parts[1..]
+                .iter()
+                .map(|part| part.parse::<usize>())
+                .collect();
+            match card_indices {
+                Ok(card_indices) => {
+                    let game_move = GameMove::ChooseCards { card_indices };
+                    match game.accept_move(game.acting_player_index(), game_move) {
+                        Ok(_) => println!("Cards chosen."),
+                        Err(e) => println!("Error: {}", e),
+                    }
+                }
+                Err(_) => println!("Invalid card index. Please enter numbers only."),
+            }
+        }
+        "gain" => {
+            if parts.len() < 2 {
+                println!("Usage: gain <card_name>");
+                return;
+            }
+
+            let card_name = parts[1..].join(" ");
+            match game.supply.find_card(&card_name) {
+                Ok(card) => {
+                    let game_move = GameMove::GainCard { card };
+                    match game.accept_move(game.acting_player_index(), game_move) {
+                        Ok(_) => println!("Card gained successfully."),
+                        Err(e) => println!("Error: {}", e),
+                    }
+                }
+                Err(e) => println!("Error: {}", e),
+            }
+        }
+        "option" => {
+            if parts.len() < 2 {
+                println!("Usage: option <option_index>");
+                return;
+            }
+
+            match parts[1].parse::<usize>() {
+                Ok(option_index) => {
+                    let game_move = GameMove::ChooseOption { option_index };
+                    match game.accept_move(game.acting_player_index(), game_move) {
+                        Ok(_) => println!("Option chosen."),
+                        Err(e) => println!("Error: {}", e),
+                    }
+                }
+                Err(_) => println!("Invalid option index. Please enter a number."),
+            }
+        }
+        "autoplay" => {
+            if parts.get(1) == Some(&"treasures") {
+                let game_move = GameMove::PlayAllTreasures;
+                match game.accept_move(game.acting_player_index(), game_move) {
+                    Ok(_) => println!("Treasures played."),
                     Err(e) => println!("Error: {}", e),
                 }
             } else {
-                println!("Invalid card name. Please enter a valid card name.");
+                println!("Did you mean 'autoplay treasures'?");
+            }
+        }
+        "auto" => {
+            if parts.len() < 3 {
+                println!("Usage: auto <actions|treasures|turn|play> <on|off>");
+                return;
+            }
+
+            let enabled = match parts[2] {
+                "on" => true,
+                "off" => false,
+                _ => {
+                    println!("Please enter 'on' or 'off'.");
+                    return;
+                }
+            };
+            // While someone is Possessed, their turn plays by the possessor's policy
+            let acting_player_index = game.acting_player_index();
+            let policy = &mut game.players[acting_player_index].auto_play;
+            match parts[1] {
+                "actions" => policy.end_actions = enabled,
+                "treasures" => policy.end_treasures = enabled,
+                "turn" => policy.end_turn = enabled,
+                "play" => policy.play_treasures = enabled,
+                _ => {
+                    println!("Unknown setting. Choose actions, treasures, turn or play.");
+                    return;
+                }
+            }
+            println!("Auto-play policy updated: {:?}", policy);
+            if let Err(e) = game.auto_advance() {
+                println!("Error: {}", e);
+            }
+        }
+        "spend" => {
+            let game_move = match parts.get(1).map(|part| part.to_lowercase()).as_deref() {
+                Some("villager") => GameMove::SpendVillager,
+                Some("coffer") => GameMove::SpendCoffer,
+                _ => {
+                    println!("Usage: spend <villager|coffer>");
+                    return;
+                }
+            };
+            match game.accept_move(game.acting_player_index(), game_move) {
+                Ok(_) => println!("Spent."),
+                Err(e) => println!("Error: {}", e),
+            }
+        }
+        "moves" => {
+            println!("Legal moves: {:#?}", game.legal_moves());
+        }
+        "bot" => {
+            if parts.len() < 2 {
+                println!("Usage: bot <strategy>");
+                return;
+            }
+
+            let name = parts[1..].join(" ");
+            let mut strategy = match StrategySpec::load(&name) {
+                Ok(spec) => spec.build(rng().random()),
+                Err(e) => {
+                    println!("Error: {}", e);
+                    return;
+                }
+            };
+            match play_bot_move(game, strategy.as_mut()) {
+                Ok(line) => println!("{line}"),
+                Err(e) => println!("Error: {}", e),
+            }
+        }
+        "card" => {
+            if parts.len() < 2 {
+                println!("Usage: card <card_name>");
+                return;
+            }
+
+            let card_name = parts[1..].join(" ");
+            match find_card(&card_name) {
+                Ok(card) => {
+                    let info = card.info();
+                    println!("{} ({:?}) - {}", info.name, info.types, card.cost());
+                    println!("{}", info.text);
+                }
+                Err(e) => println!("Error: {}", e),
+            }
+        }
+        "expansions" => {
+            for expansion in expansions() {
+                let cards: Vec<Card> = cards_in(expansion).collect();
+                let missing = cards.iter().filter(|card| !card.is_implemented()).count();
+                println!(
+                    "{expansion}: {} cards, {missing} not implemented yet",
+                    cards.len()
+                );
+            }
+        }
+        "expansion" => {
+            if parts.len() < 2 {
+                println!("Usage: expansion <name>");
+                return;
+            }
+
+            let name = parts[1..].join(" ");
+            let Some(expansion) = find_expansion(&name) else {
+                println!(
+                    "Error: Unknown expansion '{name}'; this build has {}",
+                    expansions().collect::<Vec<_>>().join(", ")
+                );
+                return;
+            };
+            for card in cards_in(expansion) {
+                let missing = if card.is_implemented() {
+                    ""
+                } else {
+                    " (not implemented yet)"
+                };
+                println!(
+                    "{} ({:?}) - {}{missing}",
+                    card.name(),
+                    card.info().types,
+                    card.cost()
+                );
             }
         }
         "end" => {
@@ -986,7 +4670,7 @@ fn process_command(game: &mut Game, command: &str) {
                     // End turn move
                     let game_move = GameMove::EndTurn;
 
-                    match game.accept_move(game.curr_player_index, game_move) {
+                    match game.accept_move(game.acting_player_index(), game_move) {
                         Ok(_) => println!("Turn ended."),
                         Err(e) => println!("Error: {}", e),
                     }
@@ -994,7 +4678,7 @@ fn process_command(game: &mut Game, command: &str) {
                     // End actions move
                     let game_move = GameMove::EndActions;
 
-                    match game.accept_move(game.curr_player_index, game_move) {
+                    match game.accept_move(game.acting_player_index(), game_move) {
                         Ok(_) => println!("Actions ended."),
                         Err(e) => println!("Error: {}", e),
                     }
@@ -1002,21 +4686,45 @@ fn process_command(game: &mut Game, command: &str) {
                     // End treasures move
                     let game_move = GameMove::EndTreasures;
 
-                    match game.accept_move(game.curr_player_index, game_move) {
+                    match game.accept_move(game.acting_player_index(), game_move) {
                         Ok(_) => println!("Treasures ended."),
                         Err(e) => println!("Error: {}", e),
                     }
+                } else if parts[1] == "buys" {
+                    let game_move = GameMove::EndBuys;
+
+                    match game.accept_move(game.acting_player_index(), game_move) {
+                        Ok(_) => println!("Buys ended."),
+                        Err(e) => println!("Error: {}", e),
+                    }
                 } else {
-                    println!("Did you mean 'end turn', 'end actions', or 'end treasures'?");
+                    println!(
+                        "Did you mean 'end turn', 'end actions', 'end treasures' or 'end buys'?"
+                    );
                 }
             }
         }
         "help" => {
             println!("Available commands:");
-            println!("  play <card_index> - Play a card from your hand");
+            println!(
+                "  play <card_index> [way] - Play a card from your hand, optionally using a Way"
+            );
             println!("  buy <card_name>   - Buy a card from the supply");
+            println!("  choose [indices]  - Choose cards from your hand for a pending decision");
+            println!("  gain <card_name>  - Choose a card to gain for a pending decision");
+            println!("  option <index>    - Choose an option for a pending decision");
+            println!("  autoplay treasures - Play all basic treasures in your hand");
+            println!("  spend villager    - Spend a Villager for +1 Action");
+            println!("  spend coffer      - Spend a Coffer for +$1");
+            println!("  auto <setting> <on|off> - Toggle automatic steps (actions, treasures, turn, play)");
+            println!("  moves             - List the legal moves");
+            println!("  bot <strategy>    - Let a computer strategy make the next move");
+            println!("  card <card_name>  - Show a card's types, cost and text");
+            println!("  expansions        - List the expansions in this build");
+            println!("  expansion <name>  - List an expansion's cards");
             println!("  end actions       - End actions");
             println!("  end treasures     - End treasures");
+            println!("  end buys          - End buys and move on to the Night phase");
             println!("  end turn          - End your turn");
             println!("  help              - Show this help message");
             println!("  quit              - Exit the game");
@@ -1024,3 +4732,609 @@ fn process_command(game: &mut Game, command: &str) {
         _ => println!("Unknown command. Type 'help' for available commands."),
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::cards::testing::{choose_cards, choose_option, end_turn, gain, play, scenario};
+
+    /// A player with exactly these cards in their deck (top card last) and
+    /// discard pile, and an empty hand.
+    fn player_with(deck: &[&str], discard: &[&str]) -> Player {
+        let mut player = Player::new(0, 7, false, &[]);
+        player.hand.clear();
+        player.deck = deck.iter().map(|&name| card(name)).collect();
+        player.discard = discard.iter().map(|&name| card(name)).collect();
+        player
+    }
+
+    fn names(cards: &[Card]) -> Vec<&'static str> {
+        cards.iter().map(|card| card.name()).collect()
+    }
+
+    #[test]
+    fn draw_takes_the_rest_of_the_deck_before_reshuffling() {
+        let mut player = player_with(&["Copper", "Silver"], &["Gold", "Estate", "Duchy"]);
+
+        assert_eq!(player.draw(3), 3);
+
+        assert_eq!(names(&player.hand[..2]), ["Silver", "Copper"]);
+        assert!(["Gold", "Estate", "Duchy"].contains(&player.hand[2].name()));
+        assert_eq!(player.deck.len(), 2);
+        assert!(player.discard.is_empty());
+        assert_eq!(player.shuffle_count(), 1);
+    }
+
+    #[test]
+    fn draw_does_not_reshuffle_while_the_deck_lasts() {
+        let mut player = player_with(&["Copper", "Silver", "Gold"], &["Estate"]);
+
+        assert_eq!(player.draw(3), 3);
+
+        assert_eq!(names(&player.hand), ["Gold", "Silver", "Copper"]);
+        assert!(player.deck.is_empty());
+        assert_eq!(names(&player.discard), ["Estate"]);
+        assert_eq!(player.shuffle_count(), 0);
+    }
+
+    #[test]
+    fn draw_stops_when_deck_and_discard_are_empty() {
+        let mut player = player_with(&["Copper"], &["Silver"]);
+
+        assert_eq!(player.draw(5), 2);
+
+        assert_eq!(names(&player.hand), ["Copper", "Silver"]);
+        assert!(player.deck.is_empty() && player.discard.is_empty());
+        assert_eq!(player.shuffle_count(), 1);
+        // With nothing left to shuffle, drawing again neither draws nor shuffles
+        assert_eq!(player.draw(1), 0);
+        assert_eq!(player.shuffle_count(), 1);
+    }
+
+    #[test]
+    fn shuffle_count_counts_each_reshuffle() {
+        let mut player = player_with(&[], &["Copper", "Silver"]);
+
+        player.draw(2);
+        player.discard.append(&mut player.hand);
+        player.draw(1);
+        player.draw(1);
+
+        assert_eq!(player.shuffle_count(), 2);
+        assert_eq!(player.hand.len(), 2);
+    }
+
+    #[test]
+    fn reshuffles_are_the_same_for_the_same_seed() {
+        let discard = ["Copper", "Silver", "Gold", "Estate", "Duchy", "Province"];
+        let mut first = player_with(&[], &discard);
+        let mut second = player_with(&[], &discard);
+
+        first.draw(6);
+        second.draw(6);
+
+        assert_eq!(first.hand, second.hand);
+    }
+
+    #[test]
+    fn minus_card_token_makes_only_the_next_draw_short() {
+        let mut player = player_with(&["Copper", "Silver", "Gold"], &[]);
+        player.minus_card_token = true;
+
+        assert_eq!(player.draw(2), 1);
+        assert_eq!(player.draw(1), 1);
+
+        assert_eq!(names(&player.hand), ["Gold", "Silver"]);
+        assert!(!player.minus_card_token);
+    }
+
+    #[test]
+    #[cfg(feature = "adventures")]
+    fn minus_coin_token_only_takes_from_coins_got_after_it() {
+        let mut player = player_with(&[], &[]);
+        player.coins = 3;
+        player.take_minus_coin_token();
+        player.settle_minus_coin_token();
+        assert_eq!(player.coins, 3);
+
+        player.pay(&Cost::coins(2));
+        player.coins += 2;
+        player.settle_minus_coin_token();
+
+        assert_eq!(player.coins, 2);
+        assert_eq!(player.minus_coin_token, None);
+    }
+
+    /// Plays Bridge `times` times for the current player, as if from their hand.
+    #[cfg(feature = "intrigue")]
+    fn play_bridges(game: &mut Game, times: usize) {
+        let bridge = card("Bridge");
+        let on_play = bridge.behaviour().and_then(|b| b.on_play).unwrap();
+        for _ in 0..times {
+            on_play(game, bridge).unwrap();
+        }
+    }
+
+    #[test]
+    #[cfg(feature = "intrigue")]
+    fn bridges_stack() {
+        let mut game = Game::initialise_game_with_seed(2, 1);
+        play_bridges(&mut game, 2);
+
+        assert_eq!(game.cost_of(card("Gold")), Cost::coins(4));
+        assert_eq!(game.cost_of(card("Province")), Cost::coins(6));
+    }
+
+    #[test]
+    #[cfg(feature = "intrigue")]
+    fn cost_reductions_stop_at_zero() {
+        let mut game = Game::initialise_game_with_seed(2, 1);
+        play_bridges(&mut game, 3);
+
+        assert_eq!(game.cost_of(card("Copper")), Cost::coins(0));
+        assert_eq!(game.cost_of(card("Silver")), Cost::coins(0));
+        assert_eq!(game.cost_of(card("Gold")), Cost::coins(3));
+    }
+
+    #[test]
+    #[cfg(all(feature = "intrigue", feature = "alchemy", feature = "empires"))]
+    fn cost_reductions_leave_potions_and_debt_alone() {
+        let mut game = Game::initialise_game_with_seed(2, 1);
+        play_bridges(&mut game, 2);
+
+        assert_eq!(
+            game.cost_of(card("Golem")),
+            Cost {
+                coins: 2,
+                potions: 1,
+                debt: 0
+            }
+        );
+        assert_eq!(
+            game.cost_of(card("Engineer")),
+            Cost {
+                coins: 0,
+                potions: 0,
+                debt: 4
+            }
+        );
+    }
+
+    #[test]
+    #[cfg(feature = "intrigue")]
+    fn cost_reductions_end_with_the_turn() {
+        let mut game = Game::initialise_game_with_seed(2, 1);
+        play_bridges(&mut game, 1);
+        assert_eq!(game.cost_of(card("Gold")), Cost::coins(5));
+
+        game.clean_up().unwrap();
+
+        assert!(game.cost_modifiers.is_empty());
+        assert_eq!(game.cost_of(card("Gold")), Cost::coins(6));
+    }
+
+    #[test]
+    #[cfg(any(feature = "dark_ages", feature = "allies"))]
+    fn trash_cards_costing_finds_the_trashed_cards_in_the_cost_range() {
+        let mut game = Game::initialise_game_with_seed(2, 1);
+        for name in ["Copper", "Estate", "Silver", "Gold"] {
+            game.trash.add(card(name));
+        }
+
+        assert_eq!(
+            game.trash_cards_costing(2, 4),
+            [(1, card("Estate")), (2, card("Silver"))]
+        );
+        assert!(game.trash_cards_costing(7, 8).is_empty());
+    }
+
+    #[test]
+    fn merchant_gives_a_coin_for_the_first_silver_only() {
+        let mut game = Game::initialise_game_with_seed(2, 1);
+        let merchant = card("Merchant");
+        let on_play = merchant.behaviour().and_then(|b| b.on_play).unwrap();
+        on_play(&mut game, merchant).unwrap();
+
+        game.play_treasure(card("Silver")).unwrap();
+        game.play_treasure(card("Silver")).unwrap();
+
+        assert_eq!(game.current_player_read_only().coins, 5);
+    }
+
+    #[test]
+    fn moat_in_hand_protects_from_attacks() {
+        let mut game = Game::initialise_game_with_seed(3, 1);
+        let moated = (game.curr_player_index + 1) % 3;
+        let exposed = (game.curr_player_index + 2) % 3;
+        game.players[moated].hand.push(card("Moat"));
+        game.players[exposed].discard.push(card("Moat"));
+
+        assert_eq!(game.attacked_player_indices(), [exposed]);
+    }
+
+    #[test]
+    #[cfg(feature = "prosperity")]
+    fn grand_market_cannot_be_bought_with_copper_in_play() {
+        let mut game = buying_with(6);
+        let grand_market = card("Grand Market");
+        assert!(!game.buying_forbidden(grand_market));
+
+        game.current_player().played.push(card("Copper"));
+
+        assert!(game.buying_forbidden(grand_market));
+        assert!(!game.buying_forbidden(card("Gold")));
+    }
+
+    /// A game with the default kingdom in the current player's Buy phase,
+    /// with `coins` to spend.
+    fn buying_with(coins: u32) -> Game {
+        let mut game = Game::initialise_game_with_seed(2, 5);
+        game.game_phase = GamePhase::BuyPhase;
+        game.current_player().coins = coins;
+        game
+    }
+
+    #[test]
+    #[cfg(any(
+        feature = "seaside",
+        feature = "prosperity",
+        feature = "adventures",
+        feature = "empires",
+        feature = "nocturne",
+        feature = "renaissance",
+        feature = "menagerie",
+        feature = "hinterlands",
+        feature = "allies",
+        feature = "plunder"
+    ))]
+    fn bought_cards_are_paid_for_before_they_are_gained() {
+        let mut game = buying_with(7);
+        let coins_when_gained = std::sync::Arc::new(std::sync::Mutex::new(None));
+        let seen = coins_when_gained.clone();
+        game.add_trigger(Expiry::EndOfTurn, move |game, event| {
+            if let Event::Gain { player_index, .. } = event {
+                *seen.lock().unwrap() = Some(game.players[player_index].coins);
+            }
+            Ok(true)
+        });
+
+        let player_index = game.curr_player_index;
+        game.accept_move(player_index, GameMove::BuyCard { card: card("Gold") })
+            .unwrap();
+
+        assert_eq!(*coins_when_gained.lock().unwrap(), Some(1));
+    }
+
+    #[test]
+    fn buying_from_an_empty_pile_costs_nothing() {
+        let mut game = buying_with(8);
+        let province = card("Province");
+        game.supply.pile_mut(province).unwrap().cards.clear();
+
+        let player_index = game.curr_player_index;
+        let result = game.accept_move(player_index, GameMove::BuyCard { card: province });
+
+        assert!(matches!(result, Err(GameError::CardSupplyDepleted(_))));
+        let player = game.current_player_read_only();
+        assert_eq!((player.coins, player.buys), (8, 1));
+    }
+
+    #[test]
+    fn decisions_left_with_nothing_to_choose_are_skipped() {
+        let mut game = Game::initialise_game_with_seed(2, 3);
+        let player_index = game.curr_player_index;
+        let gold = card("Gold");
+        game.supply.pile_mut(gold).unwrap().cards.truncate(1);
+        for _ in 0..2 {
+            let prompt = Prompt::CardFromSupply {
+                max_cost: gold.cost(),
+                exact: true,
+                filter: CardFilter::Named("Gold"),
+            };
+            game.ask(player_index, gold, prompt, |game, player_index, answer| {
+                game.gain_card(player_index, answer.supply_card(), GainDestination::Discard)
+            });
+        }
+        game.run_pending().unwrap();
+
+        game.accept_move(player_index, GameMove::GainCard { card: gold })
+            .unwrap();
+
+        // The second Gold can't be gained, so play goes on without it
+        assert!(game.pending.is_empty());
+        assert!(game.legal_moves().contains(&GameMove::EndTurn));
+        assert_eq!(game.supply.count(gold), Some(0));
+    }
+
+    #[test]
+    fn basic_victory_and_curse_piles_scale_with_the_number_of_players() {
+        let pile_sizes = |num_players: usize| {
+            let game = Game::initialise_game_with_seed(num_players, 1);
+            ["Estate", "Duchy", "Province", "Curse"].map(|name| {
+                game.supply.pile(card(name)).unwrap().cards.len()
+            })
+        };
+
+        assert_eq!(pile_sizes(2), [8, 8, 8, 10]);
+        assert_eq!(pile_sizes(3), [12, 12, 12, 20]);
+        assert_eq!(pile_sizes(5), [12, 12, 15, 40]);
+        assert_eq!(pile_sizes(6), [12, 12, 18, 50]);
+    }
+
+    /// A game for `num_players` with `empty` piles other than the Provinces and
+    /// Colonies run out.
+    fn with_empty_piles(num_players: usize, empty: usize) -> Game {
+        let mut game = Game::initialise_game_with_seed(num_players, 1);
+        let kingdom_piles = game
+            .supply
+            .piles
+            .iter_mut()
+            .filter(|pile| !["Province", "Colony"].contains(&pile.name.as_str()));
+        for pile in kingdom_piles.take(empty) {
+            pile.cards.clear();
+        }
+        game
+    }
+
+    #[test]
+    fn three_empty_piles_end_the_game_with_up_to_four_players() {
+        for num_players in [2, 4] {
+            let game = with_empty_piles(num_players, 2);
+            assert!(!game.supply.check_game_over(num_players));
+            let game = with_empty_piles(num_players, 3);
+            assert!(game.supply.check_game_over(num_players));
+        }
+    }
+
+    #[test]
+    fn four_empty_piles_end_the_game_with_five_or_more_players() {
+        for num_players in [5, 6] {
+            let game = with_empty_piles(num_players, 3);
+            assert!(!game.supply.check_game_over(num_players));
+            let game = with_empty_piles(num_players, 4);
+            assert!(game.supply.check_game_over(num_players));
+        }
+    }
+
+    #[test]
+    #[cfg(feature = "prosperity")]
+    fn game_ends_once_the_colonies_run_out() {
+        let mut game = scenario(&["Monument"], [&[], &[]]);
+        game.supply.pile_mut(card("Colony")).unwrap().cards.clear();
+
+        end_turn(&mut game);
+
+        assert!(game.winners.is_some());
+    }
+
+    #[test]
+    #[cfg(feature = "prosperity")]
+    fn vp_tokens_count_toward_the_final_score() {
+        let mut game = scenario(&["Monument"], [&["Monument"], &[]]);
+        let player_index = game.curr_player_index;
+
+        play(&mut game, "Monument");
+
+        // Neither player has any Victory cards
+        assert_eq!(game.score(player_index), 1);
+        assert_eq!(game.score((player_index + 1) % 2), 0);
+        game.supply.pile_mut(card("Colony")).unwrap().cards.clear();
+        end_turn(&mut game);
+        assert_eq!(game.winners, Some(vec![player_index]));
+    }
+
+    fn players_arg(seats: &[&str]) -> Vec<String> {
+        vec!["--players".to_owned(), seats.join(",")]
+    }
+
+    #[test]
+    fn players_from_args_seats_up_to_six_players() {
+        let seats = players_from_args(&players_arg(&["human", "Big Money"]), 1).unwrap();
+        assert!(matches!(seats[..], [Seat::Human, Seat::Bot(_)]));
+        for num_players in [5, 6] {
+            let seats = players_from_args(&players_arg(&vec!["random"; num_players]), 1).unwrap();
+            assert_eq!(seats.len(), num_players);
+        }
+    }
+
+    #[test]
+    fn players_from_args_rejects_too_few_or_too_many_seats() {
+        for num_players in [1, 7] {
+            let result = players_from_args(&players_arg(&vec!["human"; num_players]), 1);
+            assert!(matches!(result, Err(GameError::InvalidPlayers(_))));
+        }
+    }
+
+    #[test]
+    fn auto_command_sets_the_possessor_policy_during_a_possessed_turn() {
+        let mut game = Game::initialise_game_with_seed(2, 3);
+        let possessed = game.curr_player_index;
+        let possessor = (possessed + 1) % 2;
+        game.possessed_by = Some(possessor);
+
+        process_command(&mut game, "auto turn off");
+
+        assert!(!game.players[possessor].auto_play.end_turn);
+        assert!(game.players[possessed].auto_play.end_turn);
+    }
+
+    #[test]
+    fn autoplay_treasures_plays_only_the_basic_treasures() {
+        let mut game = scenario(&DEFAULT_KINGDOM, [&["Copper", "Estate", "Silver"], &[]]);
+        let player_index = game.curr_player_index;
+        game.accept_move(player_index, GameMove::EndActions).unwrap();
+
+        process_command(&mut game, "autoplay treasures");
+
+        let player = game.current_player_read_only();
+        assert_eq!(names(&player.played), ["Copper", "Silver"]);
+        assert_eq!(names(&player.hand), ["Estate"]);
+        assert_eq!(player.coins, 3);
+    }
+
+    /// A game in the current player's Action phase with the given hand, who
+    /// auto-plays their basic Treasures on top of the default policy.
+    fn auto_playing(hand: &[&str]) -> Game {
+        let mut game = scenario(&DEFAULT_KINGDOM, [hand, &[]]);
+        game.current_player().auto_play = AutoPlayPolicy {
+            play_treasures: true,
+            ..AutoPlayPolicy::default()
+        };
+        game
+    }
+
+    #[test]
+    fn auto_play_goes_on_to_buying_once_the_treasures_are_played() {
+        let mut game = auto_playing(&["Copper", "Silver"]);
+        let player_index = game.curr_player_index;
+
+        game.accept_move(player_index, GameMove::EndActions).unwrap();
+
+        assert!(matches!(game.game_phase, GamePhase::BuyPhase));
+        assert_eq!(game.current_player_read_only().coins, 3);
+    }
+
+    #[test]
+    #[cfg(feature = "prosperity")]
+    fn auto_play_stops_for_a_treasure_with_a_choice() {
+        let mut game = auto_playing(&["Copper", "Loan"]);
+        let player_index = game.curr_player_index;
+
+        game.accept_move(player_index, GameMove::EndActions).unwrap();
+
+        // Loan isn't a basic Treasure, so playing it is left to the player
+        assert!(matches!(game.game_phase, GamePhase::TreasurePhase));
+        let player = game.current_player_read_only();
+        assert_eq!(names(&player.hand), ["Loan"]);
+        assert_eq!(player.coins, 1);
+    }
+
+    #[test]
+    fn auto_play_ends_the_turn_after_buying_with_no_night_card() {
+        let mut game = auto_playing(&[]);
+        let player_index = game.curr_player_index;
+        game.game_phase = GamePhase::BuyPhase;
+
+        game.accept_move(player_index, GameMove::EndBuys).unwrap();
+
+        assert_ne!(game.curr_player_index, player_index);
+    }
+
+    #[test]
+    #[cfg(feature = "nocturne")]
+    fn auto_play_stops_for_the_night_with_a_night_card() {
+        let mut game = auto_playing(&["Night Watchman"]);
+        let player_index = game.curr_player_index;
+        game.game_phase = GamePhase::BuyPhase;
+
+        game.accept_move(player_index, GameMove::EndBuys).unwrap();
+
+        assert!(matches!(game.game_phase, GamePhase::NightPhase));
+        assert_eq!(game.curr_player_index, player_index);
+    }
+
+    #[test]
+    fn turn_records_show_plays_buys_gains_and_trashes() {
+        let kingdom = ["Sentry", "Remodel", "Village"];
+        let hand: &[&str] = &["Sentry", "Remodel", "Estate", "Copper", "Copper"];
+        let mut game = scenario(&kingdom, [hand, &[]]);
+        let player_index = game.curr_player_index;
+
+        // Sentry draws a Silver and looks at two more
+        play(&mut game, "Sentry");
+        choose_option(&mut game, 0);
+        choose_option(&mut game, 1);
+        play(&mut game, "Remodel");
+        choose_cards(&mut game, &["Estate"]);
+        gain(&mut game, "Silver");
+        for game_move in [
+            GameMove::EndActions,
+            GameMove::PlayAllTreasures,
+            GameMove::EndTreasures,
+            GameMove::BuyCard {
+                card: card("Village"),
+            },
+        ] {
+            game.accept_move(player_index, game_move).unwrap();
+        }
+        end_turn(&mut game);
+
+        let turn = game.history.last_turn(player_index).unwrap();
+        assert_eq!((turn.turn_number, turn.extra_turn), (1, false));
+        assert_eq!(
+            names(&turn.played),
+            ["Sentry", "Remodel", "Copper", "Copper", "Silver"]
+        );
+        assert_eq!(names(&turn.bought), ["Village"]);
+        let gained: Vec<Card> = turn.gained_by(player_index).collect();
+        assert_eq!(names(&gained), ["Silver", "Village"]);
+        let trashed: Vec<Card> = turn.trashed_by(player_index).collect();
+        assert_eq!(names(&trashed), ["Silver", "Estate"]);
+    }
+
+    #[test]
+    fn extra_turns_are_recorded_as_extra() {
+        let mut game = scenario(&DEFAULT_KINGDOM, [&[], &[]]);
+        let player_index = game.curr_player_index;
+        game.extra_turns.push_back(ExtraTurn {
+            player_index,
+            hand_size: 5,
+        });
+
+        end_turn(&mut game);
+
+        assert_eq!(game.curr_player_index, player_index);
+        let turn = &game.history.current_turn;
+        assert_eq!((turn.turn_number, turn.extra_turn), (2, true));
+        assert_eq!(game.history.regular_turns_taken(player_index), 1);
+
+        end_turn(&mut game);
+
+        assert_ne!(game.curr_player_index, player_index);
+        assert!(!game.history.current_turn.extra_turn);
+        assert!(game.history.last_turn(player_index).unwrap().extra_turn);
+    }
+
+    #[test]
+    fn possessed_turn_is_played_by_the_possessor() {
+        let kingdom: Vec<Card> = DEFAULT_KINGDOM.iter().map(|&name| card(name)).collect();
+        let mut game = Game::initialise_game_with_kingdom(2, 3, &kingdom).unwrap();
+        let possessed = game.curr_player_index;
+        let possessor = (possessed + 1) % 2;
+        game.possessed_by = Some(possessor);
+        assert_eq!(game.acting_player_index(), possessor);
+        assert!(game
+            .accept_move(
+                possessed,
+                GameMove::BuyCard {
+                    card: card("Copper")
+                }
+            )
+            .is_err());
+
+        let discard = game.players[possessed].discard.len();
+        game.gain_card(possessed, card("Silver"), GainDestination::Discard)
+            .unwrap();
+        assert_eq!(game.players[possessed].discard.len(), discard);
+        assert_eq!(
+            game.players[possessor].discard.last(),
+            Some(&card("Silver"))
+        );
+
+        let estates = |game: &Game| {
+            game.players[possessed]
+                .all_cards()
+                .filter(|&owned| owned == card("Estate"))
+                .count()
+        };
+        let before = estates(&game);
+        game.trash_card(possessed, card("Estate"));
+        game.clean_up().unwrap();
+        game.run_pending().unwrap();
+        assert!(game.trash.cards.is_empty());
+        assert_eq!(estates(&game), before + 1);
+        // The player then takes their own turn
+        assert_eq!(game.curr_player_index, possessed);
+        assert_eq!(game.possessed_by, None);
+    }
+}
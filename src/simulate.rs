@@ -0,0 +1,475 @@
+//! Pits strategies against each other over many seeded games, spread across
+//! the machine's cores, and reports how each of them did.
+//!
+//! Game `g` is seeded with `seed + g`, so any one game can be played again,
+//! and chairs rotate from game to game so that every strategy sits in each.
+
+use std::{
+    str::FromStr,
+    sync::atomic::{AtomicUsize, Ordering},
+    thread,
+};
+
+use rand::{rng, rngs::StdRng, Rng, SeedableRng};
+
+use crate::{
+    card, expansions_from_args, kingdom_from_args, random_kingdom,
+    strategy::{PlayerView, Strategy, StrategySpec},
+    Card, Game, DEFAULT_KINGDOM,
+};
+
+/// Games still going once a player has taken this many turns are stopped.
+const MAX_TURNS: u32 = 250;
+
+/// For 95% confidence intervals.
+const Z: f64 = 1.96;
+
+const USAGE: &str = "Usage: simulate --strategies <strategy or file>,... [--games <count>] \
+    [--seed <seed>] [--threads <count>] [--kingdom <card>,...] [--expansions <expansion>,... \
+    [--kingdoms <count>]]";
+
+struct Options {
+    strategies: Vec<StrategySpec>,
+    games: usize,
+    seed: u64,
+    threads: usize,
+    kingdoms: Vec<Vec<Card>>,
+    // Where any Bane comes from
+    expansions: Vec<&'static str>,
+}
+
+/// How a game ended, if it did.
+enum Outcome {
+    Finished,
+    TooLong,
+    // A strategy made a move the game wouldn't accept
+    Failed(String),
+}
+
+/// The result of one game. Scores and winners are by strategy, not by chair.
+struct GameResult {
+    kingdom_index: usize,
+    outcome: Outcome,
+    scores: Vec<i32>,
+    winners: Vec<usize>,
+    // Turns taken by whoever took the most, not counting extra turns
+    turns: u32,
+}
+
+/// Runs the simulation described by the arguments following `simulate`, and
+/// prints the report.
+pub(crate) fn run(args: &[String]) -> Result<(), String> {
+    let options = parse_options(args)?;
+    let results = play_games(&options);
+    report(&options, &results);
+    Ok(())
+}
+
+fn parse_options(args: &[String]) -> Result<Options, String> {
+    let Some(names) = option_value::<String>(args, "--strategies")? else {
+        return Err(USAGE.to_owned());
+    };
+    let strategies = names
+        .split(',')
+        .map(|name| StrategySpec::load(name.trim()))
+        .collect::<Result<Vec<_>, _>>()?;
+    if !(2..=6).contains(&strategies.len()) {
+        return Err(format!(
+            "a game needs 2 to 6 strategies, not {}",
+            strategies.len()
+        ));
+    }
+
+    let games = option_value(args, "--games")?.unwrap_or(1000);
+    let seed = option_value(args, "--seed")?.unwrap_or_else(|| rng().random());
+    let threads = match option_value(args, "--threads")? {
+        Some(threads) => threads,
+        None => thread::available_parallelism().map_or(1, |threads| threads.get()),
+    }
+    .max(1);
+
+    let named = kingdom_from_args(args).map_err(|e| e.to_string())?;
+    let expansions = expansions_from_args(args).map_err(|e| e.to_string())?;
+    let num_kingdoms = option_value(args, "--kingdoms")?.unwrap_or(1);
+    let kingdoms = match (named, &expansions) {
+        (named, Some(expansions)) => {
+            // Each kingdom is picked with its own seed, drawn from the main one
+            let mut kingdom_rng = StdRng::seed_from_u64(seed);
+            (0..num_kingdoms.max(1))
+                .map(|_| {
+                    random_kingdom(
+                        named.clone().unwrap_or_default(),
+                        expansions,
+                        kingdom_rng.random(),
+                    )
+                    .map_err(|e| e.to_string())
+                })
+                .collect::<Result<Vec<_>, _>>()?
+        }
+        _ if num_kingdoms > 1 => {
+            return Err("--kingdoms needs --expansions to pick the kingdoms from".to_owned());
+        }
+        (Some(kingdom), None) => vec![kingdom],
+        (None, None) => vec![DEFAULT_KINGDOM.iter().map(|&name| card(name)).collect()],
+    };
+    let expansions = expansions.unwrap_or_else(|| crate::cards::expansions().collect());
+    for kingdom in &kingdoms {
+        Game::initialise_game_with_expansions(strategies.len(), seed, kingdom, &expansions)
+            .map_err(|e| e.to_string())?;
+    }
+
+    Ok(Options {
+        strategies,
+        games,
+        seed,
+        threads,
+        kingdoms,
+        expansions,
+    })
+}
+
+/// The value following `name` in the arguments, if `name` is there.
+fn option_value<T: FromStr>(args: &[String], name: &str) -> Result<Option<T>, String> {
+    let Some(position) = args.iter().position(|arg| arg == name) else {
+        return Ok(None);
+    };
+    let Some(value) = args.get(position + 1) else {
+        return Err(format!("{name} needs a value. {USAGE}"));
+    };
+    value
+        .parse()
+        .map(Some)
+        .map_err(|_| format!("'{value}' isn't a valid value for {name}"))
+}
+
+/// Plays every game, with each thread taking the next game not yet started
+/// until there are none left. Results are in game order.
+fn play_games(options: &Options) -> Vec<GameResult> {
+    let next_game = AtomicUsize::new(0);
+    let mut results: Vec<(usize, GameResult)> = thread::scope(|scope| {
+        let workers: Vec<_> = (0..options.threads.min(options.games.max(1)))
+            .map(|_| {
+                scope.spawn(|| {
+                    let mut results = Vec::new();
+                    loop {
+                        let game_number = next_game.fetch_add(1, Ordering::Relaxed);
+                        if game_number >= options.games {
+                            return results;
+                        }
+                        results.push((game_number, play_game(options, game_number)));
+                    }
+                })
+            })
+            .collect();
+        workers
+            .into_iter()
+            .flat_map(|worker| worker.join().expect("a simulation thread panicked"))
+            .collect()
+    });
+    results.sort_by_key(|&(game_number, _)| game_number);
+    results.into_iter().map(|(_, result)| result).collect()
+}
+
+fn play_game(options: &Options, game_number: usize) -> GameResult {
+    let num_players = options.strategies.len();
+    let seed = options.seed.wrapping_add(game_number as u64);
+    let kingdom_index = game_number % options.kingdoms.len();
+    // The strategy in each chair, moving one chair along every game
+    let strategy_in = |player_index: usize| (player_index + game_number) % num_players;
+    let mut bots: Vec<Box<dyn Strategy>> = (0..num_players)
+        .map(|player_index| {
+            options.strategies[strategy_in(player_index)]
+                .build(seed.wrapping_add(player_index as u64 + 1))
+        })
+        .collect();
+
+    let mut result = GameResult {
+        kingdom_index,
+        outcome: Outcome::Finished,
+        scores: vec![0; num_players],
+        winners: Vec::new(),
+        turns: 0,
+    };
+    let mut game = match Game::initialise_game_with_expansions(
+        num_players,
+        seed,
+        &options.kingdoms[kingdom_index],
+        &options.expansions,
+    ) {
+        Ok(game) => game,
+        Err(e) => {
+            result.outcome = Outcome::Failed(e.to_string());
+            return result;
+        }
+    };
+    while game.winners.is_none() {
+        if (0..num_players).any(|p| game.history.regular_turns_taken(p) > MAX_TURNS) {
+            result.outcome = Outcome::TooLong;
+            break;
+        }
+        let player_index = game.acting_player_index();
+        let game_move = bots[player_index].choose_move(&PlayerView::new(&game, player_index));
+        if let Err(e) = game.accept_move(player_index, game_move.clone()) {
+            result.outcome = Outcome::Failed(format!(
+                "game {game_number}: {} made the move {game_move:?}: {e}",
+                bots[player_index].name()
+            ));
+            break;
+        }
+    }
+
+    for player_index in 0..num_players {
+        result.scores[strategy_in(player_index)] = game.score(player_index);
+    }
+    if let Some(winners) = &game.winners {
+        result.winners = winners.iter().map(|&p| strategy_in(p)).collect();
+    }
+    result.turns = (0..num_players)
+        .map(|p| game.history.regular_turns_taken(p))
+        .max()
+        .unwrap_or(0);
+    result
+}
+
+/// Totals over a set of finished games, by strategy.
+struct Tally {
+    games: usize,
+    wins: Vec<usize>,
+    ties: Vec<usize>,
+    vp: Vec<i64>,
+    turns: u64,
+}
+
+impl Tally {
+    fn of<'a>(num_strategies: usize, results: impl Iterator<Item = &'a GameResult>) -> Self {
+        let mut tally = Tally {
+            games: 0,
+            wins: vec![0; num_strategies],
+            ties: vec![0; num_strategies],
+            vp: vec![0; num_strategies],
+            turns: 0,
+        };
+        for result in results.filter(|result| matches!(result.outcome, Outcome::Finished)) {
+            tally.games += 1;
+            tally.turns += u64::from(result.turns);
+            for (strategy_index, &score) in result.scores.iter().enumerate() {
+                tally.vp[strategy_index] += i64::from(score);
+            }
+            match result.winners[..] {
+                [winner] => tally.wins[winner] += 1,
+                ref winners => {
+                    for &winner in winners {
+                        tally.ties[winner] += 1;
+                    }
+                }
+            }
+        }
+        tally
+    }
+
+    fn average(&self, total: f64) -> f64 {
+        total / self.games.max(1) as f64
+    }
+
+    /// One row per strategy: wins and shared wins with their 95% intervals,
+    /// and average VP.
+    fn print_rows(&self, names: &[&str], indent: &str) {
+        let width = names.iter().map(|name| name.len()).max().unwrap_or(0);
+        println!(
+            "{indent}{:width$}  {:<24}  {:<24}  {:>7}",
+            "Strategy", "Win rate (95% CI)", "Tie rate (95% CI)", "Avg VP"
+        );
+        for (strategy_index, name) in names.iter().enumerate() {
+            println!(
+                "{indent}{name:width$}  {:<24}  {:<24}  {:>7.1}",
+                rate(self.wins[strategy_index], self.games),
+                rate(self.ties[strategy_index], self.games),
+                self.average(self.vp[strategy_index] as f64)
+            );
+        }
+    }
+}
+
+/// A proportion with its 95% Wilson score interval, which stays sensible for
+/// rates near 0% or 100% and for few games.
+fn rate(successes: usize, trials: usize) -> String {
+    if trials == 0 {
+        return "-".to_owned();
+    }
+    let n = trials as f64;
+    let p = successes as f64 / n;
+    let denominator = 1.0 + Z * Z / n;
+    let centre = (p + Z * Z / (2.0 * n)) / denominator;
+    let margin = Z * (p * (1.0 - p) / n + Z * Z / (4.0 * n * n)).sqrt() / denominator;
+    format!(
+        "{:.1}% ({:.1}-{:.1}%)",
+        100.0 * p,
+        100.0 * (centre - margin).max(0.0),
+        100.0 * (centre + margin).min(1.0)
+    )
+}
+
+fn kingdom_names(kingdom: &[Card]) -> String {
+    kingdom
+        .iter()
+        .map(|card| card.name())
+        .collect::<Vec<_>>()
+        .join(", ")
+}
+
+fn report(options: &Options, results: &[GameResult]) {
+    // Strategies are shown by the name they give themselves, numbered in case
+    // the same one plays more than once
+    let names: Vec<String> = options
+        .strategies
+        .iter()
+        .enumerate()
+        .map(|(strategy_index, spec)| format!("{}. {}", strategy_index + 1, spec.build(0).name()))
+        .collect();
+    let names: Vec<&str> = names.iter().map(String::as_str).collect();
+    let num_strategies = names.len();
+
+    println!(
+        "{} games on {} kingdom{}, seed {}, {} thread{}",
+        options.games,
+        options.kingdoms.len(),
+        if options.kingdoms.len() == 1 { "" } else { "s" },
+        options.seed,
+        options.threads,
+        if options.threads == 1 { "" } else { "s" },
+    );
+    if let [kingdom] = &options.kingdoms[..] {
+        println!("Kingdom: {}", kingdom_names(kingdom));
+    }
+    println!();
+
+    let tally = Tally::of(num_strategies, results.iter());
+    tally.print_rows(&names, "");
+    println!(
+        "Average game length: {:.1} turns",
+        tally.average(tally.turns as f64)
+    );
+
+    let too_long = results
+        .iter()
+        .filter(|result| matches!(result.outcome, Outcome::TooLong))
+        .count();
+    let failures: Vec<&str> = results
+        .iter()
+        .filter_map(|result| match &result.outcome {
+            Outcome::Failed(reason) => Some(reason.as_str()),
+            _ => None,
+        })
+        .collect();
+    if too_long > 0 {
+        println!("{too_long} games were stopped after {MAX_TURNS} turns and aren't counted");
+    }
+    if let Some(first) = failures.first() {
+        println!(
+            "{} games stopped on an illegal move and aren't counted, the first in {first}",
+            failures.len()
+        );
+    }
+
+    if options.kingdoms.len() > 1 {
+        for (kingdom_index, kingdom) in options.kingdoms.iter().enumerate() {
+            let tally = Tally::of(
+                num_strategies,
+                results
+                    .iter()
+                    .filter(|result| result.kingdom_index == kingdom_index),
+            );
+            println!();
+            println!(
+                "Kingdom {}: {} ({} games, {:.1} turns on average)",
+                kingdom_index + 1,
+                kingdom_names(kingdom),
+                tally.games,
+                tally.average(tally.turns as f64)
+            );
+            tally.print_rows(&names, "  ");
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn options(strategies: &[&str], games: usize, threads: usize) -> Options {
+        Options {
+            strategies: strategies
+                .iter()
+                .map(|&name| StrategySpec::load(name).unwrap())
+                .collect(),
+            games,
+            seed: 7,
+            threads,
+            kingdoms: vec![DEFAULT_KINGDOM.iter().map(|&name| card(name)).collect()],
+            expansions: crate::cards::expansions().collect(),
+        }
+    }
+
+    fn finished(scores: Vec<i32>, winners: Vec<usize>, turns: u32) -> GameResult {
+        GameResult {
+            kingdom_index: 0,
+            outcome: Outcome::Finished,
+            scores,
+            winners,
+            turns,
+        }
+    }
+
+    #[test]
+    fn tally_counts_wins_and_ties_of_finished_games_only() {
+        let results = [
+            finished(vec![30, 20], vec![0], 18),
+            finished(vec![25, 25], vec![0, 1], 20),
+            finished(vec![10, 40], vec![1], 16),
+            finished(vec![35, 12], vec![0], 22),
+            GameResult {
+                outcome: Outcome::TooLong,
+                ..finished(vec![0, 0], vec![], MAX_TURNS)
+            },
+        ];
+
+        let tally = Tally::of(2, results.iter());
+
+        assert_eq!(tally.games, 4);
+        assert_eq!(tally.wins, [2, 1]);
+        assert_eq!(tally.ties, [1, 1]);
+        assert_eq!(tally.vp, [100, 97]);
+        assert_eq!(tally.turns, 76);
+    }
+
+    #[test]
+    fn simulated_wins_add_up_to_the_games_played() {
+        let options = options(&["Big Money", "Smithy-BM"], 12, 3);
+
+        let results = play_games(&options);
+        let tally = Tally::of(2, results.iter());
+
+        assert_eq!(results.len(), 12);
+        assert!(results
+            .iter()
+            .all(|result| matches!(result.outcome, Outcome::Finished)));
+        assert_eq!(tally.games, 12);
+        let shared = results
+            .iter()
+            .filter(|result| result.winners.len() > 1)
+            .count();
+        assert_eq!(tally.wins.iter().sum::<usize>() + shared, 12);
+    }
+
+    #[test]
+    fn results_do_not_depend_on_the_number_of_threads() {
+        let winners = |threads: usize| -> Vec<Vec<usize>> {
+            play_games(&options(&["Big Money", "Smithy-BM"], 8, threads))
+                .into_iter()
+                .map(|result| result.winners)
+                .collect()
+        };
+
+        assert_eq!(winners(1), winners(4));
+    }
+}
@@ -0,0 +1,142 @@
+//! The Big Money family: strategies that buy Treasure and green cards and at
+//! most a few kingdom cards, playing them the simple way. They make the
+//! yardstick any other strategy should beat.
+
+use super::{play_simply, PlayerView, Strategy};
+use crate::cards::{card, Card};
+use crate::GameMove;
+
+/// The first card in `rules` whose condition holds and that can be bought now.
+fn first_to_buy<const N: usize>(view: &PlayerView, rules: [(&str, bool); N]) -> Option<Card> {
+    let moves = view.legal_moves();
+    rules
+        .into_iter()
+        .filter(|&(_, wanted)| wanted)
+        .map(|(name, _)| card(name))
+        .find(|&card| moves.contains(&GameMove::BuyCard { card }))
+}
+
+/// Provinces with $8, Gold with $6 or $7, Silver with $3 to $5, and nothing else.
+pub(crate) struct BigMoney;
+
+impl Strategy for BigMoney {
+    fn name(&self) -> &str {
+        "Big Money"
+    }
+
+    fn choose_move(&mut self, view: &PlayerView) -> GameMove {
+        play_simply(view, |view| {
+            first_to_buy(view, [("Province", true), ("Gold", true), ("Silver", true)])
+        })
+    }
+}
+
+/// Big Money that waits for enough money in its deck before buying
+/// Provinces, and dances down to Duchies and Estates as the Provinces run out.
+pub(crate) struct BigMoneyUltimate;
+
+impl Strategy for BigMoneyUltimate {
+    fn name(&self) -> &str {
+        "Big Money Ultimate"
+    }
+
+    fn choose_move(&mut self, view: &PlayerView) -> GameMove {
+        play_simply(view, |view| {
+            let provinces_left = view.supply_count(card("Province"));
+            first_to_buy(
+                view,
+                [
+                    ("Province", view.total_money() > 18),
+                    ("Duchy", provinces_left <= 4),
+                    ("Estate", provinces_left <= 2),
+                    ("Gold", true),
+                    ("Duchy", provinces_left <= 6),
+                    ("Silver", true),
+                ],
+            )
+        })
+    }
+}
+
+/// Big Money with a Smithy, and another for every eleven cards in the deck.
+pub(crate) struct SmithyBigMoney;
+
+impl Strategy for SmithyBigMoney {
+    fn name(&self) -> &str {
+        "Smithy-BM"
+    }
+
+    fn choose_move(&mut self, view: &PlayerView) -> GameMove {
+        play_simply(view, |view| {
+            let provinces_left = view.supply_count(card("Province"));
+            let deck_size = view.owned_cards().count();
+            first_to_buy(
+                view,
+                [
+                    ("Province", view.total_money() > 15),
+                    ("Duchy", provinces_left <= 4),
+                    ("Estate", provinces_left <= 2),
+                    ("Gold", true),
+                    ("Duchy", provinces_left <= 5),
+                    (
+                        "Smithy",
+                        view.count_owned(card("Smithy")) < (deck_size + 1) / 11,
+                    ),
+                    ("Silver", true),
+                ],
+            )
+        })
+    }
+}
+
+/// Big Money that picks up two Laboratories with its first $5 hands.
+pub(crate) struct DoubleLab;
+
+impl Strategy for DoubleLab {
+    fn name(&self) -> &str {
+        "Double Lab"
+    }
+
+    fn choose_move(&mut self, view: &PlayerView) -> GameMove {
+        play_simply(view, |view| {
+            let provinces_left = view.supply_count(card("Province"));
+            first_to_buy(
+                view,
+                [
+                    ("Province", view.total_money() > 15),
+                    ("Duchy", provinces_left <= 4),
+                    ("Estate", provinces_left <= 2),
+                    ("Gold", true),
+                    ("Laboratory", view.count_owned(card("Laboratory")) < 2),
+                    ("Silver", true),
+                ],
+            )
+        })
+    }
+}
+
+/// Big Money with two Witches, bought ahead of Gold.
+pub(crate) struct WitchBigMoney;
+
+impl Strategy for WitchBigMoney {
+    fn name(&self) -> &str {
+        "Witch-BM"
+    }
+
+    fn choose_move(&mut self, view: &PlayerView) -> GameMove {
+        play_simply(view, |view| {
+            let provinces_left = view.supply_count(card("Province"));
+            first_to_buy(
+                view,
+                [
+                    ("Province", view.total_money() > 15),
+                    ("Duchy", provinces_left <= 4),
+                    ("Estate", provinces_left <= 2),
+                    ("Witch", view.count_owned(card("Witch")) < 2),
+                    ("Gold", true),
+                    ("Silver", true),
+                ],
+            )
+        })
+    }
+}
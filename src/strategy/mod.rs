@@ -0,0 +1,348 @@
+//! Computer players. A `Strategy` is asked for a move whenever its player is
+//! the one play is waiting on, sees the game only through that player's
+//! `PlayerView`, and its moves go through `Game::accept_move` like anyone else's.
+
+mod big_money;
+mod random;
+mod rules;
+
+use std::{fs, path::Path};
+
+use crate::cards::{Card, CardType};
+use crate::{Decision, Game, GameMove, GamePhase, Prompt};
+
+pub(crate) use big_money::{BigMoney, BigMoneyUltimate, DoubleLab, SmithyBigMoney, WitchBigMoney};
+pub(crate) use random::Random;
+pub(crate) use rules::BuyRules;
+
+pub(crate) trait Strategy {
+    /// The name shown to players and in logs.
+    fn name(&self) -> &str;
+
+    /// The next move for the player whose view this is. It should be one of
+    /// `view.legal_moves()`; anything else is rejected by the game.
+    fn choose_move(&mut self, view: &PlayerView) -> GameMove;
+}
+
+/// What a player can see of the game: everything public, plus their own
+/// cards. Strategies decide from this rather than from the `Game` itself.
+pub(crate) struct PlayerView<'a> {
+    game: &'a Game,
+    player_index: usize,
+}
+
+impl<'a> PlayerView<'a> {
+    pub(crate) fn new(game: &'a Game, player_index: usize) -> Self {
+        PlayerView { game, player_index }
+    }
+
+    /// The hand the player is making moves for, which is someone else's while
+    /// they are possessing them.
+    pub(crate) fn hand(&self) -> &[Card] {
+        &self.game.players[self.acting_for()].hand
+    }
+
+    /// The phase of the turn in progress, which may be someone else's.
+    pub(crate) fn phase(&self) -> &GamePhase {
+        &self.game.game_phase
+    }
+
+    /// The decision this player owes, if play is waiting on one.
+    pub(crate) fn decision(&self) -> Option<&Decision> {
+        self.game
+            .pending_decision()
+            .filter(|_| self.game.acting_player_index() == self.player_index)
+    }
+
+    /// Whose cards the player's moves are about: their own, unless they are
+    /// possessing the player whose turn it is.
+    fn acting_for(&self) -> usize {
+        if self.game.acting_player_index() == self.player_index {
+            self.game.acting_for()
+        } else {
+            self.player_index
+        }
+    }
+
+    pub(crate) fn legal_moves(&self) -> Vec<GameMove> {
+        self.game.legal_moves()
+    }
+
+    /// Every card the player owns, wherever it is.
+    pub(crate) fn owned_cards(&self) -> impl Iterator<Item = Card> + '_ {
+        self.game.players[self.player_index].all_cards()
+    }
+
+    pub(crate) fn count_owned(&self, card: Card) -> usize {
+        self.owned_cards().filter(|&owned| owned == card).count()
+    }
+
+    /// The coins all the player's Treasures make between them, not counting
+    /// what special Treasures add when played.
+    pub(crate) fn total_money(&self) -> u32 {
+        self.owned_cards()
+            .filter(|card| card.is(CardType::Treasure))
+            .map(|card| card.info().coins)
+            .sum()
+    }
+
+    /// Cards left in the pile a card comes from; 0 if there is no such pile.
+    pub(crate) fn supply_count(&self, card: Card) -> usize {
+        self.game.supply.count(card).unwrap_or(0) as usize
+    }
+
+    pub(crate) fn empty_piles(&self) -> usize {
+        self.game.supply.num_empty_supply_piles() as usize
+    }
+
+    /// Coins left to spend this turn, after paying off any Debt.
+    pub(crate) fn coins(&self) -> u32 {
+        let player = &self.game.players[self.acting_for()];
+        player.coins.saturating_sub(player.debt)
+    }
+
+    /// How many turns the player has started, not counting extra turns.
+    pub(crate) fn turn_number(&self) -> u32 {
+        self.game.history.regular_turns_taken(self.player_index)
+    }
+
+    /// The player's score minus the best of the other players' scores, so
+    /// negative when behind.
+    pub(crate) fn vp_lead(&self) -> i32 {
+        let best_other = (0..self.game.players.len())
+            .filter(|&player_index| player_index != self.player_index)
+            .map(|player_index| self.game.score(player_index))
+            .max()
+            .unwrap_or(0);
+        self.game.score(self.player_index) - best_other
+    }
+}
+
+/// A strategy picked by name or read from a file of buy rules, from which
+/// players can be made for as many games as needed.
+#[derive(Clone)]
+pub(crate) enum StrategySpec {
+    Named(String),
+    Rules(BuyRules),
+}
+
+impl StrategySpec {
+    /// The name of one of the strategies here (see `by_name`), or the path
+    /// of a file of buy rules (see `rules`).
+    pub(crate) fn load(spec: &str) -> Result<Self, String> {
+        if by_name(spec, 0).is_some() {
+            return Ok(StrategySpec::Named(spec.to_owned()));
+        }
+        let path = Path::new(spec);
+        if !path.is_file() {
+            return Err(format!(
+                "'{spec}' is neither a strategy ({}) nor a file of buy rules",
+                names().join(", ")
+            ));
+        }
+        let text = fs::read_to_string(path).map_err(|e| format!("can't read {spec}: {e}"))?;
+        let default_name = path
+            .file_stem()
+            .and_then(|stem| stem.to_str())
+            .unwrap_or(spec);
+        BuyRules::parse(&text, default_name)
+            .map(StrategySpec::Rules)
+            .map_err(|e| format!("{spec}, {e}"))
+    }
+
+    /// A player following the strategy. `seed` decides any choices it makes at random.
+    pub(crate) fn build(&self, seed: u64) -> Box<dyn Strategy> {
+        match self {
+            StrategySpec::Named(name) => by_name(name, seed).expect("checked when loaded"),
+            StrategySpec::Rules(rules) => Box::new(rules.clone()),
+        }
+    }
+}
+
+/// The strategies that can be picked by name.
+fn names() -> Vec<&'static str> {
+    vec![
+        "Big Money",
+        "Big Money Ultimate",
+        "Smithy-BM",
+        "Double Lab",
+        "Witch-BM",
+        "Random",
+    ]
+}
+
+/// A strategy by its name, ignoring case and punctuation, or a common
+/// abbreviation such as "BMU".
+fn by_name(name: &str, seed: u64) -> Option<Box<dyn Strategy>> {
+    let name: String = name
+        .chars()
+        .filter(|c| c.is_alphanumeric())
+        .collect::<String>()
+        .to_lowercase();
+    let strategy: Box<dyn Strategy> = match name.as_str() {
+        "bigmoney" | "bm" => Box::new(BigMoney),
+        "bigmoneyultimate" | "bmu" => Box::new(BigMoneyUltimate),
+        "smithybm" | "smithy" => Box::new(SmithyBigMoney),
+        "doublelab" | "lab" => Box::new(DoubleLab),
+        "witchbm" | "witch" => Box::new(WitchBigMoney),
+        "random" => Box::new(Random::new(seed)),
+        _ => return None,
+    };
+    Some(strategy)
+}
+
+/// How the simple strategies play everything but their buys: Actions that
+/// give +Actions first, then whichever draws most; every Treasure; nothing
+/// at night. `buy` picks what to buy, if anything, each time there's a Buy
+/// to use.
+pub(crate) fn play_simply(
+    view: &PlayerView,
+    buy: impl FnOnce(&PlayerView) -> Option<Card>,
+) -> GameMove {
+    if let Some(decision) = view.decision() {
+        return answer_simply(view, decision);
+    }
+    let moves = view.legal_moves();
+    let hand = view.hand();
+    match view.phase() {
+        GamePhase::ActionPhase => moves
+            .into_iter()
+            .filter_map(|game_move| match game_move {
+                GameMove::PlayCard {
+                    card_index,
+                    way: None,
+                } => Some(card_index),
+                _ => None,
+            })
+            .max_by_key(|&card_index| {
+                let info = hand[card_index].info();
+                (info.actions > 0, info.cards)
+            })
+            .map_or(GameMove::EndActions, |card_index| GameMove::PlayCard {
+                card_index,
+                way: None,
+            }),
+        GamePhase::TreasurePhase => {
+            if moves.contains(&GameMove::PlayAllTreasures) {
+                GameMove::PlayAllTreasures
+            } else {
+                moves
+                    .into_iter()
+                    .find(|game_move| matches!(game_move, GameMove::PlayCard { .. }))
+                    .unwrap_or(GameMove::EndTreasures)
+            }
+        }
+        GamePhase::BuyPhase => buy(view)
+            .map(|card| GameMove::BuyCard { card })
+            .filter(|game_move| moves.contains(game_move))
+            .unwrap_or(GameMove::EndBuys),
+        GamePhase::NightPhase => GameMove::EndTurn,
+    }
+}
+
+/// Answers a decision without looking at what asked: gives up the least
+/// useful cards asked for (as few as allowed), gains the most expensive card
+/// offered, and takes the first option.
+fn answer_simply(view: &PlayerView, decision: &Decision) -> GameMove {
+    match &decision.prompt {
+        Prompt::CardsFromHand { min, filter, .. } => {
+            let mut candidates: Vec<usize> = (0..view.hand().len())
+                .filter(|&card_index| filter.matches(view.hand()[card_index]))
+                .collect();
+            candidates.sort_by_key(|&card_index| worth(view.hand()[card_index]));
+            candidates.truncate(*min);
+            candidates.sort();
+            GameMove::ChooseCards {
+                card_indices: candidates,
+            }
+        }
+        Prompt::CardFromSupply { .. } => view
+            .legal_moves()
+            .into_iter()
+            .max_by_key(|game_move| match game_move {
+                GameMove::GainCard { card } => {
+                    let cost = card.cost();
+                    (!card.is(CardType::Curse), cost.coins + 2 * cost.potions)
+                }
+                _ => (false, 0),
+            })
+            .unwrap_or(GameMove::EndTurn),
+        Prompt::Options { .. } => GameMove::ChooseOption { option_index: 0 },
+    }
+}
+
+/// A rough measure of how much a card is worth having: Curses least, then
+/// cards that do nothing in hand, then Copper, then by cost.
+fn worth(card: Card) -> u32 {
+    if card.is(CardType::Curse) {
+        0
+    } else if card.is(CardType::Ruins) || card.is(CardType::Shelter) {
+        1
+    } else if card.is(CardType::Victory)
+        && !card.is(CardType::Action)
+        && !card.is(CardType::Treasure)
+    {
+        // Victory cards are worth keeping at the end, but a strategy that
+        // wants them buys them; when asked, they are what's least missed
+        2
+    } else if card.name() == "Copper" {
+        3
+    } else {
+        4 + card.cost().coins + 2 * card.cost().potions
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{card, DEFAULT_KINGDOM};
+
+    /// Plays a seeded game on the default kingdom between the named strategies
+    /// until it ends, failing on any move the game refuses.
+    fn play_out(strategies: &[&str], seed: u64) -> Game {
+        let kingdom: Vec<Card> = DEFAULT_KINGDOM.iter().map(|&name| card(name)).collect();
+        let mut game =
+            Game::initialise_game_with_kingdom(strategies.len(), seed, &kingdom).unwrap();
+        let mut bots: Vec<Box<dyn Strategy>> = strategies
+            .iter()
+            .enumerate()
+            .map(|(player_index, name)| by_name(name, seed + player_index as u64).unwrap())
+            .collect();
+        for _ in 0..10_000 {
+            if game.winners.is_some() {
+                return game;
+            }
+            let player_index = game.acting_player_index();
+            let game_move = bots[player_index].choose_move(&PlayerView::new(&game, player_index));
+            if let Err(e) = game.accept_move(player_index, game_move.clone()) {
+                panic!(
+                    "{} made the move {game_move:?}: {e}",
+                    bots[player_index].name()
+                );
+            }
+        }
+        panic!("the game hadn't ended after 10000 moves");
+    }
+
+    #[test]
+    fn bots_play_a_seeded_game_to_the_end() {
+        let game = play_out(&["Big Money", "Smithy-BM"], 42);
+        let winners = game.winners.clone().unwrap();
+        assert!(!winners.is_empty());
+
+        // The same seed plays out the same game
+        let again = play_out(&["Big Money", "Smithy-BM"], 42);
+        assert_eq!(again.winners, Some(winners));
+        assert_eq!(
+            (0..2).map(|p| again.score(p)).collect::<Vec<_>>(),
+            (0..2).map(|p| game.score(p)).collect::<Vec<_>>()
+        );
+    }
+
+    #[test]
+    fn random_bot_only_makes_legal_moves() {
+        for seed in 0..3 {
+            play_out(&["Random", "Big Money", "Random"], seed);
+        }
+    }
+}
@@ -0,0 +1,37 @@
+//! A strategy that plays at random, to shake out the engine and to lose to.
+
+use rand::{rngs::StdRng, seq::IndexedRandom, SeedableRng};
+
+use super::{PlayerView, Strategy};
+use crate::GameMove;
+
+/// Makes any legal move, all equally likely, except that it never ends its
+/// turn straight away while there's anything else to do.
+pub(crate) struct Random {
+    rng: StdRng,
+}
+
+impl Random {
+    pub(crate) fn new(seed: u64) -> Self {
+        Random {
+            rng: StdRng::seed_from_u64(seed),
+        }
+    }
+}
+
+impl Strategy for Random {
+    fn name(&self) -> &str {
+        "Random"
+    }
+
+    fn choose_move(&mut self, view: &PlayerView) -> GameMove {
+        let mut moves = view.legal_moves();
+        if moves.len() > 1 {
+            moves.retain(|game_move| *game_move != GameMove::EndTurn);
+        }
+        moves
+            .choose(&mut self.rng)
+            .cloned()
+            .unwrap_or(GameMove::EndTurn)
+    }
+}
@@ -0,0 +1,411 @@
+//! Strategies written as text, in the spirit of Geronimoo's simulator: an
+//! ordered list of buy rules, each naming a card and the conditions under
+//! which to buy it. For example:
+//!
+//! ```text
+//! # Lines starting with '#' are comments
+//! name: Smithy Big Money
+//! buy Province if money > 15
+//! buy Duchy if supply(Province) <= 4
+//! buy Estate if supply(Province) <= 2
+//! buy Gold
+//! buy Smithy if deck(Smithy) < 1 and turn <= 10
+//! buy Silver
+//! ```
+//!
+//! Whenever there's a Buy to use, the first rule whose conditions all hold
+//! and whose card can be bought is followed; if there is none, the Buy phase
+//! ends. Everything else is played the way the Big Money strategies play.
+
+use thiserror::Error;
+
+use super::{play_simply, PlayerView, Strategy};
+use crate::cards::{find_card, Card};
+use crate::GameMove;
+
+#[derive(Debug, Error, PartialEq)]
+pub(crate) enum ParseError {
+    #[error("line {line}: {message}")]
+    Line { line: usize, message: String },
+
+    #[error("there are no buy rules; add lines like `buy Gold if coins >= 6`")]
+    NoRules,
+}
+
+/// Something a condition can compare.
+#[derive(Debug, Clone)]
+enum Term {
+    Number(i64),
+    // Coins left to spend this turn
+    Coins,
+    // What all the player's Treasures make between them
+    Money,
+    Turn,
+    // VP ahead of the best other player, negative when behind
+    Lead,
+    EmptyPiles,
+    // Copies of a card the player owns
+    InDeck(Card),
+    // Cards left in the card's pile
+    InSupply(Card),
+}
+
+const TERMS: &str =
+    "a number, coins, money, turn, lead, empty_piles, deck(<card>) or supply(<card>)";
+
+#[derive(Debug, Clone, Copy)]
+enum Comparison {
+    Less,
+    LessOrEqual,
+    Equal,
+    NotEqual,
+    GreaterOrEqual,
+    Greater,
+}
+
+/// Operators by how they are written. Two-character ones come first, so
+/// that `<=` isn't read as `<` followed by `=`.
+const COMPARISONS: [(&str, Comparison); 7] = [
+    ("<=", Comparison::LessOrEqual),
+    (">=", Comparison::GreaterOrEqual),
+    ("==", Comparison::Equal),
+    ("!=", Comparison::NotEqual),
+    ("<", Comparison::Less),
+    (">", Comparison::Greater),
+    ("=", Comparison::Equal),
+];
+
+#[derive(Debug, Clone)]
+struct Condition {
+    left: Term,
+    comparison: Comparison,
+    right: Term,
+}
+
+#[derive(Debug, Clone)]
+struct BuyRule {
+    card: Card,
+    conditions: Vec<Condition>,
+}
+
+/// A strategy made of buy rules.
+#[derive(Debug, Clone)]
+pub(crate) struct BuyRules {
+    name: String,
+    rules: Vec<BuyRule>,
+}
+
+impl BuyRules {
+    /// Reads buy rules from their text. The strategy is called `default_name`
+    /// unless the text has a `name:` line.
+    pub(crate) fn parse(text: &str, default_name: &str) -> Result<Self, ParseError> {
+        let mut name = default_name.to_owned();
+        let mut rules = Vec::new();
+        for (line_index, line) in text.lines().enumerate() {
+            let line = line.split('#').next().unwrap_or_default().trim();
+            if line.is_empty() {
+                continue;
+            }
+            match line.split_once(':') {
+                Some((key, value)) if key.trim().eq_ignore_ascii_case("name") => {
+                    name = value.trim().to_owned();
+                }
+                _ => rules.push(parse_rule(line).map_err(|message| ParseError::Line {
+                    line: line_index + 1,
+                    message,
+                })?),
+            }
+        }
+        if rules.is_empty() {
+            return Err(ParseError::NoRules);
+        }
+        Ok(BuyRules { name, rules })
+    }
+}
+
+impl Strategy for BuyRules {
+    fn name(&self) -> &str {
+        &self.name
+    }
+
+    fn choose_move(&mut self, view: &PlayerView) -> GameMove {
+        play_simply(view, |view| {
+            let moves = view.legal_moves();
+            self.rules
+                .iter()
+                .filter(|rule| {
+                    rule.conditions
+                        .iter()
+                        .all(|condition| condition.holds(view))
+                })
+                .map(|rule| rule.card)
+                .find(|&card| moves.contains(&GameMove::BuyCard { card }))
+        })
+    }
+}
+
+impl Condition {
+    fn holds(&self, view: &PlayerView) -> bool {
+        let (left, right) = (self.left.value(view), self.right.value(view));
+        match self.comparison {
+            Comparison::Less => left < right,
+            Comparison::LessOrEqual => left <= right,
+            Comparison::Equal => left == right,
+            Comparison::NotEqual => left != right,
+            Comparison::GreaterOrEqual => left >= right,
+            Comparison::Greater => left > right,
+        }
+    }
+}
+
+impl Term {
+    fn value(&self, view: &PlayerView) -> i64 {
+        match *self {
+            Term::Number(number) => number,
+            Term::Coins => view.coins().into(),
+            Term::Money => view.total_money().into(),
+            Term::Turn => view.turn_number().into(),
+            Term::Lead => view.vp_lead().into(),
+            Term::EmptyPiles => view.empty_piles() as i64,
+            Term::InDeck(card) => view.count_owned(card) as i64,
+            Term::InSupply(card) => view.supply_count(card) as i64,
+        }
+    }
+}
+
+/// `buy <card> [if <condition> and <condition> ...]`
+fn parse_rule(line: &str) -> Result<BuyRule, String> {
+    let rest = match line.split_once(char::is_whitespace) {
+        Some((keyword, rest)) if keyword.eq_ignore_ascii_case("buy") => rest,
+        _ if line.eq_ignore_ascii_case("buy") => return Err("`buy` needs a card".to_owned()),
+        _ => {
+            return Err(format!(
+                "expected `buy <card> [if <condition>]`, found '{line}'"
+            ))
+        }
+    };
+    let Some((card_name, conditions)) = split_at_word(rest, "if") else {
+        return Ok(BuyRule {
+            card: parse_card(rest)?,
+            conditions: Vec::new(),
+        });
+    };
+    let card = parse_card(card_name)?;
+    if conditions.trim().is_empty() {
+        return Err(format!("`if` after {} needs a condition", card.name()));
+    }
+    let mut parsed = Vec::new();
+    let mut rest = conditions;
+    while let Some((condition, more)) = split_at_word(rest, "and") {
+        parsed.push(parse_condition(condition)?);
+        rest = more;
+    }
+    parsed.push(parse_condition(rest)?);
+    Ok(BuyRule {
+        card,
+        conditions: parsed,
+    })
+}
+
+/// Splits around the first standalone, case-insensitive occurrence of `word`.
+fn split_at_word<'a>(text: &'a str, word: &str) -> Option<(&'a str, &'a str)> {
+    let lower = text.to_ascii_lowercase();
+    let mut start = 0;
+    while let Some(found) = lower[start..].find(word) {
+        let at = start + found;
+        let end = at + word.len();
+        let before = lower[..at].chars().next_back();
+        let after = lower[end..].chars().next();
+        if before.is_none_or(char::is_whitespace) && after.is_none_or(char::is_whitespace) {
+            return Some((&text[..at], &text[end..]));
+        }
+        start = end;
+    }
+    None
+}
+
+fn parse_card(name: &str) -> Result<Card, String> {
+    let name = name.trim();
+    if name.is_empty() {
+        return Err("a card name is missing".to_owned());
+    }
+    find_card(name).map_err(|e| e.to_string())
+}
+
+/// `<term> <comparison> <term>`
+fn parse_condition(text: &str) -> Result<Condition, String> {
+    let text = text.trim();
+    if text.is_empty() {
+        return Err("`and` needs a condition on both sides".to_owned());
+    }
+    let Some((at, symbol, comparison)) = text
+        .char_indices()
+        .filter(|&(_, c)| matches!(c, '<' | '>' | '=' | '!'))
+        .find_map(|(at, _)| {
+            COMPARISONS
+                .iter()
+                .find(|(symbol, _)| text[at..].starts_with(symbol))
+                .map(|&(symbol, comparison)| (at, symbol, comparison))
+        })
+    else {
+        return Err(format!(
+            "`{text}` needs a comparison: <, <=, =, !=, >= or >"
+        ));
+    };
+    Ok(Condition {
+        left: parse_term(&text[..at], text)?,
+        comparison,
+        right: parse_term(&text[at + symbol.len()..], text)?,
+    })
+}
+
+fn parse_term(text: &str, condition: &str) -> Result<Term, String> {
+    let term = text.trim();
+    if term.is_empty() {
+        return Err(format!(
+            "`{condition}` is missing one side of its comparison"
+        ));
+    }
+    if let Ok(number) = term.parse() {
+        return Ok(Term::Number(number));
+    }
+    if let Some((function, argument)) = term.split_once('(') {
+        let Some(card_name) = argument.strip_suffix(')') else {
+            return Err(format!("`{term}` is missing its closing ')'"));
+        };
+        let card = parse_card(card_name)?;
+        return match function.trim().to_ascii_lowercase().as_str() {
+            "deck" => Ok(Term::InDeck(card)),
+            "supply" => Ok(Term::InSupply(card)),
+            _ => Err(format!(
+                "'{}' isn't something a condition can use; try {TERMS}",
+                function.trim()
+            )),
+        };
+    }
+    match term.to_ascii_lowercase().as_str() {
+        "coins" => Ok(Term::Coins),
+        "money" => Ok(Term::Money),
+        "turn" => Ok(Term::Turn),
+        "lead" => Ok(Term::Lead),
+        "empty_piles" => Ok(Term::EmptyPiles),
+        _ => Err(format!(
+            "'{term}' isn't something a condition can use; try {TERMS}"
+        )),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// The message for the error on `line`, the rule's only line.
+    fn error(line: &str) -> String {
+        match BuyRules::parse(line, "test") {
+            Err(ParseError::Line { line: 1, message }) => message,
+            other => panic!("expected an error on line 1, got {other:?}"),
+        }
+    }
+
+    #[test]
+    fn parses_rules_and_a_name() {
+        let text =
+            "# A comment\nname: Mine\n\nbuy Province if money > 15 and turn >= 10\nbuy gold\n";
+        let rules = BuyRules::parse(text, "file").unwrap();
+        assert_eq!(rules.name, "Mine");
+        assert_eq!(rules.rules.len(), 2);
+        assert_eq!(rules.rules[0].conditions.len(), 2);
+        assert_eq!(rules.rules[1].card, find_card("Gold").unwrap());
+    }
+
+    #[test]
+    fn uses_the_default_name_without_a_name_line() {
+        let rules = BuyRules::parse("buy Silver", "file").unwrap();
+        assert_eq!(rules.name, "file");
+    }
+
+    #[test]
+    fn reports_the_line_of_an_error() {
+        let text = "buy Gold\n# fine so far\nsell Copper";
+        assert!(matches!(
+            BuyRules::parse(text, "test"),
+            Err(ParseError::Line { line: 3, .. })
+        ));
+    }
+
+    #[test]
+    fn rejects_text_without_rules() {
+        assert_eq!(
+            BuyRules::parse("# nothing\nname: Idle\n", "test").unwrap_err(),
+            ParseError::NoRules
+        );
+    }
+
+    #[test]
+    fn rejects_lines_that_are_not_buy_rules() {
+        assert_eq!(
+            error("sell Copper"),
+            "expected `buy <card> [if <condition>]`, found 'sell Copper'"
+        );
+        assert_eq!(error("buy"), "`buy` needs a card");
+    }
+
+    #[test]
+    fn rejects_unknown_and_missing_cards() {
+        assert_eq!(
+            error("buy Provnce"),
+            "Unknown card 'Provnce'. Did you mean Province?"
+        );
+        assert_eq!(error("buy  if coins > 3"), "a card name is missing");
+        assert_eq!(error("buy Gold if deck() < 1"), "a card name is missing");
+    }
+
+    #[test]
+    fn rejects_an_if_without_a_condition() {
+        assert_eq!(error("buy Gold if"), "`if` after Gold needs a condition");
+    }
+
+    #[test]
+    fn rejects_an_and_without_a_condition() {
+        assert_eq!(
+            error("buy Gold if coins >= 6 and"),
+            "`and` needs a condition on both sides"
+        );
+    }
+
+    #[test]
+    fn rejects_conditions_without_a_comparison() {
+        assert_eq!(
+            error("buy Gold if coins 5"),
+            "`coins 5` needs a comparison: <, <=, =, !=, >= or >"
+        );
+    }
+
+    #[test]
+    fn rejects_comparisons_missing_a_side() {
+        assert_eq!(
+            error("buy Gold if >= 6"),
+            "`>= 6` is missing one side of its comparison"
+        );
+    }
+
+    #[test]
+    fn rejects_unknown_terms() {
+        assert_eq!(
+            error("buy Gold if cash >= 6"),
+            format!("'cash' isn't something a condition can use; try {TERMS}")
+        );
+        assert_eq!(
+            error("buy Gold if hand(Gold) < 1"),
+            format!("'hand' isn't something a condition can use; try {TERMS}")
+        );
+    }
+
+    #[test]
+    fn rejects_unclosed_brackets() {
+        assert_eq!(
+            error("buy Smithy if deck(Smithy < 1"),
+            "`deck(Smithy` is missing its closing ')'"
+        );
+    }
+}